@@ -0,0 +1,48 @@
+//! Traits for converting between Neovim [`Object`](crate::Object)s and Rust
+//! types.
+
+#[cfg(feature = "unstable")]
+use std::fmt::Debug;
+
+#[doc(inline)]
+pub use oxi_types::conversion::*;
+
+#[cfg(feature = "unstable")]
+use crate::api;
+
+/// Checks that `value` survives a round trip through Neovim unchanged.
+///
+/// The value is converted into an [`Object`](crate::Object), copied by
+/// Neovim via [`api::id`] and converted back, failing if that doesn't
+/// give back a value equal to the original one. This is meant to be used in
+/// tests of [`ToObject`] and [`FromObject`] implementations.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{self as oxi, conversion};
+///
+/// #[oxi::test]
+/// fn vec_roundtrip() {
+///     conversion::verify(&vec![1, 2, 3]).unwrap();
+/// }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub fn verify<T>(value: &T) -> crate::Result<()>
+where
+    T: Clone + Debug + PartialEq + ToObject + FromObject,
+{
+    let obj = value.clone().to_object()?;
+    let roundtripped = T::from_object(api::id(&obj))?;
+
+    if &roundtripped != value {
+        return Err(api::Error::Other(format!(
+            "{value:?} became {roundtripped:?} after a round trip through \
+             Neovim"
+        ))
+        .into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Helpers to catch memory and reference leaks while developing a plugin.
+//!
+//! With the `leak-check` feature enabled `nvim-oxi` keeps track of the
+//! [`Array`](crate::Array) and [`Dictionary`](crate::Dictionary) buffers it
+//! allocates and of the references to Lua values it creates in the
+//! registry, e.g. when wrapping a Rust closure in a
+//! [`Function`](crate::Function). [`live_counts`] reports how many of them
+//! are still alive, so comparing two reports taken before and after some
+//! code has run shows whether it leaked anything.
+//!
+//! Values allocated by Neovim and handed over to Rust aren't tracked, so
+//! freeing them doesn't affect the counts.
+//!
+//! Tracking every allocation has a cost, so this feature is meant to be
+//! enabled in tests and debug builds only. Tests can also fail on leaks by
+//! passing the `leak_check` attribute to [`#[oxi::test]`](crate::test).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::{self as oxi, debug};
+//!
+//! let before = debug::live_counts();
+//! do_stuff();
+//!
+//! if let Some(leaked) = debug::live_counts().leaked_since(&before) {
+//!     oxi::print!("leaked {leaked}");
+//! }
+//! ```
+
+use std::fmt;
+
+/// The number of values tracked by the `leak-check` feature that are
+/// currently alive, as returned by [`live_counts`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct LiveCounts {
+    /// The number of [`Array`](crate::Array)s allocated and not yet freed.
+    pub arrays: usize,
+
+    /// The number of [`Dictionary`](crate::Dictionary)s allocated and not
+    /// yet freed.
+    pub dictionaries: usize,
+
+    /// The number of references to Lua values created in the registry and
+    /// not yet released.
+    pub lua_refs: usize,
+}
+
+impl LiveCounts {
+    /// Returns `true` if no tracked value is alive.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns how many more values are alive now than in `before`, or
+    /// `None` if nothing was leaked in between.
+    #[inline]
+    pub fn leaked_since(&self, before: &Self) -> Option<Self> {
+        let leaked = Self {
+            arrays: self.arrays.saturating_sub(before.arrays),
+            dictionaries: self
+                .dictionaries
+                .saturating_sub(before.dictionaries),
+            lua_refs: self.lua_refs.saturating_sub(before.lua_refs),
+        };
+
+        (!leaked.is_empty()).then_some(leaked)
+    }
+}
+
+impl fmt::Display for LiveCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} arrays, {} dictionaries and {} Lua references",
+            self.arrays, self.dictionaries, self.lua_refs
+        )
+    }
+}
+
+/// Returns the number of tracked values that are currently alive.
+pub fn live_counts() -> LiveCounts {
+    LiveCounts {
+        arrays: oxi_types::leak_check::live_arrays(),
+        dictionaries: oxi_types::leak_check::live_dictionaries(),
+        lua_refs: oxi_luajit::leak_check::live_refs(),
+    }
+}
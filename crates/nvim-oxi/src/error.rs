@@ -5,28 +5,78 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 /// `nvim-oxi`'s error type.
 #[derive(Clone, Debug, ThisError)]
-#[cfg_attr(not(feature = "mlua"), derive(Eq, PartialEq))]
+#[cfg_attr(not(feature = "mlua"), derive(PartialEq))]
+#[cfg_attr(not(any(feature = "mlua", feature = "lsp")), derive(Eq))]
 pub enum Error {
-    #[error(transparent)]
+    #[error("{0}")]
     Lua(#[from] oxi_luajit::Error),
 
-    #[error(transparent)]
+    #[error("{0}")]
     Api(#[from] oxi_api::Error),
 
-    #[error(transparent)]
+    #[error("{0}")]
     Nvim(#[from] oxi_types::Error),
 
-    #[error(transparent)]
+    #[error("{0}")]
     ObjectConversion(#[from] oxi_types::conversion::Error),
 
-    #[error(transparent)]
+    #[error("{0}")]
     Serde(#[from] oxi_types::serde::Error),
 
+    #[cfg(feature = "diagnostic")]
+    #[error("{0}")]
+    Diagnostic(#[from] oxi_diagnostic::Error),
+
     #[cfg(feature = "libuv")]
-    #[error(transparent)]
+    #[error("{0}")]
     Libuv(#[from] oxi_libuv::Error),
 
+    #[cfg(feature = "lsp")]
+    #[error("{0}")]
+    Lsp(#[from] oxi_lsp::Error),
+
+    #[cfg(feature = "state")]
+    #[error("{0}")]
+    Persist(#[from] crate::persist::Error),
+
+    #[cfg(feature = "treesitter")]
+    #[error("{0}")]
+    Treesitter(#[from] oxi_treesitter::Error),
+
     #[cfg(feature = "mlua")]
-    #[error(transparent)]
+    #[error("{0}")]
     Mlua(#[from] mlua::Error),
 }
+
+impl Error {
+    /// Returns the kind of the error returned by Neovim, or `None` if the
+    /// error didn't come from Neovim.
+    pub fn nvim_error_kind(&self) -> Option<oxi_types::ErrorKind> {
+        match self {
+            Self::Nvim(err) => err.kind(),
+            Self::Api(err) => err.nvim_error_kind(),
+            _ => None,
+        }
+    }
+
+    /// Returns the stack traceback of the Lua error that caused this error,
+    /// if any.
+    pub fn lua_traceback(&self) -> Option<&str> {
+        match self {
+            Self::Lua(oxi_luajit::Error::LuaError { traceback, .. }) => {
+                traceback.as_deref()
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the path to the value that couldn't be converted if this is a
+    /// conversion error, or an empty slice otherwise.
+    pub fn conversion_path(&self) -> &[oxi_types::conversion::PathSegment] {
+        match self {
+            Self::ObjectConversion(err) => err.path(),
+            Self::Api(err) => err.conversion_path(),
+            _ => &[],
+        }
+    }
+}
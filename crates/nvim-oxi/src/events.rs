@@ -0,0 +1,81 @@
+//! A typed event bus built on [`User`][1] autocommands.
+//!
+//! [`emit`] triggers the `User` autocommands matching the name of the event,
+//! passing them the payload in the `data` field of their arguments, while
+//! [`on`] registers a handler receiving the payload of every emitted event
+//! with the given name. Payloads are converted to and from Rust types using
+//! [Serde], so plugins can share typed events with each other and with Lua
+//! code listening on the same `User` pattern.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::events;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Indexed {
+//!     files: usize,
+//! }
+//!
+//! events::on("MyPluginIndexed", |Indexed { files }| {
+//!     nvim_oxi::print!("indexed {files} files");
+//!     Ok(())
+//! })?;
+//!
+//! events::emit("MyPluginIndexed", &Indexed { files: 42 })?;
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/autocmd.html#User
+//! [Serde]: https://serde.rs
+
+use oxi_types::serde::{Deserializer, Serializer};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::{
+    self,
+    opts::{CreateAutocmdOpts, ExecAutocmdsOpts},
+    types::{AutocmdCallbackArgs, AutocmdEvent},
+};
+use crate::Result;
+
+/// Emits the event `name`, executing the `User` autocommands whose pattern
+/// matches it with `payload` as their `data`.
+pub fn emit<T>(name: &str, payload: &T) -> Result<()>
+where
+    T: Serialize + ?Sized,
+{
+    let opts = ExecAutocmdsOpts::builder()
+        .patterns(name)
+        .data(payload.serialize(Serializer::new())?)
+        .modeline(false)
+        .build();
+
+    api::exec_autocmds([AutocmdEvent::User], &opts)?;
+    Ok(())
+}
+
+/// Calls `handler` with the payload of every event `name` emitted, either
+/// via [`emit`] or by `nvim_exec_autocmds()` from Lua.
+///
+/// Returns the id of the underlying autocommand, which can be passed to
+/// [`api::del_autocmd`] to stop listening. An event whose payload can't be
+/// deserialized into a `T` is reported as an error without calling
+/// `handler`.
+pub fn on<T, F>(name: &str, mut handler: F) -> Result<u32>
+where
+    T: DeserializeOwned,
+    F: FnMut(T) -> Result<()> + 'static,
+{
+    let opts = CreateAutocmdOpts::builder()
+        .patterns([name])
+        .desc(format!("nvim-oxi handler for the {name} event"))
+        .callback(move |args: AutocmdCallbackArgs| {
+            let payload = T::deserialize(Deserializer::new(args.data))?;
+            handler(payload)?;
+            Ok::<_, crate::Error>(false)
+        })
+        .build();
+
+    Ok(api::create_autocmd([AutocmdEvent::User], &opts)?)
+}
@@ -11,9 +11,26 @@
 #![deny(nonstandard_style)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
+pub mod conversion;
+#[cfg(feature = "leak-check")]
+#[cfg_attr(docsrs, doc(cfg(feature = "leak-check")))]
+pub mod debug;
 mod entrypoint;
 mod error;
+pub mod events;
+#[cfg(feature = "state")]
+#[cfg_attr(docsrs, doc(cfg(feature = "state")))]
+pub mod persist;
+pub mod picker;
+pub mod process;
+pub mod rpc;
 mod toplevel;
+pub mod ui;
+#[cfg(feature = "vim-api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "vim-api")))]
+pub mod vim_api;
+#[cfg(feature = "libuv")]
+mod writer;
 
 pub mod api {
     //! Bindings to the [Neovim C API][api].
@@ -21,6 +38,10 @@ pub mod api {
     //! [api]: https://neovim.io/doc/user/api.html
     #[doc(inline)]
     pub use oxi_api::*;
+
+    #[cfg(feature = "libuv")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "libuv")))]
+    pub use crate::writer::ThreadSafeWriter;
 }
 
 #[cfg(feature = "libuv")]
@@ -34,6 +55,16 @@ pub mod libuv {
     pub use oxi_libuv::*;
 }
 
+#[cfg(feature = "lsp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lsp")))]
+pub mod lsp {
+    //! Bindings to Neovim's [`vim.lsp`][1] module.
+    //!
+    //! [1]: https://neovim.io/doc/user/lsp.html
+    #[doc(inline)]
+    pub use oxi_lsp::*;
+}
+
 pub mod lua {
     //! Low-level Rust bindings to [LuaJIT], the Lua version used by Neovim.
     //!
@@ -81,17 +112,31 @@ pub mod mlua {
     }
 }
 
-// #[cfg(feature = "diagnostic")]
-// #[cfg_attr(docsrs, doc(cfg(feature = "diagnostic")))]
-// pub mod diagnostic {
-//     #[doc(inline)]
-//     pub use nvim_diagnostic::*;
-// }
+#[cfg(feature = "diagnostic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostic")))]
+pub mod diagnostic {
+    //! Bindings to Neovim's [`vim.diagnostic`][1] module.
+    //!
+    //! [1]: https://neovim.io/doc/user/diagnostic.html
+    #[doc(inline)]
+    pub use oxi_diagnostic::*;
+}
+
+#[cfg(feature = "treesitter")]
+#[cfg_attr(docsrs, doc(cfg(feature = "treesitter")))]
+pub mod treesitter {
+    //! Bindings to Neovim's [`vim.treesitter`][1] module.
+    //!
+    //! [1]: https://neovim.io/doc/user/treesitter.html
+    #[doc(inline)]
+    pub use oxi_treesitter::*;
+}
 
 #[doc(hidden)]
 pub use entrypoint::entrypoint;
 pub use error::{Error, Result};
-pub use oxi_luajit::{dbg, print};
+pub use oxi_luajit::{dbg, print, println};
+pub use oxi_macros::oxi_export as export;
 pub use oxi_macros::oxi_module as module;
 #[cfg(feature = "test")]
 #[cfg_attr(docsrs, doc(cfg(feature = "test")))]
@@ -128,6 +173,43 @@ pub mod __test {
 
         target_dir.into()
     }
+
+    /// Executes the `setup` chunk of Lua code passed to `#[oxi::test]`.
+    pub fn exec_lua(chunk: &str) {
+        // Wrapping the chunk in a function lets `luaeval()` run statements,
+        // and works with every supported version of Neovim.
+        let expr = format!("(function()\n{chunk}\nend)()");
+        if let Err(err) =
+            crate::api::call_function::<_, crate::Object>("luaeval", (expr,))
+        {
+            panic!("setup failed: {err}");
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __module {
+    use thiserror::Error as ThisError;
+
+    /// The error returned by the functions exported by a `#[module]` with a
+    /// deferred initialization.
+    #[derive(Debug, ThisError)]
+    pub enum ExportError<E> {
+        #[error(transparent)]
+        DeferredInit(crate::Error),
+
+        #[error(transparent)]
+        Call(E),
+    }
+
+    /// Runs the deferred initialization, if it hasn't run yet, before
+    /// calling `fun`.
+    pub fn with_deferred_init<R, E>(
+        fun: impl FnOnce() -> Result<R, E>,
+    ) -> Result<R, ExportError<E>> {
+        crate::run_deferred_init().map_err(ExportError::DeferredInit)?;
+        fun().map_err(ExportError::Call)
+    }
 }
 
 pub use toplevel::*;
@@ -0,0 +1,349 @@
+//! A persistent key-value store for plugin state, enabled by the `state`
+//! feature.
+//!
+//! Unlike [`api::State`](crate::api::State), which lives in Vimscript
+//! variables and is lost when Neovim exits, the values of a [`Store`] are
+//! saved to disk and survive restarts.
+//!
+//! Every [`Store`] is namespaced by the name of the plugin using it, and keeps
+//! each of its values in a separate file under
+//! `stdpath("data")/{namespace}/state`. Values can be of any type implementing
+//! [`Serialize`] and [`DeserializeOwned`], and are encoded either as JSON or as
+//! MessagePack depending on the store's [`Format`].
+//!
+//! Writes are atomic: values are first written to a temporary file which is
+//! then renamed over the old one, so a crash mid-write never leaves a
+//! truncated file behind.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::persist::Store;
+//!
+//! let store = Store::new("my-plugin")?;
+//!
+//! let runs = store.get::<u32>("runs")?.unwrap_or(0);
+//! store.set("runs", &(runs + 1))?;
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error as ThisError;
+
+use crate::api;
+use crate::Result;
+
+/// The errors returned by a [`Store`].
+///
+/// The underlying errors are reference-counted so that the error can be
+/// cloned like the other [`nvim_oxi::Error`](crate::Error)s.
+#[derive(Clone, Debug, ThisError)]
+pub enum Error {
+    /// A namespace or key that can't be used as a file name.
+    #[error("invalid state {what} `{name}`")]
+    InvalidName { what: &'static str, name: String },
+
+    /// Reading or writing a file or directory of the store failed.
+    #[error("{}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: Arc<io::Error>,
+    },
+
+    /// A value couldn't be encoded or decoded as JSON.
+    #[error("{}: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: Arc<serde_json::Error>,
+    },
+
+    /// A value couldn't be encoded as MessagePack.
+    #[error("{}: {source}", path.display())]
+    MsgpackEncode {
+        path: PathBuf,
+        #[source]
+        source: Arc<rmp_serde::encode::Error>,
+    },
+
+    /// A value couldn't be decoded from MessagePack.
+    #[error("{}: {source}", path.display())]
+    MsgpackDecode {
+        path: PathBuf,
+        #[source]
+        source: Arc<rmp_serde::decode::Error>,
+    },
+}
+
+impl Error {
+    /// Returns the kind of the underlying IO error, if this is one.
+    #[inline]
+    pub fn io_error_kind(&self) -> Option<io::ErrorKind> {
+        match self {
+            Self::Io { source, .. } => Some(source.kind()),
+            _ => None,
+        }
+    }
+}
+
+/// Errors are compared by kind and path, since the underlying errors can't
+/// be compared themselves.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        use Error::*;
+
+        match (self, other) {
+            (
+                InvalidName { what, name },
+                InvalidName { what: other_what, name: other_name },
+            ) => what == other_what && name == other_name,
+
+            (Io { path, source }, Io { path: other_path, source: other }) => {
+                path == other_path && source.kind() == other.kind()
+            },
+
+            (Json { path, .. }, Json { path: other, .. })
+            | (
+                MsgpackEncode { path, .. },
+                MsgpackEncode { path: other, .. },
+            )
+            | (
+                MsgpackDecode { path, .. },
+                MsgpackDecode { path: other, .. },
+            ) => path == other,
+
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Error {}
+
+/// The encoding used to store the values of a [`Store`] on disk.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// Human-readable JSON, saved in `.json` files.
+    #[default]
+    Json,
+
+    /// Compact MessagePack, saved in `.msgpack` files.
+    Msgpack,
+}
+
+impl Format {
+    #[inline]
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Msgpack => "msgpack",
+        }
+    }
+
+    fn encode<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        path: &Path,
+    ) -> Result<Vec<u8>> {
+        let path = path.to_owned();
+        let res = match self {
+            Self::Json => serde_json::to_vec_pretty(value)
+                .map_err(|err| Error::Json { path, source: Arc::new(err) }),
+
+            Self::Msgpack => rmp_serde::to_vec_named(value).map_err(|err| {
+                Error::MsgpackEncode { path, source: Arc::new(err) }
+            }),
+        };
+        res.map_err(Into::into)
+    }
+
+    fn decode<T: DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+        path: &Path,
+    ) -> Result<T> {
+        let path = path.to_owned();
+        let res = match self {
+            Self::Json => serde_json::from_slice(bytes)
+                .map_err(|err| Error::Json { path, source: Arc::new(err) }),
+
+            Self::Msgpack => rmp_serde::from_slice(bytes).map_err(|err| {
+                Error::MsgpackDecode { path, source: Arc::new(err) }
+            }),
+        };
+        res.map_err(Into::into)
+    }
+}
+
+/// A namespaced, file-backed key-value store.
+///
+/// Keys must be non-empty, can't start with a `.` and can't contain path
+/// separators, since they're used as file names.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Store {
+    dir: PathBuf,
+    format: Format,
+}
+
+impl Store {
+    /// Creates a new JSON store for the given namespace, saving its values
+    /// under `stdpath("data")/{namespace}/state`.
+    ///
+    /// The directory is only created when the first value is written.
+    pub fn new(namespace: &str) -> Result<Self> {
+        validate_name("namespace", namespace)?;
+        let dir = api::fn_::stdpath("data")?.join(namespace).join("state");
+        Ok(Self::with_dir(dir))
+    }
+
+    /// Creates a new JSON store saving its values in the given directory.
+    pub fn with_dir<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into(), format: Format::default() }
+    }
+
+    /// Sets the format used to encode the values of the store.
+    ///
+    /// Stores using different formats don't see each other's values, even
+    /// if they share the same directory.
+    #[inline]
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Returns the directory the values of the store are saved in.
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the file the value of `key` is saved in.
+    pub fn path(&self, key: &str) -> Result<PathBuf> {
+        validate_name("key", key)?;
+        Ok(self.file(key))
+    }
+
+    /// Returns the value stored under `key`, or `None` if there isn't one.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let path = self.path(key)?;
+
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(None)
+            },
+            Err(err) => return Err(io_error(&path, err)),
+        };
+
+        self.format.decode(&bytes, &path).map(Some)
+    }
+
+    /// Stores `value` under `key`, overwriting any previous value.
+    pub fn set<T: Serialize + ?Sized>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<()> {
+        let path = self.path(key)?;
+        let bytes = self.format.encode(value, &path)?;
+
+        fs::create_dir_all(&self.dir)
+            .map_err(|err| io_error(&self.dir, err))?;
+
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            self.format.extension(),
+            std::process::id()
+        ));
+
+        write_file(&tmp, &bytes)
+            .and_then(|()| fs::rename(&tmp, &path))
+            .map_err(|err| {
+                let _ = fs::remove_file(&tmp);
+                io_error(&path, err)
+            })
+    }
+
+    /// Removes the value stored under `key`, returning whether there was one.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        let path = self.path(key)?;
+
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(io_error(&path, err)),
+        }
+    }
+
+    /// Returns the keys of all the values in the store, sorted
+    /// alphabetically.
+    pub fn keys(&self) -> Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(Vec::new())
+            },
+            Err(err) => return Err(io_error(&self.dir, err)),
+        };
+
+        let mut keys = Vec::new();
+
+        for entry in entries {
+            let path = entry.map_err(|err| io_error(&self.dir, err))?.path();
+
+            if path.extension().and_then(|ext| ext.to_str())
+                != Some(self.format.extension())
+            {
+                continue;
+            }
+
+            if let Some(key) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                keys.push(key.to_owned());
+            }
+        }
+
+        keys.sort_unstable();
+
+        Ok(keys)
+    }
+
+    /// Removes all the values in the store.
+    pub fn clear(&self) -> Result<()> {
+        for key in self.keys()? {
+            self.remove(&key)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn file(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.{}", self.format.extension()))
+    }
+}
+
+fn validate_name(what: &'static str, name: &str) -> Result<()> {
+    let is_valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(std::path::is_separator);
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidName { what, name: name.to_owned() }.into())
+    }
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+fn io_error(path: &Path, err: io::Error) -> crate::Error {
+    Error::Io { path: path.to_owned(), source: Arc::new(err) }.into()
+}
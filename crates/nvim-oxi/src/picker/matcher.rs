@@ -0,0 +1,96 @@
+/// Decides which items of a [`Picker`](super::Picker) match the query and in
+/// which order they're listed.
+///
+/// Closures taking the query and the text of an item are matchers too.
+pub trait Matcher {
+    /// Returns the score of `item` for `query`, or `None` if it doesn't
+    /// match. Items with higher scores are listed first, and items with the
+    /// same score are listed in the order they were added.
+    fn score(&self, query: &str, item: &str) -> Option<i64>;
+}
+
+impl<F> Matcher for F
+where
+    F: Fn(&str, &str) -> Option<i64>,
+{
+    #[inline]
+    fn score(&self, query: &str, item: &str) -> Option<i64> {
+        self(query, item)
+    }
+}
+
+/// The default [`Matcher`], matching the items that contain all the
+/// characters of the query in the same order, although not necessarily
+/// next to each other.
+///
+/// The comparison ignores case unless the query contains an uppercase
+/// character. Consecutive matches and matches at the start of a word score
+/// higher, while the characters skipped between matches lower the score.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SubsequenceMatcher;
+
+impl SubsequenceMatcher {
+    const MATCH: i64 = 16;
+    const CONSECUTIVE: i64 = 16;
+    const WORD_START: i64 = 8;
+    const GAP: i64 = 1;
+}
+
+impl Matcher for SubsequenceMatcher {
+    fn score(&self, query: &str, item: &str) -> Option<i64> {
+        let ignore_case = !query.chars().any(char::is_uppercase);
+
+        let mut query = query.chars().peekable();
+        let mut score = 0;
+        let mut prev = None;
+        let mut last_match = None;
+
+        for (idx, ch) in item.chars().enumerate() {
+            let expected = match query.peek() {
+                Some(&expected) => expected,
+                None => break,
+            };
+
+            let is_match = if ignore_case {
+                ch.to_lowercase().eq(expected.to_lowercase())
+            } else {
+                ch == expected
+            };
+
+            if is_match {
+                score += Self::MATCH;
+
+                let skipped = match last_match {
+                    Some(last) => idx - last - 1,
+                    None => idx,
+                };
+
+                if skipped == 0 && last_match.is_some() {
+                    score += Self::CONSECUTIVE;
+                }
+
+                if is_word_start(prev, ch) {
+                    score += Self::WORD_START;
+                }
+
+                score -= skipped as i64 * Self::GAP;
+                last_match = Some(idx);
+                query.next();
+            }
+
+            prev = Some(ch);
+        }
+
+        query.peek().is_none().then_some(score)
+    }
+}
+
+/// Whether `ch` starts a word, either because it follows a separator or
+/// because it's an uppercase character following a lowercase one.
+fn is_word_start(prev: Option<char>, ch: char) -> bool {
+    match prev {
+        None => true,
+        Some(prev) if !prev.is_alphanumeric() => true,
+        Some(prev) => prev.is_lowercase() && ch.is_uppercase(),
+    }
+}
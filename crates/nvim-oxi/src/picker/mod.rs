@@ -0,0 +1,517 @@
+//! A fuzzy finder made of a prompt and a list of results, each displayed in
+//! its own floating window.
+//!
+//! A [`Picker`] lists the items whose text matches the query typed in the
+//! prompt, ordered by the score given to them by its [`Matcher`]. Pressing
+//! `<CR>` closes the picker and passes the selected item to the
+//! [`on_select`](Picker::on_select) callback, while `<Esc>`, `<C-c>` or
+//! leaving the prompt window cancels it. `<C-n>`/`<Down>` and
+//! `<C-p>`/`<Up>` move the selection.
+//!
+//! Items can be added to an open picker via [`PickerHandle::extend`], e.g.
+//! as they're produced by an external [`process`](crate::process).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::{api, picker::Picker};
+//!
+//! let buffers = api::list_bufs()
+//!     .map(|buf| buf.get_name().unwrap().display().to_string());
+//!
+//! Picker::new(buffers)
+//!     .prompt("Buffers> ")
+//!     .on_select(|name| Ok(api::command(&format!("buffer {name}"))?))
+//!     .open()?;
+//! ```
+
+mod matcher;
+
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::fmt;
+use std::rc::Rc;
+
+pub use matcher::*;
+use oxi_types::Function;
+
+use crate::api::{
+    self,
+    options::{self, Scope},
+    opts::{CreateAutocmdOpts, SetKeymapOpts},
+    types::{
+        AutocmdCallbackArgs,
+        AutocmdEvent,
+        Mode,
+        WindowBorder,
+        WindowConfig,
+        WindowConfigBuilder,
+        WindowRelativeTo,
+        WindowStyle,
+    },
+    Buffer,
+    Window,
+};
+use crate::Result;
+
+type SelectCallback<T> = Box<dyn FnOnce(T) -> Result<()>>;
+
+type CancelCallback = Box<dyn FnOnce() -> Result<()>>;
+
+type Action<T> = fn(&PickerHandle<T>) -> Result<()>;
+
+/// A builder for the pickers listing items of type `T`, which are displayed
+/// using their [`Display`](fmt::Display) implementation.
+pub struct Picker<T> {
+    items: Vec<T>,
+    matcher: Box<dyn Matcher>,
+    prompt: String,
+    size: Option<(u32, u32)>,
+    on_select: Option<SelectCallback<T>>,
+    on_cancel: Option<CancelCallback>,
+}
+
+impl<T: fmt::Display + 'static> Picker<T> {
+    /// Creates a new picker listing the given items.
+    #[inline]
+    pub fn new(items: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+            matcher: Box::new(SubsequenceMatcher),
+            prompt: "> ".to_owned(),
+            size: None,
+            on_select: None,
+            on_cancel: None,
+        }
+    }
+
+    /// The [`Matcher`] used to filter and sort the items. Defaults to
+    /// [`SubsequenceMatcher`].
+    #[inline]
+    pub fn matcher<M: Matcher + 'static>(&mut self, matcher: M) -> &mut Self {
+        self.matcher = Box::new(matcher);
+        self
+    }
+
+    /// Registers a callback called when the user cancels the picker.
+    #[inline]
+    pub fn on_cancel<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce() -> Result<()> + 'static,
+    {
+        self.on_cancel = Some(Box::new(fun));
+        self
+    }
+
+    /// Registers a callback called with the item selected by the user, after
+    /// the picker has been closed.
+    #[inline]
+    pub fn on_select<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce(T) -> Result<()> + 'static,
+    {
+        self.on_select = Some(Box::new(fun));
+        self
+    }
+
+    /// The text displayed before the query. Defaults to `"> "`.
+    #[inline]
+    pub fn prompt(&mut self, prompt: impl Into<String>) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// The width of the picker and the height of the results window, in
+    /// cells. Defaults to 60% of the width and half of the height of the
+    /// editor.
+    #[inline]
+    pub fn size(&mut self, width: u32, height: u32) -> &mut Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Opens the picker, entering the prompt window in insert mode. The
+    /// items and the callbacks are moved into the opened picker.
+    pub fn open(&mut self) -> Result<PickerHandle<T>> {
+        let columns = options::get::<u32>("columns", Scope::Global)?;
+        let lines = options::get::<u32>("lines", Scope::Global)?;
+
+        let (width, height) =
+            self.size.unwrap_or((columns * 3 / 5, lines / 2));
+        let width = width.min(columns.saturating_sub(2)).max(1);
+        let height = height.min(lines.saturating_sub(6)).max(1);
+
+        // The prompt and the results are stacked on top of each other, each
+        // surrounded by a border.
+        let row = lines.saturating_sub(height + 5) / 2;
+        let col = columns.saturating_sub(width + 2) / 2;
+
+        let mut prompt_buf = scratch_buffer()?;
+        options::set("buftype", "prompt", Scope::Buffer(&prompt_buf))?;
+        api::call_function::<_, oxi_types::Object>(
+            "prompt_setprompt",
+            (prompt_buf.clone(), self.prompt.as_str()),
+        )?;
+
+        let results_buf = scratch_buffer()?;
+
+        let prompt_win = api::open_win(
+            &prompt_buf,
+            true,
+            &float_config(row, col, width, 1).focusable(true).build(),
+        )?;
+
+        let results_win = api::open_win(
+            &results_buf,
+            false,
+            &float_config(row + 3, col, width, height)
+                .focusable(false)
+                .build(),
+        )?;
+
+        options::set("cursorline", true, Scope::Window(&results_win))?;
+
+        let items = std::mem::take(&mut self.items)
+            .into_iter()
+            .map(Entry::new)
+            .collect();
+
+        let state = Rc::new(RefCell::new(State {
+            items,
+            matcher: std::mem::replace(
+                &mut self.matcher,
+                Box::new(SubsequenceMatcher),
+            ),
+            prompt_len: self.prompt.len(),
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            prompt_win,
+            results_buf,
+            results_win,
+            on_select: self.on_select.take(),
+            on_cancel: self.on_cancel.take(),
+            closed: false,
+        }));
+
+        let handle = PickerHandle { state };
+
+        let keymaps: [(&str, Action<T>); 7] = [
+            ("<CR>", PickerHandle::confirm),
+            ("<Esc>", PickerHandle::cancel),
+            ("<C-c>", PickerHandle::cancel),
+            ("<C-n>", PickerHandle::next),
+            ("<Down>", PickerHandle::next),
+            ("<C-p>", PickerHandle::previous),
+            ("<Up>", PickerHandle::previous),
+        ];
+
+        for (lhs, action) in keymaps {
+            for mode in [Mode::Insert, Mode::Normal] {
+                set_keymap(&mut prompt_buf, mode, lhs, &handle, action)?;
+            }
+        }
+
+        let normal_keymaps: [(&str, Action<T>); 2] =
+            [("j", PickerHandle::next), ("k", PickerHandle::previous)];
+
+        for (lhs, action) in normal_keymaps {
+            set_keymap(&mut prompt_buf, Mode::Normal, lhs, &handle, action)?;
+        }
+
+        let on_change = {
+            let handle = handle.clone();
+            CreateAutocmdOpts::builder()
+                .buffer(prompt_buf.clone())
+                .callback(move |_: AutocmdCallbackArgs| {
+                    handle.refresh()?;
+                    Ok::<_, crate::Error>(false)
+                })
+                .build()
+        };
+
+        api::create_autocmd(
+            [AutocmdEvent::TextChanged, AutocmdEvent::TextChangedI],
+            &on_change,
+        )?;
+
+        // Closing windows isn't allowed while `WinLeave` autocommands are
+        // running, so cancelling is deferred.
+        let on_leave = {
+            let handle = handle.clone();
+            CreateAutocmdOpts::builder()
+                .buffer(prompt_buf)
+                .callback(move |_: AutocmdCallbackArgs| {
+                    let handle = handle.clone();
+                    crate::schedule(move |()| handle.cancel());
+                    Ok::<_, crate::Error>(true)
+                })
+                .build()
+        };
+
+        api::create_autocmd([AutocmdEvent::WinLeave], &on_leave)?;
+
+        handle.refresh()?;
+        api::command("startinsert")?;
+
+        Ok(handle)
+    }
+}
+
+/// A handle to an open [`Picker`].
+pub struct PickerHandle<T> {
+    state: Rc<RefCell<State<T>>>,
+}
+
+impl<T> Clone for PickerHandle<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { state: Rc::clone(&self.state) }
+    }
+}
+
+impl<T: fmt::Display + 'static> PickerHandle<T> {
+    /// Closes the picker without calling any of its callbacks. Does nothing
+    /// if it's already closed.
+    pub fn close(&self) -> Result<()> {
+        let (prompt_win, results_win) = {
+            let state = &mut *self.state.borrow_mut();
+
+            if state.closed {
+                return Ok(());
+            }
+
+            state.closed = true;
+            (state.prompt_win.clone(), state.results_win.clone())
+        };
+
+        api::command("stopinsert")?;
+
+        for win in [prompt_win, results_win] {
+            if win.is_valid() {
+                win.close(true)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds more items to the picker, updating the results if it's still
+    /// open.
+    pub fn extend(&self, items: impl IntoIterator<Item = T>) -> Result<()> {
+        let is_open = {
+            let state = &mut *self.state.borrow_mut();
+            state.items.extend(items.into_iter().map(Entry::new));
+            !state.closed
+        };
+
+        if is_open {
+            self.refresh()?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the picker is still open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        !self.state.borrow().closed
+    }
+
+    /// Returns the query currently typed in the prompt.
+    #[inline]
+    pub fn query(&self) -> String {
+        self.state.borrow().query.clone()
+    }
+
+    /// Returns the text of the selected item, if any item matches the
+    /// query.
+    #[inline]
+    pub fn selected(&self) -> Option<String> {
+        let state = self.state.borrow();
+        state.selected_idx().map(|idx| state.items[idx].text.clone())
+    }
+
+    fn cancel(&self) -> Result<()> {
+        if !self.is_open() {
+            return Ok(());
+        }
+
+        self.close()?;
+
+        match self.state.borrow_mut().on_cancel.take() {
+            Some(on_cancel) => on_cancel(),
+            None => Ok(()),
+        }
+    }
+
+    fn confirm(&self) -> Result<()> {
+        if self.state.borrow().selected_idx().is_none() {
+            return Ok(());
+        }
+
+        self.close()?;
+
+        let (item, on_select) = {
+            let state = &mut *self.state.borrow_mut();
+            let idx = state.selected_idx().expect("checked above");
+            (state.items.swap_remove(idx).item, state.on_select.take())
+        };
+
+        match on_select {
+            Some(on_select) => on_select(item),
+            None => Ok(()),
+        }
+    }
+
+    fn next(&self) -> Result<()> {
+        self.move_selection(|selected, len| (selected + 1) % len)
+    }
+
+    fn previous(&self) -> Result<()> {
+        self.move_selection(|selected, len| (selected + len - 1) % len)
+    }
+
+    fn move_selection(&self, fun: fn(usize, usize) -> usize) -> Result<()> {
+        let (mut results_win, selected) = {
+            let state = &mut *self.state.borrow_mut();
+
+            if state.matches.is_empty() {
+                return Ok(());
+            }
+
+            state.selected = fun(state.selected, state.matches.len());
+            (state.results_win.clone(), state.selected)
+        };
+
+        results_win.set_cursor(selected + 1, 0)?;
+        Ok(())
+    }
+
+    /// Filters the items using the query typed in the prompt and displays
+    /// the ones that match.
+    fn refresh(&self) -> Result<()> {
+        let (prompt_win, prompt_len) = {
+            let state = self.state.borrow();
+            (state.prompt_win.clone(), state.prompt_len)
+        };
+
+        let line = prompt_win.current_line()?.to_string_lossy().into_owned();
+        let query = line.get(prompt_len..).unwrap_or_default().to_owned();
+
+        let (mut results_buf, mut results_win, lines) = {
+            let state = &mut *self.state.borrow_mut();
+            state.update(query);
+
+            let lines = state
+                .matches
+                .iter()
+                .map(|&idx| state.items[idx].text.clone())
+                .collect::<Vec<_>>();
+
+            (state.results_buf.clone(), state.results_win.clone(), lines)
+        };
+
+        results_buf.set_lines(.., false, lines)?;
+        results_win.set_cursor(1, 0)?;
+
+        Ok(())
+    }
+}
+
+struct State<T> {
+    items: Vec<Entry<T>>,
+    matcher: Box<dyn Matcher>,
+    prompt_len: usize,
+    query: String,
+    /// The indices of the items matching the query, from best to worst.
+    matches: Vec<usize>,
+    selected: usize,
+    prompt_win: Window,
+    results_buf: Buffer,
+    results_win: Window,
+    on_select: Option<SelectCallback<T>>,
+    on_cancel: Option<CancelCallback>,
+    closed: bool,
+}
+
+impl<T> State<T> {
+    fn selected_idx(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    fn update(&mut self, query: String) {
+        let mut matches = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                let score = self.matcher.score(&query, &entry.text)?;
+                Some((idx, score))
+            })
+            .collect::<Vec<_>>();
+
+        matches.sort_by_key(|&(_, score)| Reverse(score));
+
+        self.matches = matches.into_iter().map(|(idx, _)| idx).collect();
+        self.selected = 0;
+        self.query = query;
+    }
+}
+
+struct Entry<T> {
+    /// The text of the item as displayed in the results window, which can't
+    /// contain newlines.
+    text: String,
+    item: T,
+}
+
+impl<T: fmt::Display> Entry<T> {
+    fn new(item: T) -> Self {
+        let text = item.to_string().replace('\n', " ");
+        Self { text, item }
+    }
+}
+
+fn set_keymap<T: fmt::Display + 'static>(
+    buf: &mut Buffer,
+    mode: Mode,
+    lhs: &str,
+    handle: &PickerHandle<T>,
+    action: Action<T>,
+) -> Result<()> {
+    let handle = handle.clone();
+
+    let opts = SetKeymapOpts::builder()
+        .callback(Function::from_fn_mut(move |()| action(&handle)))
+        .noremap(true)
+        .nowait(true)
+        .silent(true)
+        .build();
+
+    Ok(buf.set_keymap(mode, lhs, "", &opts)?)
+}
+
+fn scratch_buffer() -> Result<Buffer> {
+    let buf = api::create_buf(false, true)?;
+    options::set("bufhidden", "wipe", Scope::Buffer(&buf))?;
+    Ok(buf)
+}
+
+fn float_config(
+    row: u32,
+    col: u32,
+    width: u32,
+    height: u32,
+) -> WindowConfigBuilder {
+    let mut builder = WindowConfig::builder();
+    builder
+        .relative(WindowRelativeTo::Editor)
+        .row(row)
+        .col(col)
+        .width(width)
+        .height(height)
+        .border(WindowBorder::Rounded)
+        .style(WindowStyle::Minimal);
+    builder
+}
@@ -0,0 +1,465 @@
+//! Spawning of external processes, the Rust equivalent of
+//! [`vim.system()`][1].
+//!
+//! Processes are started as Neovim [jobs][2], so their output is read by the
+//! editor's event loop and passed to the callbacks registered on the
+//! [`Command`] without blocking. [`Child::wait`] and [`Command::output`] can
+//! be used to block until the process exits, which also blocks the editor.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::process::Command;
+//!
+//! Command::new("rustfmt")
+//!     .args(["--emit", "stdout"])
+//!     .stdin("fn main() {}")
+//!     .on_stdout(|chunk| Ok(nvim_oxi::print!("{chunk}")))
+//!     .on_exit(|status| Ok(nvim_oxi::print!("rustfmt exited with {}", status.code())))
+//!     .spawn()?;
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/lua.html#vim.system()
+//! [2]: https://neovim.io/doc/user/job_control.html
+
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+use oxi_luajit::{self as lua, ffi::*};
+use oxi_types::{
+    self as nvim,
+    conversion::FromObject,
+    Array,
+    Dictionary,
+    Function,
+    Integer,
+    LuaRef,
+    Object,
+};
+
+use crate::api;
+use crate::Result;
+
+type OutputCallback = Box<dyn FnMut(String) -> Result<()>>;
+
+type ExitCallback = Box<dyn FnOnce(ExitStatus) -> Result<()>>;
+
+/// A builder for the processes spawned as Neovim jobs.
+///
+/// Unless stated otherwise the process inherits the editor's environment and
+/// working directory.
+#[derive(Default)]
+pub struct Command {
+    program: String,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    clear_env: bool,
+    stdin: Option<Vec<u8>>,
+    on_stdout: Option<OutputCallback>,
+    on_stderr: Option<OutputCallback>,
+    on_exit: Option<ExitCallback>,
+}
+
+impl Command {
+    /// Creates a new command running `program`, which is looked up in
+    /// `$PATH` if it's not a path.
+    #[inline]
+    pub fn new(program: impl Into<String>) -> Self {
+        Self { program: program.into(), ..Default::default() }
+    }
+
+    /// Adds an argument to the command.
+    #[inline]
+    pub fn arg(&mut self, arg: impl Into<String>) -> &mut Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds multiple arguments to the command.
+    #[inline]
+    pub fn args<S, I>(&mut self, args: I) -> &mut Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the working directory of the process.
+    #[inline]
+    pub fn current_dir(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        self.cwd = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    /// Sets an environment variable of the process.
+    #[inline]
+    pub fn env(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets multiple environment variables of the process.
+    #[inline]
+    pub fn envs<K, V, I>(&mut self, vars: I) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.env.extend(vars.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Doesn't pass the editor's environment to the process, only the
+    /// variables set via [`env`](Self::env) and [`envs`](Self::envs).
+    #[inline]
+    pub fn env_clear(&mut self) -> &mut Self {
+        self.clear_env = true;
+        self
+    }
+
+    /// Writes `data` to the process' stdin right after it's spawned, then
+    /// closes it. If this is not set stdin is left open and can be written
+    /// to via [`Child::write`].
+    #[inline]
+    pub fn stdin(&mut self, data: impl Into<Vec<u8>>) -> &mut Self {
+        self.stdin = Some(data.into());
+        self
+    }
+
+    /// Registers a callback called with the chunks of text written by the
+    /// process to its stdout. Lines can be split across chunks.
+    #[inline]
+    pub fn on_stdout<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(String) -> Result<()> + 'static,
+    {
+        self.on_stdout = Some(Box::new(fun));
+        self
+    }
+
+    /// Registers a callback called with the chunks of text written by the
+    /// process to its stderr. Lines can be split across chunks.
+    #[inline]
+    pub fn on_stderr<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(String) -> Result<()> + 'static,
+    {
+        self.on_stderr = Some(Box::new(fun));
+        self
+    }
+
+    /// Registers a callback called when the process exits, after all of its
+    /// output has been passed to the other callbacks.
+    #[inline]
+    pub fn on_exit<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce(ExitStatus) -> Result<()> + 'static,
+    {
+        self.on_exit = Some(Box::new(fun));
+        self
+    }
+
+    /// Spawns the process. The callbacks are moved into the spawned job, so
+    /// they have to be registered again to spawn the command a second time.
+    pub fn spawn(&mut self) -> Result<Child> {
+        let cmd = std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|arg| nvim::String::from(arg.as_str()))
+            .collect::<Array>();
+
+        let mut opts = Vec::<(&str, Object)>::new();
+
+        if let Some(cwd) = &self.cwd {
+            opts.push(("cwd", cwd.to_string_lossy().as_ref().into()));
+        }
+
+        if !self.env.is_empty() {
+            let env = self
+                .env
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect::<Dictionary>();
+            opts.push(("env", env.into()));
+        }
+
+        if self.clear_env {
+            opts.push(("clear_env", true.into()));
+        }
+
+        let status = Rc::new(Cell::new(None));
+        let refs = Rc::new(RefCell::new(Vec::<LuaRef>::new()));
+
+        if let Some(fun) = self.on_stdout.take() {
+            let fun = output_callback(fun);
+            refs.borrow_mut().push(fun.lua_ref());
+            opts.push(("on_stdout", fun.into()));
+        }
+
+        if let Some(fun) = self.on_stderr.take() {
+            let fun = output_callback(fun);
+            refs.borrow_mut().push(fun.lua_ref());
+            opts.push(("on_stderr", fun.into()));
+        }
+
+        let on_exit = {
+            let fun = self.on_exit.take();
+            let status = Rc::clone(&status);
+            let refs = Rc::clone(&refs);
+
+            Function::from_fn_once(
+                move |(_, code, _): (Integer, Integer, nvim::String)| {
+                    let exit_status = ExitStatus(code as i32);
+                    status.set(Some(exit_status));
+                    // The process won't call any other callback.
+                    refs.borrow().iter().for_each(|&lua_ref| unref(lua_ref));
+                    fun.map_or(Ok(()), |fun| fun(exit_status))
+                },
+            )
+        };
+
+        refs.borrow_mut().push(on_exit.lua_ref());
+        opts.push(("on_exit", on_exit.into()));
+
+        let opts = opts.into_iter().collect::<Dictionary>();
+
+        let unref_all = || refs.borrow().iter().for_each(|&r| unref(r));
+
+        let id = match api::call_function::<_, i64>("jobstart", (cmd, opts)) {
+            Ok(id) => id,
+            Err(err) => {
+                unref_all();
+                return Err(err.into());
+            },
+        };
+
+        if id <= 0 {
+            unref_all();
+
+            let msg = if id == -1 {
+                format!("`{}` is not executable", self.program)
+            } else {
+                format!("jobstart() failed to spawn `{}`", self.program)
+            };
+
+            return Err(api::Error::Other(msg).into());
+        }
+
+        let child = Child { id: id as u32, status };
+
+        if let Some(data) = self.stdin.take() {
+            child.write(&data)?;
+            child.close_stdin()?;
+        }
+
+        Ok(child)
+    }
+
+    /// Spawns the process and blocks until it exits, collecting its output.
+    /// Stdin is closed right away if no data was set via
+    /// [`stdin`](Self::stdin).
+    ///
+    /// This replaces the callbacks registered via
+    /// [`on_stdout`](Self::on_stdout) and [`on_stderr`](Self::on_stderr).
+    pub fn output(&mut self) -> Result<Output> {
+        let stdout = Rc::new(RefCell::new(String::new()));
+        let stderr = Rc::new(RefCell::new(String::new()));
+
+        let out = Rc::clone(&stdout);
+        self.on_stdout(move |chunk| {
+            out.borrow_mut().push_str(&chunk);
+            Ok(())
+        });
+
+        let err = Rc::clone(&stderr);
+        self.on_stderr(move |chunk| {
+            err.borrow_mut().push_str(&chunk);
+            Ok(())
+        });
+
+        let has_stdin = self.stdin.is_some();
+        let child = self.spawn()?;
+        if !has_stdin {
+            child.close_stdin()?;
+        }
+
+        let status = child.wait()?;
+
+        Ok(Output { status, stdout: stdout.take(), stderr: stderr.take() })
+    }
+}
+
+/// Wraps a callback passed to [`Command::on_stdout`] or
+/// [`Command::on_stderr`] into the function called by `jobstart()`.
+fn output_callback(
+    mut fun: OutputCallback,
+) -> Function<(Integer, Array, nvim::String), ()> {
+    Function::from_fn_mut(
+        move |(_, data, _): (Integer, Array, nvim::String)| {
+            // The lines in `data` are the chunk split on newlines, and a
+            // chunk with a single empty line signals EOF.
+            let lines = data
+                .into_iter()
+                .map(|line| {
+                    nvim::String::from_object(line)
+                        .map(|line| line.to_string_lossy().into_owned())
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            let chunk = lines.join("\n");
+
+            if chunk.is_empty() {
+                Ok(())
+            } else {
+                fun(chunk)
+            }
+        },
+    )
+}
+
+/// Removes a function from the Lua registry.
+fn unref(lua_ref: LuaRef) {
+    unsafe {
+        lua::with_state(|lstate| {
+            luaL_unref(lstate, LUA_REGISTRYINDEX, lua_ref);
+        })
+    };
+}
+
+/// A process spawned via [`Command::spawn`].
+#[derive(Clone, Debug)]
+pub struct Child {
+    id: u32,
+    status: Rc<Cell<Option<ExitStatus>>>,
+}
+
+impl Child {
+    /// The id of the job, which is also the id of its channel.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Writes `data` to the process' stdin.
+    pub fn write(&self, data: &[u8]) -> Result<()> {
+        // A list of lines is sent joined by newlines, which unlike a string
+        // can also hold invalid UTF-8.
+        let lines = data
+            .split(|&byte| byte == b'\n')
+            .map(nvim::String::from_bytes)
+            .collect::<Array>();
+
+        api::call_function::<_, Object>("chansend", (self.id, lines))?;
+        Ok(())
+    }
+
+    /// Closes the process' stdin.
+    pub fn close_stdin(&self) -> Result<()> {
+        api::call_function::<_, Object>("chanclose", (self.id, "stdin"))?;
+        Ok(())
+    }
+
+    /// Stops the process by sending it `SIGTERM`, followed by `SIGKILL` if
+    /// it's still running after a timeout.
+    pub fn kill(&self) -> Result<()> {
+        api::call_function::<_, Object>("jobstop", (self.id,))?;
+        Ok(())
+    }
+
+    /// Returns the exit status of the process if it has already exited,
+    /// without blocking.
+    #[inline]
+    pub fn try_wait(&self) -> Option<ExitStatus> {
+        self.status.get()
+    }
+
+    /// Blocks until the process exits, returning its exit status. The
+    /// callbacks of the process are still called while waiting.
+    pub fn wait(&self) -> Result<ExitStatus> {
+        self.jobwait(-1).map(|status| status.expect("no timeout"))
+    }
+
+    /// Like [`wait`](Self::wait), but returns `None` if the process is still
+    /// running after `timeout`.
+    pub fn wait_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<ExitStatus>> {
+        let millis = Integer::try_from(timeout.as_millis()).unwrap_or(-1);
+        self.jobwait(millis)
+    }
+
+    fn jobwait(&self, timeout: Integer) -> Result<Option<ExitStatus>> {
+        if let Some(status) = self.status.get() {
+            return Ok(Some(status));
+        }
+
+        let codes = api::call_function::<_, Vec<Integer>>(
+            "jobwait",
+            (Array::from_iter([self.id]), timeout),
+        )?;
+
+        match codes.first().copied().unwrap_or(-3) {
+            -1 => Ok(None),
+
+            -2 => Err(api::Error::Other(format!(
+                "waiting for job {} was interrupted",
+                self.id
+            ))
+            .into()),
+
+            // The job has already exited and its exit has been processed.
+            -3 => match self.status.get() {
+                Some(status) => Ok(Some(status)),
+                None => Err(api::Error::Other(format!(
+                    "{} is not a valid job id",
+                    self.id
+                ))
+                .into()),
+            },
+
+            code => Ok(Some(ExitStatus(code as i32))),
+        }
+    }
+}
+
+/// The exit status of a process.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ExitStatus(i32);
+
+impl ExitStatus {
+    /// The exit code of the process. Processes killed by a signal have a
+    /// code of `128` plus the signal number.
+    #[inline]
+    pub fn code(&self) -> i32 {
+        self.0
+    }
+
+    /// Whether the process exited successfully, i.e. with a code of `0`.
+    #[inline]
+    pub fn success(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// The output of a process run via [`Command::output`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Output {
+    /// The exit status of the process.
+    pub status: ExitStatus,
+
+    /// Everything the process wrote to its stdout.
+    pub stdout: String,
+
+    /// Everything the process wrote to its stderr.
+    pub stderr: String,
+}
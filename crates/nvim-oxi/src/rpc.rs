@@ -0,0 +1,264 @@
+//! Helpers to talk to external processes over [msgpack-RPC][1] channels.
+//!
+//! An [`RpcChannel`] wraps the id of a channel opened with `rpc = true`, e.g.
+//! by [`jobstart()`][2] or [`sockconnect()`][3]. Arguments and return values
+//! are converted to and from Rust types using [Serde], and Neovim takes care
+//! of encoding them as msgpack.
+//!
+//! # Handling requests
+//!
+//! Requests sent by the remote process are handled by Neovim's API, so they
+//! can't be routed to Rust directly. Handlers registered via
+//! [`RpcChannel::on_request`] are instead called by the Lua function
+//! returned by [`dispatcher`], with signature `(channel_id, method, args)`.
+//! Plugins should expose it in their module table, e.g.
+//!
+//! ```ignore
+//! #[nvim_oxi::module]
+//! fn my_plugin() -> nvim_oxi::Result<nvim_oxi::Dictionary> {
+//!     Ok(nvim_oxi::Dictionary::from_iter([(
+//!         "rpc_dispatch",
+//!         nvim_oxi::rpc::dispatcher(),
+//!     )]))
+//! }
+//! ```
+//!
+//! so that the remote can call it with [`nvim_exec_lua`][4]:
+//!
+//! ```text
+//! nvim_exec_lua("return require('my_plugin').rpc_dispatch(...)", [channel_id, "method", args])
+//! ```
+//!
+//! where `channel_id` is the id of the channel as seen by Neovim, which the
+//! remote can get from [`nvim_get_api_info`][5].
+//!
+//! [1]: https://neovim.io/doc/user/api.html#RPC
+//! [2]: https://neovim.io/doc/user/builtin.html#jobstart()
+//! [3]: https://neovim.io/doc/user/builtin.html#sockconnect()
+//! [4]: https://neovim.io/doc/user/api.html#nvim_exec_lua()
+//! [5]: https://neovim.io/doc/user/api.html#nvim_get_api_info()
+//! [Serde]: https://serde.rs
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use oxi_types::{
+    serde::{Deserializer, Serializer},
+    Array,
+    Dictionary,
+    Function,
+    Object,
+    ObjectKind,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::api;
+use crate::Result;
+
+type Handler = Box<dyn FnMut(Object) -> Result<Object>>;
+
+/// The Lua function returned by [`dispatcher`].
+pub type Dispatcher = Function<(u32, String, Object), Object>;
+
+thread_local! {
+    static HANDLERS: RefCell<HashMap<(u32, String), Handler>> =
+        RefCell::new(HashMap::new());
+
+    static DISPATCHER: RefCell<Option<Dispatcher>> =
+        const { RefCell::new(None) };
+}
+
+/// The kind of socket to connect to in [`RpcChannel::connect`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SocketMode {
+    /// A named pipe or Unix domain socket.
+    Pipe,
+
+    /// A TCP socket, with an address of the form `host:port`.
+    Tcp,
+}
+
+impl SocketMode {
+    #[inline]
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pipe => "pipe",
+            Self::Tcp => "tcp",
+        }
+    }
+}
+
+/// A msgpack-RPC channel to an external process.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RpcChannel {
+    id: u32,
+}
+
+impl RpcChannel {
+    /// Wraps the id of an already opened RPC channel.
+    #[inline]
+    pub fn from_id(id: u32) -> Self {
+        Self { id }
+    }
+
+    /// Spawns `cmd` as a job talking msgpack-RPC over its stdio, returning a
+    /// channel to it. The first item of `cmd` is the executable, the rest are
+    /// its arguments.
+    pub fn spawn<I, S>(cmd: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<oxi_types::String>,
+    {
+        let cmd = Array::from_iter(cmd.into_iter().map(Into::into));
+        let opts = Dictionary::from_iter([("rpc", true)]);
+        let id = api::call_function::<_, i64>("jobstart", (cmd, opts))?;
+        Self::from_chan_id(id, "jobstart")
+    }
+
+    /// Connects to the socket at `address`, returning an RPC channel to it.
+    pub fn connect(mode: SocketMode, address: &str) -> Result<Self> {
+        let opts = Dictionary::from_iter([("rpc", true)]);
+        let id = api::call_function::<_, i64>(
+            "sockconnect",
+            (mode.as_str(), address, opts),
+        )?;
+        Self::from_chan_id(id, "sockconnect")
+    }
+
+    /// Validates the channel id returned by `jobstart()` or `sockconnect()`.
+    fn from_chan_id(id: i64, fun: &str) -> Result<Self> {
+        match u32::try_from(id) {
+            Ok(id) if id > 0 => Ok(Self { id }),
+            _ => Err(api::Error::Other(format!(
+                "{fun}() failed to open a channel, returned {id}"
+            ))
+            .into()),
+        }
+    }
+
+    /// Closes the channel.
+    pub fn close(self) -> Result<()> {
+        api::call_function::<_, Object>("chanclose", (self.id,))?;
+        HANDLERS.with(|handlers| {
+            handlers.borrow_mut().retain(|(id, _), _| *id != self.id)
+        });
+        Ok(())
+    }
+
+    /// The id of the channel.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sends a notification to the remote without waiting for a response.
+    ///
+    /// If `args` serializes to an array its elements are passed as separate
+    /// parameters, otherwise it's passed as the only parameter. Use `()` to
+    /// pass no parameters.
+    pub fn notify<A>(&self, method: &str, args: A) -> Result<()>
+    where
+        A: Serialize,
+    {
+        let args = self.rpc_args(method, args)?;
+        api::call_function::<_, Object>("rpcnotify", args)?;
+        Ok(())
+    }
+
+    /// Registers a handler for the requests with the given method, replacing
+    /// the previous one if any. See the [module-level
+    /// documentation](self#handling-requests) for how the remote can call
+    /// it via the [`dispatcher`].
+    pub fn on_request<A, R, F>(&self, method: &str, mut handler: F)
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+        F: FnMut(A) -> Result<R> + 'static,
+    {
+        let handler: Handler = Box::new(move |args| {
+            let args = A::deserialize(Deserializer::new(args))?;
+            Ok(handler(args)?.serialize(Serializer::new())?)
+        });
+
+        HANDLERS.with(move |handlers| {
+            handlers.borrow_mut().insert((self.id, method.to_owned()), handler)
+        });
+    }
+
+    /// Sends a request to the remote and blocks until it responds.
+    ///
+    /// Arguments are passed like in [`notify`](Self::notify).
+    pub fn request<A, R>(&self, method: &str, args: A) -> Result<R>
+    where
+        A: Serialize,
+        R: DeserializeOwned,
+    {
+        let args = self.rpc_args(method, args)?;
+        let res = api::call_function::<_, Object>("rpcrequest", args)?;
+        Ok(R::deserialize(Deserializer::new(res))?)
+    }
+
+    /// Sends raw bytes to the channel.
+    ///
+    /// This is mostly useful to talk to channels not using msgpack-RPC.
+    pub fn send(&self, data: &str) -> Result<()> {
+        Ok(api::chan_send(self.id, data)?)
+    }
+
+    /// Builds the arguments of `rpcnotify()` and `rpcrequest()`.
+    fn rpc_args<A: Serialize>(&self, method: &str, args: A) -> Result<Array> {
+        let args = args.serialize(Serializer::new())?;
+
+        let mut rpc_args = vec![Object::from(self.id), Object::from(method)];
+
+        match args.kind() {
+            ObjectKind::Nil => {},
+            ObjectKind::Array => rpc_args
+                .extend(Vec::<Object>::deserialize(Deserializer::new(args))?),
+            _ => rpc_args.push(args),
+        }
+
+        Ok(Array::from_iter(rpc_args))
+    }
+}
+
+/// Returns the Lua function dispatching requests to the handlers registered
+/// via [`RpcChannel::on_request`]. See the [module-level
+/// documentation](self#handling-requests) for how to expose it.
+///
+/// Every plugin has its own dispatcher, which only knows about the handlers
+/// registered by that plugin.
+pub fn dispatcher() -> Dispatcher {
+    if let Some(dispatcher) = DISPATCHER.with(|cell| cell.borrow().clone()) {
+        return dispatcher;
+    }
+
+    let dispatcher = Function::from_fn_mut(
+        |(id, method, args): (u32, String, Object)| -> Result<Object> {
+            let key = (id, method);
+
+            // The handler is taken out of the map while it runs so that it
+            // can register other handlers.
+            let mut handler = HANDLERS
+                .with(|handlers| handlers.borrow_mut().remove(&key))
+                .ok_or_else(|| {
+                    api::Error::Other(format!(
+                        "no handler registered for method {:?} on channel {}",
+                        key.1, key.0
+                    ))
+                })?;
+
+            let res = handler(args);
+
+            HANDLERS.with(|handlers| {
+                handlers.borrow_mut().entry(key).or_insert(handler);
+            });
+
+            res
+        },
+    );
+
+    DISPATCHER.with(|cell| *cell.borrow_mut() = Some(dispatcher.clone()));
+
+    dispatcher
+}
@@ -1,8 +1,19 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
 use oxi_luajit::{self as lua, ffi::*, macros::cstr};
 use oxi_types::Function;
 
+use crate::api::{self, opts::CreateAutocmdOpts, types::AutocmdEvent};
 use crate::Result;
 
+type DeferredInit = Box<dyn FnOnce() -> Result<()>>;
+
+thread_local! {
+    static DEFERRED_INITS: RefCell<Vec<(DeferredInitEvent, DeferredInit)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
 /// Binding to [`vim.schedule()`][1].
 ///
 /// Schedules a callback to be invoked soon by the main event-loop. Useful to
@@ -37,3 +48,302 @@ where
         })
     };
 }
+
+/// The outcome of [`wait_for`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WaitOutcome {
+    /// The predicate returned `true`.
+    Ok,
+
+    /// The timeout elapsed before the predicate returned `true`.
+    TimedOut,
+
+    /// The wait was interrupted by the user with `<C-c>`.
+    Interrupted,
+}
+
+/// Binding to [`vim.wait()`][1].
+///
+/// Calls `predicate` every `interval` until it returns `true` or until
+/// `timeout` has elapsed, processing Neovim's events in between. Callbacks
+/// scheduled via [`schedule`], timers and job output keep running during the
+/// wait, so this can be used to block on the result of an asynchronous task
+/// from a test or a synchronous command.
+///
+/// Fails if called from a context where the event loop can't be processed,
+/// e.g. a [`libuv`](crate::libuv) callback, instead of deadlocking.
+///
+/// [1]: https://neovim.io/doc/user/lua.html#vim.wait()
+pub fn wait_for<F>(
+    timeout: Duration,
+    interval: Duration,
+    mut predicate: F,
+) -> Result<WaitOutcome>
+where
+    F: FnMut() -> bool + 'static,
+{
+    let millis = |duration: Duration| {
+        lua_Integer::try_from(duration.as_millis()).unwrap_or(lua_Integer::MAX)
+    };
+
+    let fun =
+        Function::from_fn_mut(move |()| Ok::<_, crate::Error>(predicate()));
+
+    unsafe {
+        lua::with_state(|lstate| {
+            let top = lua_gettop(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            lua_getfield(lstate, -1, cstr!("wait"));
+            lua_pushinteger(lstate, millis(timeout));
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, fun.lua_ref());
+            lua_pushinteger(lstate, millis(interval));
+
+            let res = match lua_pcall(lstate, 3, 2, 0) {
+                LUA_OK if lua_toboolean(lstate, -2) == 1 => {
+                    Ok(WaitOutcome::Ok)
+                },
+                LUA_OK if lua_tointeger(lstate, -1) == -2 => {
+                    Ok(WaitOutcome::Interrupted)
+                },
+                LUA_OK => Ok(WaitOutcome::TimedOut),
+                _ => {
+                    let msg = lua::utils::error_message(lstate, -1);
+                    Err(lua::Error::RuntimeError(msg).into())
+                },
+            };
+
+            lua_settop(lstate, top);
+            luaL_unref(lstate, LUA_REGISTRYINDEX, fun.lua_ref());
+
+            res
+        })
+    }
+}
+
+/// A handle to a listener registered via [`on_key`]. The listener is removed
+/// when the handle is dropped.
+#[derive(Debug)]
+pub struct OnKeyHandle {
+    ns_id: u32,
+    fun: Function<oxi_types::String, ()>,
+}
+
+impl OnKeyHandle {
+    /// The namespace id the listener is registered with.
+    #[inline]
+    pub fn ns_id(&self) -> u32 {
+        self.ns_id
+    }
+}
+
+impl Drop for OnKeyHandle {
+    fn drop(&mut self) {
+        unsafe {
+            lua::with_state(|lstate| {
+                // Calling `vim.on_key(nil, ns_id)` removes the listener.
+                lua_pushnil(lstate);
+                let _ = call_on_key(lstate, self.ns_id);
+                luaL_unref(lstate, LUA_REGISTRYINDEX, self.fun.lua_ref());
+            })
+        };
+    }
+}
+
+/// Calls `vim.on_key(fn, ns_id)` in protected mode, where `fn` is the value
+/// at the top of the stack, which is popped.
+unsafe fn call_on_key(lstate: *mut lua_State, ns_id: u32) -> Result<()> {
+    let top = lua_gettop(lstate) - 1;
+
+    lua_getglobal(lstate, cstr!("vim"));
+    lua_getfield(lstate, -1, cstr!("on_key"));
+    lua_pushvalue(lstate, -3);
+    lua_pushinteger(lstate, ns_id as lua_Integer);
+
+    let res = match lua_pcall(lstate, 2, 0, 0) {
+        LUA_OK => Ok(()),
+        _ => {
+            let msg = lua::utils::error_message(lstate, -1);
+            Err(lua::Error::RuntimeError(msg).into())
+        },
+    };
+
+    lua_settop(lstate, top);
+
+    res
+}
+
+/// Binding to [`vim.on_key()`][1].
+///
+/// Registers a listener called with every key pressed by the user, after
+/// mappings have been applied. Keys are passed in the form returned by
+/// [`keytrans()`][2], e.g. `<BS>` or `<C-w>`, instead of as raw termcodes.
+///
+/// The listener is removed when the returned [`OnKeyHandle`] is dropped.
+///
+/// [1]: https://neovim.io/doc/user/lua.html#vim.on_key()
+/// [2]: https://neovim.io/doc/user/builtin.html#keytrans()
+pub fn on_key<F>(mut fun: F) -> Result<OnKeyHandle>
+where
+    F: FnMut(String) -> Result<()> + 'static,
+{
+    let ns_id = crate::api::create_namespace("");
+
+    let fun = Function::from_fn_mut(move |key: oxi_types::String| {
+        let key = crate::api::call_function::<_, String>("keytrans", (key,))?;
+        fun(key)
+    });
+
+    let res = unsafe {
+        lua::with_state(|lstate| {
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, fun.lua_ref());
+            call_on_key(lstate, ns_id)
+        })
+    };
+
+    if let Err(err) = res {
+        fun.remove_from_lua_registry();
+        return Err(err);
+    }
+
+    Ok(OnKeyHandle { ns_id, fun })
+}
+
+/// The event on which the initialization deferred via [`defer_init_on`]
+/// runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeferredInitEvent {
+    /// After Neovim has finished starting up ([`VimEnter`][1]).
+    ///
+    /// [1]: https://neovim.io/doc/user/autocmd.html#VimEnter
+    #[default]
+    VimEnter,
+
+    /// After a UI has attached ([`UIEnter`][1]). This never happens in a
+    /// headless instance until a UI attaches to it.
+    ///
+    /// [1]: https://neovim.io/doc/user/autocmd.html#UIEnter
+    UIEnter,
+}
+
+impl DeferredInitEvent {
+    /// Returns whether the event has already happened.
+    fn has_happened(self) -> Result<bool> {
+        if api::get_vvar::<u8>("vim_did_enter")? != 1 {
+            return Ok(false);
+        }
+
+        Ok(match self {
+            Self::VimEnter => true,
+            Self::UIEnter => api::list_uis().next().is_some(),
+        })
+    }
+
+    /// Returns whether the initialization deferred until `self` is due when
+    /// `event` happens. `UIEnter` always follows `VimEnter`.
+    fn is_due_on(self, event: Self) -> bool {
+        self == event || event == Self::UIEnter
+    }
+}
+
+/// Defers part of a plugin's initialization until Neovim has finished
+/// starting up, keeping the work done when the plugin is loaded cheap.
+///
+/// This is the same as calling [`defer_init_on`] with
+/// [`DeferredInitEvent::VimEnter`].
+pub fn defer_init<F, E>(fun: F) -> Result<()>
+where
+    F: FnOnce() -> std::result::Result<(), E> + 'static,
+    E: Into<crate::Error>,
+{
+    defer_init_on(DeferredInitEvent::VimEnter, fun)
+}
+
+/// Defers part of a plugin's initialization until `event` happens, keeping
+/// the work done when the plugin is loaded cheap.
+///
+/// `fun` is called on the next iteration of the event loop if `event` has
+/// already happened when the plugin is loaded. Call [`run_deferred_init`] at
+/// the start of the commands and functions that rely on it, so that they
+/// also work when used before that.
+///
+/// This is usually set up via the `deferred` and `on` arguments of
+/// [`#[module]`](macro@crate::module) rather than called directly, in which
+/// case the exported functions of the module call [`run_deferred_init`]
+/// themselves.
+pub fn defer_init_on<F, E>(event: DeferredInitEvent, fun: F) -> Result<()>
+where
+    F: FnOnce() -> std::result::Result<(), E> + 'static,
+    E: Into<crate::Error>,
+{
+    let is_first = DEFERRED_INITS.with(|inits| {
+        let mut inits = inits.borrow_mut();
+        let is_first = inits.iter().all(|(ev, _)| *ev != event);
+        let init: DeferredInit = Box::new(move || fun().map_err(Into::into));
+        inits.push((event, init));
+        is_first
+    });
+
+    // The trigger is already set up by the first function still pending on
+    // the same event.
+    if !is_first {
+        return Ok(());
+    }
+
+    if event.has_happened()? {
+        schedule(move |()| run_deferred_init_on(event));
+        return Ok(());
+    }
+
+    let autocmd_event = match event {
+        DeferredInitEvent::VimEnter => AutocmdEvent::VimEnter,
+        DeferredInitEvent::UIEnter => AutocmdEvent::UIEnter,
+    };
+
+    let opts = CreateAutocmdOpts::builder()
+        .desc("nvim-oxi deferred init")
+        .once(true)
+        .callback(move |_| run_deferred_init_on(event).map(|()| false))
+        .build();
+
+    api::create_autocmd([autocmd_event], &opts)?;
+
+    Ok(())
+}
+
+/// Runs the initialization deferred via [`defer_init`] or [`defer_init_on`]
+/// right away, unless it has already run.
+///
+/// Every deferred function is called even if a previous one fails, in which
+/// case the first error is returned.
+pub fn run_deferred_init() -> Result<()> {
+    run_deferred_inits(|_| true)
+}
+
+/// Runs the deferred functions that are due when `event` happens.
+fn run_deferred_init_on(event: DeferredInitEvent) -> Result<()> {
+    run_deferred_inits(|ev| ev.is_due_on(event))
+}
+
+fn run_deferred_inits(
+    is_due: impl Fn(DeferredInitEvent) -> bool,
+) -> Result<()> {
+    let inits = DEFERRED_INITS.with(|inits| {
+        let mut inits = inits.borrow_mut();
+        let (due, pending) =
+            inits.drain(..).partition::<Vec<_>, _>(|(ev, _)| is_due(*ev));
+        *inits = pending;
+        due
+    });
+
+    let mut res = Ok(());
+
+    for (_, init) in inits {
+        let init_res = init();
+        if res.is_ok() {
+            res = init_res;
+        }
+    }
+
+    res
+}
@@ -0,0 +1,290 @@
+use oxi_types::{
+    iter::ArrayIterator,
+    serde::Deserializer,
+    Array,
+    Dictionary,
+    Object,
+};
+use serde::de::DeserializeOwned;
+
+use crate::Result;
+
+/// A cell of a [`UiEvent::GridLine`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GridCell {
+    /// The text of the cell, usually a single character. It's empty for the
+    /// right half of a double-width character.
+    pub text: String,
+
+    /// The id of the highlight of the cell, or `None` if it's the same as
+    /// the one of the previous cell in the line.
+    pub hl_id: Option<u32>,
+
+    /// How many times the cell is repeated.
+    pub repeat: u32,
+}
+
+/// An update sent by Neovim to an attached UI, as part of a [`redraw`][1]
+/// notification.
+///
+/// Only the events of the global and of the [`ext_linegrid`][2] protocol
+/// are typed, the others are returned as [`UiEvent::Other`].
+///
+/// [1]: https://neovim.io/doc/user/ui.html#ui-events
+/// [2]: https://neovim.io/doc/user/ui.html#ui-linegrid
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiEvent {
+    Bell,
+
+    BusyStart,
+
+    BusyStop,
+
+    /// The default colors, where `-1` means that the color is unset.
+    DefaultColorsSet {
+        rgb_fg: i64,
+        rgb_bg: i64,
+        rgb_sp: i64,
+        cterm_fg: i64,
+        cterm_bg: i64,
+    },
+
+    /// The end of a batch of updates, after which the screen can be
+    /// rendered.
+    Flush,
+
+    GridClear {
+        grid: u32,
+    },
+
+    GridCursorGoto {
+        grid: u32,
+        row: u32,
+        col: u32,
+    },
+
+    GridDestroy {
+        grid: u32,
+    },
+
+    /// Redraws a contiguous part of a row of a grid, starting from
+    /// `col_start`.
+    GridLine {
+        grid: u32,
+        row: u32,
+        col_start: u32,
+        cells: Vec<GridCell>,
+    },
+
+    GridResize {
+        grid: u32,
+        width: u32,
+        height: u32,
+    },
+
+    /// Scrolls the region of a grid going from `top` to `bot` (exclusive)
+    /// and from `left` to `right` (exclusive) by `rows` rows, upwards if
+    /// positive and downwards if negative. `cols` is always zero.
+    GridScroll {
+        grid: u32,
+        top: u32,
+        bot: u32,
+        left: u32,
+        right: u32,
+        rows: i64,
+        cols: i64,
+    },
+
+    /// Defines the highlight with the given id, used by
+    /// [`GridCell`](GridCell::hl_id)s.
+    HlAttrDefine {
+        id: u32,
+        rgb_attrs: Dictionary,
+        cterm_attrs: Dictionary,
+        info: Array,
+    },
+
+    HlGroupSet {
+        name: String,
+        hl_id: u32,
+    },
+
+    /// The editor has changed mode, where `mode_idx` is the index of the mode
+    /// in the `mode_info` list of the last [`UiEvent::ModeInfoSet`].
+    ModeChange {
+        mode: String,
+        mode_idx: u32,
+    },
+
+    ModeInfoSet {
+        cursor_style_enabled: bool,
+        mode_info: Vec<Dictionary>,
+    },
+
+    MouseOff,
+
+    MouseOn,
+
+    OptionSet {
+        name: String,
+        value: Object,
+    },
+
+    SetIcon {
+        icon: String,
+    },
+
+    SetTitle {
+        title: String,
+    },
+
+    Suspend,
+
+    VisualBell,
+
+    /// An event not typed by this crate.
+    Other {
+        name: String,
+        args: Array,
+    },
+}
+
+impl UiEvent {
+    /// Parses the parameters of a `redraw` notification, i.e. a list of
+    /// `[name, args...]` batches, into the events they contain.
+    pub fn from_redraw(params: Array) -> Result<Vec<Self>> {
+        let mut events = Vec::new();
+
+        for batch in params {
+            let mut batch = Args(deserialize::<Array>(batch)?.into_iter());
+            let name = batch.next::<String>()?;
+
+            for args in batch.0 {
+                let args = Args(deserialize::<Array>(args)?.into_iter());
+                events.push(Self::parse(name.clone(), args)?);
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn parse(name: String, mut args: Args) -> Result<Self> {
+        let event = match name.as_str() {
+            "bell" => Self::Bell,
+
+            "busy_start" => Self::BusyStart,
+
+            "busy_stop" => Self::BusyStop,
+
+            "default_colors_set" => Self::DefaultColorsSet {
+                rgb_fg: args.next()?,
+                rgb_bg: args.next()?,
+                rgb_sp: args.next()?,
+                cterm_fg: args.next()?,
+                cterm_bg: args.next()?,
+            },
+
+            "flush" => Self::Flush,
+
+            "grid_clear" => Self::GridClear { grid: args.next()? },
+
+            "grid_cursor_goto" => Self::GridCursorGoto {
+                grid: args.next()?,
+                row: args.next()?,
+                col: args.next()?,
+            },
+
+            "grid_destroy" => Self::GridDestroy { grid: args.next()? },
+
+            "grid_line" => Self::GridLine {
+                grid: args.next()?,
+                row: args.next()?,
+                col_start: args.next()?,
+                cells: args
+                    .next::<Array>()?
+                    .into_iter()
+                    .map(|cell| {
+                        let mut cell =
+                            Args(deserialize::<Array>(cell)?.into_iter());
+                        Ok(GridCell {
+                            text: cell.next()?,
+                            hl_id: cell.next()?,
+                            repeat: cell.next::<Option<u32>>()?.unwrap_or(1),
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            },
+
+            "grid_resize" => Self::GridResize {
+                grid: args.next()?,
+                width: args.next()?,
+                height: args.next()?,
+            },
+
+            "grid_scroll" => Self::GridScroll {
+                grid: args.next()?,
+                top: args.next()?,
+                bot: args.next()?,
+                left: args.next()?,
+                right: args.next()?,
+                rows: args.next()?,
+                cols: args.next()?,
+            },
+
+            "hl_attr_define" => Self::HlAttrDefine {
+                id: args.next()?,
+                rgb_attrs: args.next()?,
+                cterm_attrs: args.next()?,
+                info: args.next()?,
+            },
+
+            "hl_group_set" => {
+                Self::HlGroupSet { name: args.next()?, hl_id: args.next()? }
+            },
+
+            "mode_change" => {
+                Self::ModeChange { mode: args.next()?, mode_idx: args.next()? }
+            },
+
+            "mode_info_set" => Self::ModeInfoSet {
+                cursor_style_enabled: args.next()?,
+                mode_info: args.next()?,
+            },
+
+            "mouse_off" => Self::MouseOff,
+
+            "mouse_on" => Self::MouseOn,
+
+            "option_set" => {
+                Self::OptionSet { name: args.next()?, value: args.next()? }
+            },
+
+            "set_icon" => Self::SetIcon { icon: args.next()? },
+
+            "set_title" => Self::SetTitle { title: args.next()? },
+
+            "suspend" => Self::Suspend,
+
+            "visual_bell" => Self::VisualBell,
+
+            _ => Self::Other { name, args: args.0.collect() },
+        };
+
+        Ok(event)
+    }
+}
+
+/// The arguments of an event, deserialized one at a time. Missing arguments
+/// are treated as `nil`s, which allows optional trailing arguments.
+struct Args(ArrayIterator);
+
+impl Args {
+    fn next<T: DeserializeOwned>(&mut self) -> Result<T> {
+        deserialize(self.0.next().unwrap_or_default())
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(obj: Object) -> Result<T> {
+    Ok(T::deserialize(Deserializer::new(obj))?)
+}
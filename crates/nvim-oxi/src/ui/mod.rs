@@ -0,0 +1,175 @@
+//! Helpers for plugins embedding another Neovim instance as its UI, and for
+//! reacting to the focus changes of the editor's own UIs.
+//!
+//! The `nvim_ui_*` functions can only be called over [msgpack-RPC][1] by the
+//! process acting as a UI, so a [`RemoteUi`] wraps an [`RpcChannel`] to the
+//! embedded instance, e.g. one spawned via [`RpcChannel::spawn`].
+//!
+//! The attached instance then sends its screen updates as `redraw`
+//! notifications, whose parameters can be parsed into [`UiEvent`]s via
+//! [`UiEvent::from_redraw`] by the program receiving them.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::rpc::RpcChannel;
+//! use nvim_oxi::ui::{Focus, RemoteUi, UiOptions};
+//!
+//! let chan = RpcChannel::spawn(["nvim", "--embed"])?;
+//! let ui = RemoteUi::attach(chan, 80, 24, &UiOptions::default())?;
+//!
+//! // Forward the focus of the editor to the embedded instance.
+//! nvim_oxi::ui::on_focus_change(move |focus| ui.set_focus(focus))?;
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/api.html#RPC
+
+mod event;
+
+pub use event::*;
+use serde::Serialize;
+
+use crate::api::opts::CreateAutocmdOpts;
+use crate::api::types::{AutocmdCallbackArgs, AutocmdEvent};
+use crate::rpc::RpcChannel;
+use crate::{api, Object, Result};
+
+/// Whether a UI has gained or lost focus.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Focus {
+    Gained,
+    Lost,
+}
+
+impl Focus {
+    #[inline]
+    pub fn is_gained(&self) -> bool {
+        matches!(self, Self::Gained)
+    }
+}
+
+/// The options passed to [`RemoteUi::attach`]. See [`ui-option`][1] for
+/// their meaning.
+///
+/// [1]: https://neovim.io/doc/user/ui.html#ui-option
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub struct UiOptions {
+    pub rgb: bool,
+    pub ext_cmdline: bool,
+    pub ext_hlstate: bool,
+    pub ext_linegrid: bool,
+    pub ext_messages: bool,
+    pub ext_multigrid: bool,
+    pub ext_popupmenu: bool,
+    pub ext_tabline: bool,
+    pub ext_termcolors: bool,
+    pub ext_wildmenu: bool,
+}
+
+impl Default for UiOptions {
+    /// Same as Neovim's defaults, i.e. RGB colors and no externalized
+    /// elements.
+    fn default() -> Self {
+        Self {
+            rgb: true,
+            ext_cmdline: false,
+            ext_hlstate: false,
+            ext_linegrid: false,
+            ext_messages: false,
+            ext_multigrid: false,
+            ext_popupmenu: false,
+            ext_tabline: false,
+            ext_termcolors: false,
+            ext_wildmenu: false,
+        }
+    }
+}
+
+/// A UI attached to a Neovim instance over an RPC channel.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RemoteUi {
+    channel: RpcChannel,
+}
+
+impl RemoteUi {
+    /// Binding to [`nvim_ui_attach()`][1].
+    ///
+    /// Attaches to the instance on the other side of `channel` as a UI with
+    /// the given size.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_ui_attach()
+    pub fn attach(
+        channel: RpcChannel,
+        width: u32,
+        height: u32,
+        opts: &UiOptions,
+    ) -> Result<Self> {
+        channel
+            .request::<_, Object>("nvim_ui_attach", (width, height, opts))?;
+        Ok(Self { channel })
+    }
+
+    /// The channel the UI is attached over.
+    #[inline]
+    pub fn channel(&self) -> &RpcChannel {
+        &self.channel
+    }
+
+    /// Binding to [`nvim_ui_detach()`][1].
+    ///
+    /// Detaches the UI, returning the channel it was attached over.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_ui_detach()
+    pub fn detach(self) -> Result<RpcChannel> {
+        self.channel.request::<_, Object>("nvim_ui_detach", ())?;
+        Ok(self.channel)
+    }
+
+    /// Binding to [`nvim_ui_set_focus()`][1].
+    ///
+    /// Tells the instance that the UI has gained or lost focus, firing its
+    /// `FocusGained` or `FocusLost` autocommands. Requires the instance to
+    /// be Neovim 0.9 or later.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_ui_set_focus()
+    pub fn set_focus(&self, focus: Focus) -> Result<()> {
+        self.channel
+            .request::<_, Object>("nvim_ui_set_focus", (focus.is_gained(),))?;
+        Ok(())
+    }
+
+    /// Binding to [`nvim_ui_try_resize()`][1].
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_ui_try_resize()
+    pub fn try_resize(&self, width: u32, height: u32) -> Result<()> {
+        self.channel
+            .request::<_, Object>("nvim_ui_try_resize", (width, height))?;
+        Ok(())
+    }
+}
+
+/// Registers a callback called every time one of the editor's UIs gains or
+/// loses focus, returning the id of the underlying autocommand.
+///
+/// This relies on the `FocusGained` and `FocusLost` events, which terminal
+/// UIs only fire if the terminal supports focus reporting.
+pub fn on_focus_change<F>(mut fun: F) -> Result<u32>
+where
+    F: FnMut(Focus) -> Result<()> + 'static,
+{
+    let opts = CreateAutocmdOpts::builder()
+        .desc("nvim-oxi focus change callback")
+        .callback(move |args: AutocmdCallbackArgs| {
+            let focus = if args.event == "FocusGained" {
+                Focus::Gained
+            } else {
+                Focus::Lost
+            };
+            fun(focus).map(|()| false)
+        })
+        .build();
+
+    let events = [AutocmdEvent::FocusGained, AutocmdEvent::FocusLost];
+
+    Ok(api::create_autocmd(events, &opts)?)
+}
@@ -0,0 +1,798 @@
+//! A façade over [`api`](crate::api) whose functions keep the names and
+//! argument order of the ones exposed to Lua via `vim.api`.
+//!
+//! This is meant to ease porting existing Lua plugins to Rust, by allowing to
+//! translate calls like
+//!
+//! ```lua
+//! vim.api.nvim_buf_set_lines(0, 0, -1, false, { "foo", "bar" })
+//! ```
+//!
+//! one to one, before refactoring them to use the idiomatic API. Like in Lua,
+//! a handle of `0` refers to the current buffer, window or tabpage, and
+//! negative line and column indices count from the end.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::vim_api::*;
+//!
+//! let buf = nvim_get_current_buf();
+//! nvim_buf_set_lines(buf.clone(), 0, -1, false, ["foo", "bar"])?;
+//! assert_eq!(2, nvim_buf_line_count(buf)?);
+//! ```
+
+use crate::api::opts::*;
+use crate::api::types::*;
+use crate::api::{self, Buffer, TabPage, Window};
+use crate::conversion::{FromObject, ToObject};
+use crate::{Array, Object, Result};
+
+/// Binding to [`vim.api.nvim_buf_add_highlight()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_add_highlight()
+pub fn nvim_buf_add_highlight<Buf>(
+    buffer: Buf,
+    ns_id: i64,
+    hl_group: &str,
+    line: i64,
+    col_start: i64,
+    col_end: i64,
+) -> Result<i64>
+where
+    Buf: Into<Buffer>,
+{
+    call(
+        "nvim_buf_add_highlight",
+        (buffer.into(), ns_id, hl_group, line, col_start, col_end),
+    )
+}
+
+/// Binding to [`vim.api.nvim_buf_clear_namespace()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_clear_namespace()
+pub fn nvim_buf_clear_namespace<Buf>(
+    buffer: Buf,
+    ns_id: i64,
+    line_start: i64,
+    line_end: i64,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    call::<_, Object>(
+        "nvim_buf_clear_namespace",
+        (buffer.into(), ns_id, line_start, line_end),
+    )?;
+    Ok(())
+}
+
+/// Binding to [`vim.api.nvim_buf_del_extmark()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_extmark()
+pub fn nvim_buf_del_extmark<Buf>(
+    buffer: Buf,
+    ns_id: u32,
+    id: u32,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().del_extmark(ns_id, id)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_del_keymap()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_keymap()
+pub fn nvim_buf_del_keymap<Buf>(
+    buffer: Buf,
+    mode: Mode,
+    lhs: &str,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().del_keymap(mode, lhs)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_del_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_var()
+pub fn nvim_buf_del_var<Buf>(buffer: Buf, name: &str) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().del_var(name)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_delete()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_delete()
+pub fn nvim_buf_delete<Buf>(buffer: Buf, opts: &BufDeleteOpts) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().delete(opts)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_get_changedtick()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_changedtick()
+pub fn nvim_buf_get_changedtick<Buf>(buffer: Buf) -> Result<u32>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().get_changedtick()?)
+}
+
+/// Binding to [`vim.api.nvim_buf_get_lines()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_lines()
+pub fn nvim_buf_get_lines<Buf>(
+    buffer: Buf,
+    start: i64,
+    end: i64,
+    strict_indexing: bool,
+) -> Result<Vec<crate::String>>
+where
+    Buf: Into<Buffer>,
+{
+    call("nvim_buf_get_lines", (buffer.into(), start, end, strict_indexing))
+}
+
+/// Binding to [`vim.api.nvim_buf_get_name()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_name()
+pub fn nvim_buf_get_name<Buf>(buffer: Buf) -> Result<std::path::PathBuf>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().get_name()?)
+}
+
+/// Binding to [`vim.api.nvim_buf_get_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_var()
+pub fn nvim_buf_get_var<Buf, Var>(buffer: Buf, name: &str) -> Result<Var>
+where
+    Buf: Into<Buffer>,
+    Var: FromObject,
+{
+    Ok(buffer.into().get_var(name)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_is_loaded()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_is_loaded()
+pub fn nvim_buf_is_loaded<Buf>(buffer: Buf) -> bool
+where
+    Buf: Into<Buffer>,
+{
+    buffer.into().is_loaded()
+}
+
+/// Binding to [`vim.api.nvim_buf_is_valid()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_is_valid()
+pub fn nvim_buf_is_valid<Buf>(buffer: Buf) -> bool
+where
+    Buf: Into<Buffer>,
+{
+    buffer.into().is_valid()
+}
+
+/// Binding to [`vim.api.nvim_buf_line_count()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_line_count()
+pub fn nvim_buf_line_count<Buf>(buffer: Buf) -> Result<usize>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().line_count()?)
+}
+
+/// Binding to [`vim.api.nvim_buf_set_extmark()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_extmark()
+pub fn nvim_buf_set_extmark<Buf>(
+    buffer: Buf,
+    ns_id: u32,
+    line: usize,
+    col: usize,
+    opts: &SetExtmarkOpts,
+) -> Result<u32>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().set_extmark(ns_id, line, col, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_set_keymap()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_keymap()
+pub fn nvim_buf_set_keymap<Buf>(
+    buffer: Buf,
+    mode: Mode,
+    lhs: &str,
+    rhs: &str,
+    opts: &SetKeymapOpts,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().set_keymap(mode, lhs, rhs, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_set_lines()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_lines()
+pub fn nvim_buf_set_lines<Buf, Line, Lines>(
+    buffer: Buf,
+    start: i64,
+    end: i64,
+    strict_indexing: bool,
+    replacement: Lines,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+    Lines: IntoIterator<Item = Line>,
+    Line: Into<crate::String>,
+{
+    let replacement =
+        replacement.into_iter().map(Into::into).collect::<Array>();
+
+    call::<_, Object>(
+        "nvim_buf_set_lines",
+        (buffer.into(), start, end, strict_indexing, replacement),
+    )?;
+
+    Ok(())
+}
+
+/// Binding to [`vim.api.nvim_buf_set_name()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_name()
+pub fn nvim_buf_set_name<Buf>(buffer: Buf, name: &str) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(buffer.into().set_name(name)?)
+}
+
+/// Binding to [`vim.api.nvim_buf_set_text()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_text()
+pub fn nvim_buf_set_text<Buf, Line, Lines>(
+    buffer: Buf,
+    start_row: i64,
+    start_col: i64,
+    end_row: i64,
+    end_col: i64,
+    replacement: Lines,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+    Lines: IntoIterator<Item = Line>,
+    Line: Into<crate::String>,
+{
+    let replacement =
+        replacement.into_iter().map(Into::into).collect::<Array>();
+
+    call::<_, Object>(
+        "nvim_buf_set_text",
+        (buffer.into(), start_row, start_col, end_row, end_col, replacement),
+    )?;
+
+    Ok(())
+}
+
+/// Binding to [`vim.api.nvim_buf_set_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_var()
+pub fn nvim_buf_set_var<Buf, Var>(
+    buffer: Buf,
+    name: &str,
+    value: Var,
+) -> Result<()>
+where
+    Buf: Into<Buffer>,
+    Var: ToObject,
+{
+    Ok(buffer.into().set_var(name, value)?)
+}
+
+/// Binding to [`vim.api.nvim_call_function()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_call_function()
+pub fn nvim_call_function<Args, Ret>(fn_: &str, args: Args) -> Result<Ret>
+where
+    Args: Into<Array>,
+    Ret: FromObject,
+{
+    Ok(api::call_function(fn_, args)?)
+}
+
+/// Binding to [`vim.api.nvim_command()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_command()
+pub fn nvim_command(command: &str) -> Result<()> {
+    Ok(api::command(command)?)
+}
+
+/// Binding to [`vim.api.nvim_create_augroup()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_create_augroup()
+pub fn nvim_create_augroup(
+    name: &str,
+    opts: &CreateAugroupOpts,
+) -> Result<u32> {
+    Ok(api::create_augroup(name, opts)?.id())
+}
+
+/// Binding to [`vim.api.nvim_create_autocmd()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_create_autocmd()
+pub fn nvim_create_autocmd<Events>(
+    events: Events,
+    opts: &CreateAutocmdOpts,
+) -> Result<u32>
+where
+    Events: IntoIterator<Item = AutocmdEvent>,
+{
+    Ok(api::create_autocmd(events, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_create_buf()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_create_buf()
+pub fn nvim_create_buf(listed: bool, scratch: bool) -> Result<Buffer> {
+    Ok(api::create_buf(listed, scratch)?)
+}
+
+/// Binding to [`vim.api.nvim_create_namespace()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_create_namespace()
+pub fn nvim_create_namespace(name: &str) -> u32 {
+    api::create_namespace(name)
+}
+
+/// Binding to [`vim.api.nvim_create_user_command()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_create_user_command()
+pub fn nvim_create_user_command<Cmd>(
+    name: &str,
+    command: Cmd,
+    opts: &CreateCommandOpts,
+) -> Result<()>
+where
+    Cmd: api::StringOrFunction<CommandArgs, ()>,
+{
+    Ok(api::create_user_command(name, command, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_del_autocmd()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_del_autocmd()
+pub fn nvim_del_autocmd(id: u32) -> Result<()> {
+    Ok(api::del_autocmd(id)?)
+}
+
+/// Binding to [`vim.api.nvim_del_keymap()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_del_keymap()
+pub fn nvim_del_keymap(mode: Mode, lhs: &str) -> Result<()> {
+    Ok(api::del_keymap(mode, lhs)?)
+}
+
+/// Binding to [`vim.api.nvim_del_user_command()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_del_user_command()
+pub fn nvim_del_user_command(name: &str) -> Result<()> {
+    Ok(api::del_user_command(name)?)
+}
+
+/// Binding to [`vim.api.nvim_del_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_del_var()
+pub fn nvim_del_var(name: &str) -> Result<()> {
+    Ok(api::del_var(name)?)
+}
+
+/// Binding to [`vim.api.nvim_echo()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_echo()
+pub fn nvim_echo<'hl, Text, Chunks>(
+    chunks: Chunks,
+    history: bool,
+) -> Result<()>
+where
+    Chunks: IntoIterator<Item = (Text, Option<&'hl str>)>,
+    Text: Into<crate::String>,
+{
+    Ok(api::echo(chunks, history)?)
+}
+
+/// Binding to [`vim.api.nvim_err_writeln()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_err_writeln()
+pub fn nvim_err_writeln(str: &str) {
+    api::err_writeln(str)
+}
+
+/// Binding to [`vim.api.nvim_eval()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_eval()
+pub fn nvim_eval<V>(expr: &str) -> Result<V>
+where
+    V: FromObject,
+{
+    Ok(api::eval(expr)?)
+}
+
+/// Binding to [`vim.api.nvim_feedkeys()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_feedkeys()
+pub fn nvim_feedkeys(keys: &str, mode: Mode, escape_ks: bool) {
+    api::feedkeys(keys, mode, escape_ks)
+}
+
+/// Binding to [`vim.api.nvim_get_current_buf()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_current_buf()
+pub fn nvim_get_current_buf() -> Buffer {
+    api::get_current_buf()
+}
+
+/// Binding to [`vim.api.nvim_get_current_line()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_current_line()
+pub fn nvim_get_current_line() -> Result<String> {
+    Ok(api::get_current_line()?)
+}
+
+/// Binding to [`vim.api.nvim_get_current_tabpage()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_current_tabpage()
+pub fn nvim_get_current_tabpage() -> TabPage {
+    api::get_current_tabpage()
+}
+
+/// Binding to [`vim.api.nvim_get_current_win()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_current_win()
+pub fn nvim_get_current_win() -> Window {
+    api::get_current_win()
+}
+
+/// Binding to [`vim.api.nvim_get_option_value()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_option_value()
+pub fn nvim_get_option_value<Opt>(
+    name: &str,
+    opts: &OptionValueOpts,
+) -> Result<Opt>
+where
+    Opt: FromObject,
+{
+    Ok(api::get_option_value(name, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_get_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_var()
+pub fn nvim_get_var<Var>(name: &str) -> Result<Var>
+where
+    Var: FromObject,
+{
+    Ok(api::get_var(name)?)
+}
+
+/// Binding to [`vim.api.nvim_get_vvar()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_vvar()
+pub fn nvim_get_vvar<Var>(name: &str) -> Result<Var>
+where
+    Var: FromObject,
+{
+    Ok(api::get_vvar(name)?)
+}
+
+/// Binding to [`vim.api.nvim_list_bufs()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_list_bufs()
+pub fn nvim_list_bufs() -> Vec<Buffer> {
+    api::list_bufs().collect()
+}
+
+/// Binding to [`vim.api.nvim_list_tabpages()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_list_tabpages()
+pub fn nvim_list_tabpages() -> Vec<TabPage> {
+    api::list_tabpages().collect()
+}
+
+/// Binding to [`vim.api.nvim_list_wins()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_list_wins()
+pub fn nvim_list_wins() -> Vec<Window> {
+    api::list_wins().collect()
+}
+
+/// Binding to [`vim.api.nvim_open_win()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_open_win()
+pub fn nvim_open_win<Buf>(
+    buffer: Buf,
+    enter: bool,
+    config: &WindowConfig,
+) -> Result<Window>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(api::open_win(&buffer.into(), enter, config)?)
+}
+
+/// Binding to [`vim.api.nvim_out_write()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_out_write()
+pub fn nvim_out_write(str: &str) {
+    api::out_write(str)
+}
+
+/// Binding to [`vim.api.nvim_replace_termcodes()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_replace_termcodes()
+pub fn nvim_replace_termcodes(
+    str: &str,
+    from_part: bool,
+    do_lt: bool,
+    special: bool,
+) -> crate::String {
+    api::replace_termcodes(str, from_part, do_lt, special)
+}
+
+/// Binding to [`vim.api.nvim_set_current_buf()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_current_buf()
+pub fn nvim_set_current_buf<Buf>(buffer: Buf) -> Result<()>
+where
+    Buf: Into<Buffer>,
+{
+    Ok(api::set_current_buf(&buffer.into())?)
+}
+
+/// Binding to [`vim.api.nvim_set_current_line()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_current_line()
+pub fn nvim_set_current_line(line: &str) -> Result<()> {
+    Ok(api::set_current_line(line)?)
+}
+
+/// Binding to [`vim.api.nvim_set_current_win()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_current_win()
+pub fn nvim_set_current_win<Win>(window: Win) -> Result<()>
+where
+    Win: Into<Window>,
+{
+    Ok(api::set_current_win(&window.into())?)
+}
+
+/// Binding to [`vim.api.nvim_set_hl()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_hl()
+pub fn nvim_set_hl(
+    ns_id: u32,
+    name: &str,
+    opts: &SetHighlightOpts,
+) -> Result<()> {
+    Ok(api::set_hl(ns_id, name, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_set_keymap()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_keymap()
+pub fn nvim_set_keymap(
+    mode: Mode,
+    lhs: &str,
+    rhs: &str,
+    opts: &SetKeymapOpts,
+) -> Result<()> {
+    Ok(api::set_keymap(mode, lhs, rhs, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_set_option_value()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_option_value()
+pub fn nvim_set_option_value<Opt>(
+    name: &str,
+    value: Opt,
+    opts: &OptionValueOpts,
+) -> Result<()>
+where
+    Opt: ToObject,
+{
+    Ok(api::set_option_value(name, value, opts)?)
+}
+
+/// Binding to [`vim.api.nvim_set_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_set_var()
+pub fn nvim_set_var<Var>(name: &str, value: Var) -> Result<()>
+where
+    Var: ToObject,
+{
+    Ok(api::set_var(name, value)?)
+}
+
+/// Binding to [`vim.api.nvim_tabpage_get_win()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_tabpage_get_win()
+pub fn nvim_tabpage_get_win<Tab>(tabpage: Tab) -> Result<Window>
+where
+    Tab: Into<TabPage>,
+{
+    Ok(tabpage.into().get_win()?)
+}
+
+/// Binding to [`vim.api.nvim_tabpage_is_valid()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_tabpage_is_valid()
+pub fn nvim_tabpage_is_valid<Tab>(tabpage: Tab) -> bool
+where
+    Tab: Into<TabPage>,
+{
+    tabpage.into().is_valid()
+}
+
+/// Binding to [`vim.api.nvim_tabpage_list_wins()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_tabpage_list_wins()
+pub fn nvim_tabpage_list_wins<Tab>(tabpage: Tab) -> Result<Vec<Window>>
+where
+    Tab: Into<TabPage>,
+{
+    Ok(tabpage.into().list_wins()?.collect())
+}
+
+/// Binding to [`vim.api.nvim_win_close()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_close()
+pub fn nvim_win_close<Win>(window: Win, force: bool) -> Result<()>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().close(force)?)
+}
+
+/// Binding to [`vim.api.nvim_win_get_buf()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_buf()
+pub fn nvim_win_get_buf<Win>(window: Win) -> Result<Buffer>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().get_buf()?)
+}
+
+/// Binding to [`vim.api.nvim_win_get_cursor()`][1].
+///
+/// The row is 1-indexed and the column is 0-indexed, like in Lua.
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_cursor()
+pub fn nvim_win_get_cursor<Win>(window: Win) -> Result<(usize, usize)>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().get_cursor()?)
+}
+
+/// Binding to [`vim.api.nvim_win_get_height()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_height()
+pub fn nvim_win_get_height<Win>(window: Win) -> Result<u32>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().get_height()?)
+}
+
+/// Binding to [`vim.api.nvim_win_get_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_var()
+pub fn nvim_win_get_var<Win, Var>(window: Win, name: &str) -> Result<Var>
+where
+    Win: Into<Window>,
+    Var: FromObject,
+{
+    Ok(window.into().get_var(name)?)
+}
+
+/// Binding to [`vim.api.nvim_win_get_width()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_width()
+pub fn nvim_win_get_width<Win>(window: Win) -> Result<u32>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().get_width()?)
+}
+
+/// Binding to [`vim.api.nvim_win_is_valid()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_is_valid()
+pub fn nvim_win_is_valid<Win>(window: Win) -> bool
+where
+    Win: Into<Window>,
+{
+    window.into().is_valid()
+}
+
+/// Binding to [`vim.api.nvim_win_set_buf()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_buf()
+pub fn nvim_win_set_buf<Win, Buf>(window: Win, buffer: Buf) -> Result<()>
+where
+    Win: Into<Window>,
+    Buf: Into<Buffer>,
+{
+    Ok(window.into().set_buf(&buffer.into())?)
+}
+
+/// Binding to [`vim.api.nvim_win_set_cursor()`][1].
+///
+/// The row is 1-indexed and the column is 0-indexed, like in Lua.
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_cursor()
+pub fn nvim_win_set_cursor<Win>(window: Win, pos: (usize, usize)) -> Result<()>
+where
+    Win: Into<Window>,
+{
+    let (row, col) = pos;
+    Ok(window.into().set_cursor(row, col)?)
+}
+
+/// Binding to [`vim.api.nvim_win_set_height()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_height()
+pub fn nvim_win_set_height<Win>(window: Win, height: u32) -> Result<()>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().set_height(height)?)
+}
+
+/// Binding to [`vim.api.nvim_win_set_var()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_var()
+pub fn nvim_win_set_var<Win, Var>(
+    window: Win,
+    name: &str,
+    value: Var,
+) -> Result<()>
+where
+    Win: Into<Window>,
+    Var: ToObject,
+{
+    Ok(window.into().set_var(name, value)?)
+}
+
+/// Binding to [`vim.api.nvim_win_set_width()`][1].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_width()
+pub fn nvim_win_set_width<Win>(window: Win, width: u32) -> Result<()>
+where
+    Win: Into<Window>,
+{
+    Ok(window.into().set_width(width)?)
+}
+
+/// Calls an API function through Vimscript, which lets Neovim itself resolve
+/// negative indices exactly like it does for Lua.
+fn call<Args, Ret>(fn_: &str, args: Args) -> Result<Ret>
+where
+    Args: Into<Array>,
+    Ret: FromObject,
+{
+    Ok(api::call_function(fn_, args)?)
+}
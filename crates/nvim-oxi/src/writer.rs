@@ -0,0 +1,98 @@
+use std::io;
+use std::sync::mpsc::{self, Sender};
+
+use oxi_libuv::{AsyncHandle, Error as LibuvError};
+
+/// A message sent from a [`ThreadSafeWriter`] to the main thread.
+enum Message {
+    Write(Vec<u8>),
+    Flush,
+}
+
+/// An [`io::Write`] adapter that can be sent to other threads, forwarding
+/// whatever is written to it to a writer living on the main thread.
+///
+/// The writes are queued and an [`AsyncHandle`] wakes up Neovim's event loop,
+/// which then passes them to the wrapped writer in the order they were made.
+/// This means they're applied asynchronously: once a `write` or `flush`
+/// returns, the text has been queued but not necessarily written yet.
+///
+/// The writer can be cloned to write from several threads at once. It has to
+/// be created on the main thread.
+///
+/// # Examples
+///
+/// ```ignore
+/// use std::io::Write;
+/// use nvim_oxi::api::{self, BufferWriter, ThreadSafeWriter};
+///
+/// let buf = api::create_buf(true, true)?;
+/// let mut writer = ThreadSafeWriter::new(BufferWriter::new(buf))?;
+///
+/// std::thread::spawn(move || {
+///     writeln!(writer, "written from another thread").unwrap();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ThreadSafeWriter {
+    sender: Sender<Message>,
+    handle: AsyncHandle,
+}
+
+impl ThreadSafeWriter {
+    /// Creates a new writer forwarding its writes to `writer` on the main
+    /// thread.
+    ///
+    /// If `writer` returns an error the messages queued up to that point are
+    /// dropped.
+    pub fn new<W>(mut writer: W) -> Result<Self, LibuvError>
+    where
+        W: io::Write + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        let handle = AsyncHandle::new(move || -> io::Result<()> {
+            for message in receiver.try_iter() {
+                match message {
+                    Message::Write(bytes) => writer.write_all(&bytes)?,
+                    Message::Flush => writer.flush()?,
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(Self { sender, handle })
+    }
+
+    fn send(&self, message: Message) -> io::Result<()> {
+        self.sender.send(message).map_err(|_| {
+            // The receiver lives as long as the async handle, which is never
+            // closed.
+            to_io_error("the main thread stopped receiving writes")
+        })?;
+
+        self.handle.send().map_err(to_io_error)
+    }
+}
+
+impl io::Write for ThreadSafeWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.send(Message::Write(buf.to_vec()))?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.send(Message::Flush)
+    }
+}
+
+// `io::Error::other` requires a more recent Rust version than the MSRV.
+#[allow(clippy::io_other_error)]
+fn to_io_error<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::Other, err)
+}
@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+
+use oxi_types::{self as nvim, Array};
+
+use crate::fn_::fnameescape;
+use crate::types::ArglistError;
+use crate::Result;
+
+/// Returns the entries of the [argument list][1] of the current window, like
+/// [`argv()`][2].
+///
+/// [1]: https://neovim.io/doc/user/editing.html#arglist
+/// [2]: https://neovim.io/doc/user/builtin.html#argv()
+pub fn arglist() -> Result<Vec<PathBuf>> {
+    let args =
+        crate::call_function::<_, Vec<nvim::String>>("argv", Array::new())?;
+    Ok(args.into_iter().map(Into::into).collect())
+}
+
+/// Returns the 0-indexed position of the current entry in the argument list,
+/// like [`argidx()`][1].
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#argidx()
+pub fn argidx() -> Result<usize> {
+    crate::call_function("argidx", Array::new())
+}
+
+/// Appends the given files to the argument list, like [`:$argadd`][1].
+///
+/// The paths are escaped, so wildcards and special characters like `%` are
+/// taken literally.
+///
+/// [1]: https://neovim.io/doc/user/editing.html#%3Aargadd
+pub fn argadd<P, I>(paths: I) -> Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = P>,
+{
+    let args = escape_all(paths)?;
+    if args.is_empty() {
+        return Ok(());
+    }
+    crate::command(&format!("$argadd {args}"))
+}
+
+/// Removes the given files from the argument list, like [`:argdelete`][1].
+///
+/// The paths are escaped, so they're matched literally instead of being used
+/// as patterns. Deleting a file that isn't in the argument list is an error.
+///
+/// [1]: https://neovim.io/doc/user/editing.html#%3Aargdelete
+pub fn argdelete<P, I>(paths: I) -> Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = P>,
+{
+    let args = escape_all(paths)?;
+    if args.is_empty() {
+        return Ok(());
+    }
+    crate::command(&format!("argdelete {args}"))
+}
+
+/// Edits the argument at the given 0-indexed position, like
+/// [`:argument`][1].
+///
+/// [1]: https://neovim.io/doc/user/editing.html#%3Aargument
+pub fn argument(index: usize) -> Result<()> {
+    let len = argc()?;
+
+    if len == 0 {
+        return Err(ArglistError::Empty.into());
+    } else if index >= len {
+        return Err(ArglistError::OutOfRange { index, len }.into());
+    }
+
+    crate::command(&format!("{}argument", index + 1))
+}
+
+/// Edits the argument after the current one, like [`:next`][1].
+///
+/// [1]: https://neovim.io/doc/user/editing.html#%3Anext
+pub fn argnext() -> Result<()> {
+    let len = argc()?;
+
+    if len == 0 {
+        return Err(ArglistError::Empty.into());
+    }
+
+    let index = argidx()? + 1;
+
+    if index >= len {
+        return Err(ArglistError::AfterLast.into());
+    }
+
+    argument(index)
+}
+
+/// Edits the argument before the current one, like [`:previous`][1].
+///
+/// [1]: https://neovim.io/doc/user/editing.html#%3Aprevious
+pub fn argprev() -> Result<()> {
+    if argc()? == 0 {
+        return Err(ArglistError::Empty.into());
+    }
+
+    match argidx()?.checked_sub(1) {
+        Some(index) => argument(index),
+        None => Err(ArglistError::BeforeFirst.into()),
+    }
+}
+
+fn argc() -> Result<usize> {
+    crate::call_function("argc", Array::new())
+}
+
+fn escape_all<P, I>(paths: I) -> Result<String>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = P>,
+{
+    paths
+        .into_iter()
+        .map(fnameescape)
+        .collect::<Result<Vec<_>>>()
+        .map(|args| args.join(" "))
+}
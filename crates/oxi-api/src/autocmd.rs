@@ -12,6 +12,7 @@ use super::types::*;
 use super::LUA_INTERNAL_CALL;
 use crate::choose;
 use crate::Result;
+use crate::StringOrInt;
 use crate::SuperIterator;
 
 /// Binding to [`nvim_clear_autocmds()`][1].
@@ -27,14 +28,18 @@ pub fn clear_autocmds(opts: &ClearAutocmdsOpts) -> Result<()> {
 
 /// Binding to [`nvim_create_augroup()`][1].
 ///
-/// Creates a new autocommand group or gets an existing one. To get the id of
-/// an existing augroup set the
-/// [`clear`](super::opts::CreateAugroupOptsBuilder::clear) field of `opts` to
-/// `false`.
+/// Creates a new autocommand group or gets an existing one, returning a
+/// handle to it. Unless the
+/// [`clear`](super::opts::CreateAugroupOptsBuilder::clear) field of `opts` is
+/// set to `false`, the autocommands already in the group are cleared, so
+/// calling this every time a plugin is reloaded doesn't register them twice.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_create_augroup()
-pub fn create_augroup(name: &str, opts: &CreateAugroupOpts) -> Result<u32> {
-    let name = nvim::String::from(name);
+pub fn create_augroup(
+    name: &str,
+    opts: &CreateAugroupOpts,
+) -> Result<AuGroup> {
+    let name = nvim::SmallString::from(name);
     let mut err = nvim::Error::new();
     let id = unsafe {
         nvim_create_augroup(
@@ -44,7 +49,61 @@ pub fn create_augroup(name: &str, opts: &CreateAugroupOpts) -> Result<u32> {
             &mut err,
         )
     };
-    choose!(err, Ok(id.try_into().expect("always positive")))
+    choose!(err, Ok(AuGroup { id: id.try_into()? }))
+}
+
+/// A handle to an autocommand group, as returned by [`create_augroup()`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AuGroup {
+    id: u32,
+}
+
+impl AuGroup {
+    /// Creates a new autocommand in the group. The
+    /// [`group`](super::opts::CreateAutocmdOptsBuilder::group) field of
+    /// `opts` is overridden with the group's id.
+    pub fn add_autocmd<I>(
+        &self,
+        events: I,
+        opts: &CreateAutocmdOpts,
+    ) -> Result<u32>
+    where
+        I: IntoIterator<Item = AutocmdEvent>,
+    {
+        let mut opts = opts.clone();
+        opts.set_group(self.id);
+        create_autocmd(events, &opts)
+    }
+
+    /// Clears all the autocommands in the group, without deleting it.
+    pub fn clear(&self) -> Result<()> {
+        clear_autocmds(&ClearAutocmdsOpts::builder().group(self.id).build())
+    }
+
+    /// Deletes the group together with all its autocommands.
+    pub fn del(self) -> Result<()> {
+        del_augroup_by_id(self.id)
+    }
+
+    /// The id of the group.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl From<AuGroup> for u32 {
+    #[inline]
+    fn from(group: AuGroup) -> Self {
+        group.id
+    }
+}
+
+impl StringOrInt for AuGroup {
+    #[inline]
+    fn to_object(self) -> Object {
+        self.id.into()
+    }
 }
 
 /// Binding to [`nvim_create_autocmd()`][1].
@@ -52,12 +111,9 @@ pub fn create_augroup(name: &str, opts: &CreateAugroupOpts) -> Result<u32> {
 /// Creates a new autocommand.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_create_autocmd()
-pub fn create_autocmd<'a, I>(
-    events: I,
-    opts: &CreateAutocmdOpts,
-) -> Result<u32>
+pub fn create_autocmd<I>(events: I, opts: &CreateAutocmdOpts) -> Result<u32>
 where
-    I: IntoIterator<Item = &'a str>,
+    I: IntoIterator<Item = AutocmdEvent>,
 {
     let events = Object::from(Array::from_iter(events));
     let mut err = nvim::Error::new();
@@ -69,7 +125,7 @@ where
             &mut err,
         )
     };
-    choose!(err, Ok(id.try_into().expect("always positive")))
+    choose!(err, Ok(id.try_into()?))
 }
 
 /// Binding to [`nvim_del_augroup_by_id()`][1].
@@ -89,7 +145,7 @@ pub fn del_augroup_by_id(id: u32) -> Result<()> {
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_del_augroup_by_name()
 pub fn del_augroup_by_name(name: &str) -> Result<()> {
-    let name = nvim::String::from(name);
+    let name = nvim::SmallString::from(name);
     let mut err = nvim::Error::new();
     unsafe { nvim_del_augroup_by_name(name.non_owning(), &mut err) };
     choose!(err, ())
@@ -112,9 +168,9 @@ pub fn del_autocmd(id: u32) -> Result<()> {
 /// match `opts`.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_exec_autocmds()
-pub fn exec_autocmds<'a, I>(events: I, opts: &ExecAutocmdsOpts) -> Result<()>
+pub fn exec_autocmds<I>(events: I, opts: &ExecAutocmdsOpts) -> Result<()>
 where
-    I: IntoIterator<Item = &'a str>,
+    I: IntoIterator<Item = AutocmdEvent>,
 {
     let events = Object::from(Array::from_iter(events));
     let mut err = nvim::Error::new();
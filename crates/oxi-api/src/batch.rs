@@ -0,0 +1,143 @@
+use std::ops::RangeBounds;
+
+use oxi_types::{
+    self as nvim,
+    conversion::{FromObject, ToObject},
+    Array,
+    Integer,
+    Object,
+};
+
+use crate::choose;
+use crate::decoration_context::ensure_not_redrawing;
+use crate::ffi::global::nvim_call_atomic;
+use crate::utils;
+use crate::Buffer;
+use crate::LUA_INTERNAL_CALL;
+use crate::{Error, Result};
+
+/// A queue of API calls executed together by [`batch()`].
+#[derive(Debug, Default)]
+pub struct Batch {
+    calls: Vec<Object>,
+    error: Option<Error>,
+}
+
+impl Batch {
+    /// Queues a call to the API function `method`, e.g. `"nvim_set_var"`,
+    /// with the given arguments.
+    pub fn call(&mut self, method: &str, args: impl Into<Array>) -> &mut Self {
+        let call = Array::from_iter([
+            Object::from(nvim::String::from(method)),
+            Object::from(args.into()),
+        ]);
+        self.calls.push(call.into());
+        self
+    }
+
+    /// Queues a call to [`command`](crate::command).
+    pub fn command(&mut self, command: &str) -> &mut Self {
+        self.call("nvim_command", (nvim::String::from(command),))
+    }
+
+    /// Queues a call to [`Buffer::set_lines`].
+    pub fn set_lines<Line, Lines, R>(
+        &mut self,
+        buffer: &Buffer,
+        line_range: R,
+        strict_indexing: bool,
+        replacement: Lines,
+    ) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        let rpl = replacement.into_iter().map(Into::into).collect::<Array>();
+        let (start, end) = utils::range_to_limits(line_range);
+        self.call(
+            "nvim_buf_set_lines",
+            (buffer, start, end, strict_indexing, rpl),
+        )
+    }
+
+    /// Queues a call to [`set_var`](crate::set_var).
+    pub fn set_var<Var>(&mut self, name: &str, value: Var) -> &mut Self
+    where
+        Var: ToObject,
+    {
+        match value.to_object() {
+            Ok(value) => {
+                self.call("nvim_set_var", (nvim::String::from(name), value))
+            },
+
+            Err(err) => {
+                self.error.get_or_insert(err.into());
+                self
+            },
+        }
+    }
+}
+
+/// Binding to [`nvim_call_atomic()`][1].
+///
+/// Executes the calls queued on the [`Batch`] passed to `fun` one after
+/// the other, in the order they were queued.
+///
+/// `nvim_call_atomic()` is meant to be used by remote clients, to send many
+/// calls in a single RPC request. A plugin calls the API functions directly,
+/// so this isn't faster than making the calls one at a time. Nor is it
+/// atomic in the transactional sense: the calls that succeeded before a
+/// failing one aren't undone.
+///
+/// Returns the result of every call in order. The calls after one that
+/// fails aren't executed, so the last result is the only one that can be
+/// an error.
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_call_atomic()
+pub fn batch<F>(fun: F) -> Result<Vec<Result<Object>>>
+where
+    F: FnOnce(&mut Batch),
+{
+    let mut batch = Batch::default();
+    fun(&mut batch);
+
+    if let Some(err) = batch.error {
+        return Err(err);
+    }
+
+    if batch.calls.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    ensure_not_redrawing("nvim_call_atomic")?;
+
+    let calls = Array::from_iter(batch.calls);
+    let mut err = nvim::Error::new();
+    let res = unsafe {
+        nvim_call_atomic(
+            LUA_INTERNAL_CALL,
+            calls.non_owning(),
+            #[cfg(not(feature = "neovim-0-8"))]
+            std::ptr::null_mut(),
+            &mut err,
+        )
+    };
+    choose!(err, decode_results(res))
+}
+
+/// Decodes the `[results, error]` pair returned by `nvim_call_atomic()`,
+/// where `error` is either `nil` or an `[index, type, message]` triple.
+fn decode_results(res: Array) -> Result<Vec<Result<Object>>> {
+    let (results, failure) = <(Vec<Object>, Object)>::from_object(res.into())?;
+
+    let mut results = results.into_iter().map(Ok).collect::<Vec<_>>();
+
+    if !failure.is_nil() {
+        let (_idx, _kind, msg) =
+            <(Integer, Integer, String)>::from_object(failure)?;
+        results.push(Err(Error::custom(msg)));
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,112 @@
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use oxi_luajit::{self as lua, ffi::*, macros::cstr};
+use oxi_types::{Dictionary, Function, Object, ObjectKind};
+
+/// A handle to the callbacks registered by
+/// [`Buffer::attach_scoped`](crate::Buffer::attach_scoped).
+///
+/// Dropping the handle or calling [`detach`](BufAttachHandle::detach) on it
+/// detaches the callbacks, which won't be called anymore. Use
+/// [`forget`](BufAttachHandle::forget) to keep them attached until one of
+/// them returns `true` or the buffer is unloaded.
+///
+/// Neovim only detaches the callbacks on the next buffer event, at which
+/// point the [`on_detach`](crate::opts::BufAttachOptsBuilder::on_detach)
+/// callback is called.
+#[must_use = "dropping a `BufAttachHandle` detaches its callbacks"]
+#[derive(Debug)]
+pub struct BufAttachHandle {
+    detached: Rc<Cell<bool>>,
+}
+
+impl BufAttachHandle {
+    /// Detaches the callbacks.
+    #[inline]
+    pub fn detach(self) {}
+
+    /// Consumes the handle without detaching the callbacks.
+    #[inline]
+    pub fn forget(self) {
+        std::mem::forget(self)
+    }
+}
+
+impl Drop for BufAttachHandle {
+    #[inline]
+    fn drop(&mut self) {
+        self.detached.set(true);
+    }
+}
+
+/// Wraps the callbacks in the options passed to `nvim_buf_attach()` so that
+/// they detach once the returned handle is dropped.
+pub(crate) fn make_detachable(opts: &mut Dictionary) -> BufAttachHandle {
+    let detached = Rc::new(Cell::new(false));
+
+    let is_detached = Function::<(), bool>::from_fn({
+        let detached = Rc::clone(&detached);
+        move |()| Ok::<_, Infallible>(detached.get())
+    });
+
+    // Neovim only looks at the values returned by these callbacks, so
+    // `on_lines` is always set to have one that can return `true`.
+    for name in ["on_bytes", "on_changedtick", "on_lines", "on_reload"] {
+        let wrapped = match opts.get(name) {
+            Some(obj) if matches!(obj.kind(), ObjectKind::LuaRef) => {
+                wrap(Some(obj), &is_detached)
+            },
+            _ if name == "on_lines" => wrap(None, &is_detached),
+            _ => continue,
+        };
+        opts.insert(name, wrapped);
+    }
+
+    is_detached.remove_from_lua_registry();
+
+    BufAttachHandle { detached }
+}
+
+/// Returns a Lua function which calls `callback`, if any, with the same
+/// arguments, unless `is_detached` returns `true`, in which case it returns
+/// `true` without calling it.
+fn wrap(
+    callback: Option<&Object>,
+    is_detached: &Function<(), bool>,
+) -> Object {
+    unsafe {
+        lua::with_state(|lstate| {
+            let res = luaL_loadstring(
+                lstate,
+                cstr!(
+                    "local callback, is_detached = ...
+                    return function(...)
+                        if is_detached() then return true end
+                        if callback then return callback(...) end
+                    end"
+                ),
+            );
+            debug_assert_eq!(0, res);
+
+            // Neovim only frees the references to the callbacks it's given,
+            // so the original one is released once it's been captured by
+            // the wrapper.
+            match callback {
+                Some(obj) => {
+                    let luaref = obj.as_luaref_unchecked();
+                    lua_rawgeti(lstate, LUA_REGISTRYINDEX, luaref);
+                    luaL_unref(lstate, LUA_REGISTRYINDEX, luaref);
+                },
+                None => lua_pushnil(lstate),
+            }
+
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, is_detached.lua_ref());
+
+            lua_call(lstate, 2, 1);
+
+            Object::from_luaref(luaL_ref(lstate, LUA_REGISTRYINDEX))
+        })
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt;
-use std::ops::RangeBounds;
+use std::io::BufRead;
+use std::iter::FusedIterator;
+use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 use std::result::Result as StdResult;
 
@@ -7,24 +9,36 @@ use oxi_luajit::{self as lua, Poppable, Pushable};
 use oxi_types::{
     self as nvim,
     conversion::{self, FromObject, ToObject},
+    iter::ArrayIterator,
     Array,
     BufHandle,
     Dictionary,
-    Function,
     Integer,
     Object,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::choose;
+use crate::decoration_context::ensure_not_redrawing;
 use crate::ffi::buffer::*;
+use crate::fn_;
+use crate::options::{self, Scope};
 use crate::opts::*;
-use crate::types::{CommandArgs, CommandInfos, KeymapInfos, Mode};
+use crate::types::{
+    BufferDisplay,
+    CommandArgs,
+    CommandInfos,
+    FileFormat,
+    KeymapInfos,
+    Mode,
+    SwapInfo,
+};
 use crate::utils;
 use crate::StringOrFunction;
 use crate::SuperIterator;
 use crate::LUA_INTERNAL_CALL;
 use crate::{Error, Result};
+use crate::{BufAttachHandle, TabPage, Window};
 
 /// A wrapper around a Neovim buffer handle.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -98,14 +112,35 @@ impl Buffer {
     ///
     /// Used to register a set of callbacks on specific buffer events.
     ///
+    /// The callbacks stay attached until one of them returns `true` or the
+    /// buffer is unloaded. Use [`attach_scoped`](Buffer::attach_scoped) to
+    /// detach them from Rust instead.
+    ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_attach()
     pub fn attach(
         &self,
         send_buffer: bool,
         opts: &BufAttachOpts,
     ) -> Result<()> {
+        self.attach_dict(send_buffer, Dictionary::from(opts))
+    }
+
+    /// Like [`attach`](Buffer::attach), but the callbacks are also detached
+    /// when the returned [`BufAttachHandle`] is dropped. See its
+    /// documentation for more infos.
+    pub fn attach_scoped(
+        &self,
+        send_buffer: bool,
+        opts: &BufAttachOpts,
+    ) -> Result<BufAttachHandle> {
+        let mut opts = Dictionary::from(opts);
+        let handle = crate::buf_attach::make_detachable(&mut opts);
+        self.attach_dict(send_buffer, opts)?;
+        Ok(handle)
+    }
+
+    fn attach_dict(&self, send_buffer: bool, opts: Dictionary) -> Result<()> {
         let mut err = nvim::Error::new();
-        let opts = Dictionary::from(opts);
         let has_attached = unsafe {
             nvim_buf_attach(
                 LUA_INTERNAL_CALL,
@@ -124,23 +159,49 @@ impl Buffer {
         )
     }
 
+    /// Converts the 0-indexed byte `offset` into the 0-indexed row and byte
+    /// column it falls on, counting line endings like
+    /// [`get_offset`](Buffer::get_offset) does.
+    ///
+    /// An offset equal to the size of the buffer returns the row just past
+    /// the last line. Use [`text::byte_to_col`](crate::text::byte_to_col) to
+    /// convert the column into another encoding.
+    pub fn byte_to_pos(&self, offset: usize) -> Result<(usize, usize)> {
+        let line_count = self.line_count()?;
+
+        if offset > self.get_offset(line_count)? {
+            return Err(Error::custom(format!(
+                "byte offset {offset} is past the end of the buffer"
+            )));
+        }
+
+        // Binary search for the last line starting at or before `offset`.
+        let (mut lo, mut hi) = (0, line_count);
+        while lo < hi {
+            let mid = hi - (hi - lo) / 2;
+            if self.get_offset(mid)? <= offset {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        Ok((lo, offset - self.get_offset(lo)?))
+    }
+
     /// Binding to [`nvim_buf_call()`][1].
     ///
-    /// Calls a function with this buffer as the temporary current buffer.
+    /// Calls a function with this buffer as the temporary current buffer,
+    /// returning its result.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_call()
     pub fn call<F, R>(&self, fun: F) -> Result<R>
     where
         F: FnOnce(()) -> Result<R> + 'static,
-        R: Pushable + FromObject,
+        R: 'static,
     {
-        let fun = Function::from_fn_once(fun);
-        let mut err = nvim::Error::new();
-        let obj = unsafe { nvim_buf_call(self.0, fun.lua_ref(), &mut err) };
-
-        choose!(err, {
-            fun.remove_from_lua_registry();
-            Ok(R::from_object(obj)?)
+        crate::utils::call_lua_fn(fun, |lua_ref, err| {
+            let _ = unsafe { nvim_buf_call(self.0, lua_ref, err) };
         })
     }
 
@@ -159,7 +220,7 @@ impl Buffer {
         Cmd: StringOrFunction<CommandArgs, ()>,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let command = command.to_object();
         unsafe {
             nvim_buf_create_user_command(
@@ -181,8 +242,9 @@ impl Buffer {
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_keymap()
     pub fn del_keymap(&mut self, mode: Mode, lhs: &str) -> Result<()> {
+        mode.ensure_not_all("nvim_buf_del_keymap")?;
         let mut err = nvim::Error::new();
-        let mode = nvim::String::from(mode);
+        let mode = mode.as_nvim_str();
         let lhs = nvim::String::from(lhs);
         unsafe {
             nvim_buf_del_keymap(
@@ -203,7 +265,7 @@ impl Buffer {
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_mark()
     pub fn del_mark(&mut self, name: char) -> Result<()> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let was_deleted =
             unsafe { nvim_buf_del_mark(self.0, name.non_owning(), &mut err) };
         choose!(
@@ -225,7 +287,7 @@ impl Buffer {
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_user_command()
     pub fn del_user_command(&mut self, name: &str) -> Result<()> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe {
             nvim_buf_del_user_command(self.0, name.non_owning(), &mut err)
         };
@@ -239,7 +301,7 @@ impl Buffer {
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_var()
     pub fn del_var(&mut self, name: &str) -> Result<()> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe { nvim_buf_del_var(self.0, name.non_owning(), &mut err) };
         choose!(err, ())
     }
@@ -251,19 +313,59 @@ impl Buffer {
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_delete()
     pub fn delete(self, opts: &BufDeleteOpts) -> Result<()> {
+        ensure_not_redrawing("nvim_buf_delete")?;
         let mut err = nvim::Error::new();
         let opts = Dictionary::from(opts);
         unsafe { nvim_buf_delete(self.0, opts.non_owning(), &mut err) };
         choose!(err, ())
     }
 
+    /// Returns the windows of the current tabpage displaying the buffer,
+    /// together with the range of lines visible in each of them.
+    ///
+    /// This is useful to only compute decorations for the visible parts of a
+    /// buffer. The returned vector is empty if the buffer is not visible.
+    pub fn display_info(&self) -> Result<Vec<BufferDisplay>> {
+        let displaying = |window: &Window| match window.get_buf() {
+            Ok(buf) => buf == *self,
+            Err(_) => false,
+        };
+
+        TabPage::current()
+            .list_wins()?
+            .filter(displaying)
+            .map(|window| {
+                let first: usize =
+                    crate::call_function("line", ("w0", &window))?;
+                let last: usize =
+                    crate::call_function("line", ("w$", &window))?;
+                Ok(BufferDisplay {
+                    window,
+                    visible_lines: first.saturating_sub(1)..last,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the buffer's [`'fileformat'`][1], i.e. the line ending used
+    /// when the buffer is written. See the [`text`](crate::text) module to
+    /// split and join lines with it.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'fileformat'
+    pub fn fileformat(&self) -> Result<FileFormat> {
+        let name = options::get::<String>("fileformat", Scope::Buffer(self))?;
+        FileFormat::from_name(&name).ok_or_else(|| {
+            Error::custom(format!("invalid 'fileformat' {name}"))
+        })
+    }
+
     /// Binding to [`nvim_buf_get_changedtick()`][1].
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_changedtick()
     pub fn get_changedtick(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
         let ct = unsafe { nvim_buf_get_changedtick(self.0, &mut err) };
-        choose!(err, Ok(ct.try_into().expect("always positive")))
+        choose!(err, Ok(ct.try_into()?))
     }
 
     /// Binding to [`nvim_buf_get_commands()`][1].
@@ -286,23 +388,37 @@ impl Buffer {
 
     /// Binding to [`nvim_buf_get_keymap()`][1].
     ///
+    /// Returns an iterator over the buffer-local mapping definitions. Use
+    /// [`Mode::All`] to get the mappings for every mode at once.
+    ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_keymap()
     pub fn get_keymap(
         &self,
         mode: Mode,
     ) -> Result<impl SuperIterator<KeymapInfos>> {
-        let mut err = nvim::Error::new();
-        let mode = nvim::String::from(mode);
-        let maps = unsafe {
-            nvim_buf_get_keymap(self.0, mode.non_owning(), &mut err)
+        let modes = match mode {
+            Mode::All => &Mode::DISJOINT[..],
+            _ => std::slice::from_ref(&mode),
         };
-        choose!(
-            err,
-            Ok({
+
+        let mut keymaps = Vec::new();
+
+        for &mode in modes {
+            let mut err = nvim::Error::new();
+            let mode = mode.as_nvim_str();
+            let maps = unsafe {
+                nvim_buf_get_keymap(self.0, mode.non_owning(), &mut err)
+            };
+            if err.is_err() {
+                return Err(err.into());
+            }
+            keymaps.extend(
                 maps.into_iter()
-                    .map(|obj| KeymapInfos::from_object(obj).unwrap())
-            })
-        )
+                    .map(|obj| KeymapInfos::from_object(obj).unwrap()),
+            );
+        }
+
+        Ok(keymaps.into_iter())
     }
 
     /// Binding to [`nvim_buf_get_lines()`][1].
@@ -328,11 +444,7 @@ impl Buffer {
                 start,
                 end,
                 strict_indexing,
-<<<<<<< HEAD:crates/nvim-api/src/buffer.rs
-                #[cfg(feature = "neovim-nightly")]
-=======
                 #[cfg(not(feature = "neovim-0-8"))]
->>>>>>> upstream/main:crates/oxi-api/src/buffer.rs
                 // The nvim_buf_get_lines() function returns no line if we use an actual lstate here
                 std::ptr::null_mut(),
                 &mut err,
@@ -356,15 +468,10 @@ impl Buffer {
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_mark()
     pub fn get_mark(&self, name: char) -> Result<(usize, usize)> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let mark =
             unsafe { nvim_buf_get_mark(self.0, name.non_owning(), &mut err) };
-        choose!(err, {
-            let mut iter = mark.into_iter().map(usize::from_object);
-            let row = iter.next().expect("row is present")?;
-            let col = iter.next().expect("col is present")?;
-            Ok((row, col))
-        })
+        choose!(err, Ok(mark.decode()?))
     }
 
     /// Binding to [`nvim_buf_get_name()`][1].
@@ -389,7 +496,7 @@ impl Buffer {
         let mut err = nvim::Error::new();
         let offset =
             unsafe { nvim_buf_get_offset(self.0, index as Integer, &mut err) };
-        choose!(err, Ok(offset.try_into().expect("offset is positive")))
+        choose!(err, Ok(offset.try_into()?))
     }
 
     /// Binding to [`nvim_buf_get_option()`][1].
@@ -397,12 +504,19 @@ impl Buffer {
     /// Gets a buffer option value.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_option()
+    #[deprecated(note = "use `options::get` with `Scope::Buffer` instead")]
     pub fn get_option<Opt>(&self, name: &str) -> Result<Opt>
     where
         Opt: FromObject,
     {
+        #[cfg(feature = "neovim-nightly")]
+        crate::deprecation::check(
+            "nvim_buf_get_option",
+            "nvim_get_option_value",
+        )?;
+
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let obj = unsafe {
             nvim_buf_get_option(
                 self.0,
@@ -446,11 +560,7 @@ impl Buffer {
                 end,
                 end_col.try_into()?,
                 opts.non_owning(),
-<<<<<<< HEAD:crates/nvim-api/src/buffer.rs
-                #[cfg(feature = "neovim-nightly")]
-=======
                 #[cfg(not(feature = "neovim-0-8"))]
->>>>>>> upstream/main:crates/oxi-api/src/buffer.rs
                 // The nvim_buf_get_text() function returns no line if we use an actual lstate here
                 std::ptr::null_mut(),
                 &mut err,
@@ -476,12 +586,26 @@ impl Buffer {
         Var: FromObject,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let obj =
             unsafe { nvim_buf_get_var(self.0, name.non_owning(), &mut err) };
         choose!(err, Ok(Var::from_object(obj)?))
     }
 
+    /// Returns whether the buffer has changes that haven't been written,
+    /// i.e. whether it's [`'modified'`][1] and its [`'buftype'`][2] allows
+    /// writing it to a file.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'modified'
+    /// [2]: https://neovim.io/doc/user/options.html#'buftype'
+    pub fn has_unsaved_changes(&self) -> Result<bool> {
+        if !self.is_modified()? {
+            return Ok(false);
+        }
+        let buftype = options::get::<String>("buftype", Scope::Buffer(self))?;
+        Ok(matches!(&*buftype, "" | "acwrite"))
+    }
+
     /// Binding to [`nvim_buf_is_loaded()`][1].
     ///
     /// Checks if a buffer is valid and loaded.
@@ -491,6 +615,13 @@ impl Buffer {
         unsafe { nvim_buf_is_loaded(self.0) }
     }
 
+    /// Returns whether the buffer's [`'modified'`][1] option is set.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'modified'
+    pub fn is_modified(&self) -> Result<bool> {
+        options::get("modified", Scope::Buffer(self))
+    }
+
     /// Binding to [`nvim_buf_is_valid()`][1].
     ///
     /// Checks if a buffer is valid.
@@ -500,6 +631,17 @@ impl Buffer {
         unsafe { nvim_buf_is_valid(self.0) }
     }
 
+    /// Returns whether the buffer is displayed in any window of the current
+    /// tabpage.
+    pub fn is_visible(&self) -> Result<bool> {
+        for window in TabPage::current().list_wins()? {
+            if window.get_buf()? == *self {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     /// Binding to [`nvim_buf_line_count()`][1].
     ///
     /// Returns the number of lines in the given buffer.
@@ -508,7 +650,105 @@ impl Buffer {
     pub fn line_count(&self) -> Result<usize> {
         let mut err = nvim::Error::new();
         let count = unsafe { nvim_buf_line_count(self.0, &mut err) };
-        choose!(err, Ok(count.try_into().expect("always positive")))
+        choose!(err, Ok(count.try_into()?))
+    }
+
+    /// Returns an iterator over the lines in the given range which, unlike
+    /// [`get_lines`](Buffer::get_lines), fetches them lazily in batches of
+    /// `chunk_size` lines. Indexing is zero-based, end-exclusive.
+    ///
+    /// The iterator yields an error and stops if fetching a batch fails,
+    /// e.g. because the buffer has been deleted in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn lines_chunked<R>(
+        &self,
+        line_range: R,
+        chunk_size: usize,
+    ) -> impl FusedIterator<Item = Result<nvim::String>>
+    where
+        R: RangeBounds<usize>,
+    {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        let start = match line_range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Included(&n) => n,
+        };
+
+        let end = match line_range.end_bound() {
+            Bound::Unbounded => None,
+            Bound::Excluded(&n) => Some(n),
+            Bound::Included(&n) => Some(n + 1),
+        };
+
+        LinesChunked {
+            buffer: self.clone(),
+            start,
+            end,
+            chunk_size,
+            chunk: Array::new().into_iter(),
+            is_done: false,
+        }
+    }
+
+    /// Converts the 0-indexed `row` and byte column `col` into a 0-indexed
+    /// byte offset, i.e. the inverse of [`byte_to_pos`](Buffer::byte_to_pos).
+    ///
+    /// Fails if `col` is past the end of the line. Use
+    /// [`text::col_to_byte`](crate::text::col_to_byte) to get the byte column
+    /// of a column in another encoding.
+    pub fn pos_to_byte(&self, row: usize, col: usize) -> Result<usize> {
+        let start = self.get_offset(row)?;
+
+        // The line's length can't be computed from the offset of the next
+        // one, which doesn't include a line break after the last line of a
+        // buffer with 'noeol'.
+        let len = if row < self.line_count()? {
+            self.get_lines(row..=row, true)?.next().map_or(0, |line| line.len())
+        } else {
+            0
+        };
+
+        if col > len {
+            return Err(Error::custom(format!(
+                "column {col} is past the end of line {row}"
+            )));
+        }
+
+        Ok(start + col)
+    }
+
+    /// Recovers the buffer's contents from its file's swap file, like
+    /// [`:recover`][1], e.g. after the Neovim instance editing it crashed.
+    ///
+    /// If `swap_file` is `None` the swap file is looked up from the buffer's
+    /// name, which fails if there's more than one. The buffer's current
+    /// contents are lost, and it's left modified so that the recovered
+    /// changes can be written.
+    ///
+    /// [1]: https://neovim.io/doc/user/recover.html#%3Arecover
+    pub fn recover(&self, swap_file: Option<&Path>) -> Result<()> {
+        let cmd = match swap_file {
+            Some(path) => format!("recover {}", fn_::fnameescape(path)?),
+            None => "recover".to_owned(),
+        };
+        self.call(move |()| crate::command(&cmd))
+    }
+
+    /// Returns a registrar of buffer-local keymaps which are deleted when
+    /// the filetype of the buffer changes from `filetype`, or from its
+    /// current filetype if `None`, or when the buffer is unloaded.
+    ///
+    /// See [`ScopedKeymaps`](crate::keymap::ScopedKeymaps) for more details.
+    pub fn scoped_keymaps(
+        &self,
+        filetype: Option<&str>,
+    ) -> Result<crate::keymap::ScopedKeymaps> {
+        crate::keymap::ScopedKeymaps::new(self.clone(), filetype)
     }
 
     /// Binding to [`nvim_buf_set_keymap()`][1].
@@ -524,7 +764,8 @@ impl Buffer {
         rhs: &str,
         opts: &SetKeymapOpts,
     ) -> Result<()> {
-        let mode = nvim::String::from(mode);
+        mode.ensure_not_all("nvim_buf_set_keymap")?;
+        let mode = mode.as_nvim_str();
         let lhs = nvim::String::from(lhs);
         let rhs = nvim::String::from(rhs);
         let mut err = nvim::Error::new();
@@ -559,6 +800,7 @@ impl Buffer {
         Lines: IntoIterator<Item = Line>,
         Line: Into<nvim::String>,
     {
+        ensure_not_redrawing("nvim_buf_set_lines")?;
         let rpl = replacement.into_iter().map(Into::into).collect::<Array>();
         let mut err = nvim::Error::new();
         let (start, end) = utils::range_to_limits(line_range);
@@ -576,10 +818,140 @@ impl Buffer {
         choose!(err, ())
     }
 
+    /// Replaces a line-range in the buffer with the lines yielded by
+    /// `lines`, which are written in batches of
+    /// [`chunk_size`](SetLinesChunkedOptsBuilder::chunk_size) lines instead
+    /// of being collected all at once. Indexing is zero-based,
+    /// end-exclusive and strict. Returns the number of written lines.
+    ///
+    /// Writing stops at the first error, leaving the chunks written until
+    /// then in the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk size is zero.
+    pub fn set_lines_chunked<R, Line, Lines>(
+        &mut self,
+        line_range: R,
+        lines: Lines,
+        opts: &SetLinesChunkedOpts,
+    ) -> Result<usize>
+    where
+        R: RangeBounds<usize>,
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        self.write_chunks(
+            line_range,
+            lines.into_iter().map(|line| Ok(line.into())),
+            opts,
+        )
+    }
+
+    /// Same as [`set_lines_chunked`](Buffer::set_lines_chunked), but reads
+    /// the lines from `reader` as they're written. Both `\n` and `\r\n`
+    /// line endings are recognized, and the lines don't have to be valid
+    /// UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk size is zero.
+    pub fn set_lines_from_reader<R, Rd>(
+        &mut self,
+        line_range: R,
+        reader: Rd,
+        opts: &SetLinesChunkedOpts,
+    ) -> Result<usize>
+    where
+        R: RangeBounds<usize>,
+        Rd: BufRead,
+    {
+        let lines = reader.split(b'\n').map(|line| {
+            let mut line = line.map_err(Error::custom)?;
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            Ok(nvim::String::from_bytes(&line))
+        });
+
+        self.write_chunks(line_range, lines, opts)
+    }
+
+    fn write_chunks<R, Lines>(
+        &mut self,
+        line_range: R,
+        mut lines: Lines,
+        opts: &SetLinesChunkedOpts,
+    ) -> Result<usize>
+    where
+        R: RangeBounds<usize>,
+        Lines: Iterator<Item = Result<nvim::String>>,
+    {
+        ensure_not_redrawing("nvim_buf_set_lines")?;
+        assert!(opts.chunk_size != 0, "chunk size must be non-zero");
+
+        let start = match line_range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Included(&n) => n,
+        };
+
+        // The first chunk replaces the range, the next ones are inserted
+        // after the lines written so far.
+        let mut end = match line_range.end_bound() {
+            Bound::Unbounded => -1,
+            Bound::Excluded(&n) => n as Integer,
+            Bound::Included(&n) => (n + 1) as Integer,
+        };
+
+        let mut written = 0;
+
+        loop {
+            let chunk = lines
+                .by_ref()
+                .take(opts.chunk_size)
+                .collect::<Result<Array>>()?;
+
+            let len = chunk.len();
+            let mut err = nvim::Error::new();
+            unsafe {
+                nvim_buf_set_lines(
+                    LUA_INTERNAL_CALL,
+                    self.0,
+                    (start + written) as Integer,
+                    end,
+                    true,
+                    chunk.non_owning(),
+                    &mut err,
+                )
+            };
+
+            if err.is_err() {
+                return Err(err.into());
+            }
+
+            written += len;
+            end = (start + written) as Integer;
+
+            if opts.scroll && len != 0 {
+                for mut win in crate::list_wins() {
+                    if win.get_buf()? == *self {
+                        win.set_cursor(start + written, 0)?;
+                    }
+                }
+            }
+
+            if len < opts.chunk_size {
+                return Ok(written);
+            }
+        }
+    }
+
     /// Binding to [`nvim_buf_set_mark()`][1].
     ///
     /// Sets a named mark in the buffer. Marks are (1,0)-indexed, and passing 0
-    /// as `line` deletes the mark.
+    /// as `line` deletes the mark. Uppercase and numbered marks are global,
+    /// see [`set_mark`](crate::set_mark).
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_mark()
     pub fn set_mark(
@@ -587,16 +959,18 @@ impl Buffer {
         name: char,
         line: usize,
         col: usize,
+        opts: &SetMarkOpts,
     ) -> Result<()> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
+        let opts = Dictionary::from(opts);
         let mark_was_set = unsafe {
             nvim_buf_set_mark(
                 self.0,
                 name.non_owning(),
                 line.try_into()?,
                 col.try_into()?,
-                Dictionary::new().non_owning(),
+                opts.non_owning(),
                 &mut err,
             )
         };
@@ -615,6 +989,7 @@ impl Buffer {
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_name()
     pub fn set_name<Name: AsRef<Path>>(&mut self, name: Name) -> Result<()> {
+        ensure_not_redrawing("nvim_buf_set_name")?;
         let name = nvim::String::from(name.as_ref());
         let mut err = nvim::Error::new();
         unsafe { nvim_buf_set_name(self.0, name.non_owning(), &mut err) };
@@ -627,12 +1002,19 @@ impl Buffer {
     /// (only works if there's a global fallback).
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_option()
+    #[deprecated(note = "use `options::set` with `Scope::Buffer` instead")]
     pub fn set_option<V>(&mut self, name: &str, value: V) -> Result<()>
     where
         V: ToObject,
     {
+        #[cfg(feature = "neovim-nightly")]
+        crate::deprecation::check(
+            "nvim_buf_set_option",
+            "nvim_set_option_value",
+        )?;
+
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe {
             nvim_buf_set_option(
                 LUA_INTERNAL_CALL,
@@ -663,6 +1045,7 @@ impl Buffer {
         Lines: IntoIterator<Item = Line>,
         Line: Into<nvim::String>,
     {
+        ensure_not_redrawing("nvim_buf_set_text")?;
         let mut err = nvim::Error::new();
         let (start, end) = utils::range_to_limits(line_range);
         unsafe {
@@ -689,12 +1072,12 @@ impl Buffer {
     /// Sets a buffer-scoped (`b:`) variable.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_var()
-    pub fn set_var<V>(&mut self, name: &str, value: V) -> Result<()>
+    pub fn set_var<Var>(&mut self, name: &str, value: Var) -> Result<()>
     where
-        V: ToObject,
+        Var: ToObject,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe {
             nvim_buf_set_var(
                 self.0,
@@ -705,4 +1088,163 @@ impl Buffer {
         };
         choose!(err, ())
     }
+
+    /// Returns the path of the buffer's [swap file][1], or `None` if it
+    /// doesn't have one, e.g. because it's not loaded or
+    /// [`'swapfile'`][2] is off.
+    ///
+    /// [1]: https://neovim.io/doc/user/recover.html#swap-file
+    /// [2]: https://neovim.io/doc/user/options.html#'swapfile'
+    pub fn swap_file(&self) -> Result<Option<PathBuf>> {
+        fn_::swapname(self)
+    }
+
+    /// Returns the informations stored in the buffer's swap file, or `None`
+    /// if it doesn't have one.
+    ///
+    /// See [`swap_file()`](Buffer::swap_file) and
+    /// [`fn_::swapinfo()`](crate::fn_::swapinfo).
+    pub fn swap_info(&self) -> Result<Option<SwapInfo>> {
+        self.swap_file()?.map(fn_::swapinfo).transpose()
+    }
+
+    /// Closes the current undo block, so that the next change made to the
+    /// buffer can be undone separately from the previous ones, like
+    /// [`:h undo-break`][1].
+    ///
+    /// This is useful when a plugin makes several changes in a single
+    /// callback, which would otherwise be undone all at once.
+    ///
+    /// [1]: https://neovim.io/doc/user/undo.html#undo-break
+    pub fn undo_break(&mut self) -> Result<()> {
+        // Setting 'undolevels', even to its current value, starts a new undo
+        // block.
+        let levels =
+            options::get::<Integer>("undolevels", Scope::Buffer(self))?;
+        options::set("undolevels", levels, Scope::Buffer(self))
+    }
+
+    /// Returns an iterator over the buffer-scoped (`b:`) variables, as
+    /// `(name, value)` pairs.
+    ///
+    /// This is a wrapper around [`getbufvar()`][1], since there's no API
+    /// function to list the variables.
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#getbufvar()
+    pub fn vars(&self) -> Result<impl SuperIterator<(nvim::String, Object)>> {
+        if !self.is_valid() {
+            return Err(Error::custom(format!(
+                "Invalid buffer id: {}",
+                self.0
+            )));
+        }
+        let vars =
+            crate::call_function::<_, Dictionary>("getbufvar", (self.0, ""))?;
+        Ok(vars.into_iter())
+    }
+
+    /// Calls a function with the buffer-local value of [`'undolevels'`][1]
+    /// temporarily set to `levels`, restoring the previous value afterwards
+    /// even if the function returns an error.
+    ///
+    /// Passing `-1` makes the changes done by the function impossible to
+    /// undo, which is useful when filling a scratch buffer. Note that making
+    /// a change while `'undolevels'` is negative also discards the buffer's
+    /// existing undo history, see [`:h clear-undo`][2].
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'undolevels'
+    /// [2]: https://neovim.io/doc/user/undo.html#clear-undo
+    pub fn with_undolevels<F, R>(&mut self, levels: i64, fun: F) -> Result<R>
+    where
+        F: FnOnce(&mut Self) -> Result<R>,
+    {
+        let prev = options::get::<Integer>("undolevels", Scope::Buffer(self))?;
+        options::set("undolevels", levels, Scope::Buffer(self))?;
+
+        let ret = fun(self);
+
+        let restored = options::set("undolevels", prev, Scope::Buffer(self));
+
+        let ret = ret?;
+        restored?;
+        Ok(ret)
+    }
+}
+
+/// The iterator returned by [`Buffer::lines_chunked`].
+struct LinesChunked {
+    buffer: Buffer,
+    start: usize,
+    end: Option<usize>,
+    chunk_size: usize,
+    chunk: ArrayIterator,
+    is_done: bool,
+}
+
+impl LinesChunked {
+    fn fetch_chunk(&mut self) -> Result<()> {
+        let mut chunk_end = self.start + self.chunk_size;
+
+        if let Some(end) = self.end {
+            chunk_end = chunk_end.min(end);
+        }
+
+        if chunk_end <= self.start {
+            self.is_done = true;
+            return Ok(());
+        }
+
+        let mut err = nvim::Error::new();
+        let lines = unsafe {
+            nvim_buf_get_lines(
+                LUA_INTERNAL_CALL,
+                self.buffer.0,
+                self.start as Integer,
+                chunk_end as Integer,
+                false,
+                #[cfg(not(feature = "neovim-0-8"))]
+                std::ptr::null_mut(),
+                &mut err,
+            )
+        };
+
+        if err.is_err() {
+            self.is_done = true;
+            return Err(err.into());
+        }
+
+        // Since indexing isn't strict a short chunk means we've reached the
+        // end of the buffer.
+        if lines.len() < chunk_end - self.start {
+            self.is_done = true;
+        }
+
+        self.start = chunk_end;
+        self.chunk = lines.into_iter();
+        Ok(())
+    }
 }
+
+impl Iterator for LinesChunked {
+    type Item = Result<nvim::String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(line) = self.chunk.next() {
+                return Some(
+                    nvim::String::from_object(line).map_err(Into::into),
+                );
+            }
+
+            if self.is_done {
+                return None;
+            }
+
+            if let Err(err) = self.fetch_chunk() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+impl FusedIterator for LinesChunked {}
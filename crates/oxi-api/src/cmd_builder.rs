@@ -0,0 +1,128 @@
+use crate::opts::CmdOpts;
+use crate::types::{
+    CmdInfos,
+    CmdInfosBuilder,
+    CmdMagic,
+    CmdRange,
+    CommandModifiers,
+    CommandModifiersBuilder,
+};
+use crate::Result;
+
+/// A builder for Ex commands executed via [`cmd`](crate::cmd).
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{types::SplitModifier, CmdBuilder};
+///
+/// CmdBuilder::new()
+///     .cmd("split")
+///     .args(["foo.txt"])
+///     .mods(|mods| mods.silent(true).split(SplitModifier::BotRight))
+///     .exec()?;
+///
+/// let output = CmdBuilder::new().cmd("echo").args(["1 + 1"]).output()?;
+/// assert_eq!("2", output);
+/// ```
+#[derive(Clone, Default)]
+pub struct CmdBuilder {
+    infos: CmdInfosBuilder,
+    mods: Option<CommandModifiersBuilder>,
+}
+
+impl CmdBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The arguments of the command.
+    #[inline]
+    pub fn args<S, I>(&mut self, iter: I) -> &mut Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        self.infos.args(iter);
+        self
+    }
+
+    /// Whether the command is followed by a `<bang>` (`!`).
+    #[inline]
+    pub fn bang(&mut self, bang: bool) -> &mut Self {
+        self.infos.bang(bang);
+        self
+    }
+
+    /// The name of the command.
+    #[inline]
+    pub fn cmd(&mut self, cmd: impl Into<String>) -> &mut Self {
+        self.infos.cmd(cmd);
+        self
+    }
+
+    /// The count passed to the command.
+    #[inline]
+    pub fn count(&mut self, count: u32) -> &mut Self {
+        self.infos.count(count);
+        self
+    }
+
+    #[inline]
+    pub fn magic(&mut self, magic: CmdMagic) -> &mut Self {
+        self.infos.magic(magic);
+        self
+    }
+
+    /// Sets the command modifiers via a closure receiving a
+    /// [`CommandModifiersBuilder`]. Calling this more than once keeps
+    /// updating the same modifiers.
+    #[inline]
+    pub fn mods<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce(
+            &mut CommandModifiersBuilder,
+        ) -> &mut CommandModifiersBuilder,
+    {
+        fun(self.mods.get_or_insert_with(CommandModifiers::builder));
+        self
+    }
+
+    /// The range of the command.
+    #[inline]
+    pub fn range(&mut self, range: CmdRange) -> &mut Self {
+        self.infos.range(range);
+        self
+    }
+
+    /// The register passed to the command.
+    #[inline]
+    pub fn reg(&mut self, reg: char) -> &mut Self {
+        self.infos.reg(reg);
+        self
+    }
+
+    /// Returns the [`CmdInfos`] describing the command.
+    #[inline]
+    pub fn build(&mut self) -> CmdInfos {
+        if let Some(mods) = &mut self.mods {
+            self.infos.mods(mods.build());
+        }
+        self.mods = None;
+        self.infos.build()
+    }
+
+    /// Executes the command.
+    pub fn exec(&mut self) -> Result<()> {
+        let opts = CmdOpts::builder().output(false).build();
+        crate::cmd(&self.build(), &opts)?;
+        Ok(())
+    }
+
+    /// Executes the command, returning its output.
+    pub fn output(&mut self) -> Result<String> {
+        let opts = CmdOpts::builder().output(true).build();
+        Ok(crate::cmd(&self.build(), &opts)?.unwrap_or_default())
+    }
+}
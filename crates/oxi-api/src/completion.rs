@@ -0,0 +1,265 @@
+//! A framework to implement [insert mode completion][1] sources in Rust.
+//!
+//! Neovim calls an [`'omnifunc'`][2] or [`'completefunc'`][3] twice: first to
+//! find the column where the completion starts, then to get the items
+//! completing the text between that column and the cursor. A
+//! [`CompletionSource`] implements the two phases as separate methods, and
+//! [`set_omnifunc`] and [`set_completefunc`] take care of dispatching the
+//! calls and of converting the returned [`CompletionItem`]s into the
+//! dictionaries Neovim expects.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::completion::{self, CompletionItem, CompletionSource};
+//! use nvim_oxi::api::{Buffer, Error};
+//!
+//! struct Fruits;
+//!
+//! impl CompletionSource for Fruits {
+//!     fn complete(&mut self, base: &str) -> Result<Vec<CompletionItem>, Error> {
+//!         Ok(["apple", "banana", "cherry"]
+//!             .into_iter()
+//!             .filter(|fruit| fruit.starts_with(base))
+//!             .map(|fruit| CompletionItem::new(fruit).kind("f"))
+//!             .collect())
+//!     }
+//! }
+//!
+//! completion::set_omnifunc(&Buffer::current(), Fruits)?;
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/insert.html#ins-completion
+//! [2]: https://neovim.io/doc/user/options.html#'omnifunc'
+//! [3]: https://neovim.io/doc/user/options.html#'completefunc'
+
+use oxi_types::{Array, Dictionary, Object};
+
+use crate::exprs::{self, OmnifuncArgs};
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// A source of completion items, see the [module-level](self) docs for
+/// details.
+pub trait CompletionSource: 'static {
+    /// Returns where the completion starts, given the line the cursor is on
+    /// and the (0-indexed) byte offset of the cursor in it.
+    ///
+    /// The default implementation starts the completion at the beginning of
+    /// the keyword before the cursor.
+    fn start(&mut self, line: &str, col: usize) -> Result<CompletionStart> {
+        Ok(CompletionStart::Column(keyword_start(line, col)))
+    }
+
+    /// Returns the items completing `base`, i.e. the text between the column
+    /// returned by [`start`](CompletionSource::start) and the cursor.
+    fn complete(&mut self, base: &str) -> Result<Vec<CompletionItem>>;
+}
+
+/// Where the completion starts, returned by [`CompletionSource::start`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CompletionStart {
+    /// The completion starts at the given (0-indexed) byte offset in the
+    /// line.
+    Column(usize),
+
+    /// The completion starts at the cursor, i.e. `base` will be empty.
+    Cursor,
+
+    /// Cancels the completion silently, staying in completion mode.
+    Cancel,
+
+    /// Cancels the completion silently and leaves completion mode.
+    CancelAndLeave,
+}
+
+impl From<CompletionStart> for Object {
+    fn from(start: CompletionStart) -> Self {
+        match start {
+            CompletionStart::Column(col) => (col as i64).into(),
+            CompletionStart::Cursor => (-1).into(),
+            CompletionStart::Cancel => (-2).into(),
+            CompletionStart::CancelAndLeave => (-3).into(),
+        }
+    }
+}
+
+/// A completion item, see [`:h complete-items`][1] for details.
+///
+/// [1]: https://neovim.io/doc/user/insert.html#complete-items
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CompletionItem {
+    /// The text that will be inserted.
+    pub word: String,
+
+    /// The text displayed in the popup menu instead of `word`.
+    pub abbr: Option<String>,
+
+    /// A single letter describing the type of the item, e.g. `"f"` for
+    /// functions or `"v"` for variables.
+    pub kind: Option<String>,
+
+    /// Extra text displayed after the item in the popup menu.
+    pub menu: Option<String>,
+
+    /// More information about the item, displayed in the preview window.
+    pub info: Option<String>,
+
+    /// Whether to ignore case when comparing the item with other ones.
+    pub icase: bool,
+
+    /// Whether to add the item even if an item with the same `word` is
+    /// already present.
+    pub dup: bool,
+
+    /// Whether to add the item even if `word` is empty.
+    pub empty: bool,
+}
+
+impl CompletionItem {
+    /// Creates a new item inserting `word`.
+    #[inline]
+    pub fn new(word: impl Into<String>) -> Self {
+        Self { word: word.into(), ..Default::default() }
+    }
+
+    /// Sets the text displayed in the popup menu instead of the word.
+    #[inline]
+    pub fn abbr(mut self, abbr: impl Into<String>) -> Self {
+        self.abbr = Some(abbr.into());
+        self
+    }
+
+    /// Sets the kind of the item.
+    #[inline]
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    /// Sets the extra text displayed after the item in the popup menu.
+    #[inline]
+    pub fn menu(mut self, menu: impl Into<String>) -> Self {
+        self.menu = Some(menu.into());
+        self
+    }
+
+    /// Sets the text displayed in the preview window.
+    #[inline]
+    pub fn info(mut self, info: impl Into<String>) -> Self {
+        self.info = Some(info.into());
+        self
+    }
+}
+
+impl From<CompletionItem> for Dictionary {
+    fn from(item: CompletionItem) -> Self {
+        let mut dict = Dictionary::new();
+        dict.insert("word", item.word);
+
+        let strings = [
+            ("abbr", item.abbr),
+            ("kind", item.kind),
+            ("menu", item.menu),
+            ("info", item.info),
+        ];
+
+        for (key, value) in strings {
+            if let Some(value) = value {
+                dict.insert(key, value);
+            }
+        }
+
+        for (key, value) in
+            [("icase", item.icase), ("dup", item.dup), ("empty", item.empty)]
+        {
+            if value {
+                dict.insert(key, 1);
+            }
+        }
+
+        dict
+    }
+}
+
+/// Sets the [`'omnifunc'`][1] option of `buffer` to complete with `source`.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'omnifunc'
+pub fn set_omnifunc<S: CompletionSource>(
+    buffer: &Buffer,
+    mut source: S,
+) -> Result<()> {
+    exprs::set_omnifunc(buffer, move |args| dispatch(&mut source, args))
+}
+
+/// Sets the [`'completefunc'`][1] option of `buffer` to complete with
+/// `source`.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'completefunc'
+pub fn set_completefunc<S: CompletionSource>(
+    buffer: &Buffer,
+    mut source: S,
+) -> Result<()> {
+    exprs::set_completefunc(buffer, move |args| dispatch(&mut source, args))
+}
+
+/// Calls the method of `source` matching the phase of the completion.
+fn dispatch<S: CompletionSource>(
+    source: &mut S,
+    (findstart, base): OmnifuncArgs,
+) -> Result<Object> {
+    if findstart {
+        let line = crate::get_current_line()?;
+        let (_, col) = Window::current().get_cursor()?;
+        return source.start(&line, col).map(Into::into);
+    }
+
+    let items = source
+        .complete(&base)?
+        .into_iter()
+        .map(Dictionary::from)
+        .collect::<Array>();
+
+    Ok(items.into())
+}
+
+/// Returns the byte offset of the start of the keyword ending at `col`.
+fn keyword_start(line: &str, col: usize) -> usize {
+    let mut col = col.min(line.len());
+
+    while !line.is_char_boundary(col) {
+        col -= 1;
+    }
+
+    line[..col]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| c.is_alphanumeric() || c == '_')
+        .last()
+        .map_or(col, |(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyword_start_before_cursor() {
+        assert_eq!(4, keyword_start("let foo", 7));
+        assert_eq!(4, keyword_start("let foo_bar", 7));
+        assert_eq!(8, keyword_start("let foo ", 8));
+        assert_eq!(0, keyword_start("", 0));
+        assert_eq!(2, keyword_start("a.bé", 100));
+    }
+
+    #[test]
+    fn item_into_dictionary() {
+        let item = CompletionItem::new("foo").kind("f");
+        let dict = Dictionary::from(item);
+        assert_eq!(2, dict.len());
+        assert_eq!(Some(&Object::from("f")), dict.get("kind"));
+
+        let item = CompletionItem { dup: true, ..CompletionItem::new("") };
+        assert_eq!(Some(&Object::from(1)), Dictionary::from(item).get("dup"));
+    }
+}
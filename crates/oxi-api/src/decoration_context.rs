@@ -0,0 +1,185 @@
+//! Tracks which callback of a decoration provider is currently running, so
+//! that API functions which aren't safe to call while Neovim is redrawing
+//! can fail with an error instead of leaving the editor in a broken state.
+
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::fmt;
+
+use oxi_luajit::{self as lua, ffi::*, macros::cstr};
+use oxi_types::{Function, Object};
+
+use crate::{Error, Result};
+
+thread_local! {
+    static CURRENT: Cell<Option<DecorationCallback>> =
+        const { Cell::new(None) };
+
+    /// The guards entered by the Lua functions wrapped by
+    /// [`guard_function`], innermost last.
+    static LUA_GUARDS: RefCell<Vec<CallbackGuard>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// The callbacks of a
+/// [decoration provider](crate::set_decoration_provider).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DecorationCallback {
+    Start,
+    Buf,
+    Win,
+    Line,
+    End,
+}
+
+impl DecorationCallback {
+    /// Whether the callback runs in the middle of a window being redrawn,
+    /// during which only a few API functions like
+    /// [`Buffer::set_extmark()`](crate::Buffer::set_extmark) can be called.
+    #[inline]
+    pub fn is_redrawing(&self) -> bool {
+        matches!(self, Self::Win | Self::Line)
+    }
+}
+
+impl fmt::Display for DecorationCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Start => "on_start",
+            Self::Buf => "on_buf",
+            Self::Win => "on_win",
+            Self::Line => "on_line",
+            Self::End => "on_end",
+        })
+    }
+}
+
+/// Returns the decoration provider callback currently running, if any.
+pub fn current_decoration_callback() -> Option<DecorationCallback> {
+    CURRENT.with(Cell::get)
+}
+
+/// Marks the given callback as running until the guard is dropped.
+pub(crate) struct CallbackGuard {
+    previous: Option<DecorationCallback>,
+}
+
+impl CallbackGuard {
+    pub(crate) fn enter(callback: DecorationCallback) -> Self {
+        let previous = CURRENT.with(|current| current.replace(Some(callback)));
+        Self { previous }
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
+/// Wraps the Lua function `fun` in another one which marks `callback` as
+/// running while `fun` is called, like the closures passed to the options of
+/// [`set_decoration_provider`](crate::set_decoration_provider) are.
+pub(crate) fn guard_function(
+    fun: Object,
+    callback: DecorationCallback,
+) -> Object {
+    let enter = Function::<(), ()>::from_fn(move |()| {
+        let guard = CallbackGuard::enter(callback);
+        LUA_GUARDS.with(|guards| guards.borrow_mut().push(guard));
+        Ok::<_, Infallible>(())
+    });
+
+    let leave = Function::<(), ()>::from_fn(|()| {
+        let guard = LUA_GUARDS.with(|guards| guards.borrow_mut().pop());
+        drop(guard);
+        Ok::<_, Infallible>(())
+    });
+
+    let wrapped = unsafe {
+        lua::with_state(|lstate| {
+            let res = luaL_loadstring(
+                lstate,
+                cstr!(
+                    "local fun, enter, leave = ...
+                    return function(...)
+                        enter()
+                        local ok, res = pcall(fun, ...)
+                        leave()
+                        if not ok then error(res, 0) end
+                        return res
+                    end"
+                ),
+            );
+            debug_assert_eq!(0, res);
+
+            // Neovim only frees the reference it's given, so the one to the
+            // original function is released once it's been captured by the
+            // wrapper.
+            let luaref = fun.as_luaref_unchecked();
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, luaref);
+            luaL_unref(lstate, LUA_REGISTRYINDEX, luaref);
+
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, enter.lua_ref());
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, leave.lua_ref());
+
+            lua_call(lstate, 3, 1);
+
+            Object::from_luaref(luaL_ref(lstate, LUA_REGISTRYINDEX))
+        })
+    };
+
+    enter.remove_from_lua_registry();
+    leave.remove_from_lua_registry();
+
+    wrapped
+}
+
+/// Fails if `function` is called from a decoration provider callback
+/// running while a window is being redrawn.
+///
+/// This is checked by the functions which change the text of a buffer or
+/// the layout of the windows, which would otherwise corrupt the redraw in
+/// progress.
+pub(crate) fn ensure_not_redrawing(function: &'static str) -> Result<()> {
+    match current_decoration_callback() {
+        Some(callback) if callback.is_redrawing() => {
+            Err(Error::NotAllowedWhileRedrawing { function, callback })
+        },
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_restores_previous_callback() {
+        assert_eq!(None, current_decoration_callback());
+
+        {
+            let _win = CallbackGuard::enter(DecorationCallback::Win);
+            assert_eq!(
+                Err(Error::NotAllowedWhileRedrawing {
+                    function: "nvim_buf_set_lines",
+                    callback: DecorationCallback::Win,
+                }),
+                ensure_not_redrawing("nvim_buf_set_lines")
+            );
+
+            {
+                let _end = CallbackGuard::enter(DecorationCallback::End);
+                assert_eq!(Ok(()), ensure_not_redrawing("nvim_buf_set_lines"));
+            }
+
+            assert_eq!(
+                Some(DecorationCallback::Win),
+                current_decoration_callback()
+            );
+        }
+
+        assert_eq!(None, current_decoration_callback());
+        assert_eq!(Ok(()), ensure_not_redrawing("nvim_buf_set_lines"));
+    }
+}
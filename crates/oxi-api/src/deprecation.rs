@@ -0,0 +1,51 @@
+//! Runtime checks for the Neovim API functions that have been deprecated
+//! upstream, enabled by the `deprecation-warnings` and `strict-deprecations`
+//! features.
+//!
+//! With `deprecation-warnings` the first call to a deprecated function
+//! displays a warning suggesting its replacement, while with
+//! `strict-deprecations` every call fails with an error instead.
+
+#[cfg(feature = "deprecation-warnings")]
+use std::{cell::RefCell, collections::HashSet};
+
+use crate::Result;
+
+#[cfg(feature = "deprecation-warnings")]
+thread_local! {
+    /// The functions that have already been warned about.
+    static WARNED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// Warns about or errors on the call to the deprecated `function`, depending
+/// on the enabled features.
+#[cfg(feature = "deprecation-warnings")]
+#[cfg_attr(feature = "neovim-0-8", allow(dead_code))]
+pub(crate) fn check(
+    function: &'static str,
+    replacement: &'static str,
+) -> Result<()> {
+    let msg = format!(
+        "nvim-oxi: `{function}` is deprecated, use `{replacement}` instead"
+    );
+
+    if cfg!(feature = "strict-deprecations") {
+        return Err(crate::Error::custom(msg));
+    }
+
+    if WARNED.with(|warned| warned.borrow_mut().insert(function)) {
+        crate::echo([(msg.as_str(), Some("WarningMsg"))], true)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "deprecation-warnings"))]
+#[cfg_attr(feature = "neovim-0-8", allow(dead_code))]
+#[inline(always)]
+pub(crate) fn check(
+    _function: &'static str,
+    _replacement: &'static str,
+) -> Result<()> {
+    Ok(())
+}
@@ -6,18 +6,33 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Clone, Debug, ThisError, Eq, PartialEq)]
 pub enum Error {
-    #[error(transparent)]
+    #[error("{0}")]
     FromInt(#[from] std::num::TryFromIntError),
 
-    #[error(transparent)]
+    #[error("{0}")]
     FromUtf8(#[from] std::string::FromUtf8Error),
 
-    #[error(transparent)]
+    #[error("{0}")]
     Nvim(#[from] oxi_types::Error),
 
-    #[error(transparent)]
+    #[error("{0}")]
     ObjectConversion(#[from] oxi_types::conversion::Error),
 
+    #[error("{0}")]
+    Arglist(#[from] crate::types::ArglistError),
+
+    #[error("{0}")]
+    OpenFile(#[from] crate::types::OpenFileError),
+
+    #[error(
+        "{function}() can't be called from the `{callback}` callback of a \
+         decoration provider"
+    )]
+    NotAllowedWhileRedrawing {
+        function: &'static str,
+        callback: crate::DecorationCallback,
+    },
+
     #[error("{0}")]
     Other(String),
 }
@@ -26,4 +41,22 @@ impl Error {
     pub(crate) fn custom<M: Display>(msg: M) -> Self {
         Self::Other(msg.to_string())
     }
+
+    /// Returns the kind of the error returned by Neovim, or `None` if the
+    /// error didn't come from Neovim.
+    pub fn nvim_error_kind(&self) -> Option<oxi_types::ErrorKind> {
+        match self {
+            Self::Nvim(err) => err.kind(),
+            _ => None,
+        }
+    }
+
+    /// Returns the path to the value that couldn't be converted if this is a
+    /// conversion error, or an empty slice otherwise.
+    pub fn conversion_path(&self) -> &[oxi_types::conversion::PathSegment] {
+        match self {
+            Self::ObjectConversion(err) => err.path(),
+            _ => &[],
+        }
+    }
 }
@@ -0,0 +1,185 @@
+//! Helpers to implement expression and function options, like
+//! [`'foldexpr'`][1] or [`'omnifunc'`][2], with Rust closures.
+//!
+//! Every closure is stored in the global Lua table `nvim_oxi_exprs` under a
+//! name derived from the option and the buffer or window it's set for, and
+//! the option is set to call it via [`v:lua`][3]. Setting the same option
+//! again for the same buffer or window replaces the previous closure.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{self, exprs, Window};
+//!
+//! // Fold every paragraph.
+//! exprs::set_foldexpr(&Window::current(), |lnum| {
+//!     let line = api::call_function::<_, String>("getline", (lnum,))?;
+//!     Ok(if line.is_empty() { 0 } else { 1 })
+//! })?;
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/options.html#'foldexpr'
+//! [2]: https://neovim.io/doc/user/options.html#'omnifunc'
+//! [3]: https://neovim.io/doc/user/lua.html#v%3Alua
+
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Pushable};
+use oxi_types::{Dictionary, Function};
+
+use crate::options::{self, Scope};
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// Expands to the name of the global Lua table the closures are stored in,
+/// so that it can also be passed to [`cstr!`].
+macro_rules! table {
+    () => {
+        "nvim_oxi_exprs"
+    };
+}
+
+/// The name of the global Lua table the closures are stored in.
+const TABLE: &str = table!();
+
+/// The arguments passed to the closure set via [`set_formatexpr`].
+///
+/// 1. `lnum`: the first line to be formatted (1-indexed);
+/// 2. `count`: the number of lines to be formatted;
+/// 3. `char`: the character that was typed, if called automatically while
+///    in insert mode, or an empty string otherwise.
+pub type FormatexprArgs = (usize, usize, String);
+
+/// The arguments passed to the closure set via [`set_omnifunc`].
+///
+/// 1. `findstart`: `true` on the first call, when the closure should return
+///    the column where the completion starts, `false` on the second one,
+///    when it should return the completion items;
+/// 2. `base`: the text to complete, always empty on the first call.
+pub type OmnifuncArgs = (bool, String);
+
+/// The arguments passed to the closure set via [`set_tagfunc`].
+///
+/// 1. `pattern`: the tag identifier or pattern being searched for;
+/// 2. `flags`: the flags describing the context of the search, see
+///    [`:h tagfunc`][1] for details;
+/// 3. `info`: more information about the search, e.g. the buffer name.
+///
+/// [1]: https://neovim.io/doc/user/tagsrch.html#tag-function
+pub type TagfuncArgs = (String, String, Dictionary);
+
+/// Sets the [`'foldexpr'`][1] option of `window` to call `fun` with the
+/// (1-indexed) number of the line to compute the fold level of.
+///
+/// `'foldexpr'` is only used if [`'foldmethod'`][2] is set to `"expr"`.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'foldexpr'
+/// [2]: https://neovim.io/doc/user/options.html#'foldmethod'
+pub fn set_foldexpr<F, R>(window: &Window, fun: F) -> Result<()>
+where
+    F: FnMut(usize) -> Result<R> + 'static,
+    R: Pushable + 'static,
+{
+    let name = format!("foldexpr_{}", window.0);
+    register(&name, Function::from_fn_mut(fun));
+    let expr = format!("v:lua.{TABLE}.{name}(v:lnum)");
+    options::set("foldexpr", expr.as_str(), Scope::Window(window))
+}
+
+/// Sets the [`'formatexpr'`][1] option of `buffer` to call `fun`, which
+/// should return `true` to fall back to Neovim's internal formatting.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'formatexpr'
+pub fn set_formatexpr<F>(buffer: &Buffer, mut fun: F) -> Result<()>
+where
+    F: FnMut(FormatexprArgs) -> Result<bool> + 'static,
+{
+    let name = format!("formatexpr_{}", buffer.0);
+    register(
+        &name,
+        Function::from_fn_mut(move |args| fun(args).map(i32::from)),
+    );
+    let expr = format!("v:lua.{TABLE}.{name}(v:lnum, v:count, v:char)");
+    options::set("formatexpr", expr.as_str(), Scope::Buffer(buffer))
+}
+
+/// Sets the [`'omnifunc'`][1] option of `buffer` to call `fun`.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'omnifunc'
+pub fn set_omnifunc<F, R>(buffer: &Buffer, mut fun: F) -> Result<()>
+where
+    F: FnMut(OmnifuncArgs) -> Result<R> + 'static,
+    R: Pushable + 'static,
+{
+    let name = format!("omnifunc_{}", buffer.0);
+    register(
+        &name,
+        Function::from_fn_mut(move |(findstart, base): (i64, String)| {
+            fun((findstart != 0, base))
+        }),
+    );
+    let func = format!("v:lua.{TABLE}.{name}");
+    options::set("omnifunc", func.as_str(), Scope::Buffer(buffer))
+}
+
+/// Sets the [`'completefunc'`][1] option of `buffer` to call `fun`, which is
+/// called with the same arguments as the one set via [`set_omnifunc`].
+///
+/// [1]: https://neovim.io/doc/user/options.html#'completefunc'
+pub fn set_completefunc<F, R>(buffer: &Buffer, mut fun: F) -> Result<()>
+where
+    F: FnMut(OmnifuncArgs) -> Result<R> + 'static,
+    R: Pushable + 'static,
+{
+    let name = format!("completefunc_{}", buffer.0);
+    register(
+        &name,
+        Function::from_fn_mut(move |(findstart, base): (i64, String)| {
+            fun((findstart != 0, base))
+        }),
+    );
+    let func = format!("v:lua.{TABLE}.{name}");
+    options::set("completefunc", func.as_str(), Scope::Buffer(buffer))
+}
+
+/// Sets the [`'tagfunc'`][1] option of `buffer` to call `fun`, which should
+/// return a list of dictionaries describing the matching tags, or nil to
+/// fall back to the tags files.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'tagfunc'
+pub fn set_tagfunc<F, R>(buffer: &Buffer, fun: F) -> Result<()>
+where
+    F: FnMut(TagfuncArgs) -> Result<R> + 'static,
+    R: Pushable + 'static,
+{
+    let name = format!("tagfunc_{}", buffer.0);
+    register(&name, Function::from_fn_mut(fun));
+    let func = format!("v:lua.{TABLE}.{name}");
+    options::set("tagfunc", func.as_str(), Scope::Buffer(buffer))
+}
+
+/// Stores `fun` in the `nvim_oxi_exprs` table under the given name, creating
+/// the table if it doesn't exist yet.
+///
+/// The table becomes the only owner of the function, so the closure it
+/// replaces, if any, can be garbage collected.
+fn register<A, R>(name: &str, fun: Function<A, R>) {
+    unsafe {
+        lua::with_state(|lstate| {
+            lua_getglobal(lstate, cstr!(table!()));
+
+            if lua_type(lstate, -1) != LUA_TTABLE {
+                lua_pop(lstate, 1);
+                lua_createtable(lstate, 0, 0);
+                lua_pushstring(lstate, cstr!(table!()));
+                lua_pushvalue(lstate, -2);
+                lua_rawset(lstate, LUA_GLOBALSINDEX);
+            }
+
+            lua_pushlstring(lstate, name.as_ptr() as *const _, name.len());
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, fun.lua_ref());
+            lua_rawset(lstate, -3);
+            lua_pop(lstate, 1);
+        })
+    };
+
+    fun.remove_from_lua_registry();
+}
@@ -139,14 +139,7 @@ impl Buffer {
                 )));
             }
 
-            let mut iter = tuple.into_iter();
-            let row =
-                usize::from_object(iter.next().expect("row is present"))?;
-            let col =
-                usize::from_object(iter.next().expect("col is present"))?;
-            let infos =
-                iter.next().map(ExtmarkInfos::from_object).transpose()?;
-            Ok((row, col, infos))
+            Ok(tuple.decode()?)
         })
     }
 
@@ -157,7 +150,8 @@ impl Buffer {
     /// tuples in "traversal order". Like for [`Buffer::get_extmark_by_id`],
     /// the `infos` are present only if the
     /// [`details`](crate::opts::GetExtmarksOptsBuilder::details) option field
-    /// was set to `true`.
+    /// was set to `true`, in which case they include the extmark's
+    /// decorations (virtual text, highlight groups, signs, etc).
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_extmarks()
     pub fn get_extmarks(
@@ -180,32 +174,14 @@ impl Buffer {
                 &mut err,
             )
         };
-        choose!(
-            err,
-            Ok({
-                extmarks.into_iter().map(|tuple| {
-                    let mut iter =
-                        Array::from_object(tuple).unwrap().into_iter();
-                    let id =
-                        u32::from_object(iter.next().expect("id is present"))
-                            .unwrap();
-                    let row = usize::from_object(
-                        iter.next().expect("row is present"),
-                    )
-                    .unwrap();
-                    let col = usize::from_object(
-                        iter.next().expect("col is present"),
-                    )
-                    .unwrap();
-                    let infos = iter
-                        .next()
-                        .map(ExtmarkInfos::from_object)
-                        .transpose()
-                        .unwrap();
-                    (id, row, col, infos)
-                })
-            })
-        )
+        choose!(err, {
+            extmarks
+                .into_iter()
+                .map(|tuple| Array::from_object(tuple)?.decode())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map(Vec::into_iter)
+                .map_err(Into::into)
+        })
     }
 
     /// Binding to [`nvim_buf_set_extmark()`][1].
@@ -232,7 +208,7 @@ impl Buffer {
                 &mut err,
             )
         };
-        choose!(err, Ok(id.try_into().expect("always positive")))
+        choose!(err, Ok(id.try_into()?))
     }
 }
 
@@ -243,7 +219,7 @@ impl Buffer {
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_create_namespace()
 pub fn create_namespace(name: &str) -> u32 {
-    let name = nvim::String::from(name);
+    let name = nvim::SmallString::from(name);
     unsafe { nvim_create_namespace(name.non_owning()) }
         .try_into()
         .expect("always positive")
@@ -267,6 +243,12 @@ pub fn get_namespaces() -> impl SuperIterator<(String, u32)> {
 ///
 /// Sets or changes a decoration provider for a namespace.
 ///
+/// The `on_win` and `on_line` callbacks run while Neovim is redrawing a
+/// window, so the functions which would change the text of a buffer or the
+/// layout of the windows, like [`Buffer::set_lines()`] or
+/// [`command()`](crate::command),
+/// fail with [`Error::NotAllowedWhileRedrawing`] when called from them.
+///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_set_decoration_provider()
 pub fn set_decoration_provider(
     ns_id: u32,
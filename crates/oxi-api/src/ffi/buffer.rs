@@ -103,13 +103,8 @@ extern "C" {
         start: Integer,
         end: Integer,
         strict_indexing: bool,
-<<<<<<< HEAD:crates/nvim-api/src/ffi/buffer.rs
-        #[cfg(feature = "neovim-nightly")]
-        lstate: *mut luajit_bindings::ffi::lua_State,
-=======
         #[cfg(not(feature = "neovim-0-8"))]
         lstate: *mut oxi_luajit::ffi::lua_State,
->>>>>>> upstream/main:crates/oxi-api/src/ffi/buffer.rs
         err: *mut Error,
     ) -> Array;
 
@@ -151,13 +146,8 @@ extern "C" {
         end_row: Integer,
         end_col: Integer,
         opts: NonOwning<Dictionary>,
-<<<<<<< HEAD:crates/nvim-api/src/ffi/buffer.rs
-        #[cfg(feature = "neovim-nightly")]
-        lstate: *mut luajit_bindings::ffi::lua_State,
-=======
         #[cfg(not(feature = "neovim-0-8"))]
         lstate: *mut oxi_luajit::ffi::lua_State,
->>>>>>> upstream/main:crates/oxi-api/src/ffi/buffer.rs
         err: *mut Error,
     ) -> Array;
 
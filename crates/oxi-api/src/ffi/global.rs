@@ -14,6 +14,14 @@ use oxi_types::{
 use crate::opts::*;
 
 extern "C" {
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c#L1746
+    pub(crate) fn nvim_call_atomic(
+        channel_id: u64,
+        calls: NonOwning<Array>,
+        #[cfg(not(feature = "neovim-0-8"))] arena: *mut core::ffi::c_void,
+        err: *mut Error,
+    ) -> Array;
+
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c#L1037
     pub(crate) fn nvim_chan_send(
         chan: Integer,
@@ -130,6 +138,15 @@ extern "C" {
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c#L849
     pub(crate) fn nvim_get_current_win() -> WinHandle;
 
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c#L97
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    pub(crate) fn nvim_get_hl(
+        ns_id: Integer,
+        opts: *const GetHighlightOpts,
+        arena: *mut core::ffi::c_void,
+        err: *mut Error,
+    ) -> Dictionary;
+
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/deprecated.c#L188
     pub(crate) fn nvim_get_hl_by_id(
         hl_id: Integer,
@@ -210,6 +227,33 @@ extern "C" {
         err: *mut Error,
     ) -> Object;
 
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c
+    #[cfg(feature = "unstable")]
+    pub(crate) fn nvim__id(
+        obj: NonOwning<Object>,
+        #[cfg(feature = "neovim-nightly")] arena: *mut core::ffi::c_void,
+    ) -> Object;
+
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c
+    #[cfg(feature = "unstable")]
+    pub(crate) fn nvim__id_array(
+        arr: NonOwning<Array>,
+        #[cfg(feature = "neovim-nightly")] arena: *mut core::ffi::c_void,
+    ) -> Array;
+
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c
+    #[cfg(feature = "unstable")]
+    pub(crate) fn nvim__id_dictionary(
+        dct: NonOwning<Dictionary>,
+        #[cfg(feature = "neovim-nightly")] arena: *mut core::ffi::c_void,
+    ) -> Dictionary;
+
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c
+    #[cfg(feature = "unstable")]
+    pub(crate) fn nvim__id_float(
+        flt: oxi_types::Float,
+    ) -> oxi_types::Float;
+
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vim.c#L305
     pub(crate) fn nvim_input(keys: NonOwning<String>) -> Integer;
 
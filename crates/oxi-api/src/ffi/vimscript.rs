@@ -51,6 +51,15 @@ extern "C" {
         error: *mut Error,
     ) -> String;
 
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/vimscript.c#L51
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    pub(crate) fn nvim_exec2(
+        channel_id: u64,
+        src: NonOwning<String>,
+        opts: *const ExecOpts,
+        error: *mut Error,
+    ) -> Dictionary;
+
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/command.c#L98
     pub(crate) fn nvim_parse_cmd(
         src: NonOwning<String>,
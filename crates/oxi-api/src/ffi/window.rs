@@ -1,3 +1,5 @@
+#[cfg(feature = "neovim-nightly")]
+use oxi_types::Dictionary;
 use oxi_types::{
     Array,
     Boolean,
@@ -12,6 +14,9 @@ use oxi_types::{
     WinHandle,
 };
 
+#[cfg(feature = "neovim-nightly")]
+use crate::opts::WinTextHeightOpts;
+
 extern "C" {
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/window.c#L428
     pub(crate) fn nvim_win_call(
@@ -118,6 +123,13 @@ extern "C" {
         err: *mut Error,
     );
 
+    // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/window.c#L442
+    pub(crate) fn nvim_win_set_hl_ns(
+        win: WinHandle,
+        ns_id: Integer,
+        err: *mut Error,
+    );
+
     // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/options.c#L431
     pub(crate) fn nvim_win_set_option(
         channel_id: u64,
@@ -141,4 +153,12 @@ extern "C" {
         width: Integer,
         err: *mut Error,
     );
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c
+    #[cfg(feature = "neovim-nightly")]
+    pub(crate) fn nvim_win_text_height(
+        win: WinHandle,
+        opts: *const WinTextHeightOpts,
+        err: *mut Error,
+    ) -> Dictionary;
 }
@@ -0,0 +1,206 @@
+use crate::options::{self, Scope};
+use crate::types::WindowConfig;
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// Number of `zindex` values reserved to each [`FloatLayer`].
+const LAYER_SIZE: u32 = 10;
+
+/// A named layer in a [`FloatStack`]. Floats in a layer are always drawn on
+/// top of the floats in the layers before it.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FloatLayer {
+    /// Backdrops and other decorations drawn behind the content.
+    Background,
+
+    /// The main content of the UI.
+    Content,
+
+    /// Borders, scrollbars and other accents drawn on top of the content.
+    BorderAccent,
+
+    /// Tooltips, hovers and other short-lived popups.
+    Tooltip,
+}
+
+impl FloatLayer {
+    const ALL: [Self; 4] =
+        [Self::Background, Self::Content, Self::BorderAccent, Self::Tooltip];
+
+    #[inline]
+    const fn idx(self) -> usize {
+        self as usize
+    }
+
+    /// The lowest `zindex` of the layer. Neovim's default `zindex` for
+    /// floats is 50, so [`Content`](Self::Content) starts there.
+    #[inline]
+    pub const fn base_zindex(self) -> u32 {
+        match self {
+            Self::Background => 40,
+            Self::Content => 50,
+            Self::BorderAccent => 60,
+            Self::Tooltip => 90,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Layer {
+    /// The floats in the layer, from bottom to top.
+    windows: Vec<Window>,
+
+    /// The `winblend` set on the floats opened in the layer.
+    blend: Option<u8>,
+}
+
+/// A manager for stacked floating windows.
+///
+/// Every float opened via [`open`](FloatStack::open) is assigned a `zindex`
+/// within the range reserved to its [`FloatLayer`], with floats opened later
+/// going on top of the ones opened before them. This keeps the stacking
+/// order consistent across UIs made of many floats.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, types::*, FloatLayer, FloatStack};
+///
+/// let mut stack = FloatStack::new();
+/// stack.blend(FloatLayer::Background, 30)?;
+///
+/// let config = WindowConfig::builder()
+///     .relative(WindowRelativeTo::Editor)
+///     .height(10)
+///     .width(40)
+///     .row(5)
+///     .col(5)
+///     .build();
+///
+/// let buf = api::create_buf(false, true)?;
+/// let backdrop = stack.open(&buf, false, FloatLayer::Background, &config)?;
+/// let content = stack.open(&buf, true, FloatLayer::Content, &config)?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FloatStack {
+    layers: [Layer; 4],
+}
+
+impl FloatStack {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `winblend` of the floats in the given layer, including the
+    /// ones that are already open.
+    pub fn blend(&mut self, layer: FloatLayer, blend: u8) -> Result<()> {
+        let blend = blend.min(100);
+        let layer = &mut self.layers[layer.idx()];
+        layer.blend = Some(blend);
+        for win in layer.windows.iter().filter(|win| win.is_valid()) {
+            options::set("winblend", blend, Scope::Window(win))?;
+        }
+        Ok(())
+    }
+
+    /// Closes all the floats in the stack.
+    pub fn close_all(&mut self, force: bool) -> Result<()> {
+        for layer in self.layers.iter_mut() {
+            for win in layer.windows.drain(..).filter(Window::is_valid) {
+                win.close(force)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the layer the given float was opened in, if it's managed by
+    /// this stack.
+    pub fn layer_of(&self, win: &Window) -> Option<FloatLayer> {
+        FloatLayer::ALL
+            .into_iter()
+            .find(|layer| self.layers[layer.idx()].windows.contains(win))
+    }
+
+    /// Opens a new float in the given layer via [`open_win`](crate::open_win),
+    /// putting it on top of the other floats in the same layer. The `zindex`
+    /// of `config` is overridden.
+    pub fn open(
+        &mut self,
+        buf: &Buffer,
+        enter: bool,
+        layer: FloatLayer,
+        config: &WindowConfig,
+    ) -> Result<Window> {
+        self.prune();
+
+        let mut config = config.clone();
+        config.zindex = Some(self.next_zindex(layer));
+
+        let win = crate::open_win(buf, enter, &config)?;
+
+        let layer = &mut self.layers[layer.idx()];
+        if let Some(blend) = layer.blend {
+            options::set("winblend", blend, Scope::Window(&win))?;
+        }
+        layer.windows.push(win.clone());
+
+        Ok(win)
+    }
+
+    /// Moves the given float on top of the other floats in its layer. Does
+    /// nothing if the float isn't managed by this stack.
+    pub fn raise(&mut self, win: &Window) -> Result<()> {
+        self.prune();
+
+        let layer = match self.layer_of(win) {
+            Some(layer) => layer,
+            None => return Ok(()),
+        };
+
+        let windows = &mut self.layers[layer.idx()].windows;
+        let pos = windows.iter().position(|w| w == win).expect("is managed");
+        let win = windows.remove(pos);
+        windows.push(win);
+
+        self.restack(layer)
+    }
+
+    /// Returns an iterator over the open floats in the given layer, from
+    /// bottom to top.
+    pub fn windows(
+        &self,
+        layer: FloatLayer,
+    ) -> impl Iterator<Item = &Window> + '_ {
+        self.layers[layer.idx()].windows.iter().filter(|win| win.is_valid())
+    }
+
+    /// Returns the `zindex` the next float opened in `layer` will get. Floats
+    /// past the tenth one in a layer all share the same `zindex`.
+    fn next_zindex(&self, layer: FloatLayer) -> u32 {
+        let len = self.layers[layer.idx()].windows.len() as u32;
+        layer.base_zindex() + len.min(LAYER_SIZE - 1)
+    }
+
+    /// Forgets about the floats that have been closed.
+    fn prune(&mut self) {
+        for layer in self.layers.iter_mut() {
+            layer.windows.retain(Window::is_valid);
+        }
+    }
+
+    /// Reassigns the `zindex`es of the floats in `layer` based on their
+    /// position in the stack.
+    fn restack(&mut self, layer: FloatLayer) -> Result<()> {
+        let windows = &mut self.layers[layer.idx()].windows;
+        for (idx, win) in windows.iter_mut().enumerate() {
+            let zindex =
+                layer.base_zindex() + (idx as u32).min(LAYER_SIZE - 1);
+            // Keys missing from the config are left unchanged when
+            // reconfiguring a float.
+            win.set_config(&WindowConfig::builder().zindex(zindex).build())?;
+        }
+        Ok(())
+    }
+}
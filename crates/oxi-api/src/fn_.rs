@@ -0,0 +1,217 @@
+//! Typed bindings to some of the builtin [Vimscript functions][1], exposed in
+//! Lua through the `vim.fn` table.
+//!
+//! All the functions in this module are thin wrappers around
+//! [`call_function`](crate::call_function) which take care of converting the
+//! arguments and the return value to and from the right Rust types. Functions
+//! that aren't covered here can still be called via
+//! [`call_function`](crate::call_function).
+//!
+//! Vimscript "booleans" (i.e. the numbers `0` and `1`) are returned as
+//! [`bool`]s.
+//!
+//! Most bindings are generated by the `vimscript_functions!` macro from the
+//! signatures declared at the bottom of this file. Neovim's `eval.lua` can't
+//! be used as the source of those, since it only records the number of
+//! arguments of each function before 0.10. The functions whose arguments or
+//! return values need more than a plain conversion are written by hand.
+//!
+//! [1]: https://neovim.io/doc/user/builtin.html
+
+use std::path::{Path, PathBuf};
+
+use oxi_types::conversion::FromObject;
+use oxi_types::{self as nvim, Array, Dictionary, Integer};
+
+use crate::call_function;
+use crate::types::SwapInfo;
+use crate::Buffer;
+use crate::{Error, Result};
+use crate::{StringOrInt, StringOrListOfStrings};
+
+/// Binding to [`bufnr()`][1].
+///
+/// Returns the [`Buffer`] matching the given buffer name pattern, or `None` if
+/// no buffer matches it.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#bufnr()
+pub fn bufnr(buf: &str) -> Result<Option<Buffer>> {
+    let nr = call_function::<_, Integer>("bufnr", (buf,))?;
+    Ok((nr != -1).then(|| (nr as i32).into()))
+}
+
+/// Binding to [`fnameescape()`][1].
+///
+/// Escapes a file name so that it can be used as a command argument.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#fnameescape()
+pub fn fnameescape<P: AsRef<Path>>(fname: P) -> Result<String> {
+    let fname = nvim::String::from(fname.as_ref());
+    call_function("fnameescape", (fname,))
+}
+
+/// Binding to [`fnamemodify()`][1].
+///
+/// Modifies the file name `fname` according to the [filename
+/// modifiers](https://neovim.io/doc/user/cmdline.html#filename-modifiers)
+/// in `mods`, e.g. `":p:h"`.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#fnamemodify()
+pub fn fnamemodify<P: AsRef<Path>>(fname: P, mods: &str) -> Result<PathBuf> {
+    let fname = nvim::String::from(fname.as_ref());
+    call_function::<_, nvim::String>("fnamemodify", (fname, mods))
+        .map(Into::into)
+}
+
+/// Binding to [`getline()`][1].
+///
+/// Returns a line of the current buffer. `lnum` can either be a 1-based line
+/// number or a string like `"."` or `"$"`.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#getline()
+pub fn getline<Lnum: StringOrInt>(lnum: Lnum) -> Result<String> {
+    call_function("getline", Array::from_iter([lnum.to_object()]))
+}
+
+/// Binding to [`getline()`][1] called with an `{end}` argument.
+///
+/// Returns the lines of the current buffer between `start` and `end`, both
+/// inclusive.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#getline()
+pub fn getlines<Start, End>(start: Start, end: End) -> Result<Vec<String>>
+where
+    Start: StringOrInt,
+    End: StringOrInt,
+{
+    let args = Array::from_iter([start.to_object(), end.to_object()]);
+    call_function("getline", args)
+}
+
+/// Binding to [`swapinfo()`][1].
+///
+/// Returns the informations stored in the swap file at `fname`, failing if
+/// it can't be read or isn't a swap file.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#swapinfo()
+pub fn swapinfo<P: AsRef<Path>>(fname: P) -> Result<SwapInfo> {
+    let fname = fname.as_ref();
+    let infos = call_function::<_, Dictionary>(
+        "swapinfo",
+        (nvim::String::from(fname),),
+    )?;
+    if let Some(err) = infos.get("error") {
+        let err = String::from_object(err.clone())?;
+        return Err(Error::custom(format!("{}: {err}", fname.display())));
+    }
+    Ok(SwapInfo::from_object(infos.into())?)
+}
+
+/// Binding to [`swapname()`][1].
+///
+/// Returns the path of the swap file of `buf`, or `None` if it doesn't have
+/// one.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#swapname()
+pub fn swapname(buf: &Buffer) -> Result<Option<PathBuf>> {
+    let name = call_function::<_, nvim::String>("swapname", (buf,))?;
+    Ok((!name.is_empty()).then(|| name.into()))
+}
+
+/// Binding to [`system()`][1].
+///
+/// Runs `cmd` in a shell and returns its output. If `cmd` is a list of
+/// strings it's executed directly, without going through the shell.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#system()
+pub fn system<Cmd: StringOrListOfStrings>(cmd: Cmd) -> Result<String> {
+    call_function("system", Array::from_iter([cmd.to_object()]))
+}
+
+/// Binding to [`systemlist()`][1].
+///
+/// Same as [`system`] but returns the output as a list of lines.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#systemlist()
+pub fn systemlist<Cmd>(cmd: Cmd) -> Result<Vec<String>>
+where
+    Cmd: StringOrListOfStrings,
+{
+    call_function("systemlist", Array::from_iter([cmd.to_object()]))
+}
+
+/// Generates a binding to each of the given Vimscript functions, converting
+/// the arguments to [`Object`](nvim::Object)s and the return value from one.
+///
+/// The return type can be followed by `= conversion`, a closure mapping the
+/// value returned by Neovim to it.
+macro_rules! vimscript_functions {
+    ($(
+        $(#[doc = $doc:literal])*
+        fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty $(= $conv:expr)?;
+    )*) => {
+        $(
+            #[doc = concat!("Binding to [`", stringify!($name), "()`][1].")]
+            #[doc = ""]
+            $(#[doc = $doc])*
+            #[doc = ""]
+            #[doc = concat!(
+                "[1]: https://neovim.io/doc/user/builtin.html#",
+                stringify!($name),
+                "()"
+            )]
+            pub fn $name($($arg: $ty),*) -> Result<$ret> {
+                #[allow(unused_mut)]
+                let mut args = Array::new();
+                $(args.push($arg);)*
+                vimscript_functions!(@call $name, args $(, $conv)?)
+            }
+        )*
+    };
+
+    (@call $name:ident, $args:expr) => {
+        call_function(stringify!($name), $args)
+    };
+
+    (@call $name:ident, $args:expr, $conv:expr) => {
+        call_function(stringify!($name), $args).map($conv)
+    };
+}
+
+vimscript_functions! {
+    /// Returns the byte index of the column position given by `expr`, e.g.
+    /// `"."` for the cursor position or `"$"` for the end of the cursor line.
+    fn col(expr: &str) -> usize;
+
+    /// Returns whether an executable with the given name exists.
+    fn executable(name: &str) -> bool = |n: Integer| n == 1;
+
+    /// Returns whether the variable, option, command or function named by
+    /// `expr` is defined.
+    fn exists(expr: &str) -> bool = |n: Integer| n != 0;
+
+    /// Expands wildcards and special keywords like `%`, `<cfile>` or
+    /// `<sfile>`.
+    fn expand(expr: &str) -> String;
+
+    /// Returns the current working directory.
+    fn getcwd() -> PathBuf = |cwd: nvim::String| cwd.into();
+
+    /// Returns whether the given feature is supported, e.g. `"nvim-0.9"` or
+    /// `"win32"`.
+    fn has(feature: &str) -> bool = |n: Integer| n == 1;
+
+    /// Returns the 1-based line number of the position given by `expr`, e.g.
+    /// `"."` for the cursor position or `"$"` for the last line of the
+    /// buffer.
+    fn line(expr: &str) -> usize;
+
+    /// Escapes a string so that it can be used as a shell command argument.
+    fn shellescape(str: &str) -> String;
+
+    /// Returns the [standard
+    /// path](https://neovim.io/doc/user/starting.html#base-directories)
+    /// locations for various kinds of user-specific files, e.g. `"config"`
+    /// or `"data"`.
+    fn stdpath(what: &str) -> PathBuf = |path: nvim::String| path.into();
+}
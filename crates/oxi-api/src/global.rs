@@ -10,13 +10,14 @@ use oxi_types::{
 };
 
 use crate::choose;
+use crate::decoration_context::ensure_not_redrawing;
 use crate::ffi::global::*;
 use crate::opts::*;
 use crate::types::*;
 use crate::StringOrFunction;
 use crate::SuperIterator;
 use crate::LUA_INTERNAL_CALL;
-use crate::{Buffer, TabPage, Window};
+use crate::{Buffer, Keys, TabPage, Window};
 use crate::{Error, Result};
 
 /// Binding to [`nvim_chan_send()`][1].
@@ -77,6 +78,7 @@ where
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_del_current_line()
 pub fn del_current_line() -> Result<()> {
+    ensure_not_redrawing("nvim_del_current_line")?;
     let mut err = nvim::Error::new();
     unsafe { nvim_del_current_line(&mut err) };
     choose!(err, ())
@@ -89,7 +91,8 @@ pub fn del_current_line() -> Result<()> {
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_del_keymap()
 pub fn del_keymap(mode: Mode, lhs: &str) -> Result<()> {
-    let mode = nvim::String::from(mode);
+    mode.ensure_not_all("nvim_del_keymap")?;
+    let mode = mode.as_nvim_str();
     let lhs = nvim::String::from(lhs);
     let mut err = nvim::Error::new();
     unsafe {
@@ -218,10 +221,21 @@ pub fn eval_statusline(
 /// [1]: https://neovim.io/doc/user/api.html#nvim_feedkeys()
 pub fn feedkeys(keys: &str, mode: Mode, escape_ks: bool) {
     let keys = nvim::String::from(keys);
-    let mode = nvim::String::from(mode);
+    let mode = mode.as_nvim_str();
     unsafe { nvim_feedkeys(keys.non_owning(), mode.non_owning(), escape_ks) }
 }
 
+/// Like [`feedkeys`], but takes the keys as a [`Keys`] sequence, translating
+/// its special keys via [`replace_termcodes`] before feeding them.
+///
+/// Unlike [`feedkeys`] there's no `escape_ks` argument, since the bytes
+/// produced by [`replace_termcodes`] must never be escaped.
+pub fn feedkeys_typed(keys: Keys, mode: Mode) {
+    let keys = keys.to_termcodes();
+    let mode = mode.as_nvim_str();
+    unsafe { nvim_feedkeys(keys.non_owning(), mode.non_owning(), false) }
+}
+
 /// Binding to [`nvim_get_all_options_info()`][1].
 ///
 /// Gets the option information for all options.
@@ -260,9 +274,12 @@ pub fn get_chan_info(channel_id: u32) -> Result<ChannelInfos> {
 pub fn get_color_by_name(name: &str) -> Result<u32> {
     let name = nvim::String::from(name);
     let color = unsafe { nvim_get_color_by_name(name.non_owning()) };
-    (color != -1).then(|| color.try_into().unwrap()).ok_or_else(|| {
-        Error::custom(format!("{name:?} is not a valid color name"))
-    })
+    if color == -1 {
+        return Err(Error::custom(format!(
+            "{name:?} is not a valid color name"
+        )));
+    }
+    Ok(color.try_into()?)
 }
 
 /// Binding to [`nvim_get_color_map()`][1].
@@ -346,14 +363,49 @@ pub fn get_current_win() -> Window {
     unsafe { nvim_get_current_win() }.into()
 }
 
+/// Binding to [`nvim_get_hl()`][1].
+///
+/// Gets the highlight definitions of the namespace `ns_id`, or of the global
+/// namespace if `ns_id` is 0. If the `id` or `name` option is set only the
+/// definition of that group is returned, otherwise all of them are.
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_hl()
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+pub fn get_hl(ns_id: u32, opts: &GetHighlightOpts) -> Result<GetHlInfos> {
+    let mut err = nvim::Error::new();
+    let hl = unsafe {
+        nvim_get_hl(ns_id.into(), opts, core::ptr::null_mut(), &mut err)
+    };
+    choose!(
+        err,
+        Ok(if opts.is_single() {
+            GetHlInfos::Single(HighlightInfos::from_object(hl.into())?)
+        } else {
+            GetHlInfos::Map(
+                hl.into_iter()
+                    .map(|(name, infos)| {
+                        let name = name.to_string_lossy().into_owned();
+                        Ok((name, HighlightInfos::from_object(infos)?))
+                    })
+                    .collect::<Result<_>>()?,
+            )
+        })
+    )
+}
+
 /// Binding to [`nvim_get_hl_by_id()`][1].
 ///
 /// Gets a highlight definition by id.
 ///
-/// [1]: https://neovim.io/doc/user/api.html#nvim_get_hl_by_id[1]
-///
-/// [1]: https://neovim.io/doc/user/api.html#nvim_get_current_win()
+/// [1]: https://neovim.io/doc/user/api.html#nvim_get_hl_by_id()
+#[cfg_attr(
+    any(feature = "neovim-0-9", feature = "neovim-nightly"),
+    deprecated(since = "0.4.0", note = "use `get_hl` instead")
+)]
 pub fn get_hl_by_id(hl_id: u32, rgb: bool) -> Result<HighlightInfos> {
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    crate::deprecation::check("nvim_get_hl_by_id", "nvim_get_hl")?;
+
     let mut err = nvim::Error::new();
 
     let hl = unsafe {
@@ -368,7 +420,14 @@ pub fn get_hl_by_id(hl_id: u32, rgb: bool) -> Result<HighlightInfos> {
 /// Gets a highlight definition by name.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_get_hl_by_name()
+#[cfg_attr(
+    any(feature = "neovim-0-9", feature = "neovim-nightly"),
+    deprecated(since = "0.4.0", note = "use `get_hl` instead")
+)]
 pub fn get_hl_by_name(name: &str, rgb: bool) -> Result<HighlightInfos> {
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    crate::deprecation::check("nvim_get_hl_by_name", "nvim_get_hl")?;
+
     let name = nvim::String::from(name);
     let mut err = nvim::Error::new();
     let hl = unsafe {
@@ -395,13 +454,25 @@ pub fn get_hl_id_by_name(name: &str) -> Result<u32> {
 
 /// Binding to [`nvim_get_keymap()`][1].
 ///
-/// Returns an iterator over the global mapping definitions.
+/// Returns an iterator over the global mapping definitions. Use [`Mode::All`]
+/// to get the mappings for every mode at once.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_get_keymap()
 pub fn get_keymap(mode: Mode) -> impl SuperIterator<KeymapInfos> {
-    let mode = nvim::String::from(mode);
-    let keymaps = unsafe { nvim_get_keymap(mode.non_owning()) };
-    keymaps.into_iter().map(|obj| KeymapInfos::from_object(obj).unwrap())
+    let modes = match mode {
+        Mode::All => &Mode::DISJOINT[..],
+        _ => std::slice::from_ref(&mode),
+    };
+
+    modes
+        .iter()
+        .flat_map(|&mode| {
+            let mode = mode.as_nvim_str();
+            unsafe { nvim_get_keymap(mode.non_owning()) }
+        })
+        .map(|obj| KeymapInfos::from_object(obj).unwrap())
+        .collect::<Vec<_>>()
+        .into_iter()
 }
 
 /// Binding to [`nvim_get_mark()`][1].
@@ -420,16 +491,7 @@ pub fn get_mark(
     let mark = unsafe {
         nvim_get_mark(name.non_owning(), opts.non_owning(), &mut err)
     };
-    choose!(err, {
-        let mut iter = mark.into_iter();
-        let row = usize::from_object(iter.next().expect("row is present"))?;
-        let col = usize::from_object(iter.next().expect("col is present"))?;
-        let buffer =
-            Buffer::from_object(iter.next().expect("buffer is present"))?;
-        let buffername =
-            String::from_object(iter.next().expect("buffername is present"))?;
-        Ok((row, col, buffer, buffername))
-    })
+    choose!(err, Ok(mark.decode()?))
 }
 
 /// Binding to [`nvim_get_mode()`][1].
@@ -447,10 +509,14 @@ pub fn get_mode() -> Result<GotMode> {
 /// Gets the value of a global option.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_get_option()
+#[deprecated(note = "use `options::get` with `Scope::Global` instead")]
 pub fn get_option<Opt>(name: &str) -> Result<Opt>
 where
     Opt: FromObject,
 {
+    #[cfg(feature = "neovim-nightly")]
+    crate::deprecation::check("nvim_get_option", "nvim_get_option_value")?;
+
     let name = nvim::String::from(name);
     let mut err = nvim::Error::new();
     let obj = unsafe {
@@ -481,8 +547,8 @@ pub fn get_option_info(name: &str) -> Result<OptionInfos> {
 /// Gets the local value of an option if it exists, or the global value
 /// otherwise. Local values always correspond to the current buffer or window.
 ///
-/// To get a buffer-local orr window-local option for a specific buffer of
-/// window consider using [`Buffer::get_option`] or [`Window::get_option`] instead.
+/// To get an option for a specific buffer or window consider using
+/// [`options::get`](crate::options::get) instead.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_get_option_value()
 pub fn get_option_value<Opt>(name: &str, opts: &OptionValueOpts) -> Result<Opt>
@@ -574,6 +640,62 @@ where
     choose!(err, Ok(Var::from_object(obj)?))
 }
 
+/// Binding to `nvim__id()`.
+///
+/// Returns a copy of `obj` made by Neovim. This is an internal function used
+/// to test the conversion of objects across the API boundary, and shouldn't
+/// be relied upon by plugins.
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub fn id(obj: &Object) -> Object {
+    unsafe {
+        nvim__id(
+            obj.non_owning(),
+            #[cfg(feature = "neovim-nightly")]
+            core::ptr::null_mut(),
+        )
+    }
+}
+
+/// Binding to `nvim__id_array()`.
+///
+/// Returns a copy of `arr` made by Neovim, see [`id`] for details.
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub fn id_array(arr: &Array) -> Array {
+    unsafe {
+        nvim__id_array(
+            arr.non_owning(),
+            #[cfg(feature = "neovim-nightly")]
+            core::ptr::null_mut(),
+        )
+    }
+}
+
+/// Binding to `nvim__id_dictionary()`.
+///
+/// Returns a copy of `dict` made by Neovim, see [`id`] for details.
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub fn id_dictionary(dict: &Dictionary) -> Dictionary {
+    unsafe {
+        nvim__id_dictionary(
+            dict.non_owning(),
+            #[cfg(feature = "neovim-nightly")]
+            core::ptr::null_mut(),
+        )
+    }
+}
+
+/// Binding to `nvim__id_float()`.
+///
+/// Returns `float` after passing it through Neovim, see [`id`] for details.
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub fn id_float(float: f64) -> f64 {
+    unsafe { nvim__id_float(float) }
+}
+
 /// Binding to [`nvim_input()`][1].
 ///
 /// Queues raw user-input. Unlike [`api::feedkeys`](feedkeys) this uses a
@@ -711,16 +833,22 @@ pub fn load_context(ctx: EditorContext) {
 
 /// Binding to [`nvim_notify()`][1].
 ///
+/// Displays a notification via [`vim.notify()`][2], returning whatever it
+/// returns. That's always nil for the builtin implementation, but plugins
+/// replacing it usually return a handle to the notification which can be
+/// passed to [`NotifyOptsBuilder::replace`] to update it.
+///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_notify()
+/// [2]: https://neovim.io/doc/user/lua.html#vim.notify()
 pub fn notify(
     msg: &str,
     log_level: LogLevel,
     opts: &NotifyOpts,
-) -> Result<()> {
+) -> Result<Object> {
     let msg = nvim::String::from(msg);
     let opts = Dictionary::from(opts);
     let mut err = nvim::Error::new();
-    let _ = unsafe {
+    let notification = unsafe {
         nvim_notify(
             msg.non_owning(),
             log_level as Integer,
@@ -728,7 +856,7 @@ pub fn notify(
             &mut err,
         )
     };
-    choose!(err, ())
+    choose!(err, Ok(notification))
 }
 
 /// Binding to [`nvim_open_term()`][1].
@@ -739,6 +867,7 @@ pub fn notify(
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_open_term()
 pub fn open_term(buffer: &Buffer, opts: &OpenTermOpts) -> Result<u32> {
+    ensure_not_redrawing("nvim_open_term")?;
     let opts = Dictionary::from(opts);
     let mut err = nvim::Error::new();
     let channel_id =
@@ -747,7 +876,7 @@ pub fn open_term(buffer: &Buffer, opts: &OpenTermOpts) -> Result<u32> {
         err,
         match channel_id {
             0 => Err(Error::custom("Couldn't create terminal instance")),
-            other => Ok(other.try_into().expect("always positive")),
+            other => Ok(other.try_into()?),
         }
     )
 }
@@ -775,6 +904,7 @@ pub fn paste<Data>(data: Data, crlf: bool, phase: PastePhase) -> Result<bool>
 where
     Data: Into<nvim::String>,
 {
+    ensure_not_redrawing("nvim_paste")?;
     let mut err = nvim::Error::new();
     let go_on = unsafe {
         nvim_paste(data.into().non_owning(), crlf, phase as Integer, &mut err)
@@ -797,6 +927,7 @@ where
     Lines: Iterator<Item = Line>,
     Line: Into<nvim::String>,
 {
+    ensure_not_redrawing("nvim_put")?;
     let lines = lines.into_iter().map(Into::into).collect::<Array>();
     let reg_type = nvim::String::from(reg_type);
     let mut err = nvim::Error::new();
@@ -812,6 +943,76 @@ where
     choose!(err, ())
 }
 
+/// Sets the contents of `register` like a yank would, optionally triggering
+/// the [`TextYankPost`][1] autocommand.
+///
+/// Since [`v:event`][2] can only be set by Neovim itself, the autocommand
+/// receives the information about the yank in its `data` instead, i.e. in
+/// [`AutocmdCallbackArgs::data`](crate::types::AutocmdCallbackArgs::data).
+/// It's a dictionary with the same `operator`, `regcontents`, `regname`,
+/// `regtype` and `visual` keys that `v:event` has for real yanks.
+///
+/// [1]: https://neovim.io/doc/user/autocmd.html#TextYankPost
+/// [2]: https://neovim.io/doc/user/eval.html#v%3Aevent
+pub fn yank_into<Line, Lines>(
+    register: char,
+    lines: Lines,
+    reg_type: RegisterType,
+    fire_event: bool,
+) -> Result<()>
+where
+    Lines: IntoIterator<Item = Line>,
+    Line: Into<nvim::String>,
+{
+    let lines = lines.into_iter().map(Into::into).collect::<Array>();
+    let reg_type = nvim::String::from(reg_type);
+
+    crate::call_function::<_, Integer>(
+        "setreg",
+        (register, lines.clone(), reg_type),
+    )?;
+
+    if !fire_event {
+        return Ok(());
+    }
+
+    let reg_type =
+        crate::call_function::<_, String>("getregtype", (register,))?;
+
+    let data = Dictionary::from_iter([
+        ("operator", Object::from("y")),
+        ("regcontents", lines.into()),
+        ("regname", register.into()),
+        ("regtype", reg_type.into()),
+        ("visual", false.into()),
+    ]);
+
+    let opts = ExecAutocmdsOpts::builder().data(data).build();
+    crate::exec_autocmds([AutocmdEvent::TextYankPost], &opts)
+}
+
+/// Binding to [`nvim__redraw()`][1].
+///
+/// Forces a redraw of the screen, or of the parts of it selected by `opts`,
+/// even while Neovim is executing a script or a keymap.
+///
+/// `nvim__redraw()` is an experimental function added after the nightly
+/// targeted by the `neovim-nightly` feature, so it's looked up at runtime
+/// via `vim.api` instead of being linked against. This returns an error if
+/// the running Neovim doesn't provide it.
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim__redraw()
+#[cfg(feature = "neovim-nightly")]
+#[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+pub fn redraw(opts: &RedrawOpts) -> Result<()> {
+    let opts = Dictionary::from(opts);
+    crate::call_function::<_, Object>(
+        "luaeval",
+        ("vim.api.nvim__redraw(_A)", opts),
+    )?;
+    Ok(())
+}
+
 /// Binding to [`nvim_replace_termcodes()`][1].
 ///
 /// Replaces terminal codes and keycodes (`<CR>`, `<Esc>`, ...) in a string
@@ -864,6 +1065,7 @@ pub fn select_popupmenu_item(
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_set_current_buf()
 pub fn set_current_buf(buf: &Buffer) -> Result<()> {
+    ensure_not_redrawing("nvim_set_current_buf")?;
     let mut err = nvim::Error::new();
     unsafe { nvim_set_current_buf(buf.0, &mut err) };
     choose!(err, ())
@@ -893,6 +1095,7 @@ pub fn set_current_line<Line>(line: Line) -> Result<()>
 where
     Line: Into<nvim::String>,
 {
+    ensure_not_redrawing("nvim_set_current_line")?;
     let mut err = nvim::Error::new();
     unsafe { nvim_set_current_line(line.into().non_owning(), &mut err) };
     choose!(err, ())
@@ -904,6 +1107,7 @@ where
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_set_current_tabpage()
 pub fn set_current_tabpage(tabpage: &TabPage) -> Result<()> {
+    ensure_not_redrawing("nvim_set_current_tabpage")?;
     let mut err = nvim::Error::new();
     unsafe { nvim_set_current_tabpage(tabpage.0, &mut err) };
     choose!(err, ())
@@ -915,6 +1119,7 @@ pub fn set_current_tabpage(tabpage: &TabPage) -> Result<()> {
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_set_current_win()
 pub fn set_current_win(win: &Window) -> Result<()> {
+    ensure_not_redrawing("nvim_set_current_win")?;
     let mut err = nvim::Error::new();
     unsafe { nvim_set_current_win(win.0, &mut err) };
     choose!(err, ())
@@ -946,7 +1151,8 @@ pub fn set_keymap(
     rhs: &str,
     opts: &SetKeymapOpts,
 ) -> Result<()> {
-    let mode = nvim::String::from(mode);
+    mode.ensure_not_all("nvim_set_keymap")?;
+    let mode = mode.as_nvim_str();
     let lhs = nvim::String::from(lhs);
     let rhs = nvim::String::from(rhs);
     let mut err = nvim::Error::new();
@@ -963,15 +1169,40 @@ pub fn set_keymap(
     choose!(err, ())
 }
 
+/// Sets the uppercase or numbered mark `name` at the given position of
+/// `buffer`, the setter counterpart of [`get_mark`]. Marks are (1,0)-indexed.
+///
+/// Returns an error if a lowercase or special mark is used, since those are
+/// local to a buffer. Use [`Buffer::set_mark`] to set a buffer-local mark.
+pub fn set_mark(
+    name: char,
+    buffer: &Buffer,
+    line: usize,
+    col: usize,
+    opts: &SetMarkOpts,
+) -> Result<()> {
+    if MarkKind::of(name) != MarkKind::File {
+        return Err(Error::custom(format!(
+            "'{name}' is not a file mark, use `Buffer::set_mark` instead"
+        )));
+    }
+
+    buffer.clone().set_mark(name, line, col, opts)
+}
+
 /// Binding to [`nvim_set_option()`][1].
 ///
 /// Sets the global value of an option.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_set_option()
+#[deprecated(note = "use `options::set` with `Scope::Global` instead")]
 pub fn set_option<Opt>(name: &str, value: Opt) -> Result<()>
 where
     Opt: ToObject,
 {
+    #[cfg(feature = "neovim-nightly")]
+    crate::deprecation::check("nvim_set_option", "nvim_set_option_value")?;
+
     let name = nvim::String::from(name);
     let mut err = nvim::Error::new();
     unsafe {
@@ -1058,5 +1289,5 @@ pub fn strwidth(text: &str) -> Result<usize> {
     let text = nvim::String::from(text);
     let mut err = nvim::Error::new();
     let width = unsafe { nvim_strwidth(text.non_owning(), &mut err) };
-    choose!(err, Ok(width.try_into().expect("always positive")))
+    choose!(err, Ok(width.try_into()?))
 }
@@ -0,0 +1,99 @@
+//! Reading and editing the command-line [histories][1].
+//!
+//! The functions in this module wrap [`histget()`][2], [`histadd()`][3] and
+//! [`histdel()`][4], using a [`HistoryKind`] to pick the history to operate
+//! on.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{history, types::HistoryKind};
+//!
+//! history::append(HistoryKind::Cmd, "echo 'Hello'")?;
+//! assert_eq!(Some("echo 'Hello'".into()), history::get(HistoryKind::Cmd, -1)?);
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/cmdline.html#cmdline-history
+//! [2]: https://neovim.io/doc/user/builtin.html#histget()
+//! [3]: https://neovim.io/doc/user/builtin.html#histadd()
+//! [4]: https://neovim.io/doc/user/builtin.html#histdel()
+
+use oxi_types::{self as nvim, Integer};
+
+use crate::call_function;
+use crate::types::HistoryKind;
+use crate::Result;
+
+/// Returns the entry at position `index` in the given history, or `None` if
+/// there isn't one.
+///
+/// Positive indexes are absolute entry numbers, while negative ones count
+/// back from the most recent entry, i.e. `-1` is the last entry.
+pub fn get(kind: HistoryKind, index: isize) -> Result<Option<String>> {
+    let kind = nvim::String::from(kind);
+    let entry =
+        call_function::<_, String>("histget", (kind, index as Integer))?;
+    Ok((!entry.is_empty()).then_some(entry))
+}
+
+/// Returns all the entries in the given history, from the oldest to the most
+/// recent one.
+pub fn entries(kind: HistoryKind) -> Result<Vec<String>> {
+    let mut entries = Vec::new();
+
+    let mut index = -1;
+
+    while let Some(entry) = get(kind, index)? {
+        entries.push(entry);
+        index -= 1;
+    }
+
+    entries.reverse();
+
+    Ok(entries)
+}
+
+/// Returns the number of the most recent entry in the given history, or
+/// `None` if the history is empty.
+pub fn last_index(kind: HistoryKind) -> Result<Option<usize>> {
+    let kind = nvim::String::from(kind);
+    let nr = call_function::<_, Integer>("histnr", (kind,))?;
+    Ok((nr > 0).then_some(nr as usize))
+}
+
+/// Appends `entry` to the given history, moving it to the end if it's
+/// already present.
+///
+/// Returns `false` if the entry wasn't added, e.g. because it's empty or
+/// because the history is disabled by setting [`'history'`][1] to zero.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'history'
+pub fn append(kind: HistoryKind, entry: &str) -> Result<bool> {
+    let kind = nvim::String::from(kind);
+    call_function::<_, Integer>("histadd", (kind, entry)).map(|n| n == 1)
+}
+
+/// Removes the entry at position `index` from the given history, with
+/// indexes interpreted as in [`get`].
+///
+/// Returns `false` if there was no entry to remove.
+pub fn remove(kind: HistoryKind, index: isize) -> Result<bool> {
+    let kind = nvim::String::from(kind);
+    call_function::<_, Integer>("histdel", (kind, index as Integer))
+        .map(|n| n == 1)
+}
+
+/// Removes all the entries matching the regex `pattern` from the given
+/// history.
+///
+/// Returns `false` if no entry matched.
+pub fn remove_matching(kind: HistoryKind, pattern: &str) -> Result<bool> {
+    let kind = nvim::String::from(kind);
+    call_function::<_, Integer>("histdel", (kind, pattern)).map(|n| n == 1)
+}
+
+/// Removes all the entries from the given history.
+pub fn clear(kind: HistoryKind) -> Result<()> {
+    let kind = nvim::String::from(kind);
+    call_function::<_, Integer>("histdel", (kind,)).map(|_| ())
+}
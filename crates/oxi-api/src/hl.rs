@@ -0,0 +1,141 @@
+//! Utilities to cheaply refer to highlight groups from code that runs very
+//! often, like [decoration providers](crate::set_decoration_provider).
+//!
+//! Resolving a highlight group by name means allocating a
+//! [`String`](oxi_types::String) and looking the group up every time.
+//! [`intern`] does that once and returns an [`HlId`] that can be passed
+//! around as a plain integer, e.g. to
+//! [`SetExtmarkOptsBuilder::hl_group`](crate::opts::SetExtmarkOptsBuilder::hl_group).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{hl, opts::SetExtmarkOpts};
+//!
+//! let warn = hl::intern("DiagnosticWarn")?;
+//!
+//! let opts = SetExtmarkOpts::builder()
+//!     .hl_group(warn)
+//!     .ephemeral(true)
+//!     .build();
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use oxi_types::{Integer, Object};
+
+use crate::opts::{CreateAugroupOpts, CreateAutocmdOpts};
+use crate::types::AutocmdEvent;
+use crate::Result;
+use crate::{Error, StringOrInt};
+
+thread_local! {
+    static CACHE: RefCell<HashMap<String, HlId>> =
+        RefCell::new(HashMap::new());
+
+    static INVALIDATE_ON_COLORSCHEME: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The id of a highlight group, as returned by
+/// [`get_hl_id_by_name()`](crate::get_hl_id_by_name).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct HlId(u32);
+
+impl HlId {
+    /// Returns the id as an integer.
+    #[inline]
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<HlId> for u32 {
+    #[inline]
+    fn from(id: HlId) -> Self {
+        id.0
+    }
+}
+
+impl From<HlId> for Object {
+    #[inline]
+    fn from(id: HlId) -> Self {
+        (id.0 as Integer).into()
+    }
+}
+
+impl StringOrInt for HlId {
+    #[inline]
+    fn to_object(self) -> Object {
+        self.into()
+    }
+}
+
+/// Returns the [`HlId`] of the highlight group with the given name, creating
+/// the group if it doesn't exist.
+///
+/// The id is only resolved the first time a name is interned. Later calls
+/// with the same name return the cached id without calling into Neovim. The
+/// cache is cleared every time a `ColorScheme` event fires.
+pub fn intern(name: &str) -> Result<HlId> {
+    if let Some(id) = CACHE.with(|cache| cache.borrow().get(name).copied()) {
+        return Ok(id);
+    }
+
+    let id = crate::get_hl_id_by_name(name)?;
+
+    // Neovim returns 0 when the name contains invalid characters.
+    if id == 0 {
+        return Err(Error::custom(format!(
+            "invalid highlight group name: {name:?}"
+        )));
+    }
+
+    if !INVALIDATE_ON_COLORSCHEME.with(Cell::get) {
+        invalidate_on_colorscheme()?;
+        INVALIDATE_ON_COLORSCHEME.with(|flag| flag.set(true));
+    }
+
+    let id = HlId(id);
+    CACHE.with(|cache| cache.borrow_mut().insert(name.to_owned(), id));
+    Ok(id)
+}
+
+/// Returns whether the id of the highlight group with the given name is
+/// currently cached by [`intern`].
+pub fn is_interned(name: &str) -> bool {
+    CACHE.with(|cache| cache.borrow().contains_key(name))
+}
+
+/// Clears the cache used by [`intern`].
+pub fn clear_cache() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Registers an autocommand clearing the cache when a new colorscheme is
+/// loaded.
+fn invalidate_on_colorscheme() -> Result<()> {
+    // Every plugin has its own cache, so the augroup's name is made unique
+    // with the address of this function to avoid clearing the autocommands
+    // registered by other plugins.
+    let name = format!(
+        "nvim-oxi-hl-intern-{:x}",
+        invalidate_on_colorscheme as fn() -> Result<()> as usize
+    );
+
+    let group = crate::create_augroup(
+        &name,
+        &CreateAugroupOpts::builder().clear(true).build(),
+    )?;
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(group)
+        .desc("Clears the cache of interned highlight groups")
+        .callback(|_| {
+            clear_cache();
+            Ok::<_, Error>(false)
+        })
+        .build();
+
+    crate::create_autocmd([AutocmdEvent::ColorScheme], &opts).map(|_| ())
+}
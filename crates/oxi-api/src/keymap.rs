@@ -0,0 +1,588 @@
+//! A higher-level way to set and delete keymaps, modeled after Lua's
+//! [`vim.keymap`][1] module.
+//!
+//! Unlike [`set_keymap`](crate::set_keymap) and
+//! [`Buffer::set_keymap`](crate::Buffer::set_keymap), [`set`] can map the same
+//! left-hand side in several modes at once, takes either a string or a Rust
+//! closure as the right-hand side, and handles buffer-local mappings via the
+//! [`buffer`](KeymapOptsBuilder::buffer) option.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{keymap::{self, KeymapOpts}, types::Mode, Buffer};
+//!
+//! keymap::set(
+//!     [Mode::Normal, Mode::Visual],
+//!     "<Leader>y",
+//!     "\"+y",
+//!     &Default::default(),
+//! )?;
+//!
+//! let opts = KeymapOpts::builder()
+//!     .buffer(Buffer::current())
+//!     .desc("Say hi")
+//!     .build();
+//!
+//! keymap::set([Mode::Normal], "<Leader>h", |()| {
+//!     nvim_oxi::print!("Hi!");
+//!     Ok(())
+//! }, &opts)?;
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/lua.html#vim.keymap
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::options::{self, Scope};
+use crate::opts::{CreateAutocmdOpts, SetKeymapOpts};
+use crate::types::{AutocmdCallbackArgs, AutocmdEvent, KeymapInfos, Mode};
+use crate::Result;
+use crate::{Buffer, Error};
+
+type Callback = Rc<RefCell<dyn FnMut(()) -> Result<()>>>;
+
+/// The right-hand side of a keymap set via [`set`], either a string of keys
+/// or a Rust closure called when the keymap is executed.
+pub trait KeymapRhs {
+    #[doc(hidden)]
+    fn into_rhs(self) -> Rhs;
+}
+
+#[doc(hidden)]
+pub enum Rhs {
+    Keys(String),
+    Callback(Callback),
+}
+
+impl KeymapRhs for &str {
+    #[inline]
+    fn into_rhs(self) -> Rhs {
+        Rhs::Keys(self.to_owned())
+    }
+}
+
+impl KeymapRhs for String {
+    #[inline]
+    fn into_rhs(self) -> Rhs {
+        Rhs::Keys(self)
+    }
+}
+
+impl<F> KeymapRhs for F
+where
+    F: FnMut(()) -> Result<()> + 'static,
+{
+    #[inline]
+    fn into_rhs(self) -> Rhs {
+        Rhs::Callback(Rc::new(RefCell::new(self)))
+    }
+}
+
+/// Options passed to [`set`].
+///
+/// Like in [`vim.keymap.set()`][1] mappings are non-recursive by default,
+/// which can be changed via [`remap`](KeymapOptsBuilder::remap).
+///
+/// [1]: https://neovim.io/doc/user/lua.html#vim.keymap.set()
+#[derive(Clone, Debug, Default)]
+pub struct KeymapOpts {
+    buffer: Option<Buffer>,
+    desc: Option<String>,
+    expr: bool,
+    nowait: bool,
+    remap: bool,
+    replace_keycodes: Option<bool>,
+    silent: bool,
+    unique: bool,
+}
+
+impl KeymapOpts {
+    /// Creates a new [`KeymapOptsBuilder`].
+    #[inline(always)]
+    pub fn builder() -> KeymapOptsBuilder {
+        KeymapOptsBuilder::default()
+    }
+
+    /// Converts these options to the ones expected by `nvim_set_keymap()`,
+    /// setting the callback if there is one.
+    fn to_set_keymap_opts(
+        &self,
+        callback: Option<&Callback>,
+    ) -> SetKeymapOpts {
+        let mut builder = SetKeymapOpts::builder();
+
+        builder
+            .expr(self.expr)
+            .noremap(!self.remap)
+            .nowait(self.nowait)
+            .silent(self.silent)
+            .unique(self.unique);
+
+        if let Some(desc) = &self.desc {
+            builder.desc(desc);
+        }
+
+        // Like `vim.keymap.set()`, replace the keycodes of expression
+        // mappings by default.
+        if self.replace_keycodes.unwrap_or(self.expr) {
+            builder.replace_keycodes(true);
+        }
+
+        // Neovim takes ownership of the callback, so every keymap needs its
+        // own function.
+        if let Some(callback) = callback {
+            let callback = Rc::clone(callback);
+            builder.callback(move |()| {
+                let mut callback =
+                    callback.try_borrow_mut().map_err(|_| {
+                        Error::custom("keymap callback called recursively")
+                    })?;
+                callback(())
+            });
+        }
+
+        builder.build()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct KeymapOptsBuilder(KeymapOpts);
+
+impl KeymapOptsBuilder {
+    /// Makes the keymap local to the given buffer.
+    #[inline]
+    pub fn buffer(&mut self, buffer: Buffer) -> &mut Self {
+        self.0.buffer = Some(buffer);
+        self
+    }
+
+    /// A description for the keymap.
+    #[inline]
+    pub fn desc(&mut self, desc: &str) -> &mut Self {
+        self.0.desc = Some(desc.to_owned());
+        self
+    }
+
+    /// Whether the right-hand side is an expression.
+    #[inline]
+    pub fn expr(&mut self, expr: bool) -> &mut Self {
+        self.0.expr = expr;
+        self
+    }
+
+    /// For buffer-local mappings, whether Neovim should wait for more
+    /// characters to be typed if there's a global mapping that could also
+    /// match. See `:h map-nowait` for more details.
+    #[inline]
+    pub fn nowait(&mut self, nowait: bool) -> &mut Self {
+        self.0.nowait = nowait;
+        self
+    }
+
+    /// Whether the right-hand side of the mapping is remappable. Defaults to
+    /// `false`.
+    #[inline]
+    pub fn remap(&mut self, remap: bool) -> &mut Self {
+        self.0.remap = remap;
+        self
+    }
+
+    /// Whether to replace the keycodes in the string returned by an
+    /// [`expr`](KeymapOptsBuilder::expr) mapping. Defaults to `true` for
+    /// expression mappings.
+    #[inline]
+    pub fn replace_keycodes(&mut self, replace_keycodes: bool) -> &mut Self {
+        self.0.replace_keycodes = Some(replace_keycodes);
+        self
+    }
+
+    /// Whether the keymap should be silent.
+    #[inline]
+    pub fn silent(&mut self, silent: bool) -> &mut Self {
+        self.0.silent = silent;
+        self
+    }
+
+    /// If `true` setting the keymap will fail if another keymap with the same
+    /// left-hand side already exists.
+    #[inline]
+    pub fn unique(&mut self, unique: bool) -> &mut Self {
+        self.0.unique = unique;
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> KeymapOpts {
+        std::mem::take(&mut self.0)
+    }
+}
+
+/// Maps `lhs` to `rhs` in every one of the given modes, either globally or
+/// in the [`buffer`](KeymapOptsBuilder::buffer) set in the options.
+///
+/// The right-hand side can either be a string of keys or a closure, which is
+/// shared by the keymaps of all the modes.
+pub fn set<M, R>(modes: M, lhs: &str, rhs: R, opts: &KeymapOpts) -> Result<()>
+where
+    M: IntoIterator<Item = Mode>,
+    R: KeymapRhs,
+{
+    let rhs = rhs.into_rhs();
+
+    let (keys, callback) = match &rhs {
+        Rhs::Keys(keys) => (&**keys, None),
+        Rhs::Callback(callback) => ("", Some(callback)),
+    };
+
+    for mode in modes {
+        let set_opts = opts.to_set_keymap_opts(callback);
+
+        match &opts.buffer {
+            Some(buffer) => {
+                buffer.clone().set_keymap(mode, lhs, keys, &set_opts)?
+            },
+            None => crate::set_keymap(mode, lhs, keys, &set_opts)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Deletes the keymaps of `lhs` in every one of the given modes, either the
+/// global ones or the ones local to `buffer`.
+pub fn del<M>(modes: M, lhs: &str, buffer: Option<&Buffer>) -> Result<()>
+where
+    M: IntoIterator<Item = Mode>,
+{
+    for mode in modes {
+        match buffer {
+            Some(buffer) => buffer.clone().del_keymap(mode, lhs)?,
+            None => crate::del_keymap(mode, lhs)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// A registrar of buffer-local keymaps which are deleted automatically once
+/// the filetype of the buffer changes or the buffer is unloaded, returned by
+/// [`Buffer::scoped_keymaps`](crate::Buffer::scoped_keymaps).
+///
+/// This is meant for [`FileType`][1] plugins, whose keymaps would otherwise
+/// outlive the filetype they were set for.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{types::Mode, Buffer};
+///
+/// // In a `FileType rust` autocommand.
+/// let keymaps = Buffer::current().scoped_keymaps(Some("rust"))?;
+///
+/// keymaps.set(
+///     [Mode::Normal],
+///     "<LocalLeader>r",
+///     ":!cargo run<CR>",
+///     &Default::default(),
+/// )?;
+/// ```
+///
+/// [1]: https://neovim.io/doc/user/autocmd.html#FileType
+#[derive(Clone)]
+pub struct ScopedKeymaps {
+    state: Rc<RefCell<ScopedState>>,
+}
+
+struct ScopedState {
+    buffer: Buffer,
+    keymaps: Vec<(Mode, String)>,
+    autocmds: Vec<u32>,
+    has_ended: bool,
+}
+
+impl ScopedKeymaps {
+    pub(crate) fn new(buffer: Buffer, filetype: Option<&str>) -> Result<Self> {
+        let filetype = match filetype {
+            Some(filetype) => filetype.to_owned(),
+            None => options::get("filetype", Scope::Buffer(&buffer))?,
+        };
+
+        let state = Rc::new(RefCell::new(ScopedState {
+            buffer: buffer.clone(),
+            keymaps: Vec::new(),
+            autocmds: Vec::new(),
+            has_ended: false,
+        }));
+
+        let on_filetype = CreateAutocmdOpts::builder()
+            .buffer(buffer.clone())
+            .desc("Deletes the keymaps scoped to the previous filetype")
+            .callback({
+                let state = Rc::clone(&state);
+                move |args: AutocmdCallbackArgs| {
+                    if args.r#match != filetype {
+                        end_scope(&state);
+                    }
+                    Ok::<_, Error>(false)
+                }
+            })
+            .build();
+
+        let on_unload = CreateAutocmdOpts::builder()
+            .buffer(buffer)
+            .desc("Deletes the keymaps scoped to the unloaded buffer")
+            .callback({
+                let state = Rc::clone(&state);
+                move |_: AutocmdCallbackArgs| {
+                    end_scope(&state);
+                    Ok::<_, Error>(false)
+                }
+            })
+            .build();
+
+        let autocmds = vec![
+            crate::create_autocmd([AutocmdEvent::FileType], &on_filetype)?,
+            crate::create_autocmd([AutocmdEvent::BufUnload], &on_unload)?,
+        ];
+
+        state.borrow_mut().autocmds = autocmds;
+
+        Ok(Self { state })
+    }
+
+    /// Deletes all the keymaps set so far. Keymaps set afterwards are still
+    /// deleted when the scope ends.
+    pub fn clear(&self) {
+        let mut state = self.state.borrow_mut();
+        let ScopedState { buffer, keymaps, .. } = &mut *state;
+
+        // The keymaps could've already been deleted by someone else.
+        for (mode, lhs) in keymaps.drain(..) {
+            let _ = buffer.del_keymap(mode, &lhs);
+        }
+    }
+
+    /// Returns whether the scope has ended, i.e. if the filetype of the
+    /// buffer has changed or the buffer has been unloaded.
+    pub fn has_ended(&self) -> bool {
+        self.state.borrow().has_ended
+    }
+
+    /// Same as [`set`], except that the keymaps are always local to the
+    /// scope's buffer, regardless of the
+    /// [`buffer`](KeymapOptsBuilder::buffer) set in the options.
+    ///
+    /// Fails if the scope has already ended.
+    pub fn set<M, R>(
+        &self,
+        modes: M,
+        lhs: &str,
+        rhs: R,
+        opts: &KeymapOpts,
+    ) -> Result<()>
+    where
+        M: IntoIterator<Item = Mode>,
+        R: KeymapRhs,
+    {
+        let mut state = self.state.borrow_mut();
+
+        if state.has_ended {
+            return Err(Error::custom(
+                "can't set keymaps in a scope that has ended",
+            ));
+        }
+
+        let modes = modes.into_iter().collect::<Vec<_>>();
+
+        let opts =
+            KeymapOpts { buffer: Some(state.buffer.clone()), ..opts.clone() };
+
+        set(modes.iter().copied(), lhs, rhs, &opts)?;
+
+        state
+            .keymaps
+            .extend(modes.into_iter().map(|mode| (mode, lhs.to_owned())));
+
+        Ok(())
+    }
+}
+
+/// Deletes the keymaps of a scope together with the autocommands watching
+/// it.
+fn end_scope(state: &Rc<RefCell<ScopedState>>) {
+    ScopedKeymaps { state: Rc::clone(state) }.clear();
+
+    let mut state = state.borrow_mut();
+    state.has_ended = true;
+
+    for id in state.autocmds.drain(..) {
+        let _ = crate::del_autocmd(id);
+    }
+}
+
+/// A node in the tree of keymaps returned by [`tree`].
+///
+/// Every node corresponds to a sequence of keys, and its children to the
+/// sequences obtained by typing one more key. A node is a prefix if it has
+/// children, and a leaf otherwise. Both can also be mapped themselves, e.g.
+/// if both `gc` and `gcc` are mapped the `gc` node is a mapped prefix.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeymapNode {
+    /// The last key of the sequence, e.g. `"<C-x>"` or `"f"`. Empty for the
+    /// root of the tree.
+    pub key: String,
+
+    /// The whole sequence of keys leading to this node, normalized like
+    /// [`keytrans()`][1] does.
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#keytrans()
+    pub lhs: String,
+
+    /// The keymap of this sequence of keys, if it's mapped. Buffer-local
+    /// keymaps take precedence over global ones.
+    pub keymap: Option<KeymapInfos>,
+
+    /// The nodes reachable by typing one more key, sorted by key.
+    pub children: Vec<KeymapNode>,
+}
+
+impl KeymapNode {
+    /// Returns the description of the node's keymap, if any.
+    #[inline]
+    pub fn desc(&self) -> Option<&str> {
+        self.keymap.as_ref()?.desc.as_deref()
+    }
+
+    /// Returns the child reached by typing `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&KeymapNode> {
+        self.children
+            .binary_search_by(|child| child.key.as_str().cmp(key))
+            .ok()
+            .map(|idx| &self.children[idx])
+    }
+
+    /// Returns `true` if no further keys can be typed after this node.
+    #[inline]
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns `true` if the node is the start of a longer sequence of
+    /// keys.
+    #[inline]
+    pub fn is_prefix(&self) -> bool {
+        !self.children.is_empty()
+    }
+
+    /// Inserts `keymap` at the node reached by typing `keys` from this one,
+    /// creating the intermediate nodes if needed.
+    fn insert(&mut self, keys: &[&str], keymap: KeymapInfos) {
+        let (key, rest) = match keys.split_first() {
+            Some(split) => split,
+            None => {
+                self.keymap = Some(keymap);
+                return;
+            },
+        };
+
+        let idx = match self
+            .children
+            .binary_search_by(|child| child.key.as_str().cmp(key))
+        {
+            Ok(idx) => idx,
+            Err(idx) => {
+                let child = KeymapNode {
+                    key: (*key).to_owned(),
+                    lhs: format!("{}{}", self.lhs, key),
+                    ..Default::default()
+                };
+                self.children.insert(idx, child);
+                idx
+            },
+        };
+
+        self.children[idx].insert(rest, keymap);
+    }
+}
+
+/// Returns the tree of the keymaps of `mode` starting with `prefix`, whose
+/// root is the node of `prefix` itself. Pass an empty prefix to get all the
+/// keymaps.
+///
+/// The tree contains both the global keymaps and the ones local to the
+/// current buffer, which replace the global keymaps with the same left-hand
+/// side. Both `prefix` and the left-hand sides of the keymaps are normalized
+/// first, so e.g. `"<leader>"` and `"<C-X>"` match the keymaps set with
+/// `"<Leader>"` and `"<c-x>"`.
+pub fn tree(mode: Mode, prefix: &str) -> Result<KeymapNode> {
+    let prefix = normalize(prefix)?;
+    let prefix_len = split_keys(&prefix).len();
+
+    let mut root = KeymapNode { lhs: prefix.clone(), ..Default::default() };
+
+    let global = crate::get_keymap(mode);
+    let local = Buffer::current().get_keymap(mode)?;
+
+    for keymap in global.chain(local) {
+        let lhs = normalize(&keymap.lhs)?;
+
+        if !lhs.starts_with(&prefix) {
+            continue;
+        }
+
+        let keys = split_keys(&lhs);
+
+        // The normalized prefix could end in the middle of a key, e.g.
+        // `<C` and `<C-x>`.
+        if keys[..prefix_len.min(keys.len())].concat() != prefix {
+            continue;
+        }
+
+        root.insert(&keys[prefix_len..], keymap);
+    }
+
+    Ok(root)
+}
+
+/// Normalizes the notation of a sequence of keys.
+fn normalize(keys: &str) -> Result<String> {
+    let termcodes = crate::replace_termcodes(keys, true, true, true);
+    crate::call_function("keytrans", (termcodes,))
+}
+
+/// Splits a sequence of keys normalized by [`normalize`] into single keys,
+/// treating the `<...>` notation as one key.
+fn split_keys(keys: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut rest = keys;
+
+    while let Some(first) = rest.chars().next() {
+        let len = match (first, rest.find('>')) {
+            ('<', Some(end)) if end > 1 => end + 1,
+            _ => first.len_utf8(),
+        };
+        split.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_keys_notation() {
+        assert_eq!(vec!["g", "c", "c"], split_keys("gcc"));
+        assert_eq!(
+            vec!["<Space>", "f", "<C-x>", "é"],
+            split_keys("<Space>f<C-x>é")
+        );
+        assert_eq!(vec!["<lt>", "<", ">"], split_keys("<lt><>"));
+        assert!(split_keys("").is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use oxi_types as nvim;
+
+use crate::replace_termcodes;
+
+/// A sequence of keys written in Neovim's [key notation][1], to be fed to
+/// [`feedkeys_typed`](crate::feedkeys_typed).
+///
+/// Special keys are added via their own methods, while the text passed to
+/// [`char`](Keys::char) and [`str`](Keys::str) is always inserted literally,
+/// so that a `<` in it can't be mistaken for the start of a special key.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, types::Mode, Keys};
+///
+/// // Leave insert mode and open a vertical split.
+/// let keys = Keys::new().esc().ctrl('w').char('v');
+/// assert_eq!("<Esc><C-w>v", keys.notation());
+///
+/// api::feedkeys_typed(keys, Mode::Normal);
+/// ```
+///
+/// [1]: https://neovim.io/doc/user/intro.html#key-notation
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Keys {
+    notation: String,
+}
+
+macro_rules! special_key {
+    ($fn_name:ident, $name:literal) => {
+        #[doc = concat!("Adds the `<", $name, ">` key.")]
+        #[inline]
+        pub fn $fn_name(self) -> Self {
+            self.key($name)
+        }
+    };
+}
+
+impl Keys {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the key `c` while holding down the Alt (or Meta) key, e.g.
+    /// `<M-x>`.
+    #[inline]
+    pub fn alt(self, c: char) -> Self {
+        self.modified('M', c)
+    }
+
+    /// Adds the character `c`, which is never interpreted as key notation.
+    #[inline]
+    pub fn char(mut self, c: char) -> Self {
+        match c {
+            '<' => self.notation.push_str("<lt>"),
+            '\\' => self.notation.push_str("<Bslash>"),
+            _ => self.notation.push(c),
+        }
+        self
+    }
+
+    /// Adds the key `c` while holding down the Control key, e.g. `<C-w>`.
+    #[inline]
+    pub fn ctrl(self, c: char) -> Self {
+        self.modified('C', c)
+    }
+
+    /// Adds the special key with the given name, e.g. `"F1"` or `"Leader"`.
+    /// The name is wrapped in angle brackets.
+    #[inline]
+    pub fn key(mut self, name: &str) -> Self {
+        self.notation.push('<');
+        self.notation.push_str(name);
+        self.notation.push('>');
+        self
+    }
+
+    /// Adds keys that are already written in key notation, without escaping
+    /// them.
+    #[inline]
+    pub fn raw(mut self, notation: &str) -> Self {
+        self.notation.push_str(notation);
+        self
+    }
+
+    /// Adds every character of `text`, none of which is interpreted as key
+    /// notation.
+    #[inline]
+    pub fn str(self, text: &str) -> Self {
+        text.chars().fold(self, Self::char)
+    }
+
+    special_key!(bs, "BS");
+    special_key!(cr, "CR");
+    special_key!(del, "Del");
+    special_key!(down, "Down");
+    special_key!(esc, "Esc");
+    special_key!(left, "Left");
+    special_key!(right, "Right");
+    special_key!(tab, "Tab");
+    special_key!(up, "Up");
+
+    /// Returns the keys in key notation.
+    #[inline]
+    pub fn notation(&self) -> &str {
+        &self.notation
+    }
+
+    /// Translates the keys into the internal byte representation used by
+    /// Neovim via [`replace_termcodes`](crate::replace_termcodes).
+    #[inline]
+    pub fn to_termcodes(&self) -> nvim::String {
+        replace_termcodes(&*self.notation, true, true, true)
+    }
+
+    fn modified(mut self, modifier: char, c: char) -> Self {
+        self.notation.push('<');
+        self.notation.push(modifier);
+        self.notation.push('-');
+        match c {
+            '<' => self.notation.push_str("lt"),
+            '\\' => self.notation.push_str("Bslash"),
+            _ => self.notation.push(c),
+        }
+        self.notation.push('>');
+        self
+    }
+}
+
+impl fmt::Display for Keys {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.notation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notation() {
+        let keys = Keys::new().esc().ctrl('w').char('v');
+        assert_eq!("<Esc><C-w>v", keys.notation());
+
+        let keys = Keys::new().char('i').str("a<CR>\\").alt('<').cr();
+        assert_eq!("ia<lt>CR><Bslash><M-lt><CR>", keys.notation());
+
+        let keys = Keys::new().key("Leader").raw("<C-x><C-o>").tab();
+        assert_eq!("<Leader><C-x><C-o><Tab>", keys.to_string());
+    }
+}
@@ -13,33 +13,77 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod arglist;
 mod autocmd;
+mod batch;
+mod buf_attach;
 mod buffer;
+mod cmd_builder;
+pub mod completion;
+mod decoration_context;
+mod deprecation;
 mod error;
+pub mod exprs;
 mod extmark;
 mod ffi;
+mod float_stack;
+pub mod fn_;
 mod global;
+pub mod history;
+pub mod hl;
+pub mod keymap;
+mod keys;
+mod namespace;
+pub mod options;
+mod open_file;
 pub mod opts;
+mod preview;
+mod save;
+pub mod semantic;
 pub(crate) mod serde_utils;
+mod split;
+mod state;
 mod tabpage;
+mod terminal;
+pub mod text;
 mod trait_utils;
 pub mod types;
 pub(crate) mod utils;
+pub mod viewport;
 mod vimscript;
 mod win_config;
 mod window;
+mod writer;
 
+pub use arglist::*;
 pub use autocmd::*;
+pub use batch::*;
+pub use buf_attach::*;
 pub use buffer::*;
+pub use cmd_builder::*;
+pub use decoration_context::{
+    current_decoration_callback,
+    DecorationCallback,
+};
 pub use error::Error;
 use error::Result;
 pub use extmark::*;
+pub use float_stack::*;
 pub use global::*;
+pub use keys::*;
+pub use namespace::*;
+pub use open_file::*;
+pub use preview::*;
+pub use save::*;
+pub use split::*;
+pub use state::*;
 pub use tabpage::*;
+pub use terminal::*;
 pub use trait_utils::*;
 pub use vimscript::*;
 pub use win_config::*;
 pub use window::*;
+pub use writer::*;
 
 // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/private/defs.h#L43
 const INTERNAL_CALL_MASK: u64 = 1u64 << (std::mem::size_of::<u64>() * 8 - 1);
@@ -0,0 +1,180 @@
+use std::ops::RangeBounds;
+
+use crate::opts::{SetExtmarkOpts, SetHighlightOpts};
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// A namespace for highlights and extmarks, created via
+/// [`create_namespace`](crate::create_namespace).
+///
+/// The methods of this type are thin wrappers around the corresponding
+/// functions taking a namespace id, e.g.
+/// [`Buffer::set_extmark`](crate::Buffer::set_extmark).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Namespace(u32);
+
+impl From<Namespace> for u32 {
+    #[inline(always)]
+    fn from(ns: Namespace) -> Self {
+        ns.0
+    }
+}
+
+impl Namespace {
+    /// Creates a new namespace, or gets the existing one with the same name.
+    #[inline]
+    pub fn new(name: &str) -> Self {
+        Self(crate::create_namespace(name))
+    }
+
+    /// Creates a new anonymous namespace, which is different from all the
+    /// other ones.
+    #[inline]
+    pub fn anonymous() -> Self {
+        Self::new("")
+    }
+
+    /// Returns the id of the namespace.
+    #[inline(always)]
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    /// Adds a highlight to `buffer` in this namespace. Both `line` and
+    /// `byte_range` are 0-indexed.
+    ///
+    /// See [`Buffer::add_highlight`](crate::Buffer::add_highlight) for more
+    /// details.
+    pub fn add_highlight<R>(
+        &self,
+        buffer: &Buffer,
+        hl_group: &str,
+        line: usize,
+        byte_range: R,
+    ) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        buffer.clone().add_highlight(self.0, hl_group, line, byte_range)?;
+        Ok(())
+    }
+
+    /// Clears the highlights and extmarks of this namespace in the given
+    /// (0-indexed, end-exclusive) range of lines of `buffer`.
+    #[inline]
+    pub fn clear<R>(&self, buffer: &Buffer, line_range: R) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        buffer.clone().clear_namespace(self.0, line_range)
+    }
+
+    /// Creates an extmark in this namespace, returning its id.
+    ///
+    /// See [`Buffer::set_extmark`](crate::Buffer::set_extmark) for more
+    /// details.
+    #[inline]
+    pub fn set_extmark(
+        &self,
+        buffer: &Buffer,
+        line: usize,
+        col: usize,
+        opts: &SetExtmarkOpts,
+    ) -> Result<u32> {
+        buffer.clone().set_extmark(self.0, line, col, opts)
+    }
+
+    /// Defines the highlight group `name` in this namespace. The group is
+    /// only used by the windows this namespace is set for via
+    /// [`set_for_window`](Namespace::set_for_window).
+    #[inline]
+    pub fn set_hl(&self, name: &str, opts: &SetHighlightOpts) -> Result<()> {
+        crate::set_hl(self.0, name, opts)
+    }
+
+    /// Makes `window` use the highlight groups defined in this namespace.
+    #[inline]
+    pub fn set_for_window(&self, window: &Window) -> Result<()> {
+        window.clone().set_hl_ns(self.0)
+    }
+
+    /// Returns a guard that clears this namespace in `buffer` when it's
+    /// dropped, which is useful for temporary highlights like the ones
+    /// flashed after a yank.
+    ///
+    /// Since the whole namespace is cleared, the guard should usually be
+    /// used with a namespace that's not shared with anything else, like an
+    /// [`anonymous`](Namespace::anonymous) one.
+    #[inline]
+    pub fn scoped(&self, buffer: Buffer) -> NamespaceGuard {
+        NamespaceGuard { ns: *self, buffer }
+    }
+}
+
+/// A [`Namespace`] bound to a buffer, which is cleared when the guard is
+/// dropped. Created by [`Namespace::scoped`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{Buffer, Namespace};
+///
+/// let guard = Namespace::anonymous().scoped(Buffer::current());
+/// guard.add_highlight("IncSearch", 0, ..)?;
+///
+/// // Later, e.g. in a timer callback, clear the highlight.
+/// drop(guard);
+/// ```
+#[derive(Debug)]
+pub struct NamespaceGuard {
+    ns: Namespace,
+    buffer: Buffer,
+}
+
+impl NamespaceGuard {
+    /// Adds a highlight in the guarded namespace and buffer.
+    #[inline]
+    pub fn add_highlight<R>(
+        &self,
+        hl_group: &str,
+        line: usize,
+        byte_range: R,
+    ) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.ns.add_highlight(&self.buffer, hl_group, line, byte_range)
+    }
+
+    /// Returns the buffer the guard is bound to.
+    #[inline(always)]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the guarded namespace.
+    #[inline(always)]
+    pub fn namespace(&self) -> Namespace {
+        self.ns
+    }
+
+    /// Creates an extmark in the guarded namespace and buffer, returning its
+    /// id.
+    #[inline]
+    pub fn set_extmark(
+        &self,
+        line: usize,
+        col: usize,
+        opts: &SetExtmarkOpts,
+    ) -> Result<u32> {
+        self.ns.set_extmark(&self.buffer, line, col, opts)
+    }
+}
+
+impl Drop for NamespaceGuard {
+    fn drop(&mut self) {
+        if self.buffer.is_valid() {
+            let _ = self.ns.clear(&self.buffer, ..);
+        }
+    }
+}
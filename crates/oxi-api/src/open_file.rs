@@ -0,0 +1,129 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use oxi_types::{self as nvim, Integer, Object};
+
+use crate::fn_::{fnameescape, fnamemodify};
+use crate::options::{self, Scope};
+use crate::opts::{CreateAutocmdOpts, OpenFileOpts};
+use crate::types::{
+    AutocmdCallbackArgs,
+    AutocmdEvent,
+    OpenFileError,
+    OpenMode,
+    SwapExistsAction,
+};
+use crate::{Buffer, Error, Result, Window};
+
+/// Opens the file at `path` as described by `mode`, returning its buffer and
+/// the window it's displayed in, if any.
+///
+/// Unlike running `:edit` or `:drop` via [`command()`](crate::command), an
+/// existing swap file doesn't block on the [`E325: ATTENTION`][1] prompt.
+/// It's handled as set by
+/// [`on_swap_exists`](crate::opts::OpenFileOptsBuilder::on_swap_exists)
+/// instead, which fails with an [`OpenFileError::SwapExists`] by default.
+///
+/// [1]: https://neovim.io/doc/user/usr_11.html#E325
+pub fn open_file<P: AsRef<Path>>(
+    path: P,
+    mode: OpenMode,
+    opts: &OpenFileOpts,
+) -> Result<(Buffer, Option<Window>)> {
+    let path = path.as_ref();
+
+    let cmd = match mode {
+        OpenMode::Edit => "edit",
+        OpenMode::Drop => "drop",
+        OpenMode::BAdd => return Ok((badd(path)?, None)),
+    };
+
+    let window = match (mode, opts.reuse_window) {
+        (OpenMode::Edit, true) => window_showing(path)?,
+        _ => None,
+    };
+
+    if let Some(window) = window {
+        crate::set_current_win(&window)?;
+    } else {
+        let cmd = format!("{cmd} {}", fnameescape(path)?);
+        with_swap_exists_action(opts.on_swap_exists, || crate::command(&cmd))?;
+    }
+
+    let mut window = Window::current();
+
+    if let Some((line, col)) = opts.cursor {
+        window.set_cursor(line, col)?;
+    }
+
+    Ok((Buffer::current(), Some(window)))
+}
+
+/// Adds the file to the buffer list and loads it, like `:badd` followed by
+/// `bufload()`.
+fn badd(path: &Path) -> Result<Buffer> {
+    let buffer = crate::call_function::<_, Buffer>(
+        "bufadd",
+        (nvim::String::from(path),),
+    )?;
+    options::set("buflisted", true, Scope::Buffer(&buffer))?;
+    crate::call_function::<_, Object>("bufload", (&buffer,))?;
+    Ok(buffer)
+}
+
+/// Returns the window of the current tabpage showing the file at `path`, if
+/// any.
+fn window_showing(path: &Path) -> Result<Option<Window>> {
+    let full_path = fnamemodify(path, ":p")?;
+
+    for buffer in crate::list_bufs() {
+        if buffer.get_name()? != full_path {
+            continue;
+        }
+        let id = crate::call_function::<_, Integer>("bufwinid", (&buffer,))?;
+        return Ok((id != -1).then(|| Window::from(id as i32)));
+    }
+
+    Ok(None)
+}
+
+/// Calls `fun` with a `SwapExists` autocommand answering the swap file
+/// prompt as described by `action`.
+fn with_swap_exists_action(
+    action: SwapExistsAction,
+    fun: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    let swap_file = Rc::new(RefCell::new(None::<PathBuf>));
+
+    let opts = CreateAutocmdOpts::builder()
+        .once(true)
+        .callback({
+            let swap_file = Rc::clone(&swap_file);
+            move |_: AutocmdCallbackArgs| {
+                let swapname = crate::get_vvar::<nvim::String>("swapname")?;
+                *swap_file.borrow_mut() = Some(swapname.into());
+                crate::set_vvar("swapchoice", action.swapchoice())?;
+                Ok::<_, Error>(false)
+            }
+        })
+        .build();
+
+    let id = crate::create_autocmd([AutocmdEvent::SwapExists], &opts)?;
+    let res = fun();
+    // The autocommand has already been deleted if it was triggered.
+    let _ = crate::del_autocmd(id);
+
+    if let (Some(swap_file), SwapExistsAction::Abort) =
+        (swap_file.take(), action)
+    {
+        return Err(OpenFileError::SwapExists { swap_file }.into());
+    }
+
+    res.map_err(|err| match err {
+        Error::Nvim(err) if err.to_string().contains("E37:") => {
+            OpenFileError::Modified.into()
+        },
+        err => err,
+    })
+}
@@ -0,0 +1,93 @@
+//! A single entry point to get and set the value of Neovim's
+//! [options](https://neovim.io/doc/user/options.html), be they global,
+//! buffer-local or window-local.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{
+//!     options::{self, Scope},
+//!     types::ShortmessFlag,
+//!     Buffer,
+//!     Window,
+//! };
+//!
+//! let buf = Buffer::current();
+//! let modifiable = options::get::<bool>("modifiable", Scope::Buffer(&buf))?;
+//!
+//! let win = Window::current();
+//! options::set("wrap", false, Scope::Window(&win))?;
+//!
+//! let shiftwidth = options::get::<u32>("shiftwidth", Scope::Global)?;
+//!
+//! // Only touches the `I` flag, keeping the ones set by other plugins.
+//! options::update_flags("shortmess", Scope::Global, |flags| {
+//!     flags.insert(ShortmessFlag::NoIntro);
+//! })?;
+//! ```
+
+use oxi_types::conversion::{FromObject, ToObject};
+
+use crate::opts::{OptionScope, OptionValueOpts};
+use crate::types::Flags;
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// The value of an option to target in [`get`] and [`set`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Scope<'a> {
+    /// The global value of the option.
+    Global,
+
+    /// The local value of a buffer-local option in the given buffer.
+    Buffer(&'a Buffer),
+
+    /// The local value of a window-local option in the given window.
+    Window(&'a Window),
+}
+
+impl Scope<'_> {
+    fn to_opts(self) -> OptionValueOpts {
+        let mut builder = OptionValueOpts::builder();
+        match self {
+            Self::Global => builder.scope(OptionScope::Global),
+            Self::Buffer(buf) => builder.buffer(buf.clone()),
+            Self::Window(win) => builder.window(win.clone()),
+        };
+        builder.build()
+    }
+}
+
+/// Gets the value of the option `name` in the given scope.
+///
+/// This is a wrapper around [`get_option_value()`](crate::get_option_value).
+pub fn get<Opt>(name: &str, scope: Scope<'_>) -> Result<Opt>
+where
+    Opt: FromObject,
+{
+    crate::get_option_value(name, &scope.to_opts())
+}
+
+/// Sets the value of the option `name` in the given scope.
+///
+/// This is a wrapper around [`set_option_value()`](crate::set_option_value).
+pub fn set<Opt>(name: &str, value: Opt, scope: Scope<'_>) -> Result<()>
+where
+    Opt: ToObject,
+{
+    crate::set_option_value(name, value, &scope.to_opts())
+}
+
+/// Updates the value of the flag option `name`, like [`'shortmess'`][1], in
+/// the given scope by modifying its current [`Flags`].
+///
+/// [1]: https://neovim.io/doc/user/options.html#'shortmess'
+pub fn update_flags<F>(name: &str, scope: Scope<'_>, fun: F) -> Result<()>
+where
+    F: FnOnce(&mut Flags),
+{
+    let mut flags = get::<Flags>(name, scope)?;
+    fun(&mut flags);
+    set(name, flags, scope)
+}
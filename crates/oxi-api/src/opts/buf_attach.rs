@@ -1,5 +1,6 @@
 use oxi_types::{Dictionary, Object};
 
+use crate::types::BytesChangeEvent;
 use crate::Buffer;
 use crate::ToFunction;
 
@@ -15,9 +16,9 @@ use crate::ToFunction;
 /// - `f`: last row in the updated range;
 /// - `g`: byte count of previous contents;
 /// - `h`: deleted UTF-32 codepoints (if
-/// [`utf_sizes`](BufAttachOptsBuilder::utf_sizes) was `true`);
+///   [`utf_sizes`](BufAttachOptsBuilder::utf_sizes) was `true`);
 /// - `i`: deleted UTF-16 codeunits (if
-/// [`utf_sizes`](BufAttachOptsBuilder::utf_sizes) was `true`);
+///   [`utf_sizes`](BufAttachOptsBuilder::utf_sizes) was `true`);
 pub type OnLinesArgs = (
     String,
     Buffer,
@@ -30,34 +31,6 @@ pub type OnLinesArgs = (
     Option<usize>,
 );
 
-/// Arguments passed to the callback registered to [`on_bytes`](BufAttachOptsBuilder::on_bytes). The `(a, b, c, d, e, f, g, h, i, j, k, l)`
-/// - `a`: the string literal `"bytes"`;
-/// - `b`: the [`Buffer`] that triggered the callback;
-/// - `c`: the value of the buffer-local `b:changedtick` variable;
-/// - `d`: start row of the changed text (0-indexed);
-/// - `e`: start column of the changed text;
-/// - `f`: byte offset of the changed text from the start of the buffer;
-/// - `g`: number of rows deleted;
-/// - `h`: number of columns deleted;
-/// - `i`: number of bytes deleted;
-/// - `j`: number of rows added;
-/// - `k`: number of columns added;
-/// - `l`: number of bytes added;
-pub type OnBytesArgs = (
-    String,
-    Buffer,
-    u32,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-);
-
 /// Arguments passed to the callback registered to
 /// [`on_changedtick`](BufAttachOptsBuilder::on_changedtick). The first tuple
 /// element is the string literal `"changedtick"`, the second is the [`Buffer`]
@@ -108,11 +81,12 @@ pub struct BufAttachOptsBuilder(BufAttachOpts);
 
 impl BufAttachOptsBuilder {
     /// Callback invoked on change. It receives more granular information about
-    /// the change compared to [`on_lines`](BufAttachOptsBuilder::on_lines).
+    /// the change compared to [`on_lines`](BufAttachOptsBuilder::on_lines)
+    /// in the form of a [`BytesChangeEvent`].
     #[inline]
     pub fn on_bytes<F>(&mut self, on_bytes: F) -> &mut Self
     where
-        F: ToFunction<OnBytesArgs, ShouldDetach>,
+        F: ToFunction<BytesChangeEvent, ShouldDetach>,
     {
         self.0.on_bytes = on_bytes.to_object();
         self
@@ -1,19 +1,31 @@
 use oxi_types::{Dictionary, Object};
 
 /// Options passed to [`Buffer::delete()`](crate::Buffer::delete).
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct BufDeleteOpts {
     force: Object,
     unload: Object,
 }
 
 impl BufDeleteOpts {
+    /// The default options, which delete the buffer but fail if it has
+    /// unsaved changes.
+    pub const DEFAULT: Self =
+        Self { force: Object::nil(), unload: Object::nil() };
+
     #[inline(always)]
     pub fn builder() -> BufDeleteOptsBuilder {
         BufDeleteOptsBuilder::default()
     }
 }
 
+impl Default for BufDeleteOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct BufDeleteOptsBuilder(BufDeleteOpts);
 
@@ -1,5 +1,6 @@
 use oxi_types::{Array, Object};
 
+use crate::types::AutocmdEvent;
 use crate::Buffer;
 use crate::StringOrInt;
 
@@ -48,9 +49,9 @@ impl ClearAutocmdsOptsBuilder {
     /// Clear all the autocommands triggered by one or more of the specified
     /// events.
     #[inline]
-    pub fn events<'a, I>(&mut self, iter: I) -> &mut Self
+    pub fn events<I>(&mut self, iter: I) -> &mut Self
     where
-        I: IntoIterator<Item = &'a str>,
+        I: IntoIterator<Item = AutocmdEvent>,
     {
         self.0.event = Array::from_iter(iter).into();
         self
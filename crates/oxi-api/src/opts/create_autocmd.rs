@@ -41,6 +41,11 @@ impl CreateAutocmdOpts {
     pub fn builder() -> CreateAutocmdOptsBuilder {
         CreateAutocmdOptsBuilder::default()
     }
+
+    #[inline]
+    pub(crate) fn set_group(&mut self, group: u32) {
+        self.group = group.into();
+    }
 }
 
 #[derive(Clone, Default)]
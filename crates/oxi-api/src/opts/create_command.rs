@@ -91,6 +91,27 @@ impl CreateCommandOptsBuilder {
         self
     }
 
+    /// Completes the arguments of the command with the candidates returned
+    /// by `fun`, which is called with the argument being completed, the
+    /// whole command line and the byte position of the cursor in it. See
+    /// `:h :command-completion-customlist` for details.
+    ///
+    /// The candidates aren't filtered by Neovim, so `fun` should only return
+    /// the ones that match the argument.
+    #[inline]
+    pub fn complete_fn<F>(&mut self, mut fun: F) -> &mut Self
+    where
+        F: FnMut(&str, &str, usize) -> Vec<String> + 'static,
+    {
+        let fun =
+            Function::from_fn_mut(move |args: (String, String, usize)| {
+                let (arg_lead, cmd_line, cursor_pos) = args;
+                Ok::<_, crate::Error>(fun(&arg_lead, &cmd_line, cursor_pos))
+            });
+
+        self.complete(CommandComplete::CustomList(fun))
+    }
+
     #[inline]
     pub fn count(&mut self, count: impl Into<Integer>) -> &mut Self {
         self.0.count = count.into().into();
@@ -1,49 +1,121 @@
+use oxi_luajit::{self as lua, Poppable};
 use oxi_types::Object;
 
-use crate::ToFunction;
 use crate::{Buffer, Window};
+use crate::{DecorationCallback, ToFunction};
 
-// NOTE: docs say a third argument of changedtick is passed. I don't see it.
 /// Arguments passed to the function registered to
 /// [`on_buf`](DecorationProviderOptsBuilder::on_buf).
-pub type OnBufArgs = (
-    String, // the string literal "buf"
-    Buffer, // buffer
-);
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OnBufArgs {
+    /// The buffer about to be redrawn.
+    pub buffer: Buffer,
+}
 
 /// Arguments passed to the function registered to
 /// [`on_end`](DecorationProviderOptsBuilder::on_end).
-pub type OnEndArgs = (
-    String, // the string literal "end"
-    u32,    // changedtick
-);
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OnEndArgs {
+    /// The redraw tick of the redraw cycle that just ended.
+    pub tick: u32,
+}
 
 /// Arguments passed to the function registered to
 /// [`on_line`](DecorationProviderOptsBuilder::on_line).
-pub type OnLineArgs = (
-    String, // the string literal "win"
-    Window, // window
-    Buffer, // buffer
-    usize,  // row
-);
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OnLineArgs {
+    /// The window being redrawn.
+    pub window: Window,
+
+    /// The buffer displayed in the window.
+    pub buffer: Buffer,
+
+    /// The row being redrawn (0-indexed).
+    pub row: usize,
+}
 
 /// Arguments passed to the function registered to
 /// [`on_start`](DecorationProviderOptsBuilder::on_start).
-pub type OnStartArgs = (
-    String, // the string literal "start"
-    u32,    // changedtick
-    u32, /* `type`, undocumented? (https://github.com/neovim/neovim/blob/master/src/nvim/decoration_provider.c#L68) */
-);
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OnStartArgs {
+    /// The redraw tick of the redraw cycle that's starting.
+    pub tick: u32,
+}
 
 /// Arguments passed to the function registered to
 /// [`on_win`](DecorationProviderOptsBuilder::on_win).
-pub type OnWinArgs = (
-    String, // the string literal "win"
-    Window, // window
-    Buffer, // buffer
-    u32,    // topline
-    u32,    // botline guess
-);
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OnWinArgs {
+    /// The window about to be redrawn.
+    pub window: Window,
+
+    /// The buffer displayed in the window.
+    pub buffer: Buffer,
+
+    /// The first row of the buffer visible in the window (0-indexed).
+    pub toprow: usize,
+
+    /// A guess of the last row of the buffer visible in the window.
+    pub botrow: usize,
+}
+
+// The first argument passed to every callback is the name of the event, e.g.
+// "start" or "win", which we discard.
+
+// NOTE: docs say a third argument of changedtick is passed. I don't see it.
+impl Poppable for OnBufArgs {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        let (_, buffer) = <(String, Buffer)>::pop(lstate)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl Poppable for OnEndArgs {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        let (_, tick) = <(String, u32)>::pop(lstate)?;
+        Ok(Self { tick })
+    }
+}
+
+impl Poppable for OnLineArgs {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        let (_, window, buffer, row) =
+            <(String, Window, Buffer, usize)>::pop(lstate)?;
+        Ok(Self { window, buffer, row })
+    }
+}
+
+impl Poppable for OnStartArgs {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        // The third argument is the undocumented redraw `type`, see
+        // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/decoration_provider.c#L68
+        let (_, tick, _) = <(String, u32, u32)>::pop(lstate)?;
+        Ok(Self { tick })
+    }
+}
+
+impl Poppable for OnWinArgs {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        let (_, window, buffer, toprow, botrow) =
+            <(String, Window, Buffer, usize, usize)>::pop(lstate)?;
+        Ok(Self { window, buffer, toprow, botrow })
+    }
+}
 
 /// The `on_start` callback can return `false` to disable the provider until
 /// the next redraw.
@@ -95,48 +167,61 @@ impl DecorationProviderOpts {
 pub struct DecorationProviderOptsBuilder(DecorationProviderOpts);
 
 impl DecorationProviderOptsBuilder {
+    /// Callback invoked once per buffer about to be redrawn during a redraw
+    /// cycle, before any of its windows.
     #[inline]
     pub fn on_buf<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnBufArgs, ()>,
     {
-        self.0.on_buf = fun.to_object();
+        self.0.on_buf = fun.to_decoration_callback(DecorationCallback::Buf);
         self
     }
 
+    /// Callback invoked at the end of a redraw cycle.
     #[inline]
     pub fn on_end<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnEndArgs, ()>,
     {
-        self.0.on_end = fun.to_object();
+        self.0.on_end = fun.to_decoration_callback(DecorationCallback::End);
         self
     }
 
+    /// Callback invoked for every buffer row about to be redrawn, unless
+    /// [`on_win`](DecorationProviderOptsBuilder::on_win) returned `false`
+    /// for its window.
     #[inline]
     pub fn on_line<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnLineArgs, ()>,
     {
-        self.0.on_line = fun.to_object();
+        self.0.on_line = fun.to_decoration_callback(DecorationCallback::Line);
         self
     }
 
+    /// Callback invoked at the start of every redraw cycle, even if nothing
+    /// is going to be redrawn. Returning `false` disables the provider until
+    /// the next redraw cycle.
     #[inline]
     pub fn on_start<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnStartArgs, DontSkipRedrawCycle>,
     {
-        self.0.on_start = fun.to_object();
+        self.0.on_start =
+            fun.to_decoration_callback(DecorationCallback::Start);
         self
     }
 
+    /// Callback invoked for every window about to be redrawn. Returning
+    /// `false` skips the [`on_line`](DecorationProviderOptsBuilder::on_line)
+    /// callbacks for that window.
     #[inline]
     pub fn on_win<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnWinArgs, DontSkipOnLines>,
     {
-        self.0.on_win = fun.to_object();
+        self.0.on_win = fun.to_decoration_callback(DecorationCallback::Win);
         self
     }
 
@@ -0,0 +1,32 @@
+use oxi_types::Object;
+
+/// Options passed to [`exec2()`](crate::exec2).
+#[derive(Clone, Debug, Default)]
+#[repr(C)]
+pub struct ExecOpts {
+    output: Object,
+}
+
+impl ExecOpts {
+    #[inline(always)]
+    pub fn builder() -> ExecOptsBuilder {
+        ExecOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ExecOptsBuilder(ExecOpts);
+
+impl ExecOptsBuilder {
+    /// Whether to capture and return all the output, e.g. from `:echo`.
+    #[inline]
+    pub fn output(&mut self, output: bool) -> &mut Self {
+        self.0.output = output.into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> ExecOpts {
+        std::mem::take(&mut self.0)
+    }
+}
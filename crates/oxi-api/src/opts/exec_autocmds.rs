@@ -5,7 +5,7 @@ use crate::{StringOrInt, StringOrListOfStrings};
 
 /// Options passed to [`exec_autocmds()`](crate::exec_autocmds).
 #[cfg(not(feature = "neovim-nightly"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub struct ExecAutocmdsOpts {
     data: Object,
@@ -17,7 +17,7 @@ pub struct ExecAutocmdsOpts {
 
 /// Options passed to [`exec_autocmds()`](crate::exec_autocmds).
 #[cfg(feature = "neovim-nightly")]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub struct ExecAutocmdsOpts {
     buffer: Object,
@@ -28,12 +28,29 @@ pub struct ExecAutocmdsOpts {
 }
 
 impl ExecAutocmdsOpts {
+    /// Options with every field unset, i.e. those used by Neovim when
+    /// none are given.
+    pub const DEFAULT: Self = Self {
+        data: Object::nil(),
+        group: Object::nil(),
+        buffer: Object::nil(),
+        patterns: Object::nil(),
+        modeline: Object::nil(),
+    };
+
     #[inline(always)]
     pub fn builder() -> ExecAutocmdsOptsBuilder {
         ExecAutocmdsOptsBuilder::default()
     }
 }
 
+impl Default for ExecAutocmdsOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct ExecAutocmdsOptsBuilder(ExecAutocmdsOpts);
 
@@ -42,7 +59,7 @@ impl ExecAutocmdsOptsBuilder {
     /// together with [`patterns`](ExecAutocmdsOptsBuilder::patterns).
     #[inline]
     pub fn buffer(&mut self, buffer: Buffer) -> &mut Self {
-        self.0.data = buffer.into();
+        self.0.buffer = buffer.into();
         self
     }
 
@@ -1,6 +1,7 @@
 use oxi_types::{Array, Object};
 
 use crate::trait_utils::StringOrInt;
+use crate::types::AutocmdEvent;
 use crate::Buffer;
 
 /// Options passed to [`get_autocmds()`](crate::get_autocmds).
@@ -47,9 +48,9 @@ impl GetAutocmdsOptsBuilder {
     /// Get all the autocommands triggered by one or more of the specified
     /// events.
     #[inline]
-    pub fn events<'a, I>(&mut self, events: I) -> &mut Self
+    pub fn events<I>(&mut self, events: I) -> &mut Self
     where
-        I: IntoIterator<Item = &'a str>,
+        I: IntoIterator<Item = AutocmdEvent>,
     {
         self.0.events = Array::from_iter(events).into();
         self
@@ -58,11 +59,11 @@ impl GetAutocmdsOptsBuilder {
     /// Only get the autocommands belonging to a specific augroup. The
     /// augroup can be specified by both id and name.
     #[inline]
-    pub fn group<Group>(&mut self, group: impl Into<Object>) -> &mut Self
+    pub fn group<Group>(&mut self, group: Group) -> &mut Self
     where
         Group: StringOrInt,
     {
-        self.0.group = group.into();
+        self.0.group = group.to_object();
         self
     }
 
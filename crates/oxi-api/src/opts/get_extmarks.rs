@@ -1,14 +1,28 @@
-use oxi_types::{Dictionary, Object};
+use oxi_types::{self as nvim, Dictionary, Object};
+
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+use crate::types::ExtmarkType;
 
 /// Options passed to
 /// [`Buffer::get_extmarks()`](crate::Buffer::get_extmarks).
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct GetExtmarksOpts {
     details: Object,
-    limits: Object,
+    limit: Object,
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    ty: Object,
 }
 
 impl GetExtmarksOpts {
+    /// Options with every field unset, which returns all the extmarks in
+    /// the range without their details.
+    pub const DEFAULT: Self = Self {
+        details: Object::nil(),
+        limit: Object::nil(),
+        #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+        ty: Object::nil(),
+    };
+
     #[inline(always)]
     /// Creates a new [`GetExtmarksOptsBuilder`].
     pub fn builder() -> GetExtmarksOptsBuilder {
@@ -16,6 +30,13 @@ impl GetExtmarksOpts {
     }
 }
 
+impl Default for GetExtmarksOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct GetExtmarksOptsBuilder(GetExtmarksOpts);
 
@@ -30,13 +51,25 @@ impl GetExtmarksOptsBuilder {
         self
     }
 
+    /// Maximum number of extmarks to return.
     #[inline]
-    pub fn limits(&mut self, limits: bool) -> &mut Self {
-        self.0.limits = limits.into();
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.0.limit = (limit as nvim::Integer).into();
+        self
+    }
+
+    /// Only return the extmarks with the given kind of decoration.
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
+    )]
+    #[inline]
+    pub fn ty(&mut self, ty: ExtmarkType) -> &mut Self {
+        self.0.ty = nvim::String::from(ty).into();
         self
     }
 
-    /// Maximum number of extmarks to return.
     #[inline]
     pub fn build(&mut self) -> GetExtmarksOpts {
         std::mem::take(&mut self.0)
@@ -47,7 +80,9 @@ impl From<&GetExtmarksOpts> for Dictionary {
     fn from(opts: &GetExtmarksOpts) -> Self {
         Self::from_iter([
             ("details", opts.details.clone()),
-            ("limits", opts.limits.clone()),
+            ("limit", opts.limit.clone()),
+            #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+            ("type", opts.ty.clone()),
         ])
     }
 }
@@ -0,0 +1,67 @@
+use oxi_types::{self as nvim, Integer, Object};
+
+/// Options passed to [`get_hl()`](crate::get_hl).
+#[cfg(not(feature = "neovim-nightly"))]
+#[derive(Clone, Debug, Default)]
+#[repr(C)]
+pub struct GetHighlightOpts {
+    id: Object,
+    link: Object,
+    name: Object,
+}
+
+/// Options passed to [`get_hl()`](crate::get_hl).
+#[cfg(feature = "neovim-nightly")]
+#[derive(Clone, Debug, Default)]
+#[repr(C)]
+pub struct GetHighlightOpts {
+    id: Object,
+    name: Object,
+    link: Object,
+}
+
+impl GetHighlightOpts {
+    #[inline(always)]
+    pub fn builder() -> GetHighlightOptsBuilder {
+        GetHighlightOptsBuilder::default()
+    }
+
+    /// Whether the options select a single highlight group.
+    #[inline]
+    pub(crate) fn is_single(&self) -> bool {
+        !(self.id.is_nil() && self.name.is_nil())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct GetHighlightOptsBuilder(GetHighlightOpts);
+
+impl GetHighlightOptsBuilder {
+    /// Only get the definition of the highlight group with the given id.
+    #[inline]
+    pub fn id(&mut self, id: u32) -> &mut Self {
+        self.0.id = (id as Integer).into();
+        self
+    }
+
+    /// Whether to return the name of the group a highlight group is linked
+    /// to instead of resolving the link into the definition of the linked
+    /// group. Defaults to `true`.
+    #[inline]
+    pub fn link(&mut self, link: bool) -> &mut Self {
+        self.0.link = link.into();
+        self
+    }
+
+    /// Only get the definition of the highlight group with the given name.
+    #[inline]
+    pub fn name(&mut self, name: impl Into<nvim::String>) -> &mut Self {
+        self.0.name = name.into().into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> GetHighlightOpts {
+        std::mem::take(&mut self.0)
+    }
+}
@@ -10,22 +10,35 @@ mod create_autocmd;
 mod create_command;
 mod decoration_provider;
 mod eval_statusline;
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+mod exec;
 mod exec_autocmds;
 mod get_autocmds;
 mod get_commands;
 mod get_context;
 mod get_extmark_by_id;
 mod get_extmarks;
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+mod get_highlight;
 mod get_mark;
 mod get_option_value;
 mod get_text;
 mod notify;
+mod open_file;
 mod open_term;
 mod parse_cmd;
+#[cfg(feature = "neovim-nightly")]
+mod redraw;
+mod save_all;
 mod select_popup_menu_item;
 mod set_extmark;
 mod set_highlight;
 mod set_keymap;
+mod set_lines_chunked;
+mod set_mark;
+mod split;
+#[cfg(feature = "neovim-nightly")]
+mod win_text_height;
 
 pub use buf_attach::*;
 pub use buf_delete::*;
@@ -36,19 +49,32 @@ pub use create_autocmd::*;
 pub use create_command::*;
 pub use decoration_provider::*;
 pub use eval_statusline::*;
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+pub use exec::*;
 pub use exec_autocmds::*;
 pub use get_autocmds::*;
 pub use get_commands::*;
 pub use get_context::*;
 pub use get_extmark_by_id::*;
 pub use get_extmarks::*;
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+pub use get_highlight::*;
 pub use get_mark::*;
 pub use get_option_value::*;
 pub use get_text::*;
 pub use notify::*;
+pub use open_file::*;
 pub use open_term::*;
 pub use parse_cmd::*;
+#[cfg(feature = "neovim-nightly")]
+pub use redraw::*;
+pub use save_all::*;
 pub use select_popup_menu_item::*;
 pub use set_extmark::*;
 pub use set_highlight::*;
 pub use set_keymap::*;
+pub use set_lines_chunked::*;
+pub use set_mark::*;
+pub use split::*;
+#[cfg(feature = "neovim-nightly")]
+pub use win_text_height::*;
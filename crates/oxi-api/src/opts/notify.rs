@@ -1,8 +1,22 @@
-use oxi_types::Dictionary;
+use std::time::Duration;
 
-/// Options passed to [`notify()`](crate::notify). Currently unused.
+use oxi_types::{Dictionary, Object};
+
+/// Options passed to [`notify()`](crate::notify).
+///
+/// The builtin implementation of [`vim.notify()`][1] ignores all of them, but
+/// they're understood by the notification plugins replacing it, like
+/// [nvim-notify][2].
+///
+/// [1]: https://neovim.io/doc/user/lua.html#vim.notify()
+/// [2]: https://github.com/rcarriga/nvim-notify
 #[derive(Clone, Debug, Default)]
-pub struct NotifyOpts {}
+pub struct NotifyOpts {
+    icon: Object,
+    replace: Object,
+    timeout: Object,
+    title: Object,
+}
 
 impl NotifyOpts {
     #[inline(always)]
@@ -15,6 +29,36 @@ impl NotifyOpts {
 pub struct NotifyOptsBuilder(NotifyOpts);
 
 impl NotifyOptsBuilder {
+    /// Icon displayed next to the title of the notification.
+    #[inline]
+    pub fn icon(&mut self, icon: &str) -> &mut Self {
+        self.0.icon = icon.into();
+        self
+    }
+
+    /// The notification to replace, as returned by a previous call to
+    /// [`notify()`](crate::notify). This is what allows to update a
+    /// progress-style notification in place instead of stacking a new one.
+    #[inline]
+    pub fn replace(&mut self, notification: impl Into<Object>) -> &mut Self {
+        self.0.replace = notification.into();
+        self
+    }
+
+    /// How long the notification is displayed for.
+    #[inline]
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.0.timeout = (timeout.as_millis() as i64).into();
+        self
+    }
+
+    /// Title of the notification.
+    #[inline]
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        self.0.title = title.into();
+        self
+    }
+
     #[inline]
     pub fn build(&mut self) -> NotifyOpts {
         std::mem::take(&mut self.0)
@@ -22,7 +66,17 @@ impl NotifyOptsBuilder {
 }
 
 impl From<&NotifyOpts> for Dictionary {
-    fn from(_: &NotifyOpts) -> Self {
-        Dictionary::new()
+    fn from(opts: &NotifyOpts) -> Self {
+        Self::from_iter(
+            [
+                ("icon", &opts.icon),
+                ("replace", &opts.replace),
+                ("timeout", &opts.timeout),
+                ("title", &opts.title),
+            ]
+            .into_iter()
+            .filter(|(_, value)| !value.is_nil())
+            .map(|(key, value)| (key, value.clone())),
+        )
     }
 }
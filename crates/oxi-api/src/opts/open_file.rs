@@ -0,0 +1,69 @@
+use crate::types::SwapExistsAction;
+
+/// Options passed to [`open_file()`](crate::open_file).
+#[derive(Clone, Debug)]
+pub struct OpenFileOpts {
+    pub(crate) cursor: Option<(usize, usize)>,
+    pub(crate) reuse_window: bool,
+    pub(crate) on_swap_exists: SwapExistsAction,
+}
+
+impl OpenFileOpts {
+    /// The default options, usable in `const` contexts.
+    pub const DEFAULT: Self = Self {
+        cursor: None,
+        reuse_window: false,
+        on_swap_exists: SwapExistsAction::Abort,
+    };
+
+    #[inline(always)]
+    pub fn builder() -> OpenFileOptsBuilder {
+        OpenFileOptsBuilder::default()
+    }
+}
+
+impl Default for OpenFileOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct OpenFileOptsBuilder(OpenFileOpts);
+
+impl OpenFileOptsBuilder {
+    /// The (1,0)-indexed position to move the cursor to once the file is
+    /// opened. Ignored with [`OpenMode::BAdd`](crate::types::OpenMode::BAdd),
+    /// which doesn't display the file.
+    #[inline]
+    pub fn cursor(&mut self, line: usize, col: usize) -> &mut Self {
+        self.0.cursor = Some((line, col));
+        self
+    }
+
+    /// Whether to jump to a window of the current tabpage already showing
+    /// the file instead of opening it in the current window. Defaults to
+    /// `false`, but is always the case with
+    /// [`OpenMode::Drop`](crate::types::OpenMode::Drop).
+    #[inline]
+    pub fn reuse_window(&mut self, reuse_window: bool) -> &mut Self {
+        self.0.reuse_window = reuse_window;
+        self
+    }
+
+    /// What to do if the file has an existing swap file. Defaults to
+    /// [`SwapExistsAction::Abort`]. Ignored with
+    /// [`OpenMode::BAdd`](crate::types::OpenMode::BAdd), which never checks
+    /// for swap files.
+    #[inline]
+    pub fn on_swap_exists(&mut self, action: SwapExistsAction) -> &mut Self {
+        self.0.on_swap_exists = action;
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> OpenFileOpts {
+        std::mem::take(&mut self.0)
+    }
+}
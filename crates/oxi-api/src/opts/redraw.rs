@@ -0,0 +1,133 @@
+use oxi_types::{Array, Dictionary, Integer, Object};
+
+use crate::{Buffer, Window};
+
+/// Options passed to [`redraw()`](crate::redraw).
+#[derive(Clone, Debug, Default)]
+pub struct RedrawOpts {
+    buf: Object,
+    cursor: Object,
+    flush: Object,
+    range: Object,
+    statuscolumn: Object,
+    statusline: Object,
+    tabline: Object,
+    valid: Object,
+    win: Object,
+    winbar: Object,
+}
+
+impl RedrawOpts {
+    #[inline(always)]
+    pub fn builder() -> RedrawOptsBuilder {
+        RedrawOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct RedrawOptsBuilder(RedrawOpts);
+
+impl RedrawOptsBuilder {
+    /// Only redraw the windows displaying the given buffer.
+    #[inline]
+    pub fn buf(&mut self, buf: &Buffer) -> &mut Self {
+        self.0.buf = buf.into();
+        self
+    }
+
+    /// Whether to move the cursor to its position in the current window, or
+    /// in the window given by [`win`](RedrawOptsBuilder::win).
+    #[inline]
+    pub fn cursor(&mut self, cursor: bool) -> &mut Self {
+        self.0.cursor = cursor.into();
+        self
+    }
+
+    /// Whether to update the screen with the pending updates.
+    #[inline]
+    pub fn flush(&mut self, flush: bool) -> &mut Self {
+        self.0.flush = flush.into();
+        self
+    }
+
+    /// Redraw the lines from `start` (inclusive) to `end` (exclusive), both
+    /// 0-indexed, in the windows displaying the buffer given by
+    /// [`buf`](RedrawOptsBuilder::buf).
+    #[inline]
+    pub fn range(&mut self, start: usize, end: usize) -> &mut Self {
+        self.0.range =
+            Array::from_iter([start as Integer, end as Integer]).into();
+        self
+    }
+
+    /// Whether to redraw the `'statuscolumn'`.
+    #[inline]
+    pub fn statuscolumn(&mut self, statuscolumn: bool) -> &mut Self {
+        self.0.statuscolumn = statuscolumn.into();
+        self
+    }
+
+    /// Whether to redraw the `'statusline'`.
+    #[inline]
+    pub fn statusline(&mut self, statusline: bool) -> &mut Self {
+        self.0.statusline = statusline.into();
+        self
+    }
+
+    /// Whether to redraw the `'tabline'`.
+    #[inline]
+    pub fn tabline(&mut self, tabline: bool) -> &mut Self {
+        self.0.tabline = tabline.into();
+        self
+    }
+
+    /// When `true` the windows are redrawn without being marked as invalid
+    /// first, i.e. only the parts of the screen that changed are updated.
+    /// When `false` they're redrawn from scratch.
+    #[inline]
+    pub fn valid(&mut self, valid: bool) -> &mut Self {
+        self.0.valid = valid.into();
+        self
+    }
+
+    /// Only redraw the given window.
+    #[inline]
+    pub fn win(&mut self, win: &Window) -> &mut Self {
+        self.0.win = win.into();
+        self
+    }
+
+    /// Whether to redraw the `'winbar'`.
+    #[inline]
+    pub fn winbar(&mut self, winbar: bool) -> &mut Self {
+        self.0.winbar = winbar.into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> RedrawOpts {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<&RedrawOpts> for Dictionary {
+    fn from(opts: &RedrawOpts) -> Self {
+        Self::from_iter(
+            [
+                ("buf", &opts.buf),
+                ("cursor", &opts.cursor),
+                ("flush", &opts.flush),
+                ("range", &opts.range),
+                ("statuscolumn", &opts.statuscolumn),
+                ("statusline", &opts.statusline),
+                ("tabline", &opts.tabline),
+                ("valid", &opts.valid),
+                ("win", &opts.win),
+                ("winbar", &opts.winbar),
+            ]
+            .into_iter()
+            .filter(|(_, value)| !value.is_nil())
+            .map(|(key, value)| (key, value.clone())),
+        )
+    }
+}
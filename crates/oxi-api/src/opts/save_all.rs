@@ -0,0 +1,30 @@
+/// Options passed to [`save_all()`](crate::save_all).
+#[derive(Clone, Debug, Default)]
+pub struct SaveAllOpts {
+    pub(crate) only_named: bool,
+}
+
+impl SaveAllOpts {
+    #[inline(always)]
+    pub fn builder() -> SaveAllOptsBuilder {
+        SaveAllOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct SaveAllOptsBuilder(SaveAllOpts);
+
+impl SaveAllOptsBuilder {
+    /// Whether to skip the buffers without a name instead of reporting them
+    /// as failures, like `:wall` does. Defaults to `false`.
+    #[inline]
+    pub fn only_named(&mut self, only_named: bool) -> &mut Self {
+        self.0.only_named = only_named;
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> SaveAllOpts {
+        std::mem::take(&mut self.0)
+    }
+}
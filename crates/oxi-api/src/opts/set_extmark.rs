@@ -1,11 +1,22 @@
-use oxi_types::{self as nvim, Array, Integer, Object};
+use std::mem::ManuallyDrop;
+use std::ptr;
 
-use crate::trait_utils::StringOrListOfStrings;
+use oxi_types::{
+    self as nvim,
+    Array,
+    Dictionary,
+    Integer,
+    NonOwning,
+    Object,
+};
+
+use crate::trait_utils::{StringOrInt, StringOrListOfStrings};
 use crate::types::{ExtmarkHlMode, ExtmarkVirtTextPosition};
+use crate::{Error, Result};
 
 /// Options passed to [`Buffer::set_extmark()`](crate::Buffer::set_extmark).
 #[cfg(not(feature = "neovim-nightly"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub struct SetExtmarkOpts {
     id: Object,
@@ -40,7 +51,7 @@ pub struct SetExtmarkOpts {
 
 /// Options passed to [`set_extmark()`](crate::set_extmark).
 #[cfg(feature = "neovim-nightly")]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub struct SetExtmarkOpts {
     id: Object,
@@ -71,16 +82,135 @@ pub struct SetExtmarkOpts {
     conceal: Object,
     spell: Object,
     ui_watched: Object,
+    url: Object,
 }
 
 #[derive(Clone, Default)]
 pub struct SetExtmarkOptsBuilder(SetExtmarkOpts);
 
 impl SetExtmarkOpts {
+    /// Options with every field unset, usable in `const` contexts.
+    pub const DEFAULT: Self = Self {
+        id: Object::nil(),
+        end_line: Object::nil(),
+        end_row: Object::nil(),
+        end_col: Object::nil(),
+        hl_group: Object::nil(),
+        virt_text: Object::nil(),
+        virt_text_pos: Object::nil(),
+        virt_text_win_col: Object::nil(),
+        virt_text_hide: Object::nil(),
+        hl_eol: Object::nil(),
+        hl_mode: Object::nil(),
+        ephemeral: Object::nil(),
+        priority: Object::nil(),
+        right_gravity: Object::nil(),
+        end_right_gravity: Object::nil(),
+        virt_lines: Object::nil(),
+        virt_lines_above: Object::nil(),
+        virt_lines_leftcol: Object::nil(),
+        strict: Object::nil(),
+        sign_text: Object::nil(),
+        sign_hl_group: Object::nil(),
+        number_hl_group: Object::nil(),
+        line_hl_group: Object::nil(),
+        cursorline_hl_group: Object::nil(),
+        conceal: Object::nil(),
+        spell: Object::nil(),
+        ui_watched: Object::nil(),
+        #[cfg(feature = "neovim-nightly")]
+        url: Object::nil(),
+    };
+
     #[inline(always)]
     pub fn builder() -> SetExtmarkOptsBuilder {
         SetExtmarkOptsBuilder::default()
     }
+
+    /// Options highlighting the text from the extmark's position up to the
+    /// (0, 0)-indexed `(line, col)` position `end` with `hl_group`.
+    ///
+    /// This is a shorthand for setting
+    /// [`hl_group`](SetExtmarkOptsBuilder::hl_group),
+    /// [`end_row`](SetExtmarkOptsBuilder::end_row) and
+    /// [`end_col`](SetExtmarkOptsBuilder::end_col).
+    #[inline]
+    pub fn hl<Hl: StringOrInt>(hl_group: Hl, end: (usize, usize)) -> Self {
+        let (end_row, end_col) = end;
+        Self::builder()
+            .hl_group(hl_group)
+            .end_row(end_row)
+            .end_col(end_col)
+            .build()
+    }
+
+    /// Reads the options from a dictionary with the same keys accepted by
+    /// [`nvim_buf_set_extmark()`][1], without copying its values.
+    ///
+    /// This is meant to be used with the dictionaries built by an
+    /// [`ObjectArena`](oxi_types::ObjectArena), to set lots of extmarks
+    /// without allocating new options every time. The returned options
+    /// borrow the values of the dictionary.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_extmark()
+    pub fn from_dict(
+        dict: NonOwning<'_, Dictionary>,
+    ) -> Result<NonOwning<'_, Self>> {
+        // The values are still owned by the dictionary, so the options must
+        // never drop them.
+        let mut opts = ManuallyDrop::new(Self::DEFAULT);
+
+        for (key, value) in dict.iter() {
+            let field = match key.as_bytes() {
+                b"id" => &mut opts.id,
+                b"end_line" => &mut opts.end_line,
+                b"end_row" => &mut opts.end_row,
+                b"end_col" => &mut opts.end_col,
+                b"hl_group" => &mut opts.hl_group,
+                b"virt_text" => &mut opts.virt_text,
+                b"virt_text_pos" => &mut opts.virt_text_pos,
+                b"virt_text_win_col" => &mut opts.virt_text_win_col,
+                b"virt_text_hide" => &mut opts.virt_text_hide,
+                b"hl_eol" => &mut opts.hl_eol,
+                b"hl_mode" => &mut opts.hl_mode,
+                b"ephemeral" => &mut opts.ephemeral,
+                b"priority" => &mut opts.priority,
+                b"right_gravity" => &mut opts.right_gravity,
+                b"end_right_gravity" => &mut opts.end_right_gravity,
+                b"virt_lines" => &mut opts.virt_lines,
+                b"virt_lines_above" => &mut opts.virt_lines_above,
+                b"virt_lines_leftcol" => &mut opts.virt_lines_leftcol,
+                b"strict" => &mut opts.strict,
+                b"sign_text" => &mut opts.sign_text,
+                b"sign_hl_group" => &mut opts.sign_hl_group,
+                b"number_hl_group" => &mut opts.number_hl_group,
+                b"line_hl_group" => &mut opts.line_hl_group,
+                b"cursorline_hl_group" => &mut opts.cursorline_hl_group,
+                b"conceal" => &mut opts.conceal,
+                b"spell" => &mut opts.spell,
+                b"ui_watched" => &mut opts.ui_watched,
+                #[cfg(feature = "neovim-nightly")]
+                b"url" => &mut opts.url,
+                _ => {
+                    return Err(Error::custom(format_args!(
+                        "Invalid key: '{}'",
+                        key.to_string_lossy()
+                    )))
+                },
+            };
+
+            unsafe { ptr::write(field, ptr::read(value)) };
+        }
+
+        Ok(NonOwning::new(ManuallyDrop::into_inner(opts)))
+    }
+}
+
+impl Default for SetExtmarkOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
 }
 
 impl SetExtmarkOptsBuilder {
@@ -89,6 +219,9 @@ impl SetExtmarkOptsBuilder {
     ///
     /// [`hl_group`](SetExtmarkOptsBuilder::hl_group) is used to highlight the
     /// character if provided, otherwise it defaults to `hl-Conceal`.
+    ///
+    /// The text is only concealed in windows whose conceal level is set, see
+    /// [`Window::set_conceal`](crate::Window::set_conceal).
     #[inline]
     pub fn conceal(&mut self, conceal: Option<char>) -> &mut Self {
         let ch = conceal.map(nvim::String::from).unwrap_or_default();
@@ -96,15 +229,17 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
-    /// Name of the highlight group used to highlight the line when the cursor
-    /// is on the same line as the mark and `cursorline` is enabled.
+    /// Name or id of the highlight group used to highlight the line when the
+    /// cursor is on the same line as the mark and `cursorline` is enabled.
     #[inline]
-    pub fn cursorline_hl_group(
+    pub fn cursorline_hl_group<Hl>(
         &mut self,
-        cursorline_hl_group: &str,
-    ) -> &mut Self {
-        self.0.cursorline_hl_group =
-            nvim::String::from(cursorline_hl_group).into();
+        cursorline_hl_group: Hl,
+    ) -> &mut Self
+    where
+        Hl: StringOrInt,
+    {
+        self.0.cursorline_hl_group = cursorline_hl_group.to_object();
         self
     }
 
@@ -149,10 +284,10 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
-    /// Name of the highlight group used to highlight this mark.
+    /// Name or id of the highlight group used to highlight this mark.
     #[inline]
-    pub fn hl_group(&mut self, hl_group: &str) -> &mut Self {
-        self.0.hl_group = nvim::String::from(hl_group).into();
+    pub fn hl_group<Hl: StringOrInt>(&mut self, hl_group: Hl) -> &mut Self {
+        self.0.hl_group = hl_group.to_object();
         self
     }
 
@@ -170,17 +305,23 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
-    /// Name of the highlight group used to highlight the whole line.
+    /// Name or id of the highlight group used to highlight the whole line.
     #[inline]
-    pub fn line_hl_group(&mut self, line_hl_group: &str) -> &mut Self {
-        self.0.line_hl_group = nvim::String::from(line_hl_group).into();
+    pub fn line_hl_group<Hl: StringOrInt>(
+        &mut self,
+        line_hl_group: Hl,
+    ) -> &mut Self {
+        self.0.line_hl_group = line_hl_group.to_object();
         self
     }
 
-    /// Name of the highlight group used to highlight the number column.
+    /// Name or id of the highlight group used to highlight the number column.
     #[inline]
-    pub fn number_hl_group(&mut self, number_hl_group: &str) -> &mut Self {
-        self.0.number_hl_group = nvim::String::from(number_hl_group).into();
+    pub fn number_hl_group<Hl: StringOrInt>(
+        &mut self,
+        number_hl_group: Hl,
+    ) -> &mut Self {
+        self.0.number_hl_group = number_hl_group.to_object();
         self
     }
 
@@ -200,10 +341,14 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
-    /// Name of the highlight group used to highlight the sign column text.
+    /// Name or id of the highlight group used to highlight the sign column
+    /// text.
     #[inline]
-    pub fn sign_hl_group(&mut self, sign_hl_group: &str) -> &mut Self {
-        self.0.sign_hl_group = nvim::String::from(sign_hl_group).into();
+    pub fn sign_hl_group<Hl: StringOrInt>(
+        &mut self,
+        sign_hl_group: Hl,
+    ) -> &mut Self {
+        self.0.sign_hl_group = sign_hl_group.to_object();
         self
     }
 
@@ -231,6 +376,16 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
+    /// A URL the text of the mark links to. It's turned into a hyperlink by
+    /// the UIs supporting them, e.g. terminals supporting OSC 8.
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    #[inline]
+    pub fn url(&mut self, url: &str) -> &mut Self {
+        self.0.url = nvim::String::from(url).into();
+        self
+    }
+
     /// Virtual lines to add next to the mark.
     #[inline]
     pub fn virt_lines<Txt, Hl, Cnk, ChunkyCnk>(
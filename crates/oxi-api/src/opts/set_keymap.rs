@@ -5,7 +5,7 @@ use crate::ToFunction;
 /// Options passed to [`Buffer::set_keymap()`](crate::Buffer::set_keymap)
 /// and [`set_keymap()`](crate::set_keymap).
 #[cfg(not(feature = "neovim-nightly"))]
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[repr(C)]
 pub struct SetKeymapOpts {
     desc: Object,
@@ -22,7 +22,7 @@ pub struct SetKeymapOpts {
 /// Options passed to [`Buffer::set_keymap()`](crate::Buffer::set_keymap)
 /// and [`set_keymap()`](crate::set_keymap).
 #[cfg(feature = "neovim-nightly")]
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[repr(C)]
 pub struct SetKeymapOpts {
     noremap: Object,
@@ -37,6 +37,20 @@ pub struct SetKeymapOpts {
 }
 
 impl SetKeymapOpts {
+    /// Options with every field unset, i.e. a recursive mapping with no
+    /// description.
+    pub const DEFAULT: Self = Self {
+        desc: Object::nil(),
+        expr: Object::nil(),
+        script: Object::nil(),
+        silent: Object::nil(),
+        unique: Object::nil(),
+        nowait: Object::nil(),
+        noremap: Object::nil(),
+        callback: Object::nil(),
+        replace_keycodes: Object::nil(),
+    };
+
     #[inline(always)]
     /// Creates a new [`SetKeymapOptsBuilder`].
     pub fn builder() -> SetKeymapOptsBuilder {
@@ -44,6 +58,13 @@ impl SetKeymapOpts {
     }
 }
 
+impl Default for SetKeymapOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct SetKeymapOptsBuilder(SetKeymapOpts);
 
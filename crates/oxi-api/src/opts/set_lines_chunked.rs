@@ -0,0 +1,49 @@
+/// Options passed to
+/// [`Buffer::set_lines_chunked()`](crate::Buffer::set_lines_chunked) and
+/// [`Buffer::set_lines_from_reader()`](crate::Buffer::set_lines_from_reader).
+#[derive(Clone, Debug)]
+pub struct SetLinesChunkedOpts {
+    pub(crate) chunk_size: usize,
+    pub(crate) scroll: bool,
+}
+
+impl Default for SetLinesChunkedOpts {
+    #[inline]
+    fn default() -> Self {
+        Self { chunk_size: 1000, scroll: false }
+    }
+}
+
+impl SetLinesChunkedOpts {
+    #[inline(always)]
+    pub fn builder() -> SetLinesChunkedOptsBuilder {
+        SetLinesChunkedOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct SetLinesChunkedOptsBuilder(SetLinesChunkedOpts);
+
+impl SetLinesChunkedOptsBuilder {
+    /// The maximum number of lines written to the buffer at once. Defaults
+    /// to 1000.
+    #[inline]
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        self.0.chunk_size = chunk_size;
+        self
+    }
+
+    /// Whether to move the cursor of every window displaying the buffer to
+    /// the last written line after each chunk, which keeps the output in
+    /// view while it's being written. Defaults to `false`.
+    #[inline]
+    pub fn scroll(&mut self, scroll: bool) -> &mut Self {
+        self.0.scroll = scroll;
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> SetLinesChunkedOpts {
+        std::mem::take(&mut self.0)
+    }
+}
@@ -0,0 +1,29 @@
+use oxi_types::Dictionary;
+
+/// Options passed to [`Buffer::set_mark()`](crate::Buffer::set_mark) and
+/// [`set_mark()`](crate::set_mark). Currently unused.
+#[derive(Clone, Debug, Default)]
+pub struct SetMarkOpts {}
+
+impl SetMarkOpts {
+    #[inline]
+    pub fn builder() -> SetMarkOptsBuilder {
+        SetMarkOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct SetMarkOptsBuilder(SetMarkOpts);
+
+impl SetMarkOptsBuilder {
+    #[inline]
+    pub fn build(&mut self) -> SetMarkOpts {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<&SetMarkOpts> for Dictionary {
+    fn from(_: &SetMarkOpts) -> Self {
+        Dictionary::new()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::types::SplitModifier;
+use crate::Buffer;
+
+/// Options passed to [`split()`](crate::split).
+#[derive(Clone, Debug)]
+pub struct SplitOpts {
+    pub(crate) buffer: Option<Buffer>,
+    pub(crate) direction: Option<SplitModifier>,
+    pub(crate) enter: bool,
+    pub(crate) size: Option<u32>,
+    pub(crate) vertical: bool,
+}
+
+impl Default for SplitOpts {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            buffer: None,
+            direction: None,
+            enter: true,
+            size: None,
+            vertical: false,
+        }
+    }
+}
+
+impl SplitOpts {
+    #[inline(always)]
+    pub fn builder() -> SplitOptsBuilder {
+        SplitOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct SplitOptsBuilder(SplitOpts);
+
+impl SplitOptsBuilder {
+    /// The buffer to show in the new window. Defaults to the current buffer,
+    /// like `:split` does.
+    #[inline]
+    pub fn buffer(&mut self, buffer: Buffer) -> &mut Self {
+        self.0.buffer = Some(buffer);
+        self
+    }
+
+    /// Where to open the new window, e.g. [`SplitModifier::BelowRight`] for
+    /// `:belowright split`. Defaults to the position given by the
+    /// `'splitbelow'` and `'splitright'` options.
+    #[inline]
+    pub fn direction(&mut self, direction: SplitModifier) -> &mut Self {
+        self.0.direction = Some(direction);
+        self
+    }
+
+    /// Whether to make the new window the current window. Defaults to
+    /// `true`.
+    #[inline]
+    pub fn enter(&mut self, enter: bool) -> &mut Self {
+        self.0.enter = enter;
+        self
+    }
+
+    /// The height of the new window, or its width if the split is
+    /// [`vertical`](SplitOptsBuilder::vertical). Defaults to half of the
+    /// space of the split window.
+    #[inline]
+    pub fn size(&mut self, size: u32) -> &mut Self {
+        self.0.size = Some(size);
+        self
+    }
+
+    /// Whether to split the window vertically, like `:vsplit`. Defaults to
+    /// `false`.
+    #[inline]
+    pub fn vertical(&mut self, vertical: bool) -> &mut Self {
+        self.0.vertical = vertical;
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> SplitOpts {
+        std::mem::take(&mut self.0)
+    }
+}
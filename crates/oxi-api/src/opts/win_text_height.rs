@@ -0,0 +1,61 @@
+use oxi_types::{Integer, Object};
+
+/// Options passed to [`Window::text_height()`](crate::Window::text_height).
+#[derive(Clone, Debug, Default)]
+#[repr(C)]
+pub struct WinTextHeightOpts {
+    start_row: Object,
+    end_row: Object,
+    start_vcol: Object,
+    end_vcol: Object,
+}
+
+impl WinTextHeightOpts {
+    #[inline(always)]
+    pub fn builder() -> WinTextHeightOptsBuilder {
+        WinTextHeightOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct WinTextHeightOptsBuilder(WinTextHeightOpts);
+
+impl WinTextHeightOptsBuilder {
+    /// The last line to measure (0-indexed, inclusive). Defaults to the last
+    /// line of the buffer.
+    #[inline]
+    pub fn end_row(&mut self, end_row: usize) -> &mut Self {
+        self.0.end_row = (end_row as Integer).into();
+        self
+    }
+
+    /// Only measure the part of the `end_row` line before this virtual
+    /// column. Defaults to the whole line.
+    #[inline]
+    pub fn end_vcol(&mut self, end_vcol: usize) -> &mut Self {
+        self.0.end_vcol = (end_vcol as Integer).into();
+        self
+    }
+
+    /// The first line to measure (0-indexed). Defaults to the first line of
+    /// the buffer.
+    #[inline]
+    pub fn start_row(&mut self, start_row: usize) -> &mut Self {
+        self.0.start_row = (start_row as Integer).into();
+        self
+    }
+
+    /// Only measure the part of the `start_row` line starting at this
+    /// virtual column, excluding the filler and virtual lines above it.
+    /// Defaults to the whole line.
+    #[inline]
+    pub fn start_vcol(&mut self, start_vcol: usize) -> &mut Self {
+        self.0.start_vcol = (start_vcol as Integer).into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> WinTextHeightOpts {
+        std::mem::take(&mut self.0)
+    }
+}
@@ -0,0 +1,165 @@
+use oxi_types as nvim;
+
+use crate::options::{self, Scope};
+use crate::opts::BufDeleteOpts;
+use crate::types::WindowConfig;
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// Where to open a preview window.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreviewPlacement {
+    /// Open a horizontal split below the current window, optionally with the
+    /// given height.
+    Split(Option<u32>),
+
+    /// Open a vertical split to the right of the current window, optionally
+    /// with the given width.
+    VSplit(Option<u32>),
+
+    /// Open a floating window with the given config.
+    Float(Box<WindowConfig>),
+}
+
+impl From<WindowConfig> for PreviewPlacement {
+    #[inline]
+    fn from(config: WindowConfig) -> Self {
+        Self::Float(Box::new(config))
+    }
+}
+
+/// A guard returned by [`Window::open_preview`]. The window is closed and the
+/// buffer it displays is wiped out when the guard is dropped.
+#[derive(Debug)]
+pub struct PreviewWindow {
+    window: Window,
+    buffer: Buffer,
+}
+
+impl PreviewWindow {
+    /// The scratch buffer displayed in the preview window.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Consumes the guard without closing the window or wiping the buffer,
+    /// returning them.
+    #[inline]
+    pub fn leak(self) -> (Window, Buffer) {
+        let this = std::mem::ManuallyDrop::new(self);
+        (this.window.clone(), this.buffer.clone())
+    }
+
+    /// The preview window.
+    #[inline]
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+}
+
+impl Drop for PreviewWindow {
+    fn drop(&mut self) {
+        if self.window.is_valid() {
+            let _ = self.window.clone().close(true);
+        }
+        wipe(&self.buffer);
+    }
+}
+
+/// Wipes out `buffer`, ignoring any error.
+fn wipe(buffer: &Buffer) {
+    if buffer.is_valid() {
+        let opts = BufDeleteOpts::builder().force(true).build();
+        let _ = buffer.clone().delete(&opts);
+    }
+}
+
+impl Buffer {
+    /// Creates a new unlisted, non-modifiable scratch buffer containing the
+    /// given lines.
+    pub fn scratch<Line, Lines>(lines: Lines) -> Result<Self>
+    where
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        let mut buf = crate::create_buf(false, true)?;
+
+        let res = buf.set_lines(.., false, lines).and_then(|()| {
+            options::set("modifiable", false, Scope::Buffer(&buf))
+        });
+
+        if let Err(err) = res {
+            wipe(&buf);
+            return Err(err);
+        }
+
+        Ok(buf)
+    }
+}
+
+impl Window {
+    /// Opens a window displaying a new [scratch](Buffer::scratch) buffer
+    /// containing the given lines. If `enter` is `false` the focus stays on
+    /// the current window.
+    ///
+    /// The returned [`PreviewWindow`] closes the window and wipes out the
+    /// buffer when dropped.
+    pub fn open_preview<Line, Lines>(
+        lines: Lines,
+        placement: PreviewPlacement,
+        enter: bool,
+    ) -> Result<PreviewWindow>
+    where
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        let buffer = Buffer::scratch(lines)?;
+
+        match open_window(&buffer, placement, enter) {
+            Ok(window) => Ok(PreviewWindow { window, buffer }),
+            Err(err) => {
+                wipe(&buffer);
+                Err(err)
+            },
+        }
+    }
+}
+
+/// Opens the window of a preview displaying `buffer`, closing it again if
+/// any step fails.
+fn open_window(
+    buffer: &Buffer,
+    placement: PreviewPlacement,
+    enter: bool,
+) -> Result<Window> {
+    let (cmd, size) = match placement {
+        PreviewPlacement::Float(config) => {
+            return crate::open_win(buffer, enter, &config);
+        },
+        PreviewPlacement::Split(height) => ("split", height),
+        PreviewPlacement::VSplit(width) => ("vsplit", width),
+    };
+
+    let current = Window::current();
+    let size = size.map(|s| s.to_string()).unwrap_or_default();
+    crate::command(&format!("belowright {size}{cmd}"))?;
+
+    let mut window = Window::current();
+
+    let res = window.set_buf(buffer).and_then(|()| {
+        if enter {
+            Ok(())
+        } else {
+            crate::set_current_win(&current)
+        }
+    });
+
+    if let Err(err) = res {
+        let _ = window.close(true);
+        return Err(err);
+    }
+
+    Ok(window)
+}
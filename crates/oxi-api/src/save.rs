@@ -0,0 +1,41 @@
+use crate::options::{self, Scope};
+use crate::opts::SaveAllOpts;
+use crate::types::{SaveFailure, SaveFailureReason};
+use crate::{Buffer, Result};
+
+/// Returns an iterator over the loaded buffers with
+/// [unsaved changes](Buffer::has_unsaved_changes).
+pub fn modified_buffers() -> impl Iterator<Item = Buffer> {
+    crate::list_bufs().filter(|buffer| {
+        buffer.is_loaded() && buffer.has_unsaved_changes().unwrap_or_default()
+    })
+}
+
+/// Writes every buffer returned by [`modified_buffers()`], like `:wall`.
+///
+/// Unlike `:wall`, a buffer that can't be written doesn't stop the others
+/// from being written. The buffers that couldn't be written are returned
+/// instead, together with the reason.
+pub fn save_all(opts: &SaveAllOpts) -> Result<Vec<SaveFailure>> {
+    let mut failures = Vec::new();
+
+    for buffer in modified_buffers() {
+        let reason = if buffer.get_name()?.as_os_str().is_empty() {
+            if opts.only_named {
+                continue;
+            }
+            SaveFailureReason::NoName
+        } else if options::get("readonly", Scope::Buffer(&buffer))? {
+            SaveFailureReason::Readonly
+        } else {
+            match buffer.call(|()| crate::command("write")) {
+                Ok(()) => continue,
+                Err(err) => SaveFailureReason::Write(err),
+            }
+        };
+
+        failures.push(SaveFailure { buffer, reason });
+    }
+
+    Ok(failures)
+}
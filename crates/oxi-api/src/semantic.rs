@@ -0,0 +1,418 @@
+//! Rendering of [semantic tokens][1] as highlights, using the same highlight
+//! groups as Neovim's builtin LSP client.
+//!
+//! A token of type `function` with the `readonly` modifier is highlighted
+//! with the `@lsp.type.function`, `@lsp.mod.readonly` and
+//! `@lsp.typemod.function.readonly` groups, with increasing priorities. This
+//! way color schemes supporting the builtin client also work with tokens
+//! computed by other engines, e.g. ones running out of process.
+//!
+//! Tokens are passed to a [`SemanticHighlighter`] in the relative encoding
+//! used by the LSP, either as a whole array of tokens or as the edits to
+//! apply to the previous array. Only the lines whose tokens have changed are
+//! highlighted again.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::semantic::{Legend, SemanticHighlighter};
+//! use nvim_oxi::api::{types::OffsetEncoding, Buffer};
+//!
+//! let legend = Legend::new(["function", "variable"], ["readonly"]);
+//!
+//! let mut highlighter =
+//!     SemanticHighlighter::new(Buffer::current(), legend, OffsetEncoding::Utf16);
+//!
+//! // A `function` on the first line and a `readonly` `variable` on the
+//! // second one.
+//! highlighter.set_tokens(vec![0, 3, 4, 0, 0, 1, 4, 3, 1, 1])?;
+//! ```
+//!
+//! [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_semanticTokens
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+
+use crate::opts::SetExtmarkOpts;
+use crate::types::OffsetEncoding;
+use crate::Result;
+use crate::{hl, text};
+use crate::{Buffer, Namespace};
+
+/// The priority of the `@lsp.type.*` highlights, same as
+/// `vim.highlight.priorities.semantic_tokens`.
+pub const TYPE_PRIORITY: u32 = 125;
+
+/// The priority of the `@lsp.mod.*` highlights.
+pub const MODIFIER_PRIORITY: u32 = TYPE_PRIORITY + 1;
+
+/// The priority of the `@lsp.typemod.*` highlights.
+pub const TYPEMOD_PRIORITY: u32 = TYPE_PRIORITY + 2;
+
+/// Returns the highlight group of the given token type, e.g.
+/// `@lsp.type.function`.
+#[inline]
+pub fn type_group(token_type: &str) -> String {
+    format!("@lsp.type.{token_type}")
+}
+
+/// Returns the highlight group of the given token modifier, e.g.
+/// `@lsp.mod.readonly`.
+#[inline]
+pub fn modifier_group(modifier: &str) -> String {
+    format!("@lsp.mod.{modifier}")
+}
+
+/// Returns the highlight group of a token type combined with one of its
+/// modifiers, e.g. `@lsp.typemod.variable.readonly`.
+#[inline]
+pub fn typemod_group(token_type: &str, modifier: &str) -> String {
+    format!("@lsp.typemod.{token_type}.{modifier}")
+}
+
+/// The token types and modifiers a token's indices refer to.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Legend {
+    token_types: Vec<String>,
+    token_modifiers: Vec<String>,
+}
+
+impl Legend {
+    pub fn new<T, M>(token_types: T, token_modifiers: M) -> Self
+    where
+        T: IntoIterator,
+        T::Item: Into<String>,
+        M: IntoIterator,
+        M::Item: Into<String>,
+    {
+        Self {
+            token_types: token_types.into_iter().map(Into::into).collect(),
+            token_modifiers: token_modifiers
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+
+    #[inline]
+    pub fn token_types(&self) -> &[String] {
+        &self.token_types
+    }
+
+    #[inline]
+    pub fn token_modifiers(&self) -> &[String] {
+        &self.token_modifiers
+    }
+
+    /// Returns the highlight groups of a token together with their
+    /// priorities. Indices that are not part of the legend are ignored.
+    pub fn groups(
+        &self,
+        token_type: u32,
+        modifiers: u32,
+    ) -> Vec<(String, u32)> {
+        let token_type = self.token_types.get(token_type as usize);
+
+        let mut groups = token_type
+            .map(|ty| (type_group(ty), TYPE_PRIORITY))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let modifiers = self
+            .token_modifiers
+            .iter()
+            .take(u32::BITS as usize)
+            .enumerate()
+            .filter(|&(bit, _)| modifiers & (1 << bit) != 0)
+            .map(|(_, modifier)| modifier);
+
+        for modifier in modifiers {
+            groups.push((modifier_group(modifier), MODIFIER_PRIORITY));
+
+            if let Some(ty) = token_type {
+                groups.push((typemod_group(ty, modifier), TYPEMOD_PRIORITY));
+            }
+        }
+
+        groups
+    }
+}
+
+/// A semantic token with an absolute position.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Token {
+    /// The zero-indexed line of the token.
+    pub line: usize,
+
+    /// The zero-indexed column the token starts at.
+    pub start: usize,
+
+    /// The length of the token.
+    pub length: usize,
+
+    /// The index of the token's type in the [`Legend`].
+    pub token_type: u32,
+
+    /// The token's modifiers, as a bitset of indices in the [`Legend`].
+    pub modifiers: u32,
+}
+
+/// Decodes an array of tokens in the relative encoding used by the LSP, where
+/// every token is made of 5 integers: `deltaLine`, `deltaStartChar`, `length`,
+/// `tokenType` and `tokenModifiers`.
+///
+/// A trailing incomplete token is ignored.
+pub fn decode(data: &[u32]) -> impl Iterator<Item = Token> + '_ {
+    let mut line = 0;
+    let mut start = 0;
+
+    data.chunks_exact(5).map(move |chunk| {
+        let delta_line = chunk[0] as usize;
+        let delta_start = chunk[1] as usize;
+
+        if delta_line == 0 {
+            start += delta_start;
+        } else {
+            line += delta_line;
+            start = delta_start;
+        }
+
+        Token {
+            line,
+            start,
+            length: chunk[2] as usize,
+            token_type: chunk[3],
+            modifiers: chunk[4],
+        }
+    })
+}
+
+/// An edit to an array of encoded tokens, as sent by the LSP in response to a
+/// `textDocument/semanticTokens/full/delta` request.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokensEdit {
+    /// The index of the first integer to replace.
+    pub start: usize,
+
+    /// How many integers to remove.
+    pub delete_count: usize,
+
+    /// The integers to insert at `start`.
+    #[serde(default)]
+    pub data: Vec<u32>,
+}
+
+/// Highlights the semantic tokens of a buffer. The highlights are cleared
+/// when the highlighter is dropped.
+pub struct SemanticHighlighter {
+    buffer: Buffer,
+    namespace: Namespace,
+    legend: Legend,
+    encoding: OffsetEncoding,
+
+    /// The current array of encoded tokens.
+    data: Vec<u32>,
+
+    /// The currently highlighted tokens, grouped by line.
+    lines: BTreeMap<usize, Vec<Token>>,
+}
+
+impl SemanticHighlighter {
+    /// Creates a new highlighter for `buffer` using its own
+    /// [`anonymous`](Namespace::anonymous) namespace.
+    pub fn new(
+        buffer: Buffer,
+        legend: Legend,
+        encoding: OffsetEncoding,
+    ) -> Self {
+        Self {
+            buffer,
+            namespace: Namespace::anonymous(),
+            legend,
+            encoding,
+            data: Vec::new(),
+            lines: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the buffer the highlighter is bound to.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the legend used to resolve the tokens' types and modifiers.
+    #[inline]
+    pub fn legend(&self) -> &Legend {
+        &self.legend
+    }
+
+    /// Returns the namespace of the highlights.
+    #[inline]
+    pub fn namespace(&self) -> Namespace {
+        self.namespace
+    }
+
+    /// Returns the currently highlighted tokens, in order.
+    pub fn tokens(&self) -> impl Iterator<Item = &Token> + '_ {
+        self.lines.values().flatten()
+    }
+
+    /// Replaces all the tokens of the buffer with the ones encoded in `data`.
+    pub fn set_tokens(&mut self, data: Vec<u32>) -> Result<()> {
+        self.data = data;
+        self.refresh()
+    }
+
+    /// Applies the edits to the current array of encoded tokens. All the
+    /// edits refer to the array as it was before any of them is applied.
+    ///
+    /// Edits going past the end of the array are truncated.
+    pub fn apply_edits<E>(&mut self, edits: E) -> Result<()>
+    where
+        E: IntoIterator<Item = TokensEdit>,
+    {
+        let mut edits = edits.into_iter().collect::<Vec<_>>();
+
+        // Applying the edits from last to first keeps the indices of the
+        // remaining ones valid.
+        edits.sort_by_key(|edit| Reverse(edit.start));
+
+        for TokensEdit { start, delete_count, data } in edits {
+            let start = start.min(self.data.len());
+            let end = (start + delete_count).min(self.data.len());
+            self.data.splice(start..end, data);
+        }
+
+        self.refresh()
+    }
+
+    /// Removes all the highlights.
+    pub fn clear(&mut self) -> Result<()> {
+        self.data.clear();
+        self.lines.clear();
+        self.namespace.clear(&self.buffer, ..)
+    }
+
+    /// Highlights again the lines whose tokens have changed.
+    fn refresh(&mut self) -> Result<()> {
+        let mut lines = BTreeMap::<usize, Vec<Token>>::new();
+
+        for token in decode(&self.data) {
+            lines.entry(token.line).or_default().push(token);
+        }
+
+        let changed = self
+            .lines
+            .keys()
+            .chain(lines.keys())
+            .copied()
+            .filter(|line| self.lines.get(line) != lines.get(line))
+            .collect::<BTreeSet<_>>();
+
+        let line_count = self.buffer.line_count()?;
+
+        // Tokens past the end of the buffer are out of sync with its
+        // contents, so they're just skipped.
+        for line in changed.into_iter().filter(|&line| line < line_count) {
+            self.namespace.clear(&self.buffer, line..line + 1)?;
+
+            if let Some(tokens) = lines.get(&line) {
+                self.highlight_line(line, tokens)?;
+            }
+        }
+
+        self.lines = lines;
+
+        Ok(())
+    }
+
+    fn highlight_line(&self, line: usize, tokens: &[Token]) -> Result<()> {
+        let bytes = match self.buffer.get_lines(line..line + 1, true)?.next() {
+            Some(bytes) => bytes,
+            None => return Ok(()),
+        };
+
+        let bytes = bytes.as_bytes();
+
+        for token in tokens {
+            let start = text::col_to_byte(bytes, token.start, self.encoding);
+            let end = text::col_to_byte(
+                bytes,
+                token.start + token.length,
+                self.encoding,
+            );
+
+            if start >= end {
+                continue;
+            }
+
+            for (group, priority) in
+                self.legend.groups(token.token_type, token.modifiers)
+            {
+                let opts = SetExtmarkOpts::builder()
+                    .end_col(end)
+                    .hl_group(hl::intern(&group)?)
+                    .priority(priority)
+                    .strict(false)
+                    .build();
+
+                self.namespace.set_extmark(
+                    &self.buffer,
+                    line,
+                    start,
+                    &opts,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for SemanticHighlighter {
+    fn drop(&mut self) {
+        if self.buffer.is_valid() {
+            let _ = self.namespace.clear(&self.buffer, ..);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_relative_tokens() {
+        let data = [0, 3, 4, 0, 0, 0, 5, 2, 1, 1, 2, 1, 3, 0, 0, 7];
+
+        let tokens = decode(&data)
+            .map(|token| (token.line, token.start, token.length))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![(0, 3, 4), (0, 8, 2), (2, 1, 3)], tokens);
+    }
+
+    #[test]
+    fn legend_groups() {
+        let legend = Legend::new(["function", "variable"], ["readonly"]);
+
+        assert_eq!(
+            vec![
+                ("@lsp.type.variable".to_owned(), TYPE_PRIORITY),
+                ("@lsp.mod.readonly".to_owned(), MODIFIER_PRIORITY),
+                (
+                    "@lsp.typemod.variable.readonly".to_owned(),
+                    TYPEMOD_PRIORITY
+                ),
+            ],
+            legend.groups(1, 0b1)
+        );
+
+        assert!(legend.groups(2, 0b10).is_empty());
+    }
+}
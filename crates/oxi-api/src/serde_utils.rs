@@ -50,6 +50,13 @@ where
     }
 }
 
+pub(crate) fn is_present<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    de::IgnoredAny::deserialize(deserializer).map(|_| true)
+}
+
 pub(crate) fn minus_one_is_none<'de, D, T>(
     deserializer: D,
 ) -> Result<Option<T>, D::Error>
@@ -0,0 +1,38 @@
+use crate::opts::SplitOpts;
+use crate::types::CmdRange;
+use crate::{CmdBuilder, Result, Window};
+
+/// Splits the current window, like `:split` or `:vsplit` with the
+/// [`SplitOpts`]' direction and size, returning the new window.
+///
+/// The split honors the same options as the Ex commands, so e.g.
+/// `'equalalways'` can still resize the other windows.
+pub fn split(opts: &SplitOpts) -> Result<Window> {
+    let previous = crate::get_current_win();
+
+    let mut cmd = CmdBuilder::new();
+
+    cmd.cmd(if opts.vertical { "vsplit" } else { "split" });
+
+    if let Some(direction) = opts.direction {
+        cmd.mods(|mods| mods.split(direction));
+    }
+
+    if let Some(size) = opts.size {
+        cmd.range(CmdRange::Single(size as usize));
+    }
+
+    cmd.exec()?;
+
+    let mut window = crate::get_current_win();
+
+    if let Some(buffer) = &opts.buffer {
+        window.set_buf(buffer)?;
+    }
+
+    if !opts.enter {
+        crate::set_current_win(&previous)?;
+    }
+
+    Ok(window)
+}
@@ -0,0 +1,292 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use oxi_types::{
+    self as nvim,
+    conversion::FromObject,
+    serde::{Deserializer, Serializer},
+    Dictionary,
+    Integer,
+    Object,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::opts::{CreateAutocmdOpts, ExecAutocmdsOpts};
+use crate::types::{AutocmdCallbackArgs, AutocmdEvent};
+use crate::Result;
+use crate::{Buffer, Error, Window};
+
+/// Where the value of a [`State`] is stored.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StateScope {
+    /// A global variable, i.e. `g:{name}`.
+    Global,
+
+    /// A buffer-local variable, i.e. `b:{name}`.
+    Buffer(Buffer),
+
+    /// A window-local variable, i.e. `w:{name}`.
+    Window(Window),
+}
+
+impl StateScope {
+    /// The `g:`, `b:` or `w:` prefix of the variable.
+    fn prefix(&self) -> &'static str {
+        match self {
+            Self::Global => "g:",
+            Self::Buffer(_) => "b:",
+            Self::Window(_) => "w:",
+        }
+    }
+
+    /// The data attached to the `User` autocommand fired when the value
+    /// changes.
+    fn data(&self) -> Object {
+        match self {
+            Self::Global => Object::nil(),
+            Self::Buffer(buf) => Dictionary::from_iter([("buf", buf)]).into(),
+            Self::Window(win) => Dictionary::from_iter([("win", win)]).into(),
+        }
+    }
+
+    /// Whether the data attached to a `User` autocommand refers to this
+    /// scope. Data that doesn't say which buffer or window it refers to is
+    /// assumed to match.
+    fn matches(&self, data: Object) -> bool {
+        let (key, handle) = match self {
+            Self::Global => return true,
+            Self::Buffer(buf) => ("buf", buf.0),
+            Self::Window(win) => ("win", win.0),
+        };
+
+        let target = Dictionary::from_object(data)
+            .ok()
+            .and_then(|dict| dict.get(key).cloned())
+            .and_then(|obj| Integer::from_object(obj).ok());
+
+        match target {
+            Some(target) => target == handle as Integer,
+            None => true,
+        }
+    }
+
+    fn get_var(&self, name: &str) -> Result<Object> {
+        match self {
+            Self::Global => crate::get_var(name),
+            Self::Buffer(buf) => buf.get_var(name),
+            Self::Window(win) => win.get_var(name),
+        }
+    }
+
+    fn set_var(&self, name: &str, value: Object) -> Result<()> {
+        match self {
+            Self::Global => crate::set_var(name, value),
+            Self::Buffer(buf) => buf.clone().set_var(name, value),
+            Self::Window(win) => win.clone().set_var(name, value),
+        }
+    }
+}
+
+type Subscriber<T> = Box<dyn FnMut(&T)>;
+
+struct Inner<T> {
+    scope: StateScope,
+    name: String,
+    subscribers: RefCell<Vec<Subscriber<T>>>,
+    /// Set while [`State::set`] fires the `User` autocommand, so that the
+    /// subscribers aren't notified twice.
+    is_setting: Cell<bool>,
+    autocmd_id: Cell<Option<u32>>,
+}
+
+impl<T> Inner<T> {
+    /// The pattern of the `User` autocommand fired when the value changes,
+    /// e.g. `b:foo`.
+    fn pattern(&self) -> String {
+        format!("{}{}", self.scope.prefix(), self.name)
+    }
+
+    fn notify(&self, value: &T) {
+        // The subscribers are taken out of the cell while they're called, so
+        // that they can subscribe new functions or set the state again.
+        let mut subscribers = self.subscribers.take();
+
+        for subscriber in subscribers.iter_mut() {
+            subscriber(value);
+        }
+
+        let mut current = self.subscribers.borrow_mut();
+        subscribers.append(&mut current);
+        *current = subscribers;
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.autocmd_id.get() {
+            let _ = crate::del_autocmd(id);
+        }
+    }
+}
+
+/// A value shared between Rust and Lua, stored in a global, buffer-local or
+/// window-local variable.
+///
+/// The value is (de)serialized to and from the variable using [Serde], so
+/// it's always up to date on both sides: [`get`](State::get) reads the
+/// variable and [`set`](State::set) writes it.
+///
+/// Every time the value is [`set`](State::set) the subscribers registered
+/// via [`subscribe`](State::subscribe) are notified, and a `User`
+/// autocommand whose pattern is the name of the variable (e.g. `b:foo`) is
+/// fired. Lua code can listen to that autocommand to react to changes, and
+/// can fire it after modifying the variable to notify the Rust subscribers.
+/// The `data` of the autocommand contains the `buf` or `win` the variable
+/// belongs to.
+///
+/// Cloning a `State` is cheap and returns a handle to the same state.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{State, StateScope};
+///
+/// let count = State::new(StateScope::Global, "my_plugin_count", 0u32)?;
+///
+/// count.subscribe(|count| nvim_oxi::print!("count is now {count}"));
+///
+/// count.update(|count| *count += 1)?;
+/// assert_eq!(1, count.get()?);
+/// ```
+///
+/// ```lua
+/// vim.g.my_plugin_count = 42
+/// vim.api.nvim_exec_autocmds("User", { pattern = "g:my_plugin_count" })
+/// ```
+///
+/// [Serde]: https://serde.rs
+pub struct State<T> {
+    inner: Rc<Inner<T>>,
+}
+
+impl<T> Clone for State<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<T> fmt::Debug for State<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("scope", &self.inner.scope)
+            .field("name", &self.inner.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> State<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    /// Creates a new state stored in the variable `name` of the given scope.
+    /// The variable is initialized to `default` if it's not already set.
+    pub fn new(scope: StateScope, name: &str, default: T) -> Result<Self> {
+        let inner = Rc::new(Inner {
+            scope,
+            name: name.to_owned(),
+            subscribers: RefCell::new(Vec::new()),
+            is_setting: Cell::new(false),
+            autocmd_id: Cell::new(None),
+        });
+
+        if inner.scope.get_var(name).is_err() {
+            inner.scope.set_var(name, serialize(&default)?)?;
+        }
+
+        let weak = Rc::downgrade(&inner);
+
+        let opts = CreateAutocmdOpts::builder()
+            .patterns([&*inner.pattern()])
+            .callback(move |args: AutocmdCallbackArgs| {
+                if let Some(inner) = Weak::upgrade(&weak) {
+                    if !inner.is_setting.get()
+                        && inner.scope.matches(args.data)
+                    {
+                        let value = inner.scope.get_var(&inner.name)?;
+                        inner.notify(&deserialize(value)?);
+                    }
+                }
+                Ok::<_, Error>(false)
+            })
+            .build();
+
+        let id = crate::create_autocmd([AutocmdEvent::User], &opts)?;
+        inner.autocmd_id.set(Some(id));
+
+        Ok(Self { inner })
+    }
+
+    /// Returns the current value of the state.
+    pub fn get(&self) -> Result<T> {
+        deserialize(self.inner.scope.get_var(&self.inner.name)?)
+    }
+
+    /// Sets the value of the state, notifying all the subscribers.
+    pub fn set(&self, value: T) -> Result<()> {
+        let inner = &*self.inner;
+
+        inner.scope.set_var(&inner.name, serialize(&value)?)?;
+        inner.notify(&value);
+
+        let opts = ExecAutocmdsOpts::builder()
+            .patterns(&*inner.pattern())
+            .data(inner.scope.data())
+            .build();
+
+        inner.is_setting.set(true);
+        let res = crate::exec_autocmds([AutocmdEvent::User], &opts);
+        inner.is_setting.set(false);
+        res
+    }
+
+    /// Registers a function to be called with the new value every time the
+    /// state changes.
+    ///
+    /// A function subscribed from within another subscriber is only called
+    /// starting from the next change. Setting the state from within a
+    /// subscriber doesn't notify the subscribers again.
+    pub fn subscribe<F>(&self, fun: F)
+    where
+        F: FnMut(&T) + 'static,
+    {
+        self.inner.subscribers.borrow_mut().push(Box::new(fun));
+    }
+
+    /// Updates the value of the state in place, notifying all the
+    /// subscribers.
+    pub fn update<F>(&self, fun: F) -> Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.get()?;
+        fun(&mut value);
+        self.set(value)
+    }
+}
+
+fn serialize<T: Serialize>(value: &T) -> Result<Object> {
+    value
+        .serialize(Serializer::new())
+        .map_err(nvim::conversion::Error::from)
+        .map_err(Into::into)
+}
+
+fn deserialize<T: DeserializeOwned>(obj: Object) -> Result<T> {
+    T::deserialize(Deserializer::new(obj))
+        .map_err(nvim::conversion::Error::from)
+        .map_err(Into::into)
+}
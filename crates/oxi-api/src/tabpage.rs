@@ -5,6 +5,7 @@ use oxi_luajit::{self as lua, Poppable, Pushable};
 use oxi_types::{
     self as nvim,
     conversion::{self, FromObject, ToObject},
+    Dictionary,
     Object,
     TabHandle,
 };
@@ -74,6 +75,16 @@ impl TabPage {
         crate::get_current_tabpage()
     }
 
+    /// Calls a function with this tabpage and its current window as the
+    /// temporary current ones, returning its result.
+    pub fn call<R, F>(&self, fun: F) -> Result<R>
+    where
+        F: FnOnce(()) -> Result<R> + 'static,
+        R: 'static,
+    {
+        self.get_win()?.call(fun)
+    }
+
     /// Binding to [`nvim_tabpage_del_var()`][1].
     ///
     /// Removes a tab-scoped (`t:`) variable.
@@ -81,7 +92,7 @@ impl TabPage {
     /// [1]: https://neovim.io/doc/user/api.html#nvim_tabpage_del_var()
     pub fn del_var(&mut self, name: &str) -> Result<()> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe { nvim_tabpage_del_var(self.0, name.non_owning(), &mut err) };
         choose!(err, ())
     }
@@ -94,7 +105,7 @@ impl TabPage {
     pub fn get_number(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
         let number = unsafe { nvim_tabpage_get_number(self.0, &mut err) };
-        choose!(err, Ok(number.try_into().expect("always positive")))
+        choose!(err, Ok(number.try_into()?))
     }
 
     /// Binding to [`nvim_tabpage_get_var()`][1].
@@ -107,7 +118,7 @@ impl TabPage {
         Var: FromObject,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let obj = unsafe {
             nvim_tabpage_get_var(self.0, name.non_owning(), &mut err)
         };
@@ -160,7 +171,7 @@ impl TabPage {
         Var: ToObject,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe {
             nvim_tabpage_set_var(
                 self.0,
@@ -171,4 +182,19 @@ impl TabPage {
         };
         choose!(err, ())
     }
+
+    /// Returns an iterator over the tab-scoped (`t:`) variables, as
+    /// `(name, value)` pairs.
+    ///
+    /// This is a wrapper around [`gettabvar()`][1], since there's no API
+    /// function to list the variables.
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#gettabvar()
+    pub fn vars(&self) -> Result<impl SuperIterator<(nvim::String, Object)>> {
+        let vars = crate::call_function::<_, Dictionary>(
+            "gettabvar",
+            (self.get_number()?, ""),
+        )?;
+        Ok(vars.into_iter())
+    }
 }
@@ -0,0 +1,209 @@
+use crate::options::{self, Scope};
+use crate::opts::{BufAttachOpts, OnLinesArgs, OpenTermOpts, ShouldDetach};
+use crate::Result;
+use crate::{Buffer, Error};
+
+/// New output written to a [`Terminal`], passed to the callback registered
+/// via [`on_output`](Terminal::on_output).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TerminalOutput {
+    /// The lines that have been completely written since the last event, in
+    /// order.
+    pub lines: Vec<String>,
+
+    /// The contents of the last line written to, which may still be
+    /// incomplete. It's repeated in every event until more output is written
+    /// after it, at which point it's included in
+    /// [`lines`](TerminalOutput::lines).
+    pub partial: Option<String>,
+}
+
+/// A terminal buffer, either running a job started with `:terminal` or
+/// [`termopen()`][1], or opened via [`open_term()`](crate::open_term).
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#termopen()
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Terminal {
+    buffer: Buffer,
+    channel: u32,
+}
+
+impl Terminal {
+    /// Wraps an existing terminal buffer. Fails if the buffer is not a
+    /// terminal.
+    pub fn from_buffer(buffer: Buffer) -> Result<Self> {
+        let scope = Scope::Buffer(&buffer);
+
+        if options::get::<String>("buftype", scope)? != "terminal" {
+            return Err(Error::custom(format!(
+                "buffer {} is not a terminal",
+                buffer.0
+            )));
+        }
+
+        let channel = options::get::<u32>("channel", scope)?;
+        Ok(Self { buffer, channel })
+    }
+
+    /// Opens a terminal instance in `buffer` via
+    /// [`open_term()`](crate::open_term).
+    pub fn open(buffer: Buffer, opts: &OpenTermOpts) -> Result<Self> {
+        let channel = crate::open_term(&buffer, opts)?;
+        Ok(Self { buffer, channel })
+    }
+
+    /// The terminal buffer.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The id of the channel connected to the terminal.
+    #[inline]
+    pub fn channel(&self) -> u32 {
+        self.channel
+    }
+
+    /// Registers a callback invoked every time new output is written to the
+    /// terminal. The callback can detach by returning `true`.
+    ///
+    /// Terminal buffers are redrawn in place as output comes in, with empty
+    /// lines at the bottom for the rows of the screen that haven't been
+    /// written to yet, and the last line can be updated more than once as
+    /// chunks of it are written. This takes care of turning that into a
+    /// stream of complete lines, leaving the line still being written to in
+    /// [`partial`](TerminalOutput::partial).
+    ///
+    /// If the program running in the terminal rewrites lines that have
+    /// already been reported, e.g. by clearing the screen, they're reported
+    /// again.
+    pub fn on_output<F>(&self, mut fun: F) -> Result<()>
+    where
+        F: FnMut(TerminalOutput) -> Result<ShouldDetach> + 'static,
+    {
+        let mut decoder = OutputDecoder::default();
+
+        let opts = BufAttachOpts::builder()
+            .on_lines(move |args: OnLinesArgs| {
+                let (_, buf, _, first_row, ..) = args;
+                let output = decoder.update(first_row, |start| {
+                    buf.get_lines(start.., false).map(|lines| {
+                        lines
+                            .map(|line| line.to_string_lossy().into_owned())
+                            .collect()
+                    })
+                })?;
+                match output {
+                    Some(output) => fun(output),
+                    None => Ok(false),
+                }
+            })
+            .build();
+
+        self.buffer.attach(false, &opts)
+    }
+
+    /// Sends data to the terminal via [`chan_send()`](crate::chan_send).
+    pub fn send(&self, data: &str) -> Result<()> {
+        crate::chan_send(self.channel, data)
+    }
+}
+
+/// Turns the `on_lines` events of a terminal buffer into [`TerminalOutput`]s.
+#[derive(Debug, Default)]
+struct OutputDecoder {
+    /// The number of lines at the top of the buffer that have already been
+    /// reported as complete.
+    emitted: usize,
+
+    /// The last reported partial line.
+    partial: Option<String>,
+}
+
+impl OutputDecoder {
+    /// Called on every `on_lines` event. `read_from` should return the lines
+    /// of the buffer starting from the given row.
+    fn update<R>(
+        &mut self,
+        first_row: usize,
+        read_from: R,
+    ) -> Result<Option<TerminalOutput>>
+    where
+        R: FnOnce(usize) -> Result<Vec<String>>,
+    {
+        if first_row < self.emitted {
+            self.emitted = first_row;
+            self.partial = None;
+        }
+
+        let mut lines = read_from(self.emitted)?;
+
+        // Rows the program hasn't written to yet are empty.
+        while matches!(lines.last(), Some(line) if line.is_empty()) {
+            lines.pop();
+        }
+
+        let partial = lines.pop();
+        self.emitted += lines.len();
+
+        if lines.is_empty() && partial == self.partial {
+            return Ok(None);
+        }
+
+        self.partial = partial.clone();
+        Ok(Some(TerminalOutput { lines, partial }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(
+        decoder: &mut OutputDecoder,
+        first_row: usize,
+        buffer: &[&str],
+    ) -> Option<TerminalOutput> {
+        decoder
+            .update(first_row, |start| {
+                Ok(buffer[start..].iter().map(|&s| s.to_owned()).collect())
+            })
+            .unwrap()
+    }
+
+    fn output(lines: &[&str], partial: Option<&str>) -> TerminalOutput {
+        TerminalOutput {
+            lines: lines.iter().map(|&s| s.to_owned()).collect(),
+            partial: partial.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn decode_partial_lines() {
+        let mut decoder = OutputDecoder::default();
+
+        let out = feed(&mut decoder, 0, &["$ ec", "", ""]);
+        assert_eq!(Some(output(&[], Some("$ ec"))), out);
+
+        let out = feed(&mut decoder, 0, &["$ echo foo", "", ""]);
+        assert_eq!(Some(output(&[], Some("$ echo foo"))), out);
+
+        let out = feed(&mut decoder, 0, &["$ echo foo", "foo", "$ "]);
+        assert_eq!(Some(output(&["$ echo foo", "foo"], Some("$ "))), out);
+
+        // Nothing new.
+        assert_eq!(None, feed(&mut decoder, 2, &["$ echo foo", "foo", "$ "]));
+    }
+
+    #[test]
+    fn decode_rewritten_lines() {
+        let mut decoder = OutputDecoder::default();
+
+        feed(&mut decoder, 0, &["foo", "bar", "baz"]);
+
+        // The screen is cleared.
+        let out = feed(&mut decoder, 0, &["qux", "", ""]);
+        assert_eq!(Some(output(&[], Some("qux"))), out);
+    }
+}
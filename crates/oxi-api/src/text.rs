@@ -0,0 +1,324 @@
+//! Utilities to convert between text and buffer lines according to a
+//! buffer's [`FileFormat`], to find words according to a buffer's
+//! [`'iskeyword'`][iskeyword], and to convert byte columns to and from other
+//! [`OffsetEncoding`]s.
+//!
+//! Neovim stores the lines of a buffer without their line endings, and adds
+//! the ones of the buffer's `'fileformat'` when writing it. Splitting text
+//! on `\n` alone before setting it in a buffer leaves a trailing `\r` in
+//! every line of a CRLF file, which then gets written as `\r\r\n`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{text, Buffer};
+//!
+//! let mut buf = Buffer::current();
+//! let fileformat = buf.fileformat()?;
+//!
+//! // E.g. the output of a formatter.
+//! let output: Vec<u8> = format(&text::join_lines(lines, fileformat));
+//!
+//! buf.set_lines(.., true, text::split_lines(&output, fileformat))?;
+//! ```
+//!
+//! [iskeyword]: https://neovim.io/doc/user/options.html#'iskeyword'
+
+use std::ops::Range;
+
+use oxi_types as nvim;
+
+use crate::types::{FileFormat, OffsetEncoding};
+use crate::{Buffer, Result};
+
+/// The class returned by [`charclass()`][1] for keyword characters.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#charclass()
+const KEYWORD_CLASS: u8 = 2;
+
+/// Splits `text` into lines, removing their line endings.
+///
+/// Like when Neovim reads a file, a line ending at the end of the text
+/// terminates the last line instead of starting a new, empty one (see
+/// [`'endofline'`][1]). With the [`Dos`](FileFormat::Dos) format a `\n` not
+/// preceded by a `\r` also ends a line.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'endofline'
+pub fn split_lines(text: &[u8], fileformat: FileFormat) -> Vec<nvim::String> {
+    let separator = match fileformat {
+        FileFormat::Dos | FileFormat::Unix => b'\n',
+        FileFormat::Mac => b'\r',
+    };
+
+    let text = text.strip_suffix(&[separator]).unwrap_or(text);
+
+    text.split(|&byte| byte == separator)
+        .map(|line| match fileformat {
+            FileFormat::Dos => line.strip_suffix(b"\r").unwrap_or(line),
+            _ => line,
+        })
+        .map(nvim::String::from_bytes)
+        .collect()
+}
+
+/// Joins `lines` using the line ending of `fileformat`.
+///
+/// Every line is followed by a line ending, including the last one, which
+/// is how Neovim writes a buffer with [`'endofline'`][1] set.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'endofline'
+pub fn join_lines<Line, Lines>(lines: Lines, fileformat: FileFormat) -> Vec<u8>
+where
+    Lines: IntoIterator<Item = Line>,
+    Line: AsRef<[u8]>,
+{
+    let ending = fileformat.line_ending();
+
+    let mut text = Vec::new();
+
+    for line in lines {
+        text.extend_from_slice(line.as_ref());
+        text.extend_from_slice(ending);
+    }
+
+    text
+}
+
+/// Converts the byte column `byte_col` of `line` into a column counted in
+/// the units of `encoding`, e.g. to send a cursor position to a language
+/// server.
+///
+/// A byte column in the middle of a multibyte character is moved to the
+/// start of the character, and one past the end of the line to its end.
+/// Every byte of an invalid UTF-8 sequence counts as a character.
+pub fn byte_to_col(
+    line: &[u8],
+    byte_col: usize,
+    encoding: OffsetEncoding,
+) -> usize {
+    char_spans(line)
+        .take_while(|&(start, len)| start + len <= byte_col)
+        .map(|(_, len)| encoding.units(len))
+        .sum()
+}
+
+/// Converts the column `col` of `line`, counted in the units of `encoding`,
+/// into a byte column. This is the inverse of [`byte_to_col`].
+///
+/// A column in the middle of a character, e.g. between the two UTF-16 code
+/// units of an emoji, is moved to the start of the character, and one past
+/// the end of the line to its end.
+pub fn col_to_byte(
+    line: &[u8],
+    col: usize,
+    encoding: OffsetEncoding,
+) -> usize {
+    let mut units = 0;
+
+    for (start, len) in char_spans(line) {
+        units += encoding.units(len);
+        if units > col {
+            return start;
+        }
+    }
+
+    line.len()
+}
+
+/// Returns whether `c` is a keyword character in `buffer`, i.e. if it's
+/// part of a word according to the buffer's [`'iskeyword'`][1] option.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'iskeyword'
+pub fn is_keyword_char(c: char, buffer: &Buffer) -> Result<bool> {
+    buffer.call(move |()| is_keyword(c))
+}
+
+/// Returns the word containing the byte at `col` in the `row`-th line of
+/// `buffer` (both 0-indexed), together with its byte range in the line.
+///
+/// Words are made of the keyword characters of the buffer's
+/// [`'iskeyword'`][1] option, so `foo-bar` is a single word in a buffer
+/// where `-` is a keyword character. Unlike [`<cword>`][2], no word is
+/// returned if the character at `col` isn't a keyword character.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'iskeyword'
+/// [2]: https://neovim.io/doc/user/cmdline.html#%3Ccword%3E
+pub fn word_at(
+    buffer: &Buffer,
+    row: usize,
+    col: usize,
+) -> Result<Option<(String, Range<usize>)>> {
+    let line = buffer.get_lines(row..=row, true)?.next().unwrap_or_default();
+    let line = String::from_utf8(line.as_bytes().to_owned())?;
+
+    let chars = line.char_indices().collect::<Vec<_>>();
+
+    // The index of the character containing the byte at `col`.
+    let idx = match chars.iter().rposition(|&(offset, _)| offset <= col) {
+        Some(idx) if col < line.len() => idx,
+        _ => return Ok(None),
+    };
+
+    let len = line.len();
+
+    let bounds = buffer.call(move |()| {
+        if !is_keyword(chars[idx].1)? {
+            return Ok(Vec::new());
+        }
+
+        let mut start = idx;
+        while start > 0 && is_keyword(chars[start - 1].1)? {
+            start -= 1;
+        }
+
+        let mut end = idx + 1;
+        while end < chars.len() && is_keyword(chars[end].1)? {
+            end += 1;
+        }
+
+        let end = chars.get(end).map_or(len, |&(offset, _)| offset);
+        Ok(vec![chars[start].0, end])
+    })?;
+
+    Ok(match bounds[..] {
+        [start, end] => Some((line[start..end].to_owned(), start..end)),
+        _ => None,
+    })
+}
+
+/// Returns the byte offset and the length of every character of `line`.
+fn char_spans(line: &[u8]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut start = 0;
+
+    std::iter::from_fn(move || {
+        let len = match *line.get(start)? {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1,
+        };
+
+        let is_valid = matches!(
+            line.get(start + 1..start + len),
+            Some(rest) if rest.iter().all(|byte| byte & 0xC0 == 0x80)
+        );
+
+        let span = (start, if is_valid { len } else { 1 });
+        start += span.1;
+        Some(span)
+    })
+}
+
+/// Returns whether `c` is a keyword character in the current buffer.
+fn is_keyword(c: char) -> Result<bool> {
+    crate::call_function::<_, u8>("charclass", (c.to_string(),))
+        .map(|class| class == KEYWORD_CLASS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(text: &str, fileformat: FileFormat) -> Vec<String> {
+        split_lines(text.as_bytes(), fileformat)
+            .into_iter()
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn split_unix() {
+        assert_eq!(vec!["foo", "bar"], split("foo\nbar\n", FileFormat::Unix));
+        assert_eq!(vec!["foo", "bar"], split("foo\nbar", FileFormat::Unix));
+        assert_eq!(vec!["foo\r", ""], split("foo\r\n\n", FileFormat::Unix));
+        assert_eq!(vec![""], split("", FileFormat::Unix));
+    }
+
+    #[test]
+    fn split_dos() {
+        assert_eq!(
+            vec!["foo", "bar"],
+            split("foo\r\nbar\r\n", FileFormat::Dos)
+        );
+        assert_eq!(vec!["foo", "bar"], split("foo\nbar", FileFormat::Dos));
+        assert_eq!(vec!["foo\rbar"], split("foo\rbar", FileFormat::Dos));
+    }
+
+    #[test]
+    fn split_mac() {
+        assert_eq!(vec!["foo", "bar"], split("foo\rbar\r", FileFormat::Mac));
+        assert_eq!(vec!["foo\nbar"], split("foo\nbar", FileFormat::Mac));
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(
+            b"foo\r\nbar\r\n",
+            &*join_lines(["foo", "bar"], FileFormat::Dos)
+        );
+        assert_eq!(
+            b"foo\rbar\r",
+            &*join_lines(["foo", "bar"], FileFormat::Mac)
+        );
+        assert!(join_lines(Vec::<&str>::new(), FileFormat::Unix).is_empty());
+    }
+
+    #[test]
+    fn roundtrip() {
+        for fileformat in [FileFormat::Dos, FileFormat::Mac, FileFormat::Unix]
+        {
+            let lines = vec!["foo", "", "bar"];
+            let text = join_lines(&lines, fileformat);
+            assert_eq!(
+                lines,
+                split(&String::from_utf8(text).unwrap(), fileformat)
+            );
+        }
+    }
+
+    #[test]
+    fn byte_to_col_encodings() {
+        // "aé€😀b": 1, 2, 3 and 4 bytes wide, then 1 byte.
+        let line = "aé€😀b".as_bytes();
+
+        assert_eq!(10, byte_to_col(line, 10, OffsetEncoding::Utf8));
+        assert_eq!(5, byte_to_col(line, 10, OffsetEncoding::Utf16));
+        assert_eq!(4, byte_to_col(line, 10, OffsetEncoding::Utf32));
+        assert_eq!(6, byte_to_col(line, 11, OffsetEncoding::Utf16));
+
+        // In the middle of the emoji.
+        assert_eq!(3, byte_to_col(line, 8, OffsetEncoding::Utf16));
+        assert_eq!(5, byte_to_col(line, 99, OffsetEncoding::Utf32));
+    }
+
+    #[test]
+    fn col_to_byte_encodings() {
+        let line = "aé€😀b".as_bytes();
+
+        assert_eq!(6, col_to_byte(line, 6, OffsetEncoding::Utf8));
+        assert_eq!(10, col_to_byte(line, 5, OffsetEncoding::Utf16));
+        assert_eq!(10, col_to_byte(line, 4, OffsetEncoding::Utf32));
+
+        // Between the two UTF-16 code units of the emoji.
+        assert_eq!(6, col_to_byte(line, 4, OffsetEncoding::Utf16));
+        assert_eq!(11, col_to_byte(line, 99, OffsetEncoding::Utf16));
+    }
+
+    #[test]
+    fn col_to_byte_token_bounds() {
+        let line = "aé😀b".as_bytes();
+
+        assert_eq!(3, col_to_byte(line, 3, OffsetEncoding::Utf8));
+        assert_eq!(3, col_to_byte(line, 2, OffsetEncoding::Utf16));
+        assert_eq!(7, col_to_byte(line, 4, OffsetEncoding::Utf16));
+        assert_eq!(7, col_to_byte(line, 3, OffsetEncoding::Utf32));
+        assert_eq!(8, col_to_byte(line, 42, OffsetEncoding::Utf32));
+    }
+
+    #[test]
+    fn cols_invalid_utf8() {
+        let line = b"a\xE2\x82b";
+        assert_eq!(3, byte_to_col(line, 3, OffsetEncoding::Utf16));
+        assert_eq!(3, col_to_byte(line, 3, OffsetEncoding::Utf16));
+    }
+}
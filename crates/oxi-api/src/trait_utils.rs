@@ -3,6 +3,9 @@ use std::iter::FusedIterator;
 use oxi_luajit::{Poppable, Pushable};
 use oxi_types::{Array, Function, Object};
 
+use crate::decoration_context::CallbackGuard;
+use crate::DecorationCallback;
+
 /// A super trait of most common traits implemented on iterators.
 pub trait SuperIterator<I>:
     Iterator<Item = I> + ExactSizeIterator + DoubleEndedIterator + FusedIterator
@@ -62,6 +65,18 @@ impl<S: Into<String>> StringOrListOfStrings for Vec<S> {
 /// A trait implemented by closures and [`Function`]s.
 pub trait ToFunction<A, R> {
     fn to_object(self) -> Object;
+
+    /// Same as [`to_object`](ToFunction::to_object), but marks the given
+    /// decoration provider callback as running while the function is
+    /// called.
+    #[doc(hidden)]
+    #[inline]
+    fn to_decoration_callback(self, callback: DecorationCallback) -> Object
+    where
+        Self: Sized,
+    {
+        crate::decoration_context::guard_function(self.to_object(), callback)
+    }
 }
 
 impl<A, R, F> ToFunction<A, R> for F
@@ -74,6 +89,18 @@ where
     fn to_object(self) -> Object {
         Function::from_fn_mut(self).into()
     }
+
+    #[inline]
+    fn to_decoration_callback(
+        mut self,
+        callback: DecorationCallback,
+    ) -> Object {
+        Function::from_fn_mut(move |args| {
+            let _guard = CallbackGuard::enter(callback);
+            self(args)
+        })
+        .into()
+    }
 }
 
 impl<A, R> ToFunction<A, R> for Function<A, R> {
@@ -0,0 +1,35 @@
+use thiserror::Error as ThisError;
+
+/// An error returned when moving around the [argument list][1] with
+/// [`argument()`](crate::argument), [`argnext()`](crate::argnext) or
+/// [`argprev()`](crate::argprev).
+///
+/// [1]: https://neovim.io/doc/user/editing.html#arglist
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, ThisError, Eq, PartialEq, Hash)]
+pub enum ArglistError {
+    /// The argument list is empty.
+    #[error("the argument list is empty")]
+    Empty,
+
+    /// There's no argument at the given index.
+    #[error(
+        "argument index {index} is out of range for an argument list of \
+         {len} entries"
+    )]
+    OutOfRange {
+        /// The 0-indexed position of the argument.
+        index: usize,
+
+        /// The number of entries in the argument list.
+        len: usize,
+    },
+
+    /// Tried to move before the first argument.
+    #[error("can't go before the first argument")]
+    BeforeFirst,
+
+    /// Tried to move beyond the last argument.
+    #[error("can't go beyond the last argument")]
+    AfterLast,
+}
@@ -0,0 +1,207 @@
+use oxi_types::{self as nvim, Object};
+
+macro_rules! autocmd_events {
+    ($($(#[cfg($cfg:meta)])? $event:ident,)*) => {
+        /// An event that can trigger an autocommand. See
+        /// [`:h autocmd-events`](https://neovim.io/doc/user/autocmd.html#autocmd-events)
+        /// for a description of each of them.
+        #[non_exhaustive]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        pub enum AutocmdEvent {
+            $(
+                $(
+                    #[cfg($cfg)]
+                    #[cfg_attr(docsrs, doc(cfg($cfg)))]
+                )?
+                $event,
+            )*
+        }
+
+        impl AutocmdEvent {
+            /// Returns the name of the event as understood by Neovim, e.g.
+            /// `"BufEnter"`.
+            #[inline]
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(
+                        $(#[cfg($cfg)])?
+                        Self::$event => stringify!($event),
+                    )*
+                }
+            }
+
+            /// Returns the event with the given name, ignoring case like
+            /// Neovim does, or `None` if there's no such event.
+            pub fn from_name(name: &str) -> Option<Self> {
+                $(
+                    $(#[cfg($cfg)])?
+                    if name.eq_ignore_ascii_case(stringify!($event)) {
+                        return Some(Self::$event);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+autocmd_events! {
+    BufAdd,
+    BufDelete,
+    BufEnter,
+    BufFilePost,
+    BufFilePre,
+    BufHidden,
+    BufLeave,
+    BufModifiedSet,
+    BufNew,
+    BufNewFile,
+    BufReadCmd,
+    BufReadPost,
+    BufReadPre,
+    BufUnload,
+    BufWinEnter,
+    BufWinLeave,
+    BufWipeout,
+    BufWriteCmd,
+    BufWritePost,
+    BufWritePre,
+    ChanInfo,
+    ChanOpen,
+    CmdUndefined,
+    CmdlineChanged,
+    CmdlineEnter,
+    CmdlineLeave,
+    CmdwinEnter,
+    CmdwinLeave,
+    ColorScheme,
+    ColorSchemePre,
+    CompleteChanged,
+    CompleteDone,
+    CompleteDonePre,
+    CursorHold,
+    CursorHoldI,
+    CursorMoved,
+    CursorMovedI,
+    DiagnosticChanged,
+    DiffUpdated,
+    DirChanged,
+    DirChangedPre,
+    EncodingChanged,
+    ExitPre,
+    FileAppendCmd,
+    FileAppendPost,
+    FileAppendPre,
+    FileChangedRO,
+    FileChangedShell,
+    FileChangedShellPost,
+    FileReadCmd,
+    FileReadPost,
+    FileReadPre,
+    FileType,
+    FileWriteCmd,
+    FileWritePost,
+    FileWritePre,
+    FilterReadPost,
+    FilterReadPre,
+    FilterWritePost,
+    FilterWritePre,
+    FocusGained,
+    FocusLost,
+    FuncUndefined,
+    GUIEnter,
+    GUIFailed,
+    InsertChange,
+    InsertCharPre,
+    InsertEnter,
+    InsertLeave,
+    InsertLeavePre,
+    LspAttach,
+    LspDetach,
+    #[cfg(feature = "neovim-nightly")]
+    LspProgress,
+    #[cfg(feature = "neovim-nightly")]
+    LspRequest,
+    #[cfg(not(feature = "neovim-0-8"))]
+    LspTokenUpdate,
+    MenuPopup,
+    ModeChanged,
+    OptionSet,
+    QuickFixCmdPost,
+    QuickFixCmdPre,
+    QuitPre,
+    RecordingEnter,
+    RecordingLeave,
+    RemoteReply,
+    #[cfg(feature = "neovim-nightly")]
+    SafeState,
+    SearchWrapped,
+    SessionLoadPost,
+    #[cfg(feature = "neovim-nightly")]
+    SessionWritePost,
+    ShellCmdPost,
+    ShellFilterPost,
+    Signal,
+    SourceCmd,
+    SourcePost,
+    SourcePre,
+    SpellFileMissing,
+    StdinReadPost,
+    StdinReadPre,
+    SwapExists,
+    Syntax,
+    TabClosed,
+    TabEnter,
+    TabLeave,
+    TabNew,
+    TabNewEntered,
+    TermChanged,
+    TermClose,
+    TermEnter,
+    TermLeave,
+    TermOpen,
+    TermResponse,
+    TextChanged,
+    TextChangedI,
+    TextChangedP,
+    #[cfg(not(feature = "neovim-0-8"))]
+    TextChangedT,
+    TextYankPost,
+    UIEnter,
+    UILeave,
+    User,
+    VimEnter,
+    VimLeave,
+    VimLeavePre,
+    VimResized,
+    VimResume,
+    VimSuspend,
+    WinClosed,
+    WinEnter,
+    WinLeave,
+    WinNew,
+    #[cfg(not(feature = "neovim-0-8"))]
+    WinResized,
+    WinScrolled,
+}
+
+impl std::fmt::Display for AutocmdEvent {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<AutocmdEvent> for nvim::String {
+    #[inline]
+    fn from(event: AutocmdEvent) -> Self {
+        event.as_str().into()
+    }
+}
+
+impl From<AutocmdEvent> for Object {
+    #[inline]
+    fn from(event: AutocmdEvent) -> Self {
+        nvim::String::from(event).into()
+    }
+}
@@ -5,6 +5,8 @@ use oxi_types::{
 };
 use serde::Deserialize;
 
+use super::AutocmdEvent;
+use crate::serde_utils as utils;
 use crate::Buffer;
 
 /// Informations related to an autocommand.
@@ -18,9 +20,16 @@ pub struct AutocmdInfos {
     /// Whether the autocommand is a buffer-local one.
     pub buflocal: bool,
 
-    /// The command executed by the autocommand.
+    /// The command executed by the autocommand. Empty if the autocommand
+    /// runs a callback instead.
     pub command: String,
 
+    /// Whether the autocommand runs a callback instead of a command, either
+    /// a Lua function or the name of a Vimscript one.
+    #[serde(rename = "callback", default)]
+    #[serde(deserialize_with = "utils::is_present")]
+    pub has_callback: bool,
+
     /// The autocommand's description.
     #[serde(default)]
     pub desc: Option<String>,
@@ -49,6 +58,15 @@ pub struct AutocmdInfos {
     pub pattern: String,
 }
 
+impl AutocmdInfos {
+    /// Returns the event triggering the autocommand, or `None` if it's not
+    /// one of the [`AutocmdEvent`]s known to this crate.
+    #[inline]
+    pub fn autocmd_event(&self) -> Option<AutocmdEvent> {
+        AutocmdEvent::from_name(&self.event)
+    }
+}
+
 impl FromObject for AutocmdInfos {
     fn from_object(obj: Object) -> Result<Self, conversion::Error> {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
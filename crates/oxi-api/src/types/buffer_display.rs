@@ -0,0 +1,18 @@
+use std::ops::Range;
+
+use crate::Window;
+
+/// A window displaying a buffer, returned by
+/// [`Buffer::display_info()`](crate::Buffer::display_info).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BufferDisplay {
+    /// The window displaying the buffer.
+    pub window: Window,
+
+    /// The zero-indexed, end-exclusive range of buffer lines visible in the
+    /// window, i.e. from [`line("w0")`][1] to [`line("w$")`][1].
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#line()
+    pub visible_lines: Range<usize>,
+}
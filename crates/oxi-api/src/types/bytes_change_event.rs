@@ -0,0 +1,97 @@
+use oxi_luajit::{self as lua, Poppable};
+
+use crate::Buffer;
+
+/// A byte-level change to a buffer, passed to the callback registered to
+/// [`on_bytes`](crate::opts::BufAttachOptsBuilder::on_bytes).
+///
+/// The changed region starts at (`start_row`, `start_col`) and used to end at
+/// (`start_row + old_end_row`, `old_end_col`), spanning `old_end_len` bytes.
+/// It now ends at (`start_row + new_end_row`, `new_end_col`) and spans
+/// `new_end_len` bytes. If the end row is zero the end column is relative to
+/// `start_col`.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BytesChangeEvent {
+    /// The [`Buffer`] that triggered the callback.
+    pub buffer: Buffer,
+
+    /// The value of the buffer-local `b:changedtick` variable.
+    pub changedtick: u32,
+
+    /// Start row of the changed text (0-indexed).
+    pub start_row: usize,
+
+    /// Start column of the changed text.
+    pub start_col: usize,
+
+    /// Byte offset of the changed text from the start of the buffer.
+    pub byte_offset: usize,
+
+    /// Number of rows of the replaced text, relative to `start_row`.
+    pub old_end_row: usize,
+
+    /// End column of the replaced text.
+    pub old_end_col: usize,
+
+    /// Length in bytes of the replaced text.
+    pub old_end_len: usize,
+
+    /// Number of rows of the new text, relative to `start_row`.
+    pub new_end_row: usize,
+
+    /// End column of the new text.
+    pub new_end_col: usize,
+
+    /// Length in bytes of the new text.
+    pub new_end_len: usize,
+}
+
+impl Poppable for BytesChangeEvent {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        // The first argument is always the string literal "bytes".
+        let (
+            _,
+            buffer,
+            changedtick,
+            start_row,
+            start_col,
+            byte_offset,
+            old_end_row,
+            old_end_col,
+            old_end_len,
+            new_end_row,
+            new_end_col,
+            new_end_len,
+        ) = <(
+            String,
+            Buffer,
+            u32,
+            usize,
+            usize,
+            usize,
+            usize,
+            usize,
+            usize,
+            usize,
+            usize,
+            usize,
+        )>::pop(lstate)?;
+
+        Ok(Self {
+            buffer,
+            changedtick,
+            start_row,
+            start_col,
+            byte_offset,
+            old_end_row,
+            old_end_col,
+            old_end_len,
+            new_end_row,
+            new_end_col,
+            new_end_len,
+        })
+    }
+}
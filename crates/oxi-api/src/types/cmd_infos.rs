@@ -211,6 +211,7 @@ impl From<&CmdInfos> for KeyDict_cmd {
                 .unwrap_or_default(),
             mods: infos
                 .mods
+                .clone()
                 .map(|v| v.to_object().unwrap())
                 .unwrap_or_default(),
             args: Array::from_iter(infos.args.clone()).into(),
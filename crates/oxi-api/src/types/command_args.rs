@@ -5,7 +5,9 @@ use oxi_types::{
 };
 use serde::Deserialize;
 
+use super::{CommandAddr, CommandArgsRange, CommandInfos};
 use crate::serde_utils as utils;
+use crate::{Buffer, Error, Result};
 
 /// Arguments passed to functions executed by commands. See
 /// [`Buffer::create_user_command`](crate::Buffer::create_user_command) to
@@ -38,6 +40,10 @@ pub struct CommandArgs {
     #[serde(deserialize_with = "utils::empty_string_is_none")]
     pub mods: Option<String>,
 
+    /// The name of the command.
+    #[serde(default)]
+    pub name: String,
+
     /// The number of items in the command range.
     pub range: u8,
 
@@ -49,8 +55,51 @@ pub struct CommandArgs {
     pub smods: super::CommandModifiers,
 }
 
+impl CommandArgs {
+    /// Returns the [`CommandAddr`] the command was defined with, which
+    /// determines what the numbers in its range refer to.
+    pub fn addr(&self) -> Result<CommandAddr> {
+        Ok(self.definition()?.addr.unwrap_or(CommandAddr::Lines))
+    }
+
+    /// Returns the range or the count the command was executed with,
+    /// interpreted according to the command's [`CommandAddr`]. Returns
+    /// `None` if the command was defined without a range or a count.
+    ///
+    /// Note that commands taking a range always receive one, since Neovim
+    /// uses the default range when none is given.
+    pub fn typed_range(&self) -> Result<Option<CommandArgsRange>> {
+        let infos = self.definition()?;
+
+        Ok(CommandArgsRange::new(
+            infos.addr,
+            infos.range,
+            infos.count,
+            self.line1,
+            self.line2,
+            self.count,
+        ))
+    }
+
+    /// Looks up the definition of the command, giving precedence to the
+    /// commands local to the current buffer like Neovim does.
+    fn definition(&self) -> Result<CommandInfos> {
+        let opts = Default::default();
+
+        Buffer::current()
+            .get_commands(&opts)?
+            .chain(crate::get_commands(&opts)?)
+            .find(|infos| infos.name == self.name)
+            .ok_or_else(|| {
+                Error::custom(format!("no user command named '{}'", self.name))
+            })
+    }
+}
+
 impl FromObject for CommandArgs {
-    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+    fn from_object(
+        obj: Object,
+    ) -> std::result::Result<Self, conversion::Error> {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
     }
 }
@@ -58,7 +107,7 @@ impl FromObject for CommandArgs {
 impl oxi_luajit::Poppable for CommandArgs {
     unsafe fn pop(
         lstate: *mut oxi_luajit::ffi::lua_State,
-    ) -> Result<Self, oxi_luajit::Error> {
+    ) -> std::result::Result<Self, oxi_luajit::Error> {
         let obj = Object::pop(lstate)?;
 
         Self::from_object(obj)
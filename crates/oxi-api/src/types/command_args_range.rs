@@ -0,0 +1,126 @@
+use std::ops::RangeInclusive;
+
+use super::{CommandAddr, CommandRange};
+
+/// The range a user command was executed with, interpreted according to the
+/// command's [`CommandAddr`]. See
+/// [`CommandArgs::typed_range`](super::CommandArgs::typed_range).
+///
+/// Every range is inclusive and uses the same numbering as Neovim, so it's
+/// 1-based except for the `0` that some address types accept (e.g.
+/// `:0tabnew`).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CommandArgsRange {
+    /// A range of line numbers.
+    Lines(RangeInclusive<usize>),
+
+    /// A range of indices in the argument list.
+    Arguments(RangeInclusive<usize>),
+
+    /// A range of buffer numbers.
+    Buffers(RangeInclusive<usize>),
+
+    /// A range of buffer numbers, counting only loaded buffers.
+    LoadedBuffers(RangeInclusive<usize>),
+
+    /// A range of window numbers in the current tabpage.
+    Windows(RangeInclusive<usize>),
+
+    /// A range of tabpage numbers.
+    Tabs(RangeInclusive<usize>),
+
+    /// A range of entries in the quickfix list.
+    Quickfix(RangeInclusive<usize>),
+
+    /// A range the command is free to interpret.
+    Other(RangeInclusive<usize>),
+
+    /// The count of a command defined with a `count` or with a numeric
+    /// [`CommandRange::Count`].
+    Count(u32),
+}
+
+impl CommandArgsRange {
+    /// Interprets the range received by a command, given its definition.
+    /// Returns `None` if the command doesn't take a range or a count.
+    pub(crate) fn new(
+        addr: Option<CommandAddr>,
+        def_range: Option<CommandRange>,
+        def_count: Option<u32>,
+        line1: usize,
+        line2: usize,
+        count: Option<u32>,
+    ) -> Option<Self> {
+        if def_count.is_some()
+            || matches!(def_range, Some(CommandRange::Count(_)))
+        {
+            return Some(Self::Count(count.unwrap_or(line2 as u32)));
+        }
+
+        def_range?;
+
+        let range = line1..=line2;
+
+        Some(match addr.unwrap_or(CommandAddr::Lines) {
+            CommandAddr::Lines => Self::Lines(range),
+            CommandAddr::Arguments => Self::Arguments(range),
+            CommandAddr::Buffers => Self::Buffers(range),
+            CommandAddr::LoadedBuffers => Self::LoadedBuffers(range),
+            CommandAddr::Windows => Self::Windows(range),
+            CommandAddr::Tabs => Self::Tabs(range),
+            CommandAddr::Quickfix => Self::Quickfix(range),
+            CommandAddr::Other => Self::Other(range),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_range() {
+        let range = CommandArgsRange::new(None, None, None, 3, 3, None);
+        assert_eq!(None, range);
+    }
+
+    #[test]
+    fn lines_by_default() {
+        let range = CommandArgsRange::new(
+            None,
+            Some(CommandRange::CurrentLine),
+            None,
+            2,
+            5,
+            Some(5),
+        );
+        assert_eq!(Some(CommandArgsRange::Lines(2..=5)), range);
+    }
+
+    #[test]
+    fn typed_by_addr() {
+        let range = CommandArgsRange::new(
+            Some(CommandAddr::Buffers),
+            Some(CommandRange::WholeFile),
+            None,
+            1,
+            4,
+            Some(4),
+        );
+        assert_eq!(Some(CommandArgsRange::Buffers(1..=4)), range);
+    }
+
+    #[test]
+    fn count() {
+        let range = CommandArgsRange::new(
+            Some(CommandAddr::Tabs),
+            Some(CommandRange::Count(0)),
+            Some(0),
+            1,
+            7,
+            Some(7),
+        );
+        assert_eq!(Some(CommandArgsRange::Count(7)), range);
+    }
+}
@@ -3,21 +3,31 @@ use oxi_types::{
     serde::Serializer,
     Object,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use super::SplitModifier;
 use crate::serde_utils as utils;
 
 /// See `:h command-modifiers` for more infos.
 #[non_exhaustive]
-#[derive(
-    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize, Serialize,
-)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub struct CommandModifiers {
     pub browse: bool,
     pub confirm: bool,
     pub emsg_silent: bool,
+    #[serde(
+        default,
+        deserialize_with = "empty_pattern_is_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub filter: Option<FilterModifier>,
     pub hide: bool,
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
+    )]
+    pub horizontal: bool,
     pub keepalt: bool,
     pub keepjumps: bool,
     pub keepmarks: bool,
@@ -29,13 +39,237 @@ pub struct CommandModifiers {
     pub silent: bool,
     #[serde(deserialize_with = "utils::empty_string_is_none")]
     pub split: Option<SplitModifier>,
+    /// The count given to `:tab`, or `-1` if the command isn't prefixed
+    /// with it.
     pub tab: i32,
+    pub unsilent: bool,
+    /// The count given to `:verbose`, or `-1` if the command isn't
+    /// prefixed with it.
     pub verbose: i32,
     pub vertical: bool,
 }
 
+impl Default for CommandModifiers {
+    /// No modifiers. `tab` and `verbose` are set to `-1` like in the
+    /// modifiers returned by Neovim, since `0` would run the command as
+    /// `:0tab` or `:0verbose`.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            browse: false,
+            confirm: false,
+            emsg_silent: false,
+            filter: None,
+            hide: false,
+            #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+            horizontal: false,
+            keepalt: false,
+            keepjumps: false,
+            keepmarks: false,
+            keeppatterns: false,
+            lockmarks: false,
+            noautocmd: false,
+            noswapfile: false,
+            sandbox: false,
+            silent: false,
+            split: None,
+            tab: -1,
+            unsilent: false,
+            verbose: -1,
+            vertical: false,
+        }
+    }
+}
+
+/// The `filter` key of [`CommandModifiers`]. See `:h :filter` for more
+/// infos.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize, Serialize,
+)]
+pub struct FilterModifier {
+    /// Only the lines of the output matching this pattern are kept.
+    pub pattern: String,
+
+    /// Whether to invert the match, like `:filter!`.
+    pub force: bool,
+}
+
+/// Neovim always returns a `filter`, with an empty pattern if the command
+/// isn't filtered.
+fn empty_pattern_is_none<'de, D>(
+    deserializer: D,
+) -> Result<Option<FilterModifier>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let filter = Option::<FilterModifier>::deserialize(deserializer)?;
+    Ok(filter.filter(|filter| !filter.pattern.is_empty()))
+}
+
+impl CommandModifiers {
+    #[inline(always)]
+    pub fn builder() -> CommandModifiersBuilder {
+        CommandModifiersBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct CommandModifiersBuilder(CommandModifiers);
+
+impl CommandModifiersBuilder {
+    #[inline]
+    pub fn browse(&mut self, browse: bool) -> &mut Self {
+        self.0.browse = browse;
+        self
+    }
+
+    #[inline]
+    pub fn confirm(&mut self, confirm: bool) -> &mut Self {
+        self.0.confirm = confirm;
+        self
+    }
+
+    #[inline]
+    pub fn emsg_silent(&mut self, emsg_silent: bool) -> &mut Self {
+        self.0.emsg_silent = emsg_silent;
+        self
+    }
+
+    /// Only keeps the lines of the output matching `pattern`, or the ones
+    /// not matching it if `force` is `true`.
+    #[inline]
+    pub fn filter(
+        &mut self,
+        pattern: impl Into<String>,
+        force: bool,
+    ) -> &mut Self {
+        self.0.filter =
+            Some(FilterModifier { pattern: pattern.into(), force });
+        self
+    }
+
+    #[inline]
+    pub fn hide(&mut self, hide: bool) -> &mut Self {
+        self.0.hide = hide;
+        self
+    }
+
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
+    )]
+    #[inline]
+    pub fn horizontal(&mut self, horizontal: bool) -> &mut Self {
+        self.0.horizontal = horizontal;
+        self
+    }
+
+    #[inline]
+    pub fn keepalt(&mut self, keepalt: bool) -> &mut Self {
+        self.0.keepalt = keepalt;
+        self
+    }
+
+    #[inline]
+    pub fn keepjumps(&mut self, keepjumps: bool) -> &mut Self {
+        self.0.keepjumps = keepjumps;
+        self
+    }
+
+    #[inline]
+    pub fn keepmarks(&mut self, keepmarks: bool) -> &mut Self {
+        self.0.keepmarks = keepmarks;
+        self
+    }
+
+    #[inline]
+    pub fn keeppatterns(&mut self, keeppatterns: bool) -> &mut Self {
+        self.0.keeppatterns = keeppatterns;
+        self
+    }
+
+    #[inline]
+    pub fn lockmarks(&mut self, lockmarks: bool) -> &mut Self {
+        self.0.lockmarks = lockmarks;
+        self
+    }
+
+    #[inline]
+    pub fn noautocmd(&mut self, noautocmd: bool) -> &mut Self {
+        self.0.noautocmd = noautocmd;
+        self
+    }
+
+    #[inline]
+    pub fn noswapfile(&mut self, noswapfile: bool) -> &mut Self {
+        self.0.noswapfile = noswapfile;
+        self
+    }
+
+    #[inline]
+    pub fn sandbox(&mut self, sandbox: bool) -> &mut Self {
+        self.0.sandbox = sandbox;
+        self
+    }
+
+    #[inline]
+    pub fn silent(&mut self, silent: bool) -> &mut Self {
+        self.0.silent = silent;
+        self
+    }
+
+    #[inline]
+    pub fn split(&mut self, split: SplitModifier) -> &mut Self {
+        self.0.split = Some(split);
+        self
+    }
+
+    #[inline]
+    pub fn tab(&mut self, tab: i32) -> &mut Self {
+        self.0.tab = tab;
+        self
+    }
+
+    #[inline]
+    pub fn unsilent(&mut self, unsilent: bool) -> &mut Self {
+        self.0.unsilent = unsilent;
+        self
+    }
+
+    #[inline]
+    pub fn verbose(&mut self, verbose: i32) -> &mut Self {
+        self.0.verbose = verbose;
+        self
+    }
+
+    #[inline]
+    pub fn vertical(&mut self, vertical: bool) -> &mut Self {
+        self.0.vertical = vertical;
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> CommandModifiers {
+        std::mem::take(&mut self.0)
+    }
+}
+
 impl ToObject for CommandModifiers {
     fn to_object(self) -> Result<Object, conversion::Error> {
         self.serialize(Serializer::new()).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_leaves_counts_unset() {
+        let mods = CommandModifiers::builder().silent(true).build();
+        assert!(mods.silent);
+        assert_eq!(-1, mods.tab);
+        assert_eq!(-1, mods.verbose);
+    }
+}
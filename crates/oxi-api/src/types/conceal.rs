@@ -0,0 +1,118 @@
+use oxi_types as nvim;
+
+/// How concealed text is displayed in a window, i.e. the value of the
+/// [`'conceallevel'`][1] option.
+///
+/// Text is concealed either by syntax rules or by extmarks set with the
+/// [`conceal`](crate::opts::SetExtmarkOptsBuilder::conceal) option.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'conceallevel'
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ConcealLevel {
+    /// Concealed text is shown normally.
+    #[default]
+    Shown,
+
+    /// Each block of concealed text is replaced by one character: its
+    /// replacement character if it has one, the `conceal` item of
+    /// `'listchars'` if set, or a space otherwise.
+    Replaced,
+
+    /// Concealed text is completely hidden, unless it has a replacement
+    /// character.
+    ReplacedOrHidden,
+
+    /// Concealed text is completely hidden.
+    Hidden,
+}
+
+impl ConcealLevel {
+    #[inline]
+    pub(crate) fn from_level(level: u8) -> Option<Self> {
+        match level {
+            0 => Some(Self::Shown),
+            1 => Some(Self::Replaced),
+            2 => Some(Self::ReplacedOrHidden),
+            3 => Some(Self::Hidden),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn level(self) -> u8 {
+        match self {
+            Self::Shown => 0,
+            Self::Replaced => 1,
+            Self::ReplacedOrHidden => 2,
+            Self::Hidden => 3,
+        }
+    }
+}
+
+/// The modes in which the text of the cursor line is concealed, i.e. the
+/// value of the [`'concealcursor'`][1] option. By default the cursor line is
+/// never concealed.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'concealcursor'
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ConcealCursor {
+    /// Conceal in normal mode.
+    pub normal: bool,
+
+    /// Conceal in visual mode.
+    pub visual: bool,
+
+    /// Conceal in insert mode.
+    pub insert: bool,
+
+    /// Conceal when editing the command line.
+    pub command: bool,
+}
+
+impl From<&str> for ConcealCursor {
+    /// Parses the value of `'concealcursor'`, ignoring unknown flags.
+    fn from(flags: &str) -> Self {
+        Self {
+            normal: flags.contains('n'),
+            visual: flags.contains('v'),
+            insert: flags.contains('i'),
+            command: flags.contains('c'),
+        }
+    }
+}
+
+impl From<ConcealCursor> for nvim::String {
+    fn from(cursor: ConcealCursor) -> Self {
+        let flags = [
+            (cursor.normal, 'n'),
+            (cursor.visual, 'v'),
+            (cursor.insert, 'i'),
+            (cursor.command, 'c'),
+        ];
+
+        flags
+            .iter()
+            .filter_map(|&(enabled, flag)| enabled.then_some(flag))
+            .collect::<String>()
+            .as_str()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conceal_cursor_roundtrip() {
+        let cursor = ConcealCursor {
+            normal: true,
+            command: true,
+            ..Default::default()
+        };
+        let flags = nvim::String::from(cursor);
+        assert_eq!("nc", flags.to_string_lossy());
+        assert_eq!(cursor, ConcealCursor::from("nc"));
+        assert_eq!(ConcealCursor::default(), ConcealCursor::from(""));
+    }
+}
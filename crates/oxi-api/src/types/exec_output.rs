@@ -0,0 +1,22 @@
+use oxi_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+/// The result of [`exec2()`](crate::exec2).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct ExecOutput {
+    /// The output of the executed commands, only set if the
+    /// [`output`](crate::opts::ExecOptsBuilder::output) option was `true`.
+    #[serde(default)]
+    pub output: Option<String>,
+}
+
+impl FromObject for ExecOutput {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
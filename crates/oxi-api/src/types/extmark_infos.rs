@@ -7,10 +7,19 @@ use serde::Deserialize;
 
 use super::{ExtmarkHlMode, ExtmarkVirtTextPosition};
 
-/// Extmark infos returned by `Buffer::get_extmark_by_id`.
+/// Extmark infos returned by
+/// [`Buffer::get_extmark_by_id()`](crate::Buffer::get_extmark_by_id) and
+/// [`Buffer::get_extmarks()`](crate::Buffer::get_extmarks) when the `details`
+/// option is set.
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct ExtmarkInfos {
+    #[serde(default)]
+    pub conceal: Option<String>,
+
+    #[serde(default)]
+    pub cursorline_hl_group: Option<String>,
+
     #[serde(default)]
     pub end_col: Option<usize>,
 
@@ -29,14 +38,39 @@ pub struct ExtmarkInfos {
     #[serde(default)]
     pub hl_mode: Option<ExtmarkHlMode>,
 
+    #[serde(default)]
+    pub line_hl_group: Option<String>,
+
+    /// The namespace the extmark belongs to.
+    #[serde(default)]
+    pub ns_id: Option<u32>,
+
+    #[serde(default)]
+    pub number_hl_group: Option<String>,
+
     #[serde(default)]
     pub priority: Option<u32>,
 
     pub right_gravity: bool,
 
+    #[serde(default)]
+    pub sign_hl_group: Option<String>,
+
+    #[serde(default)]
+    pub sign_text: Option<String>,
+
+    #[serde(default)]
+    pub spell: Option<bool>,
+
     #[serde(default)]
     pub ui_watched: Option<bool>,
 
+    /// The URL the text of the extmark links to, set with
+    /// `SetExtmarkOptsBuilder::url()` on Neovim versions supporting
+    /// hyperlinks.
+    #[serde(default)]
+    pub url: Option<String>,
+
     #[serde(default)]
     pub virt_lines: Option<Vec<Vec<(String, String)>>>,
 
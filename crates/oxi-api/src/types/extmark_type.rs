@@ -0,0 +1,34 @@
+use oxi_types as nvim;
+use serde::Deserialize;
+
+/// The kind of decoration an extmark has, used to filter the extmarks
+/// returned by [`Buffer::get_extmarks()`](crate::Buffer::get_extmarks).
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum ExtmarkType {
+    /// Extmarks highlighting a range of text.
+    Highlight,
+
+    /// Extmarks placing a sign in the sign column.
+    Sign,
+
+    /// Extmarks with virtual text.
+    VirtText,
+
+    /// Extmarks with virtual lines.
+    VirtLines,
+}
+
+impl From<ExtmarkType> for nvim::String {
+    fn from(ty: ExtmarkType) -> Self {
+        use ExtmarkType::*;
+
+        Self::from(match ty {
+            Highlight => "highlight",
+            Sign => "sign",
+            VirtText => "virt_text",
+            VirtLines => "virt_lines",
+        })
+    }
+}
@@ -0,0 +1,48 @@
+/// The line ending used when reading and writing a buffer, i.e. the value of
+/// the [`'fileformat'`][1] option.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'fileformat'
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum FileFormat {
+    /// Lines end with `<CR><NL>`.
+    Dos,
+
+    /// Lines end with `<CR>`.
+    Mac,
+
+    /// Lines end with `<NL>`.
+    #[default]
+    Unix,
+}
+
+impl FileFormat {
+    #[inline]
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dos" => Some(Self::Dos),
+            "mac" => Some(Self::Mac),
+            "unix" => Some(Self::Unix),
+            _ => None,
+        }
+    }
+
+    /// The name of the format, as used by `'fileformat'`.
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dos => "dos",
+            Self::Mac => "mac",
+            Self::Unix => "unix",
+        }
+    }
+
+    /// The line ending of the format.
+    #[inline]
+    pub fn line_ending(self) -> &'static [u8] {
+        match self {
+            Self::Dos => b"\r\n",
+            Self::Mac => b"\r",
+            Self::Unix => b"\n",
+        }
+    }
+}
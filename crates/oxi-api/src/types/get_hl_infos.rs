@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use super::HighlightInfos;
+
+/// The highlight definitions returned by [`get_hl()`](crate::get_hl).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GetHlInfos {
+    /// The definition of the highlight group selected by the `id` or `name`
+    /// options.
+    Single(HighlightInfos),
+
+    /// The definitions of all the highlight groups in the namespace, indexed
+    /// by name.
+    Map(HashMap<String, HighlightInfos>),
+}
+
+impl GetHlInfos {
+    /// Returns the definition of a single highlight group, if the options
+    /// selected one.
+    #[inline]
+    pub fn into_single(self) -> Option<HighlightInfos> {
+        match self {
+            Self::Single(infos) => Some(infos),
+            Self::Map(_) => None,
+        }
+    }
+
+    /// Returns the definitions of all the highlight groups, if the options
+    /// didn't select a single one.
+    #[inline]
+    pub fn into_map(self) -> Option<HashMap<String, HighlightInfos>> {
+        match self {
+            Self::Single(_) => None,
+            Self::Map(map) => Some(map),
+        }
+    }
+}
@@ -9,21 +9,40 @@ use serde::Deserialize;
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Default)]
 pub struct HighlightInfos {
+    #[serde(alias = "bg")]
     pub background: Option<u32>,
     pub bg_indexed: Option<bool>,
     pub blend: Option<u32>,
     pub bold: Option<bool>,
+    pub ctermbg: Option<u32>,
+    pub ctermfg: Option<u32>,
+
+    /// Whether the group was defined with `default`, i.e. without
+    /// overriding an existing definition.
+    pub default: Option<bool>,
+
     pub fg_indexed: Option<bool>,
+    #[serde(alias = "fg")]
     pub foreground: Option<u32>,
     pub italic: Option<bool>,
+
+    /// The name of the group this group is linked to. Only set by
+    /// [`get_hl()`](crate::get_hl) when the link isn't resolved.
+    pub link: Option<String>,
+
+    pub nocombine: Option<bool>,
     pub reverse: Option<bool>,
+    #[serde(alias = "sp")]
     pub special: Option<u32>,
     pub standout: Option<bool>,
     pub strikethrough: Option<bool>,
     pub undercurl: Option<bool>,
+    #[serde(alias = "underdashed")]
     pub underdash: Option<bool>,
+    #[serde(alias = "underdotted")]
     pub underdot: Option<bool>,
     pub underline: Option<bool>,
+    #[serde(alias = "underdouble")]
     pub underlineline: Option<bool>,
     pub altfont: Option<bool>,
 }
@@ -0,0 +1,42 @@
+use oxi_types::{self as nvim, conversion::FromObject, serde::Serializer};
+use serde::Serialize;
+
+/// One of the [histories][1] Neovim keeps track of, used by the functions in
+/// the [`history`](crate::history) module.
+///
+/// [1]: https://neovim.io/doc/user/cmdline.html#cmdline-history
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub enum HistoryKind {
+    /// The history of `:` commands.
+    #[serde(rename = "cmd")]
+    Cmd,
+
+    /// The history of search strings.
+    #[serde(rename = "search")]
+    Search,
+
+    /// The history of expressions entered in the `=` register.
+    #[serde(rename = "expr")]
+    Expr,
+
+    /// The history of input lines, e.g. the ones entered in [`input()`][1].
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#input()
+    #[serde(rename = "input")]
+    Input,
+
+    /// The history of debug mode commands.
+    #[serde(rename = "debug")]
+    Debug,
+}
+
+impl From<HistoryKind> for nvim::String {
+    fn from(kind: HistoryKind) -> Self {
+        nvim::String::from_object(
+            kind.serialize(Serializer::new())
+                .expect("`HistoryKind` is serializable"),
+        )
+        .expect("`HistoryKind` is serialized into a string")
+    }
+}
@@ -0,0 +1,86 @@
+/// Where to put the cursor after replacing the text of the line it's on via
+/// [`Window::set_current_line`](crate::Window::set_current_line).
+///
+/// In both cases the cursor is never placed in the middle of a multibyte
+/// character, and it's moved to the end of the line if the new text is
+/// shorter than the old one.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum KeepCursor {
+    /// Keeps the same number of characters before the cursor, which keeps
+    /// it in place when the text before it changes encoding width, e.g.
+    /// when replacing `"e"` with `"é"`.
+    Column,
+
+    /// Keeps the same byte column.
+    Clamp,
+}
+
+impl KeepCursor {
+    /// Returns the byte column of the cursor in `new`, given that it was on
+    /// byte column `col` of `old`.
+    pub(crate) fn new_column(
+        self,
+        old: &[u8],
+        new: &[u8],
+        col: usize,
+    ) -> usize {
+        let is_char_start = |byte: &u8| byte & 0xC0 != 0x80;
+
+        match self {
+            Self::Column => {
+                let num_chars = old[..col.min(old.len())]
+                    .iter()
+                    .filter(|byte| is_char_start(byte))
+                    .count();
+
+                new.iter()
+                    .enumerate()
+                    .filter(|(_, byte)| is_char_start(byte))
+                    .nth(num_chars)
+                    .map(|(idx, _)| idx)
+                    .unwrap_or(new.len())
+            },
+
+            Self::Clamp => {
+                let mut col = col.min(new.len());
+                while col > 0 && col < new.len() && !is_char_start(&new[col]) {
+                    col -= 1;
+                }
+                col
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_counts_chars() {
+        let col = KeepCursor::Column.new_column(b"ee x", "éé x".as_bytes(), 3);
+        assert_eq!(5, col);
+
+        let col = KeepCursor::Column.new_column("éé x".as_bytes(), b"ee x", 5);
+        assert_eq!(3, col);
+    }
+
+    #[test]
+    fn clamp_keeps_bytes() {
+        let col = KeepCursor::Clamp.new_column(b"ee x", "éé x".as_bytes(), 3);
+        assert_eq!(2, col);
+
+        let col = KeepCursor::Clamp.new_column(b"ee x", b"ee xyz", 3);
+        assert_eq!(3, col);
+    }
+
+    #[test]
+    fn shorter_line_moves_to_end() {
+        let col = KeepCursor::Column.new_column(b"foo bar", b"foo", 5);
+        assert_eq!(3, col);
+
+        let col = KeepCursor::Clamp.new_column(b"foo bar", b"foo", 5);
+        assert_eq!(3, col);
+    }
+}
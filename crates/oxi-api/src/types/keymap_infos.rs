@@ -8,17 +8,23 @@ use serde::Deserialize;
 
 use super::Mode;
 use crate::serde_utils as utils;
+use crate::Buffer;
 
 #[non_exhaustive]
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize)]
 pub struct KeymapInfos {
-    /// Whether the mapping is local to a specific buffer.
-    #[serde(deserialize_with = "utils::bool_from_int")]
-    pub buffer: bool,
+    /// The buffer the mapping is local to, or `None` if it's a global
+    /// mapping.
+    #[serde(deserialize_with = "buffer_from_int")]
+    pub buffer: Option<Buffer>,
 
     /// Optional callback triggered by the keymap.
     pub callback: Option<Function<(), ()>>,
 
+    /// The description of the mapping, if it was given one.
+    #[serde(default)]
+    pub desc: Option<String>,
+
     /// Whether the keymap argument is an expression.
     #[serde(deserialize_with = "utils::bool_from_int")]
     pub expr: bool,
@@ -64,3 +70,13 @@ impl FromObject for KeymapInfos {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
     }
 }
+
+/// Neovim sets the `buffer` field to the handle of the buffer for
+/// buffer-local mappings and to zero for global ones.
+fn buffer_from_int<'de, D>(deserializer: D) -> Result<Option<Buffer>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let handle = i32::deserialize(deserializer)?;
+    Ok((handle != 0).then(|| handle.into()))
+}
@@ -0,0 +1,81 @@
+use crate::opts::{GetMarkOpts, SetMarkOpts};
+use crate::{Buffer, Result};
+
+/// The kind of a mark, which determines where it's stored. See
+/// `:h mark-motions` for more infos.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MarkKind {
+    /// A lowercase mark, local to a buffer.
+    Local,
+
+    /// An uppercase or numbered mark, which remembers the buffer it's in.
+    File,
+
+    /// A mark set by Neovim itself, like `.` or `[`, local to a buffer.
+    Special,
+}
+
+impl MarkKind {
+    /// Returns the kind of the mark called `name`.
+    #[inline]
+    pub fn of(name: char) -> Self {
+        match name {
+            'a'..='z' => Self::Local,
+            'A'..='Z' | '0'..='9' => Self::File,
+            _ => Self::Special,
+        }
+    }
+}
+
+/// The state of a mark, as returned by [`Mark::get`].
+///
+/// Can be used to restore a mark after it has been moved or deleted, e.g.
+/// by an edit of the buffer.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Mark {
+    /// The name of the mark.
+    pub name: char,
+
+    /// The kind of the mark.
+    pub kind: MarkKind,
+
+    /// The buffer the mark is in.
+    pub buffer: Buffer,
+
+    /// The 1-indexed line of the mark.
+    pub row: usize,
+
+    /// The 0-indexed byte column of the mark.
+    pub col: usize,
+}
+
+impl Mark {
+    /// Returns the mark called `name`, or `None` if it's not set. Marks local
+    /// to a buffer are looked up in `buffer`, while file marks are looked up
+    /// globally.
+    pub fn get(name: char, buffer: &Buffer) -> Result<Option<Self>> {
+        let kind = MarkKind::of(name);
+
+        let (row, col, buffer) = match kind {
+            MarkKind::File => {
+                let (row, col, buffer, _) =
+                    crate::get_mark(name, &GetMarkOpts::default())?;
+                (row, col, buffer)
+            },
+
+            MarkKind::Local | MarkKind::Special => {
+                let (row, col) = buffer.get_mark(name)?;
+                (row, col, buffer.clone())
+            },
+        };
+
+        Ok((row != 0).then_some(Self { name, kind, buffer, row, col }))
+    }
+
+    /// Sets the mark to the position it had when it was read.
+    pub fn set(&self) -> Result<()> {
+        let opts = SetMarkOpts::default();
+        self.buffer.clone().set_mark(self.name, self.row, self.col, &opts)
+    }
+}
@@ -1,7 +1,11 @@
 //! Contains various types given to and returned from Neovim API functions.
 
+mod arglist_error;
 mod autocmd_callback_args;
+mod autocmd_event;
 mod autocmd_infos;
+mod buffer_display;
+mod bytes_change_event;
 mod channel_infos;
 mod client_infos;
 mod cmd_infos;
@@ -9,34 +13,55 @@ mod cmd_magic;
 mod cmd_range;
 mod command_addr;
 mod command_args;
+mod command_args_range;
 mod command_complete;
 mod command_infos;
 mod command_modifiers;
 mod command_nargs;
 mod command_range;
+mod conceal;
 mod context_type;
 mod editor_context;
+mod exec_output;
 mod extmark_hl_mode;
 mod extmark_infos;
 mod extmark_position;
+mod extmark_type;
 mod extmark_virt_text_position;
+mod file_format;
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+mod get_hl_infos;
 mod got_mode;
 mod highlight_infos;
+mod history_kind;
+mod keep_cursor;
 mod keymap_infos;
 mod log_level;
+mod mark;
 mod mode;
 mod mouse_action;
 mod mouse_button;
+mod offset_encoding;
+mod open_file_error;
+mod open_mode;
+mod option_chars;
+mod option_flags;
 mod option_infos;
 mod parsed_viml_expression;
 mod paste_phase;
 mod proc_infos;
 mod register_type;
+mod resize;
+mod save_failure;
 mod split_modifier;
 mod statusline_highlight_infos;
 mod statusline_infos;
+mod swap_exists_action;
+mod swap_info;
 mod ui_infos;
 mod viml_ast_node;
+#[cfg(feature = "neovim-nightly")]
+mod win_text_height;
 mod window_anchor;
 mod window_border;
 mod window_border_char;
@@ -46,8 +71,12 @@ mod window_style;
 mod window_title;
 mod window_title_position;
 
+pub use arglist_error::*;
 pub use autocmd_callback_args::*;
+pub use autocmd_event::*;
 pub use autocmd_infos::*;
+pub use buffer_display::*;
+pub use bytes_change_event::*;
 pub use channel_infos::*;
 pub use client_infos::*;
 pub use cmd_infos::*;
@@ -55,34 +84,55 @@ pub use cmd_magic::*;
 pub use cmd_range::*;
 pub use command_addr::*;
 pub use command_args::*;
+pub use command_args_range::*;
 pub use command_complete::*;
 pub use command_infos::*;
 pub use command_modifiers::*;
 pub use command_nargs::*;
 pub use command_range::*;
+pub use conceal::*;
 pub use context_type::*;
 pub use editor_context::*;
+pub use exec_output::*;
 pub use extmark_hl_mode::*;
 pub use extmark_infos::*;
 pub use extmark_position::*;
+pub use extmark_type::*;
 pub use extmark_virt_text_position::*;
+pub use file_format::*;
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+pub use get_hl_infos::*;
 pub use got_mode::*;
 pub use highlight_infos::*;
+pub use history_kind::*;
+pub use keep_cursor::*;
 pub use keymap_infos::*;
 pub use log_level::*;
+pub use mark::*;
 pub use mode::*;
 pub use mouse_action::*;
 pub use mouse_button::*;
+pub use offset_encoding::*;
+pub use open_file_error::*;
+pub use open_mode::*;
+pub use option_chars::*;
+pub use option_flags::*;
 pub use option_infos::*;
 pub use parsed_viml_expression::*;
 pub use paste_phase::*;
 pub use proc_infos::*;
 pub use register_type::*;
+pub use resize::*;
+pub use save_failure::*;
 pub use split_modifier::*;
 pub use statusline_highlight_infos::*;
 pub use statusline_infos::*;
+pub use swap_exists_action::*;
+pub use swap_info::*;
 pub use ui_infos::*;
 pub use viml_ast_node::*;
+#[cfg(feature = "neovim-nightly")]
+pub use win_text_height::*;
 pub use window_anchor::*;
 pub use window_border::*;
 pub use window_border_char::*;
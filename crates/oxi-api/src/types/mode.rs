@@ -1,9 +1,22 @@
+use std::ffi::CStr;
+
 use oxi_types as nvim;
 use serde::Deserialize;
 
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub enum Mode {
+    /// Every mode. Only meaningful when getting keymaps, i.e. in
+    /// [`get_keymap`](crate::get_keymap) and
+    /// [`Buffer::get_keymap`](crate::Buffer::get_keymap).
+    ///
+    /// Setting or deleting a keymap with it returns an error, since Neovim
+    /// would treat it like
+    /// [`NormalVisualOperator`](Mode::NormalVisualOperator) and the keymap
+    /// couldn't be found again by its mode.
+    #[serde(skip_deserializing)]
+    All,
+
     #[serde(rename = "c")]
     CmdLine,
 
@@ -48,6 +61,17 @@ macro_rules! is_mode {
 }
 
 impl Mode {
+    /// Modes whose keymaps don't overlap and together cover every mode. This
+    /// is used to get the keymaps for [`Mode::All`], since Neovim doesn't
+    /// support that natively.
+    pub(crate) const DISJOINT: [Self; 4] = [
+        Self::NormalVisualOperator,
+        Self::InsertCmdLine,
+        Self::Terminal,
+        Self::Langmap,
+    ];
+
+    is_mode!(is_all, All);
     is_mode!(is_cmd_line, CmdLine);
     is_mode!(is_insert, Insert);
     is_mode!(is_langmap, Langmap);
@@ -58,24 +82,47 @@ impl Mode {
     is_mode!(is_terminal, Terminal);
     is_mode!(is_visual, Visual);
     is_mode!(is_visual_select, VisualSelect);
+
+    /// Returns an error if the mode is [`Mode::All`], which can't be used to
+    /// set or delete keymaps.
+    pub(crate) fn ensure_not_all(self, function: &str) -> crate::Result<()> {
+        if self.is_all() {
+            return Err(crate::Error::custom(format!(
+                "`Mode::All` can't be passed to `{function}`, it's only \
+                 meaningful when getting keymaps"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the mode as the string Neovim expects, borrowing a static
+    /// literal instead of allocating.
+    pub(crate) fn as_nvim_str(self) -> nvim::NonOwning<'static, nvim::String> {
+        use Mode::*;
+        let mode: &'static [u8] = match self {
+            All => b"\0",
+            CmdLine => b"c\0",
+            Insert => b"i\0",
+            InsertCmdLine => b"!\0",
+            Langmap => b"l\0",
+            NormalVisualOperator => b"\0",
+            Normal => b"n\0",
+            OperatorPending => b"o\0",
+            Select => b"s\0",
+            Terminal => b"t\0",
+            Visual => b"x\0",
+            VisualSelect => b"v\0",
+        };
+        // All the literals above are null-terminated and don't contain other
+        // null bytes.
+        let mode = unsafe { CStr::from_bytes_with_nul_unchecked(mode) };
+        nvim::String::borrowed(mode)
+    }
 }
 
 impl From<Mode> for nvim::String {
+    #[inline]
     fn from(mode: Mode) -> Self {
-        use Mode::*;
-        match mode {
-            CmdLine => "c",
-            Insert => "i",
-            InsertCmdLine => "!",
-            Langmap => "l",
-            NormalVisualOperator => "",
-            Normal => "n",
-            OperatorPending => "o",
-            Select => "s",
-            Terminal => "t",
-            Visual => "x",
-            VisualSelect => "v",
-        }
-        .into()
+        nvim::String::clone(&mode.as_nvim_str())
     }
 }
@@ -0,0 +1,41 @@
+/// The unit in which a column is measured, e.g. by a language server. See
+/// [`text::byte_to_col`](crate::text::byte_to_col) and
+/// [`text::col_to_byte`](crate::text::col_to_byte), which are also used by
+/// the [`SemanticHighlighter`](crate::semantic::SemanticHighlighter).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum OffsetEncoding {
+    /// Columns count bytes, like Neovim does.
+    #[default]
+    Utf8,
+
+    /// Columns count UTF-16 code units, like the LSP does by default.
+    Utf16,
+
+    /// Columns count characters.
+    Utf32,
+}
+
+impl OffsetEncoding {
+    /// Parses the name of an encoding as used by the LSP, i.e. one of
+    /// `"utf-8"`, `"utf-16"` or `"utf-32"`.
+    #[inline]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "utf-8" => Some(Self::Utf8),
+            "utf-16" => Some(Self::Utf16),
+            "utf-32" => Some(Self::Utf32),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of units taken by a character encoded in
+    /// `utf8_len` bytes.
+    #[inline]
+    pub(crate) fn units(self, utf8_len: usize) -> usize {
+        match self {
+            Self::Utf8 => utf8_len,
+            Self::Utf16 if utf8_len == 4 => 2,
+            Self::Utf16 | Self::Utf32 => 1,
+        }
+    }
+}
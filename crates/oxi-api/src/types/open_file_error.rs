@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use thiserror::Error as ThisError;
+
+/// An error returned by [`open_file()`](crate::open_file).
+#[non_exhaustive]
+#[derive(Clone, Debug, ThisError, Eq, PartialEq, Hash)]
+pub enum OpenFileError {
+    /// The file has an existing swap file and the
+    /// [`SwapExistsAction`](crate::types::SwapExistsAction) was `Abort`.
+    #[error("found a swap file at {}", swap_file.display())]
+    SwapExists {
+        /// The path of the existing swap file.
+        swap_file: PathBuf,
+    },
+
+    /// The current buffer has unsaved changes and can't be abandoned.
+    #[error("the current buffer has unsaved changes")]
+    Modified,
+}
@@ -0,0 +1,28 @@
+/// How [`open_file()`](crate::open_file) opens a file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum OpenMode {
+    /// Edits the file in the current window, like [`:edit`][1]. Fails if the
+    /// current buffer has unsaved changes and can't be hidden.
+    ///
+    /// [1]: https://neovim.io/doc/user/editing.html#%3Aedit_f
+    Edit,
+
+    /// Jumps to a window already showing the file, or edits it in the current
+    /// window, like [`:drop`][1]. The current window is split if its buffer
+    /// has unsaved changes and can't be hidden.
+    ///
+    /// [1]: https://neovim.io/doc/user/windows.html#%3Adrop
+    Drop,
+
+    /// Adds the file to the buffer list and loads it without displaying it,
+    /// like [`bufadd()`][1] followed by [`bufload()`][2].
+    ///
+    /// Like `bufload()`, the file is loaded even if it has an existing swap
+    /// file, so the
+    /// [`on_swap_exists`](crate::opts::OpenFileOptsBuilder::on_swap_exists)
+    /// option is ignored.
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#bufadd()
+    /// [2]: https://neovim.io/doc/user/builtin.html#bufload()
+    BAdd,
+}
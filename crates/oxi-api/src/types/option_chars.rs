@@ -0,0 +1,273 @@
+use oxi_types as nvim;
+
+/// Declares a struct representing an option whose value is a
+/// comma-separated list of `{item}:{chars}` pairs, like `'fillchars'`.
+macro_rules! chars_option {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field:ident: $ty:ty,
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+        pub struct $name {
+            $(
+                $(#[$field_attr])*
+                pub $field: Option<$ty>,
+            )*
+
+            /// The items not known by this crate, or whose value couldn't be
+            /// parsed, which are kept so that they're not lost when setting
+            /// the option back.
+            pub other: Vec<(String, String)>,
+        }
+
+        impl From<&str> for $name {
+            /// Parses the value of the option. Items that can't be parsed
+            /// are collected in [`other`](Self::other).
+            fn from(value: &str) -> Self {
+                let mut this = Self::default();
+
+                'items: for (item, chars) in parse_items(value) {
+                    $(
+                        if item == stringify!($field) {
+                            if let Some(chars) = <$ty as Chars>::parse(chars) {
+                                this.$field = Some(chars);
+                                continue 'items;
+                            }
+                        }
+                    )*
+                    this.other.push((item.to_owned(), chars.to_owned()));
+                }
+
+                this
+            }
+        }
+
+        impl From<&$name> for nvim::String {
+            fn from(option: &$name) -> Self {
+                let mut items = Vec::<(&str, String)>::new();
+
+                $(
+                    if let Some(chars) = &option.$field {
+                        items.push((stringify!($field), chars.to_string()));
+                    }
+                )*
+
+                for (item, chars) in &option.other {
+                    items.push((item, chars.clone()));
+                }
+
+                items
+                    .iter()
+                    .map(|(item, chars)| format!("{item}:{chars}"))
+                    .collect::<Vec<_>>()
+                    .join(",")
+                    .as_str()
+                    .into()
+            }
+        }
+    };
+}
+
+chars_option! {
+    /// The characters used to fill the statuslines, the vertical separators
+    /// and other special lines of a window, i.e. the value of the
+    /// [`'fillchars'`][1] option.
+    ///
+    /// Unset items use Neovim's defaults.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'fillchars'
+    pub struct Fillchars {
+        /// The statusline of the current window.
+        stl: char,
+
+        /// The statuslines of the non-current windows.
+        stlnc: char,
+
+        /// The window bar.
+        wbr: char,
+
+        /// The horizontal separators.
+        horiz: char,
+
+        /// The upwards facing horizontal separators.
+        horizup: char,
+
+        /// The downwards facing horizontal separators.
+        horizdown: char,
+
+        /// The vertical separators.
+        vert: char,
+
+        /// The left facing vertical separators.
+        vertleft: char,
+
+        /// The right facing vertical separators.
+        vertright: char,
+
+        /// The crossing separators.
+        verthoriz: char,
+
+        /// The filling of closed folds.
+        fold: char,
+
+        /// The mark of the start of an open fold.
+        foldopen: char,
+
+        /// The mark of a closed fold.
+        foldclose: char,
+
+        /// The mark of the middle of an open fold.
+        foldsep: char,
+
+        /// The deleted lines of the diff mode.
+        diff: char,
+
+        /// The message separator of the `msgsep` flag of `'display'`.
+        msgsep: char,
+
+        /// The empty lines at the end of a buffer.
+        eob: char,
+
+        /// The truncated last line of the `lastline` flag of `'display'`.
+        lastline: char,
+    }
+}
+
+chars_option! {
+    /// The characters used to display special characters in list mode, i.e.
+    /// the value of the [`'listchars'`][1] option.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'listchars'
+    pub struct Listchars {
+        /// The end of each line.
+        eol: char,
+
+        /// Tabs, shown with two or three characters.
+        tab: String,
+
+        /// Spaces.
+        space: char,
+
+        /// Sequences of spaces, cycling through the given characters.
+        multispace: String,
+
+        /// Leading spaces.
+        lead: char,
+
+        /// Sequences of leading spaces, cycling through the given characters.
+        leadmultispace: String,
+
+        /// Trailing spaces.
+        trail: char,
+
+        /// The last column of lines extending beyond the window when
+        /// `'wrap'` is off.
+        extends: char,
+
+        /// The first column of lines starting before the window when
+        /// `'wrap'` is off.
+        precedes: char,
+
+        /// Concealed text when `'conceallevel'` is 1.
+        conceal: char,
+
+        /// Non-breakable spaces.
+        nbsp: char,
+    }
+}
+
+/// A value of the items of `'fillchars'` and `'listchars'`.
+trait Chars: Sized {
+    fn parse(chars: &str) -> Option<Self>;
+}
+
+impl Chars for char {
+    fn parse(chars: &str) -> Option<Self> {
+        let mut iter = chars.chars();
+        match (iter.next(), iter.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+}
+
+impl Chars for String {
+    fn parse(chars: &str) -> Option<Self> {
+        (!chars.is_empty()).then(|| chars.to_owned())
+    }
+}
+
+/// Splits the value of the option in its `(item, chars)` pairs.
+///
+/// The characters of an item are never empty, so the first character after
+/// the colon is always part of them even if it's a comma, e.g. `fold:,`.
+fn parse_items(value: &str) -> Vec<(&str, &str)> {
+    let mut items = Vec::new();
+    let mut rest = value;
+
+    while let Some((item, after)) = rest.split_once(':') {
+        let first = after.chars().next().map_or(0, char::len_utf8);
+
+        let end =
+            after[first..].find(',').map_or(after.len(), |idx| idx + first);
+
+        items.push((item, &after[..end]));
+        rest = after.get(end + 1..).unwrap_or_default();
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fillchars_roundtrip() {
+        let fillchars = Fillchars::from("vert:│,fold:,,eob: ,foo:bar");
+        assert_eq!(Some('│'), fillchars.vert);
+        assert_eq!(Some(','), fillchars.fold);
+        assert_eq!(Some(' '), fillchars.eob);
+        assert_eq!(None, fillchars.diff);
+        assert_eq!(
+            vec![("foo".to_owned(), "bar".to_owned())],
+            fillchars.other
+        );
+
+        let value = nvim::String::from(&fillchars);
+        assert_eq!("vert:│,fold:,,eob: ,foo:bar", value.to_string_lossy());
+    }
+
+    #[test]
+    fn listchars_roundtrip() {
+        let listchars =
+            Listchars::from("tab:> ,trail:-,nbsp:+,lead:ab,multispace:---+");
+        assert_eq!(Some("> ".to_owned()), listchars.tab);
+        assert_eq!(Some('-'), listchars.trail);
+        assert_eq!(Some('+'), listchars.nbsp);
+        assert_eq!(Some("---+".to_owned()), listchars.multispace);
+        assert_eq!(None, listchars.lead);
+        assert_eq!(
+            vec![("lead".to_owned(), "ab".to_owned())],
+            listchars.other
+        );
+
+        let value = nvim::String::from(&listchars);
+        assert_eq!(
+            "tab:> ,multispace:---+,trail:-,nbsp:+,lead:ab",
+            value.to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn empty_option() {
+        assert_eq!(Fillchars::default(), Fillchars::from(""));
+        assert_eq!("", nvim::String::from(&Listchars::default()));
+    }
+}
@@ -0,0 +1,234 @@
+use oxi_types::{
+    self as nvim,
+    conversion::{self, FromObject},
+    Object,
+};
+
+/// The value of an option made of single-character flags, like
+/// [`'shortmess'`][1], [`'formatoptions'`][2] or [`'cpoptions'`][3].
+///
+/// Editing single flags via [`insert`](Flags::insert) and
+/// [`remove`](Flags::remove) instead of setting the whole option keeps the
+/// flags set by other plugins, especially when done through
+/// [`options::update_flags`](crate::options::update_flags).
+///
+/// The flags keep the order they were inserted in, and every flag appears at
+/// most once.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'shortmess'
+/// [2]: https://neovim.io/doc/user/options.html#'formatoptions'
+/// [3]: https://neovim.io/doc/user/options.html#'cpoptions'
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Flags {
+    flags: String,
+}
+
+impl Flags {
+    /// Returns the flags as they appear in the option's value.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.flags
+    }
+
+    /// Returns `true` if the flag is set.
+    #[inline]
+    pub fn contains(&self, flag: impl Into<char>) -> bool {
+        self.flags.contains(flag.into())
+    }
+
+    /// Sets the flag, returning `true` if it wasn't already set.
+    #[inline]
+    pub fn insert(&mut self, flag: impl Into<char>) -> bool {
+        let flag = flag.into();
+        let is_new = !self.flags.contains(flag);
+        if is_new {
+            self.flags.push(flag);
+        }
+        is_new
+    }
+
+    /// Returns `true` if no flag is set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
+
+    /// Returns an iterator over the flags.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        self.flags.chars()
+    }
+
+    /// Unsets the flag, returning `true` if it was set.
+    #[inline]
+    pub fn remove(&mut self, flag: impl Into<char>) -> bool {
+        let flag = flag.into();
+        let was_set = self.flags.contains(flag);
+        if was_set {
+            self.flags.retain(|ch| ch != flag);
+        }
+        was_set
+    }
+}
+
+impl From<&str> for Flags {
+    /// Parses the value of a flag option, dropping duplicated flags.
+    fn from(value: &str) -> Self {
+        let mut flags = Self::default();
+        for flag in value.chars() {
+            flags.insert(flag);
+        }
+        flags
+    }
+}
+
+impl<F: Into<char>> FromIterator<F> for Flags {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        let mut flags = Self::default();
+        for flag in iter {
+            flags.insert(flag);
+        }
+        flags
+    }
+}
+
+impl From<Flags> for Object {
+    fn from(flags: Flags) -> Self {
+        nvim::String::from(flags.flags).into()
+    }
+}
+
+impl FromObject for Flags {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        let value = nvim::String::from_object(obj)?;
+        Ok(Self::from(&*value.to_string_lossy()))
+    }
+}
+
+/// A flag of the [`'shortmess'`][1] option, used to shorten or avoid some of
+/// the messages Neovim displays.
+///
+/// [1]: https://neovim.io/doc/user/options.html#'shortmess'
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ShortmessFlag {
+    /// `f`: use `"(3 of 5)"` instead of `"(file 3 of 5)"`.
+    FileCount,
+
+    /// `i`: use `"[noeol]"` instead of `"[Incomplete last line]"`.
+    NoEol,
+
+    /// `l`: use `"999L, 888B"` instead of `"999 lines, 888 bytes"`.
+    LinesBytes,
+
+    /// `m`: use `"[+]"` instead of `"[Modified]"`.
+    Modified,
+
+    /// `n`: use `"[New]"` instead of `"[New File]"`.
+    NewFile,
+
+    /// `r`: use `"[RO]"` instead of `"[readonly]"`.
+    Readonly,
+
+    /// `w`: use `"[w]"` instead of `"written"` and `"[a]"` instead of
+    /// `"appended"`.
+    Written,
+
+    /// `x`: use `"[dos]"` instead of `"[dos format]"`, and so on.
+    FileFormat,
+
+    /// `a`: all of the abbreviations above.
+    AllAbbreviations,
+
+    /// `o`: the message for writing a file overwrites the one for reading
+    /// it.
+    OverwriteWrite,
+
+    /// `O`: the message for reading a file overwrites any previous message.
+    OverwriteRead,
+
+    /// `s`: don't give `"search hit BOTTOM, continuing at TOP"` messages.
+    NoSearchWrap,
+
+    /// `t`: truncate file messages at the start if they're too long.
+    TruncateFile,
+
+    /// `T`: truncate other messages in the middle if they're too long.
+    TruncateMessages,
+
+    /// `W`: don't give `"written"` or `"[w]"` when writing a file.
+    NoWritten,
+
+    /// `A`: don't give the `ATTENTION` message when a swap file is found.
+    NoSwapAttention,
+
+    /// `I`: don't give the intro message when starting Neovim.
+    NoIntro,
+
+    /// `c`: don't give insert mode completion messages.
+    NoCompletion,
+
+    /// `q`: use `"recording"` instead of `"recording @a"`.
+    NoRecordingRegister,
+
+    /// `F`: don't give the file info when editing a file.
+    NoFileInfo,
+
+    /// `S`: don't show the search count message when searching.
+    NoSearchCount,
+}
+
+impl From<ShortmessFlag> for char {
+    fn from(flag: ShortmessFlag) -> Self {
+        use ShortmessFlag::*;
+
+        match flag {
+            FileCount => 'f',
+            NoEol => 'i',
+            LinesBytes => 'l',
+            Modified => 'm',
+            NewFile => 'n',
+            Readonly => 'r',
+            Written => 'w',
+            FileFormat => 'x',
+            AllAbbreviations => 'a',
+            OverwriteWrite => 'o',
+            OverwriteRead => 'O',
+            NoSearchWrap => 's',
+            TruncateFile => 't',
+            TruncateMessages => 'T',
+            NoWritten => 'W',
+            NoSwapAttention => 'A',
+            NoIntro => 'I',
+            NoCompletion => 'c',
+            NoRecordingRegister => 'q',
+            NoFileInfo => 'F',
+            NoSearchCount => 'S',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dedup() {
+        let flags = Flags::from("filnxtToOFff");
+        assert_eq!("filnxtToOF", flags.as_str());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut flags = Flags::from("ltToOCF");
+
+        assert!(flags.insert(ShortmessFlag::NoIntro));
+        assert!(!flags.insert('I'));
+        assert!(flags.contains(ShortmessFlag::NoIntro));
+
+        assert!(flags.remove(ShortmessFlag::TruncateFile));
+        assert!(!flags.remove(ShortmessFlag::TruncateFile));
+
+        assert_eq!("lToOCFI", flags.as_str());
+    }
+}
@@ -177,7 +177,8 @@ impl From<DeserializedVimLExpressionAST> for VimLExpressionAst {
                     ExprScope::Var(scope) => scope as u8,
                 };
                 // SAFETY: read above.
-                let scope = unsafe { std::mem::transmute(scope) };
+                let scope =
+                    unsafe { std::mem::transmute::<u8, ExprOptScope>(scope) };
 
                 VimLAstNode::Option { scope, ident }
             },
@@ -190,7 +191,8 @@ impl From<DeserializedVimLExpressionAST> for VimLExpressionAst {
                     ExprScope::Var(scope) => scope as u8,
                 };
                 // SAFETY: read above.
-                let scope = unsafe { std::mem::transmute(scope) };
+                let scope =
+                    unsafe { std::mem::transmute::<u8, ExprVarScope>(scope) };
 
                 VimLAstNode::PlainIdentifier { scope, ident }
             },
@@ -1,18 +1,28 @@
 use oxi_types::{self as nvim, conversion::FromObject, serde::Serializer};
 use serde::{ser, Serialize};
 
+/// The type of the text put via [`put`](crate::put) or stored via
+/// [`yank_into`](crate::yank_into), see [`:h setreg()`][1].
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#setreg()
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
 pub enum RegisterType {
+    /// A block of text, optionally with the given width. If the width is
+    /// `None` it's the length of the longest line.
     #[serde(serialize_with = "serialize_blockwise")]
     BlockwiseVisual(Option<usize>),
 
+    /// Characterwise text.
     #[serde(rename = "c")]
     Charwise,
 
+    /// Linewise text.
     #[serde(rename = "l")]
     Linewise,
 
+    /// Linewise if the last line ends with a newline, characterwise
+    /// otherwise.
     #[serde(rename = "")]
     Guess,
 }
@@ -0,0 +1,24 @@
+/// How to resize a window via [`Window::resize`](crate::Window::resize).
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Resize {
+    /// Grows (or shrinks, if negative) the height of the window by the given
+    /// number of rows.
+    Rows(i32),
+
+    /// Grows (or shrinks, if negative) the width of the window by the given
+    /// number of columns.
+    Columns(i32),
+
+    /// Sets the height of the window to the given number of rows.
+    Height(u32),
+
+    /// Sets the width of the window to the given number of columns.
+    Width(u32),
+
+    /// Sets the height of the window to the number of screen lines needed to
+    /// display the whole buffer, capped at `max` rows if given. Wrapped lines
+    /// and closed folds are taken into account, see
+    /// [`Window::text_lines_height`](crate::Window::text_lines_height).
+    ToFit { max: Option<u32> },
+}
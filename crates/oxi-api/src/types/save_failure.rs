@@ -0,0 +1,28 @@
+use crate::{Buffer, Error};
+
+/// A buffer that [`save_all()`](crate::save_all) couldn't write.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaveFailure {
+    /// The buffer that wasn't written.
+    pub buffer: Buffer,
+
+    /// Why the buffer wasn't written.
+    pub reason: SaveFailureReason,
+}
+
+/// Why a buffer couldn't be written by [`save_all()`](crate::save_all).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SaveFailureReason {
+    /// The buffer has no name, so there's no file to write it to.
+    NoName,
+
+    /// The buffer's [`'readonly'`][1] option is set.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'readonly'
+    Readonly,
+
+    /// Writing the buffer failed, e.g. because its directory doesn't exist
+    /// or because of a `BufWriteCmd` autocommand.
+    Write(Error),
+}
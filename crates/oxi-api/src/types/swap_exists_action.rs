@@ -0,0 +1,36 @@
+/// What to do when a file being opened has an existing [swap file][1], i.e.
+/// it's being edited in another Neovim instance or a previous session
+/// crashed while editing it.
+///
+/// [1]: https://neovim.io/doc/user/recover.html#swap-file
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SwapExistsAction {
+    /// Doesn't open the file, returning an
+    /// [`OpenFileError::SwapExists`](crate::types::OpenFileError::SwapExists).
+    #[default]
+    Abort,
+
+    /// Opens the file read-only.
+    ReadOnly,
+
+    /// Opens the file anyway.
+    Edit,
+
+    /// Opens the file and recovers its contents from the swap file, like
+    /// [`Buffer::recover()`](crate::Buffer::recover).
+    Recover,
+}
+
+impl SwapExistsAction {
+    /// The value of [`v:swapchoice`][1] corresponding to the action.
+    ///
+    /// [1]: https://neovim.io/doc/user/eval.html#v%3Aswapchoice
+    pub(crate) fn swapchoice(self) -> &'static str {
+        match self {
+            Self::Abort => "q",
+            Self::ReadOnly => "o",
+            Self::Edit => "e",
+            Self::Recover => "r",
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use oxi_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+use crate::serde_utils as utils;
+
+/// Informations stored in a [swap file][1], returned by
+/// [`fn_::swapinfo()`](crate::fn_::swapinfo).
+///
+/// [1]: https://neovim.io/doc/user/recover.html#swap-file
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct SwapInfo {
+    /// The version of Neovim that wrote the swap file.
+    pub version: String,
+
+    /// The name of the user who wrote the swap file.
+    pub user: String,
+
+    /// The name of the host the swap file was written on.
+    pub host: String,
+
+    /// The path of the file being edited.
+    pub fname: PathBuf,
+
+    /// The id of the process owning the swap file, which may not be running
+    /// anymore if it crashed.
+    pub pid: u32,
+
+    /// When the swap file was last modified, in seconds since the Unix
+    /// epoch.
+    pub mtime: i64,
+
+    /// Whether the file had unsaved changes when the swap file was last
+    /// written, i.e. whether recovering it would restore them.
+    #[serde(deserialize_with = "utils::bool_from_int")]
+    pub dirty: bool,
+
+    /// The inode number of the file, if known.
+    #[serde(default)]
+    pub inode: Option<u64>,
+}
+
+impl FromObject for SwapInfo {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
@@ -0,0 +1,25 @@
+use oxi_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+/// The height of a range of text in a window, as returned by
+/// [`Window::text_height()`](crate::Window::text_height).
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct WinTextHeight {
+    /// The number of screen lines taken by the text, including the filler
+    /// and virtual lines.
+    pub all: usize,
+
+    /// The number of diff filler and virtual lines among them.
+    pub fill: usize,
+}
+
+impl FromObject for WinTextHeight {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
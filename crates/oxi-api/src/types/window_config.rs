@@ -11,7 +11,7 @@ use serde::Deserialize;
 use super::{WindowAnchor, WindowBorder, WindowRelativeTo, WindowStyle};
 
 #[non_exhaustive]
-#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct WindowConfig {
     /// Decides which corner of the window to place at `(row, col)`.
     pub anchor: Option<WindowAnchor>,
@@ -60,7 +60,8 @@ pub struct WindowConfig {
     /// with lower indices.
     pub zindex: Option<u32>,
 
-    /// TODO
+    /// Title of the window, displayed in its top border. Requires
+    /// [`border`](WindowConfigBuilder::border) to be set.
     #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
     #[cfg_attr(
         docsrs,
@@ -68,21 +69,83 @@ pub struct WindowConfig {
     )]
     pub title: Option<super::WindowTitle>,
 
-    /// TODO
+    /// Position of the [`title`](WindowConfigBuilder::title) in the top
+    /// border.
     #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
     #[cfg_attr(
         docsrs,
         doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
     )]
     pub title_pos: Option<super::WindowTitlePosition>,
+
+    /// Footer of the window, displayed in its bottom border. Requires
+    /// [`border`](WindowConfigBuilder::border) to be set.
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    pub footer: Option<super::WindowTitle>,
+
+    /// Position of the [`footer`](WindowConfigBuilder::footer) in the
+    /// bottom border.
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    pub footer_pos: Option<super::WindowTitlePosition>,
 }
 
 impl WindowConfig {
+    /// A configuration with every field unset, usable in `const` contexts.
+    pub const DEFAULT: Self = Self {
+        anchor: None,
+        border: None,
+        bufpos: None,
+        col: None,
+        external: None,
+        focusable: None,
+        height: None,
+        noautocmd: None,
+        relative: None,
+        row: None,
+        style: None,
+        width: None,
+        zindex: None,
+        #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+        title: None,
+        #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+        title_pos: None,
+        #[cfg(feature = "neovim-nightly")]
+        footer: None,
+        #[cfg(feature = "neovim-nightly")]
+        footer_pos: None,
+    };
+
     #[inline(always)]
     /// Creates a new `WinConfigBuilder`.
     pub fn builder() -> WindowConfigBuilder {
         WindowConfigBuilder::default()
     }
+
+    /// A floating window of the given size placed right below the cursor,
+    /// like the ones used to show hover documentation.
+    ///
+    /// The returned configuration can be tweaked further before passing it
+    /// to [`open_win()`](crate::open_win), e.g. to add a border.
+    #[inline]
+    pub fn float_at_cursor(width: u32, height: u32) -> Self {
+        Self {
+            relative: Some(WindowRelativeTo::Cursor),
+            row: Some(1.0),
+            col: Some(0.0),
+            width: Some(width),
+            height: Some(height),
+            ..Self::DEFAULT
+        }
+    }
+}
+
+impl Default for WindowConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
 }
 
 #[derive(Clone, Default)]
@@ -188,19 +251,50 @@ impl WindowConfigBuilder {
         self
     }
 
-    /// TODO
+    /// Footer of the window, displayed in its bottom border. Requires
+    /// [`border`](WindowConfigBuilder::border) to be set.
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    #[inline]
+    pub fn footer(
+        &mut self,
+        footer: impl Into<super::WindowTitle>,
+    ) -> &mut Self {
+        self.0.footer = Some(footer.into());
+        self
+    }
+
+    /// Position of the [`footer`](WindowConfigBuilder::footer) in the
+    /// bottom border.
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    #[inline]
+    pub fn footer_pos(
+        &mut self,
+        footer_pos: super::WindowTitlePosition,
+    ) -> &mut Self {
+        self.0.footer_pos = Some(footer_pos);
+        self
+    }
+
+    /// Title of the window, displayed in its top border. Requires
+    /// [`border`](WindowConfigBuilder::border) to be set.
     #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
     #[cfg_attr(
         docsrs,
         doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
     )]
     #[inline]
-    pub fn title(&mut self, title: super::WindowTitle) -> &mut Self {
-        self.0.title = Some(title);
+    pub fn title(
+        &mut self,
+        title: impl Into<super::WindowTitle>,
+    ) -> &mut Self {
+        self.0.title = Some(title.into());
         self
     }
 
-    /// TODO
+    /// Position of the [`title`](WindowConfigBuilder::title) in the top
+    /// border.
     #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
     #[cfg_attr(
         docsrs,
@@ -274,6 +368,8 @@ pub(crate) struct KeyDict_float_config {
     title: Object,
     #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
     title_pos: Object,
+    footer: Object,
+    footer_pos: Object,
     style: Object,
     noautocmd: Object,
 }
@@ -311,6 +407,10 @@ impl From<&WindowConfig> for KeyDict_float_config {
             noautocmd: config.noautocmd.into(),
             #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
             title_pos: config.title_pos.as_ref().into(),
+            #[cfg(feature = "neovim-nightly")]
+            footer: config.footer.as_ref().into(),
+            #[cfg(feature = "neovim-nightly")]
+            footer_pos: config.footer_pos.as_ref().into(),
         }
     }
 }
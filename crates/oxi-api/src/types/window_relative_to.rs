@@ -53,7 +53,9 @@ impl<'de> de::Deserialize<'de> for WindowRelativeTo {
             where
                 E: de::Error,
             {
-                let handle = i32::try_from(n).unwrap();
+                let handle = i32::try_from(n).map_err(|_| {
+                    E::invalid_value(de::Unexpected::Signed(n), &self)
+                })?;
                 Ok(WindowRelativeTo::Window(handle.into()))
             }
 
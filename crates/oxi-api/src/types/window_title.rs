@@ -1,18 +1,61 @@
 use oxi_types::{Array, Object, String};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+/// The title or footer of a floating window.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum WindowTitle {
+    /// Plain text, highlighted with the `FloatTitle` (or `FloatFooter`)
+    /// highlight group.
     SimpleString(String),
+
+    /// A list of `(text, highlight)` chunks.
     ListOfText(Vec<(String, TitleHighlight)>),
 }
 
+/// The highlight group(s) of a chunk of a [`WindowTitle`].
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(untagged)]
 pub enum TitleHighlight {
     SimpleString(String),
     ListOfString(Vec<String>),
 }
 
+impl<'de> Deserialize<'de> for WindowTitle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Title {
+            SimpleString(String),
+            ListOfText(Vec<Chunk>),
+        }
+
+        // The highlight of a chunk is omitted by `nvim_win_get_config()`
+        // if the chunk isn't highlighted.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Chunk {
+            Highlighted(String, TitleHighlight),
+            Plain([String; 1]),
+        }
+
+        Ok(match Title::deserialize(deserializer)? {
+            Title::SimpleString(value) => Self::SimpleString(value),
+            Title::ListOfText(chunks) => chunks
+                .into_iter()
+                .map(|chunk| match chunk {
+                    Chunk::Highlighted(text, hl) => (text, hl),
+                    Chunk::Plain([text]) => {
+                        (text, TitleHighlight::ListOfString(Vec::new()))
+                    },
+                })
+                .collect(),
+        })
+    }
+}
+
 impl From<&WindowTitle> for Object {
     fn from(title: &WindowTitle) -> Self {
         match title {
@@ -30,6 +73,32 @@ impl From<&WindowTitle> for Object {
     }
 }
 
+impl From<&str> for WindowTitle {
+    fn from(value: &str) -> Self {
+        Self::SimpleString(value.into())
+    }
+}
+
+impl From<String> for WindowTitle {
+    fn from(value: String) -> Self {
+        Self::SimpleString(value)
+    }
+}
+
+impl<Text, Hl> FromIterator<(Text, Hl)> for WindowTitle
+where
+    Text: Into<String>,
+    Hl: Into<TitleHighlight>,
+{
+    fn from_iter<I: IntoIterator<Item = (Text, Hl)>>(iter: I) -> Self {
+        Self::ListOfText(
+            iter.into_iter()
+                .map(|(text, hl)| (text.into(), hl.into()))
+                .collect(),
+        )
+    }
+}
+
 impl From<&TitleHighlight> for Object {
     fn from(hl: &TitleHighlight) -> Self {
         match hl {
@@ -47,6 +116,12 @@ impl From<String> for TitleHighlight {
     }
 }
 
+impl From<&str> for TitleHighlight {
+    fn from(value: &str) -> Self {
+        Self::SimpleString(value.into())
+    }
+}
+
 impl From<Vec<String>> for TitleHighlight {
     fn from(value: Vec<String>) -> Self {
         Self::ListOfString(value.into_iter().collect())
@@ -1,7 +1,9 @@
 use oxi_types::Object;
 use serde::Deserialize;
 
+/// The position of the title or footer of a floating window in its border.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum WindowTitlePosition {
     Left,
     Center,
@@ -1,6 +1,11 @@
+use std::cell::Cell;
+use std::convert::Infallible;
 use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
 
-use oxi_types::Integer;
+use oxi_types::{self as nvim, Function, Integer, LuaRef};
+
+use crate::{Error, Result};
 
 pub(crate) fn range_to_limits<R>(range: R) -> (Integer, Integer)
 where
@@ -21,3 +26,37 @@ where
 
     (start, end)
 }
+
+/// Passes a Lua function wrapping `fun` to `call`, which is expected to call
+/// it exactly once, e.g. via `nvim_win_call()`.
+///
+/// The value returned by `fun` is handed back as is instead of going through
+/// Lua, so it doesn't have to be convertible to an `Object` and its error
+/// isn't turned into a string.
+pub(crate) fn call_lua_fn<R, F, C>(fun: F, call: C) -> Result<R>
+where
+    F: FnOnce(()) -> Result<R> + 'static,
+    R: 'static,
+    C: FnOnce(LuaRef, &mut nvim::Error),
+{
+    let ret = Rc::new(Cell::new(None));
+
+    let lua_fn = Function::<(), ()>::from_fn_once({
+        let ret = Rc::clone(&ret);
+        move |()| {
+            ret.set(Some(fun(())));
+            Ok::<_, Infallible>(())
+        }
+    });
+
+    let mut err = nvim::Error::new();
+    call(lua_fn.lua_ref(), &mut err);
+    lua_fn.remove_from_lua_registry();
+
+    if err.is_err() {
+        return Err(err.into());
+    }
+
+    ret.take()
+        .unwrap_or_else(|| Err(Error::custom("the function wasn't called")))
+}
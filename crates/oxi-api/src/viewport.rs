@@ -0,0 +1,302 @@
+//! Decorations that are only computed for the visible parts of a buffer.
+//!
+//! Computing the decorations of a whole buffer every time it changes doesn't
+//! scale to large files. [`render`] instead calls a closure with the ranges
+//! of lines currently visible in the windows displaying the buffer, and
+//! calls it again for the lines that become visible as the user scrolls,
+//! clearing the decorations of the lines that are no longer visible.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::{self, opts::SetExtmarkOpts, Buffer};
+//! use nvim_oxi::api::viewport::{self, Decoration};
+//!
+//! let ns_id = api::create_namespace("my-plugin");
+//! let buf = Buffer::current();
+//!
+//! let opts = SetExtmarkOpts::builder().line_hl_group("CursorLine").build();
+//!
+//! viewport::render(ns_id, &buf, move |visible_lines| {
+//!     visible_lines
+//!         .filter(|line| line % 2 == 0)
+//!         .map(|line| Decoration::new(line, 0, opts.clone()))
+//!         .collect()
+//! })?;
+//! ```
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::opts::{CreateAugroupOpts, CreateAutocmdOpts, SetExtmarkOpts};
+use crate::types::{AutocmdCallbackArgs, AutocmdEvent};
+use crate::Result;
+use crate::{Buffer, Error};
+
+/// An extmark placed by [`render`].
+#[derive(Clone, Debug)]
+pub struct Decoration {
+    /// The zero-indexed line of the extmark.
+    pub line: usize,
+
+    /// The zero-indexed column of the extmark.
+    pub col: usize,
+
+    /// The options used to set the extmark.
+    pub opts: SetExtmarkOpts,
+}
+
+impl Decoration {
+    /// Creates a new decoration at the given position.
+    #[inline]
+    pub fn new(line: usize, col: usize, opts: SetExtmarkOpts) -> Self {
+        Self { line, col, opts }
+    }
+}
+
+type RenderFn = Box<dyn FnMut(Range<usize>) -> Vec<Decoration>>;
+
+struct Renderer {
+    ns_id: u32,
+    buffer: Buffer,
+    fun: RenderFn,
+    /// The sorted, disjoint ranges of lines whose decorations are currently
+    /// set.
+    rendered: Vec<Range<usize>>,
+}
+
+impl Renderer {
+    /// Updates the decorations to match the visible lines. If `invalidate`
+    /// is `true` all the decorations are recomputed, e.g. because the text
+    /// of the buffer changed.
+    fn update(&mut self, invalidate: bool) -> Result<()> {
+        if !self.buffer.is_valid() {
+            return Ok(());
+        }
+
+        if invalidate {
+            self.buffer.clear_namespace(self.ns_id, ..)?;
+            self.rendered.clear();
+        }
+
+        let visible = merge(
+            self.buffer
+                .display_info()?
+                .into_iter()
+                .map(|display| display.visible_lines),
+        );
+
+        if visible == self.rendered {
+            return Ok(());
+        }
+
+        for hidden in subtract(&self.rendered, &visible) {
+            self.buffer.clear_namespace(self.ns_id, hidden)?;
+        }
+
+        for shown in subtract(&visible, &self.rendered) {
+            for decoration in (self.fun)(shown.clone()) {
+                if !shown.contains(&decoration.line) {
+                    continue;
+                }
+
+                self.buffer.set_extmark(
+                    self.ns_id,
+                    decoration.line,
+                    decoration.col,
+                    &decoration.opts,
+                )?;
+            }
+        }
+
+        self.rendered = visible;
+
+        Ok(())
+    }
+}
+
+/// A handle to the decorations rendered via [`render`].
+///
+/// The decorations keep being updated after the handle is dropped, until
+/// either [`stop`](Viewport::stop) is called or the buffer is wiped out.
+pub struct Viewport {
+    augroup_id: u32,
+    renderer: Rc<RefCell<Renderer>>,
+}
+
+impl Viewport {
+    /// Recomputes the decorations of all the visible lines.
+    pub fn refresh(&self) -> Result<()> {
+        self.renderer.borrow_mut().update(true)
+    }
+
+    /// Stops updating the decorations and clears them.
+    pub fn stop(self) -> Result<()> {
+        crate::del_augroup_by_id(self.augroup_id)?;
+        let mut renderer = self.renderer.borrow_mut();
+        let ns_id = renderer.ns_id;
+        renderer.buffer.clear_namespace(ns_id, ..)
+    }
+}
+
+/// Sets the decorations returned by `fun` in the visible lines of `buffer`,
+/// keeping them up to date as the buffer is scrolled or modified.
+///
+/// `fun` is called with ranges of zero-indexed, end-exclusive lines that
+/// have become visible, and should return the decorations starting in those
+/// lines. Decorations starting outside of the range are ignored. Every
+/// decoration set in `ns_id` is cleared when its line is scrolled out of
+/// view, and all of them are recomputed when the text of the buffer changes.
+///
+/// Calling this again with the same namespace and buffer replaces the
+/// previous renderer.
+pub fn render<F>(ns_id: u32, buffer: &Buffer, fun: F) -> Result<Viewport>
+where
+    F: FnMut(Range<usize>) -> Vec<Decoration> + 'static,
+{
+    let renderer = Rc::new(RefCell::new(Renderer {
+        ns_id,
+        buffer: buffer.clone(),
+        fun: Box::new(fun),
+        rendered: Vec::new(),
+    }));
+
+    let augroup_id = crate::create_augroup(
+        &format!("nvim-oxi-viewport-{}-{}", ns_id, buffer.0),
+        &CreateAugroupOpts::builder().clear(true).build(),
+    )?
+    .id();
+
+    let update = |invalidate: bool| {
+        let renderer = Rc::clone(&renderer);
+        move |_: AutocmdCallbackArgs| {
+            // Skip the update if one is already in progress.
+            if let Ok(mut renderer) = renderer.try_borrow_mut() {
+                renderer.update(invalidate)?;
+            }
+            Ok::<_, Error>(false)
+        }
+    };
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(augroup_id)
+        .callback(update(false))
+        .build();
+
+    crate::create_autocmd([AutocmdEvent::WinScrolled], &opts)?;
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(augroup_id)
+        .buffer(buffer.clone())
+        .callback(update(false))
+        .build();
+
+    crate::create_autocmd([AutocmdEvent::BufWinEnter], &opts)?;
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(augroup_id)
+        .buffer(buffer.clone())
+        .callback(update(true))
+        .build();
+
+    crate::create_autocmd(
+        [AutocmdEvent::TextChanged, AutocmdEvent::TextChangedI],
+        &opts,
+    )?;
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(augroup_id)
+        .buffer(buffer.clone())
+        .callback(move |_: AutocmdCallbackArgs| {
+            crate::del_augroup_by_id(augroup_id)?;
+            Ok::<_, Error>(false)
+        })
+        .build();
+
+    crate::create_autocmd([AutocmdEvent::BufWipeout], &opts)?;
+
+    renderer.borrow_mut().update(false)?;
+
+    Ok(Viewport { augroup_id, renderer })
+}
+
+/// Sorts the ranges and merges the overlapping or adjacent ones.
+fn merge<I>(ranges: I) -> Vec<Range<usize>>
+where
+    I: IntoIterator<Item = Range<usize>>,
+{
+    let mut ranges = ranges
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .collect::<Vec<_>>();
+
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => {
+                last.end = last.end.max(range.end);
+            },
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// Returns the parts of the ranges in `a` that aren't covered by the ranges
+/// in `b`. Both need to be sorted and disjoint, like the ones returned by
+/// [`merge`].
+fn subtract(a: &[Range<usize>], b: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut diff = Vec::new();
+
+    for range in a {
+        let mut start = range.start;
+
+        for other in b {
+            if other.end <= start {
+                continue;
+            }
+            if other.start >= range.end {
+                break;
+            }
+            if other.start > start {
+                diff.push(start..other.start);
+            }
+            start = start.max(other.end);
+        }
+
+        if start < range.end {
+            diff.push(start..range.end);
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_ranges() {
+        assert_eq!(
+            vec![0..15, 20..30],
+            merge([20..30, 5..15, 0..10, 12..12, 15..15])
+        );
+        assert_eq!(vec![0..20], merge([0..10, 10..20]));
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn subtract_ranges() {
+        assert_eq!(vec![0..5], subtract(&[0..10], &[5..15]));
+        assert_eq!(vec![10..15], subtract(&[5..15], &[0..10]));
+        assert_eq!(vec![0..2, 4..6, 8..10], subtract(&[0..10], &[2..4, 6..8]));
+        assert_eq!(vec![0..10], subtract(&[0..10], &[20..30]));
+        assert!(subtract(&[2..8], &[0..10]).is_empty());
+    }
+}
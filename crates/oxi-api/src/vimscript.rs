@@ -1,6 +1,7 @@
 use oxi_types::{self as nvim, conversion::FromObject, Array, Object};
 
 use crate::choose;
+use crate::decoration_context::ensure_not_redrawing;
 use crate::ffi::vimscript::*;
 use crate::types::*;
 use crate::Result;
@@ -66,6 +67,7 @@ pub fn cmd(
     infos: &CmdInfos,
     opts: &super::opts::CmdOpts,
 ) -> Result<Option<String>> {
+    ensure_not_redrawing("nvim_cmd")?;
     let mut err = nvim::Error::new();
     let output =
         unsafe { nvim_cmd(LUA_INTERNAL_CALL, &infos.into(), opts, &mut err) };
@@ -80,6 +82,7 @@ pub fn cmd(
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_command()
 pub fn command(command: &str) -> Result<()> {
+    ensure_not_redrawing("nvim_command")?;
     let command = nvim::String::from(command);
     let mut err = nvim::Error::new();
     unsafe { nvim_command(command.non_owning(), &mut err) };
@@ -107,7 +110,15 @@ where
 /// output is captured and returned.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_exec()
+#[cfg_attr(
+    any(feature = "neovim-0-9", feature = "neovim-nightly"),
+    deprecated(since = "0.4.0", note = "use `exec2` instead")
+)]
 pub fn exec(src: &str, output: bool) -> Result<Option<String>> {
+    ensure_not_redrawing("nvim_exec")?;
+    #[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+    crate::deprecation::check("nvim_exec", "nvim_exec2")?;
+
     let src = nvim::String::from(src);
     let mut err = nvim::Error::new();
     let output = unsafe {
@@ -118,6 +129,49 @@ pub fn exec(src: &str, output: bool) -> Result<Option<String>> {
     })
 }
 
+/// Binding to [`nvim_exec2()`][1].
+///
+/// Executes a multiline block of Ex commands. If the
+/// [`output`](crate::opts::ExecOptsBuilder::output) option is set the output
+/// is captured and returned in [`ExecOutput::output`].
+///
+/// [1]: https://neovim.io/doc/user/api.html#nvim_exec2()
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
+)]
+pub fn exec2(src: &str, opts: &super::opts::ExecOpts) -> Result<ExecOutput> {
+    ensure_not_redrawing("nvim_exec2")?;
+    let src = nvim::String::from(src);
+    let mut err = nvim::Error::new();
+    let dict = unsafe {
+        nvim_exec2(LUA_INTERNAL_CALL, src.non_owning(), opts, &mut err)
+    };
+    choose!(err, Ok(ExecOutput::from_object(dict.into())?))
+}
+
+/// Executes the Ex command `cmd` and returns the output it printed, e.g.
+/// with `:echo` or `:ls`.
+///
+/// Output spanning several lines is returned as a single string with the
+/// lines separated by `\n`, without leading or trailing newlines. If the
+/// command fails an error is returned instead, and any output printed before
+/// the failure is discarded.
+///
+/// This is a shorthand for [`exec2()`] with the
+/// [`output`](crate::opts::ExecOptsBuilder::output) option set.
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "neovim-0-9", feature = "neovim-nightly")))
+)]
+pub fn exec_capture(cmd: &str) -> Result<String> {
+    let opts = super::opts::ExecOpts::builder().output(true).build();
+    let output = exec2(cmd, &opts)?.output.unwrap_or_default();
+    Ok(output.trim_matches('\n').to_owned())
+}
+
 /// Binding to [`nvim_parse_cmd()`][1].
 ///
 /// Parses the command line.
@@ -1,6 +1,7 @@
 use oxi_types::{self as nvim, conversion::FromObject};
 
 use crate::choose;
+use crate::decoration_context::ensure_not_redrawing;
 use crate::ffi::win_config::*;
 use crate::types::*;
 use crate::Result;
@@ -16,6 +17,7 @@ pub fn open_win(
     enter: bool,
     config: &WindowConfig,
 ) -> Result<Window> {
+    ensure_not_redrawing("nvim_open_win")?;
     let mut err = nvim::Error::new();
     let handle =
         unsafe { nvim_open_win(buf.0, enter, &config.into(), &mut err) };
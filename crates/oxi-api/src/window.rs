@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::{Bound, RangeBounds};
 use std::result::Result as StdResult;
 
 use oxi_luajit::{self as lua, Poppable, Pushable};
@@ -6,7 +7,7 @@ use oxi_types::{
     self as nvim,
     conversion::{self, FromObject, ToObject},
     Array,
-    Function,
+    Dictionary,
     Integer,
     Object,
     WinHandle,
@@ -14,10 +15,26 @@ use oxi_types::{
 use serde::{Deserialize, Serialize};
 
 use crate::choose;
+use crate::decoration_context::ensure_not_redrawing;
+use crate::ffi::vimscript::nvim_command;
 use crate::ffi::window::*;
-use crate::Result;
+use crate::options::{self, Scope};
+#[cfg(feature = "neovim-nightly")]
+use crate::opts::WinTextHeightOpts;
+#[cfg(feature = "neovim-nightly")]
+use crate::types::WinTextHeight;
+use crate::types::{
+    ConcealCursor,
+    ConcealLevel,
+    Fillchars,
+    KeepCursor,
+    Listchars,
+    Resize,
+};
+use crate::SuperIterator;
 use crate::LUA_INTERNAL_CALL;
 use crate::{Buffer, TabPage};
+use crate::{Error, Result};
 
 /// A wrapper around a Neovim window handle.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -85,21 +102,52 @@ impl Window {
 
     /// Binding to [`nvim_win_call()`][1].
     ///
-    /// Calls a function with this window as the temporary current window.
+    /// Calls a function with this window as the temporary current window,
+    /// returning its result.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_call()
     pub fn call<R, F>(&self, fun: F) -> Result<R>
     where
         F: FnOnce(()) -> Result<R> + 'static,
-        R: Pushable + FromObject,
+        R: 'static,
     {
-        let fun = Function::from_fn_once(fun);
-        let mut err = nvim::Error::new();
-        let obj = unsafe { nvim_win_call(self.0, fun.lua_ref(), &mut err) };
+        crate::utils::call_lua_fn(fun, |lua_ref, err| {
+            let _ = unsafe { nvim_win_call(self.0, lua_ref, err) };
+        })
+    }
 
-        choose!(err, {
-            fun.remove_from_lua_registry();
-            Ok(R::from_object(obj)?)
+    /// Like [`call`](Window::call), but runs the function with the
+    /// [`:keepjumps`][1] modifier and restores the view of the window, i.e.
+    /// its cursor position, scroll and folds, once the function returns.
+    /// Useful for navigation helpers that shouldn't pollute the user's
+    /// jumplist.
+    ///
+    /// As with `:keepjumps`, Ex commands executed by the function (e.g. via
+    /// [`command`](crate::command)) are parsed with their own modifiers, so
+    /// the ones that jump have to be prefixed with `keepjumps` too.
+    ///
+    /// [1]: https://neovim.io/doc/user/motion.html#%3Akeepjumps
+    pub fn call_keepjumps<R, F>(&self, fun: F) -> Result<R>
+    where
+        F: FnOnce(()) -> Result<R> + 'static,
+        R: 'static,
+    {
+        self.call(move |()| {
+            let view = crate::call_function::<_, Dictionary>(
+                "winsaveview",
+                Array::new(),
+            )?;
+
+            let ret = crate::utils::call_lua_fn(fun, |lua_ref, err| {
+                let cmd = nvim::String::from(format!(
+                    "keepjumps lua debug.getregistry()[{lua_ref}]()"
+                ));
+                unsafe { nvim_command(cmd.non_owning(), err) };
+            });
+
+            crate::call_function::<_, Object>("winrestview", (view,))?;
+
+            ret
         })
     }
 
@@ -110,11 +158,19 @@ impl Window {
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_close()
     pub fn close(self, force: bool) -> Result<()> {
+        ensure_not_redrawing("nvim_win_close")?;
         let mut err = nvim::Error::new();
         unsafe { nvim_win_close(self.0, force, &mut err) };
         choose!(err, ())
     }
 
+    /// Returns the text of the line the cursor is on in the window.
+    pub fn current_line(&self) -> Result<nvim::String> {
+        let (row, _) = self.get_cursor()?;
+        let mut lines = self.get_buf()?.get_lines(row - 1..row, true)?;
+        Ok(lines.next().unwrap_or_default())
+    }
+
     /// Binding to [`nvim_win_del_var()`][1].
     ///
     /// Removes a window-scoped (`w:`) variable.
@@ -122,7 +178,7 @@ impl Window {
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_del_var()
     pub fn del_var(&mut self, name: &str) -> Result<()> {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe { nvim_win_del_var(self.0, name.non_owning(), &mut err) };
         choose!(err, ())
     }
@@ -138,6 +194,38 @@ impl Window {
         choose!(err, Ok(handle.into()))
     }
 
+    /// Returns how concealed text is displayed in the window, and in which
+    /// modes the text of the cursor line is concealed.
+    ///
+    /// See [`set_conceal`](Window::set_conceal) for more details.
+    pub fn get_conceal(&self) -> Result<(ConcealLevel, ConcealCursor)> {
+        let level = options::get::<u8>("conceallevel", Scope::Window(self))?;
+        let level = ConcealLevel::from_level(level).ok_or_else(|| {
+            crate::Error::custom(format!("invalid 'conceallevel' {level}"))
+        })?;
+        let cursor =
+            options::get::<String>("concealcursor", Scope::Window(self))?;
+        Ok((level, ConcealCursor::from(cursor.as_str())))
+    }
+
+    /// Returns the characters used to fill the separators and special lines
+    /// of the window, i.e. the value of the [`'fillchars'`][1] option.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'fillchars'
+    pub fn get_fillchars(&self) -> Result<Fillchars> {
+        let value = options::get::<String>("fillchars", Scope::Window(self))?;
+        Ok(Fillchars::from(value.as_str()))
+    }
+
+    /// Returns the characters used to display special characters in list
+    /// mode, i.e. the value of the [`'listchars'`][1] option.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'listchars'
+    pub fn get_listchars(&self) -> Result<Listchars> {
+        let value = options::get::<String>("listchars", Scope::Window(self))?;
+        Ok(Listchars::from(value.as_str()))
+    }
+
     /// Binding to [`nvim_win_get_cursor()`][1].
     ///
     /// Gets the (1,0)-indexed cursor position in the window.
@@ -146,12 +234,7 @@ impl Window {
     pub fn get_cursor(&self) -> Result<(usize, usize)> {
         let mut err = nvim::Error::new();
         let arr = unsafe { nvim_win_get_cursor(self.0, &mut err) };
-        choose!(err, {
-            let mut iter = arr.into_iter();
-            let line = usize::from_object(iter.next().unwrap())?;
-            let col = usize::from_object(iter.next().unwrap())?;
-            Ok((line, col))
-        })
+        choose!(err, Ok(arr.decode()?))
     }
 
     /// Binding to [`nvim_win_get_height()`][1].
@@ -162,7 +245,7 @@ impl Window {
     pub fn get_height(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
         let height = unsafe { nvim_win_get_height(self.0, &mut err) };
-        choose!(err, Ok(height.try_into().expect("always positive")))
+        choose!(err, Ok(height.try_into()?))
     }
 
     /// Binding to [`nvim_win_get_number()`][1].
@@ -173,7 +256,7 @@ impl Window {
     pub fn get_number(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
         let nr = unsafe { nvim_win_get_number(self.0, &mut err) };
-        choose!(err, Ok(nr.try_into().expect("always positive")))
+        choose!(err, Ok(nr.try_into()?))
     }
 
     /// Binding to [`nvim_win_get_option()`][1].
@@ -181,12 +264,19 @@ impl Window {
     /// Gets a window option value.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_option()
+    #[deprecated(note = "use `options::get` with `Scope::Window` instead")]
     pub fn get_option<Opt>(&self, name: &str) -> Result<Opt>
     where
         Opt: FromObject,
     {
+        #[cfg(feature = "neovim-nightly")]
+        crate::deprecation::check(
+            "nvim_win_get_option",
+            "nvim_get_option_value",
+        )?;
+
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let obj = unsafe {
             nvim_win_get_option(
                 self.0,
@@ -207,12 +297,7 @@ impl Window {
     pub fn get_position(&self) -> Result<(usize, usize)> {
         let mut err = nvim::Error::new();
         let arr = unsafe { nvim_win_get_position(self.0, &mut err) };
-        choose!(err, {
-            let mut iter = arr.into_iter();
-            let line = usize::from_object(iter.next().unwrap())?;
-            let col = usize::from_object(iter.next().unwrap())?;
-            Ok((line, col))
-        })
+        choose!(err, Ok(arr.decode()?))
     }
 
     /// Binding to [`nvim_win_get_tabpage()`][1].
@@ -236,7 +321,7 @@ impl Window {
         Var: FromObject,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         let obj =
             unsafe { nvim_win_get_var(self.0, name.non_owning(), &mut err) };
         choose!(err, Ok(Var::from_object(obj)?))
@@ -250,7 +335,7 @@ impl Window {
     pub fn get_width(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
         let width = unsafe { nvim_win_get_width(self.0, &mut err) };
-        choose!(err, Ok(width.try_into().expect("always positive")))
+        choose!(err, Ok(width.try_into()?))
     }
 
     /// Binding to [`nvim_win_hide()`][1].
@@ -259,6 +344,7 @@ impl Window {
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_hide()
     pub fn hide(self) -> Result<()> {
+        ensure_not_redrawing("nvim_win_hide")?;
         let mut err = nvim::Error::new();
         unsafe { nvim_win_hide(self.0, &mut err) };
         choose!(err, ())
@@ -273,17 +359,121 @@ impl Window {
         unsafe { nvim_win_is_valid(self.0) }
     }
 
+    /// Resizes the window.
+    ///
+    /// For splits the new size is clamped so that it's never smaller than
+    /// [`winminheight`][1] or [`winminwidth`][2]. Floats can be resized down
+    /// to a single row or column.
+    ///
+    /// No window option is changed, so if [`equalalways`][3] is set the size
+    /// can be reset when other windows are opened or closed. Set
+    /// [`winfixheight`][4] or [`winfixwidth`][5] on the window to keep it.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'winminheight'
+    /// [2]: https://neovim.io/doc/user/options.html#'winminwidth'
+    /// [3]: https://neovim.io/doc/user/options.html#'equalalways'
+    /// [4]: https://neovim.io/doc/user/options.html#'winfixheight'
+    /// [5]: https://neovim.io/doc/user/options.html#'winfixwidth'
+    pub fn resize(&mut self, resize: Resize) -> Result<()> {
+        fn offset(size: u32, delta: i32) -> u32 {
+            (i64::from(size) + i64::from(delta)).clamp(0, u32::MAX.into())
+                as u32
+        }
+
+        let (height, width) = match resize {
+            Resize::Rows(delta) => {
+                (Some(offset(self.get_height()?, delta)), None)
+            },
+            Resize::Columns(delta) => {
+                (None, Some(offset(self.get_width()?, delta)))
+            },
+            Resize::Height(height) => (Some(height), None),
+            Resize::Width(width) => (None, Some(width)),
+            Resize::ToFit { max } => {
+                let lines = self.text_lines_height(..)?;
+                let lines = u32::try_from(lines).unwrap_or(u32::MAX);
+                (Some(max.map_or(lines, |max| lines.min(max))), None)
+            },
+        };
+
+        let is_float = self.get_config()?.relative.is_some();
+
+        if let Some(height) = height {
+            let min = match is_float {
+                true => 1,
+                false => {
+                    options::get::<u32>("winminheight", Scope::Global)?.max(1)
+                },
+            };
+            self.set_height(height.max(min))?;
+        }
+
+        if let Some(width) = width {
+            let min = match is_float {
+                true => 1,
+                false => {
+                    options::get::<u32>("winminwidth", Scope::Global)?.max(1)
+                },
+            };
+            self.set_width(width.max(min))?;
+        }
+
+        Ok(())
+    }
+
     /// Binding to [`nvim_win_set_buf()`][1].
     ///
     /// Sets `buffer` as the current buffer in the window.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_buf()
     pub fn set_buf(&mut self, buffer: &Buffer) -> Result<()> {
+        ensure_not_redrawing("nvim_win_set_buf")?;
         let mut err = nvim::Error::new();
         unsafe { nvim_win_set_buf(self.0, buffer.0, &mut err) };
         choose!(err, ())
     }
 
+    /// Sets how concealed text is displayed in the window, and in which modes
+    /// the text of the cursor line is concealed, i.e. the
+    /// [`'conceallevel'`][1] and [`'concealcursor'`][2] options.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'conceallevel'
+    /// [2]: https://neovim.io/doc/user/options.html#'concealcursor'
+    pub fn set_conceal(
+        &mut self,
+        level: ConcealLevel,
+        cursor: ConcealCursor,
+    ) -> Result<()> {
+        options::set("conceallevel", level.level(), Scope::Window(self))?;
+        options::set(
+            "concealcursor",
+            nvim::String::from(cursor),
+            Scope::Window(self),
+        )
+    }
+
+    /// Sets the [`'fillchars'`][1] option of the window.
+    ///
+    /// Use [`get_fillchars`](Window::get_fillchars) to only change some items
+    /// while keeping the others.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'fillchars'
+    pub fn set_fillchars(&mut self, fillchars: &Fillchars) -> Result<()> {
+        let value = nvim::String::from(fillchars);
+        options::set("fillchars", value, Scope::Window(self))
+    }
+
+    /// Sets the [`'listchars'`][1] option of the window.
+    ///
+    /// Use [`get_listchars`](Window::get_listchars) to only change some items
+    /// while keeping the others.
+    ///
+    /// [1]: https://neovim.io/doc/user/options.html#'listchars'
+    pub fn set_listchars(&mut self, listchars: &Listchars) -> Result<()> {
+        let value = nvim::String::from(listchars);
+        options::set("listchars", value, Scope::Window(self))
+    }
+
     /// Binding to [`nvim_win_set_cursor()`][1].
     ///
     /// Sets the (1,0)-indexed cursor in the window. This will scroll the
@@ -297,29 +487,72 @@ impl Window {
         choose!(err, ())
     }
 
+    /// Replaces the text of the line the cursor is on in the window, then
+    /// moves the cursor according to `keep`.
+    ///
+    /// Unlike [`set_current_line()`](crate::set_current_line) this works on
+    /// windows other than the current one.
+    pub fn set_current_line<Line>(
+        &mut self,
+        line: Line,
+        keep: KeepCursor,
+    ) -> Result<()>
+    where
+        Line: Into<nvim::String>,
+    {
+        let (row, col) = self.get_cursor()?;
+        let mut buffer = self.get_buf()?;
+        let old =
+            buffer.get_lines(row - 1..row, true)?.next().unwrap_or_default();
+        let line = line.into();
+        let col = keep.new_column(old.as_bytes(), line.as_bytes(), col);
+        buffer.set_lines(row - 1..row, true, [line])?;
+        self.set_cursor(row, col)
+    }
+
     /// Binding to [`nvim_win_set_height()`][1].
     ///
     /// Sets the window height.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_height()
     pub fn set_height(&mut self, height: u32) -> Result<()> {
+        ensure_not_redrawing("nvim_win_set_height")?;
         let mut err = nvim::Error::new();
         unsafe { nvim_win_set_height(self.0, height.into(), &mut err) };
         choose!(err, ())
     }
 
+    /// Binding to [`nvim_win_set_hl_ns()`][1].
+    ///
+    /// Sets the highlight namespace used by the window, whose highlight
+    /// groups take precedence over the global ones set in namespace `0`.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_hl_ns()
+    pub fn set_hl_ns(&mut self, ns_id: u32) -> Result<()> {
+        let mut err = nvim::Error::new();
+        unsafe { nvim_win_set_hl_ns(self.0, ns_id.into(), &mut err) };
+        choose!(err, ())
+    }
+
     /// Binding to [`nvim_win_set_option()`][1].
     ///
     /// Sets a window option value. Passing `None` as value deletes the option
     /// (only works if there's a global fallback).
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_option()
+    #[deprecated(note = "use `options::set` with `Scope::Window` instead")]
     pub fn set_option<Opt>(&mut self, name: &str, value: Opt) -> Result<()>
     where
         Opt: ToObject,
     {
+        #[cfg(feature = "neovim-nightly")]
+        crate::deprecation::check(
+            "nvim_win_set_option",
+            "nvim_set_option_value",
+        )?;
+
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe {
             nvim_win_set_option(
                 LUA_INTERNAL_CALL,
@@ -342,7 +575,7 @@ impl Window {
         Var: ToObject,
     {
         let mut err = nvim::Error::new();
-        let name = nvim::String::from(name);
+        let name = nvim::SmallString::from(name);
         unsafe {
             nvim_win_set_var(
                 self.0,
@@ -360,8 +593,131 @@ impl Window {
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_width()
     pub fn set_width(&mut self, width: u32) -> Result<()> {
+        ensure_not_redrawing("nvim_win_set_width")?;
         let mut err = nvim::Error::new();
         unsafe { nvim_win_set_width(self.0, width.into(), &mut err) };
         choose!(err, ())
     }
+
+    /// Binding to [`nvim_win_text_height()`][1].
+    ///
+    /// Computes the number of screen lines the text in the range selected by
+    /// `opts` occupies in the window, accounting for wrapping, folds, diff
+    /// filler lines and virtual lines.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_win_text_height()
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    pub fn text_height(
+        &self,
+        opts: &WinTextHeightOpts,
+    ) -> Result<WinTextHeight> {
+        let mut err = nvim::Error::new();
+        let dict = unsafe { nvim_win_text_height(self.0, opts, &mut err) };
+        choose!(err, Ok(WinTextHeight::from_object(dict.into())?))
+    }
+
+    /// Returns the number of screen lines the `rows` (0-indexed) of the
+    /// window's buffer would occupy if they were displayed in the window,
+    /// taking wrapping and closed folds into account. Useful to size
+    /// floating windows or to compute how far to scroll.
+    ///
+    /// On Neovim nightly this uses [`nvim_win_text_height()`][1],
+    /// so diff filler lines and virtual lines are counted too. On older
+    /// versions they aren't, and the height of wrapped lines is estimated
+    /// from their display width, ignoring options like `'linebreak'` and
+    /// `'showbreak'`.
+    ///
+    /// [1]: https://neovim.io/doc/user/api.html#nvim_win_text_height()
+    pub fn text_lines_height<R>(&self, rows: R) -> Result<usize>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match rows.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match rows.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.get_buf()?.line_count()?,
+        };
+
+        if start >= end {
+            return Ok(0);
+        }
+
+        #[cfg(feature = "neovim-nightly")]
+        let height = {
+            let opts = WinTextHeightOpts::builder()
+                .start_row(start)
+                .end_row(end - 1)
+                .build();
+            self.text_height(&opts)?.all
+        };
+
+        #[cfg(not(feature = "neovim-nightly"))]
+        let height = self.call(move |()| screen_lines(start, end))?;
+
+        Ok(height)
+    }
+
+    /// Returns an iterator over the window-scoped (`w:`) variables, as
+    /// `(name, value)` pairs.
+    ///
+    /// This is a wrapper around [`getwinvar()`][1], since there's no API
+    /// function to list the variables.
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#getwinvar()
+    pub fn vars(&self) -> Result<impl SuperIterator<(nvim::String, Object)>> {
+        if !self.is_valid() {
+            return Err(Error::custom(format!(
+                "Invalid window id: {}",
+                self.0
+            )));
+        }
+        let vars =
+            crate::call_function::<_, Dictionary>("getwinvar", (self.0, ""))?;
+        Ok(vars.into_iter())
+    }
+}
+
+/// Estimates the number of screen lines occupied by the lines `start..end`
+/// (0-indexed) of the current window's buffer.
+#[cfg(not(feature = "neovim-nightly"))]
+fn screen_lines(start: usize, end: usize) -> Result<usize> {
+    let width = crate::eval::<Integer>(
+        "winwidth(0) - getwininfo(win_getid())[0].textoff",
+    )?;
+    let wrap = crate::eval::<Integer>("&wrap")? != 0;
+
+    let mut height = 0;
+    let mut lnum = start + 1;
+
+    while lnum <= end {
+        let fold_end = crate::call_function::<_, Integer>(
+            "foldclosedend",
+            (lnum as Integer,),
+        )?;
+
+        // A closed fold takes a single line.
+        if fold_end != -1 {
+            height += 1;
+            lnum = fold_end as usize + 1;
+            continue;
+        }
+
+        let cells = if wrap && width > 0 {
+            crate::eval::<Integer>(&format!("virtcol([{lnum}, '$']) - 1"))?
+        } else {
+            0
+        };
+
+        height += if cells > 0 { 1 + (cells - 1) / width } else { 1 } as usize;
+        lnum += 1;
+    }
+
+    Ok(height)
 }
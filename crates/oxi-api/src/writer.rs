@@ -0,0 +1,208 @@
+use std::io;
+use std::marker::PhantomData;
+
+use oxi_types as nvim;
+
+use crate::ffi::global::*;
+use crate::Buffer;
+use crate::Result;
+
+/// An [`io::Write`] adapter that appends the written text to a [`Buffer`].
+///
+/// Every complete line is appended to the buffer as soon as its newline is
+/// written. A trailing partial line is kept until it's either completed by a
+/// later write or the writer is flushed, at which point it's added to the
+/// buffer and extended in place by the following writes. The writer is
+/// flushed when dropped.
+///
+/// If the buffer only contains a single empty line when the first line is
+/// written, e.g. because it was just created, that line is replaced.
+///
+/// Like the rest of the API, the writer can only be used on the main thread,
+/// so it doesn't implement `Send`. To write from other threads wrap it in
+/// nvim-oxi's `ThreadSafeWriter`, available with the `libuv` feature, which
+/// forwards the writes to the main thread.
+///
+/// # Examples
+///
+/// ```ignore
+/// use std::io::Write;
+/// use nvim_oxi::api::{self, BufferWriter};
+///
+/// let buf = api::create_buf(true, true)?;
+/// let mut writer = BufferWriter::new(buf);
+/// writeln!(writer, "{} files changed", 3)?;
+/// ```
+pub struct BufferWriter {
+    buffer: Buffer,
+
+    /// The last, incomplete line written to the writer.
+    partial: Vec<u8>,
+
+    /// Whether `partial` has already been added to the buffer as its last
+    /// line by a flush.
+    partial_flushed: bool,
+
+    /// Whether nothing has been added to the buffer yet.
+    untouched: bool,
+
+    _not_send: PhantomData<*const ()>,
+}
+
+impl BufferWriter {
+    /// Creates a new writer appending to `buffer`.
+    #[inline]
+    pub fn new(buffer: Buffer) -> Self {
+        Self {
+            buffer,
+            partial: Vec::new(),
+            partial_flushed: false,
+            untouched: true,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the buffer the writer appends to.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Adds `lines` to the end of the buffer, replacing its last line if it
+    /// holds a previously flushed partial line.
+    fn append(&mut self, lines: Vec<nvim::String>) -> Result<()> {
+        let count = self.buffer.line_count()?;
+
+        let start = if self.partial_flushed
+            || (self.untouched && count == 1 && self.is_first_line_empty()?)
+        {
+            count - 1
+        } else {
+            count
+        };
+
+        self.buffer.set_lines(start..count, true, lines)?;
+        self.untouched = false;
+        Ok(())
+    }
+
+    fn is_first_line_empty(&self) -> Result<bool> {
+        let first = self.buffer.get_lines(0..1, true)?.next();
+        Ok(!matches!(first, Some(line) if !line.as_bytes().is_empty()))
+    }
+}
+
+impl io::Write for BufferWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut lines = Vec::new();
+        let mut rest = buf;
+
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            self.partial.extend_from_slice(&rest[..pos]);
+            lines.push(nvim::String::from_bytes(&self.partial));
+            self.partial.clear();
+            rest = &rest[pos + 1..];
+        }
+
+        if !lines.is_empty() {
+            self.append(lines).map_err(to_io_error)?;
+            self.partial_flushed = false;
+        }
+
+        self.partial.extend_from_slice(rest);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.partial.is_empty() {
+            return Ok(());
+        }
+
+        self.append(vec![nvim::String::from_bytes(&self.partial)])
+            .map_err(to_io_error)?;
+
+        self.partial_flushed = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for BufferWriter {
+    fn drop(&mut self) {
+        if self.buffer.is_valid() {
+            let _ = io::Write::flush(self);
+        }
+    }
+}
+
+/// Where a [`MessageWriter`] writes to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MessageTarget {
+    Out,
+    Err,
+}
+
+/// An [`io::Write`] adapter that writes to the message area.
+///
+/// Neovim buffers the written text and only displays it once a newline is
+/// written, so every message should end with one.
+///
+/// Like the rest of the API, the writer can only be used on the main thread,
+/// so it doesn't implement `Send`. See [`BufferWriter`] for how to write from
+/// other threads.
+///
+/// # Examples
+///
+/// ```ignore
+/// use std::io::Write;
+/// use nvim_oxi::api::MessageWriter;
+///
+/// writeln!(MessageWriter::err(), "couldn't find {}", "foo.txt")?;
+/// ```
+pub struct MessageWriter {
+    target: MessageTarget,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl MessageWriter {
+    /// Creates a writer displaying regular messages, like
+    /// [`out_write`](crate::out_write).
+    #[inline]
+    pub fn out() -> Self {
+        Self { target: MessageTarget::Out, _not_send: PhantomData }
+    }
+
+    /// Creates a writer displaying error messages, like
+    /// [`err_write`](crate::err_write).
+    #[inline]
+    pub fn err() -> Self {
+        Self { target: MessageTarget::Err, _not_send: PhantomData }
+    }
+}
+
+impl io::Write for MessageWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let msg = nvim::String::from_bytes(buf);
+
+        unsafe {
+            match self.target {
+                MessageTarget::Out => nvim_out_write(msg.non_owning()),
+                MessageTarget::Err => nvim_err_write(msg.non_owning()),
+            }
+        }
+
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// `io::Error::other` requires a more recent Rust version than the MSRV.
+#[allow(clippy::io_other_error)]
+fn to_io_error(err: crate::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
@@ -1,32 +1,165 @@
-use std::error::Error;
+use std::collections::HashMap;
+use std::ffi::c_char;
 
 use oxi_api::Buffer;
-use oxi_luajit::{self as lua, ffi::*, macros::cstr, Pushable};
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable, Pushable};
+use oxi_types::{
+    serde::{Deserializer, Serializer},
+    Array,
+    Dictionary,
+    Object,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::opts::{ConfigOpts, GetOpts};
+use crate::Result;
+use crate::{Diagnostic, NamespaceInfo};
+
+/// Binding to [`vim.diagnostic.config()`][1].
+///
+/// Configures the diagnostics globally if `namespace` is `None`, or only the
+/// ones in the given namespace otherwise.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.config()
+pub fn config(opts: &ConfigOpts, namespace: Option<u32>) -> Result<()> {
+    call(cstr!("config"), (Dictionary::from(opts), namespace))
+}
+
+/// Binding to [`vim.diagnostic.disable()`][1].
+///
+/// Disables the diagnostics in `buffer`, or in all the buffers if it's
+/// `None`, limited to the given namespace if any.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.disable()
+pub fn disable(buffer: Option<&Buffer>, namespace: Option<u32>) -> Result<()> {
+    call(cstr!("disable"), (buffer.cloned(), namespace))
+}
 
 /// Binding to [`vim.diagnostic.enable()`][1].
 ///
+/// Enables the diagnostics in `buffer`, or in all the buffers if it's `None`,
+/// limited to the given namespace if any.
+///
 /// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.enable()
-pub fn enable(
+pub fn enable(buffer: Option<&Buffer>, namespace: Option<u32>) -> Result<()> {
+    call(cstr!("enable"), (buffer.cloned(), namespace))
+}
+
+/// Binding to [`vim.diagnostic.get()`][1].
+///
+/// Returns the diagnostics in `buffer`, or in all the buffers if it's `None`.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.get()
+pub fn get(
+    buffer: Option<&Buffer>,
+    opts: &GetOpts,
+) -> Result<Vec<Diagnostic>> {
+    let diagnostics = call::<_, Array>(
+        cstr!("get"),
+        (buffer.cloned(), Dictionary::from(opts)),
+    )?;
+
+    diagnostics.into_iter().map(deserialize).collect()
+}
+
+/// Binding to [`vim.diagnostic.get_namespace()`][1].
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.get_namespace()
+pub fn get_namespace(namespace: u32) -> Result<NamespaceInfo> {
+    deserialize(call::<_, Object>(cstr!("get_namespace"), namespace)?)
+}
+
+/// Binding to [`vim.diagnostic.get_namespaces()`][1].
+///
+/// Returns the namespaces that have been used to publish diagnostics, indexed
+/// by their id.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.get_namespaces()
+pub fn get_namespaces() -> Result<HashMap<u32, NamespaceInfo>> {
+    call::<_, HashMap<u32, Object>>(cstr!("get_namespaces"), ())?
+        .into_iter()
+        .map(|(id, info)| Ok((id, deserialize(info)?)))
+        .collect()
+}
+
+/// Binding to [`vim.diagnostic.reset()`][1].
+///
+/// Removes the diagnostics in `buffer`, or in all the buffers if it's `None`,
+/// limited to the given namespace if any.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.reset()
+pub fn reset(namespace: Option<u32>, buffer: Option<&Buffer>) -> Result<()> {
+    call(cstr!("reset"), (namespace, buffer.cloned()))
+}
+
+/// Binding to [`vim.diagnostic.set()`][1].
+///
+/// Replaces the diagnostics of `namespace` in `buffer` with `diagnostics`.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.set()
+pub fn set<D>(
+    namespace: u32,
     buffer: &Buffer,
-    namespace: Option<u32>,
-) -> Result<(), Box<dyn Error> /* TODO: actual error */> {
-    let res = unsafe {
-        lua::with_state(|lua_state| {
-            lua_getglobal(lua_state, cstr!("vim"));
-            lua_getfield(lua_state, -1, cstr!("diagnostic"));
-            lua_getfield(lua_state, -1, cstr!("enable"));
-            let _ = buffer.clone().push(lua_state);
-            let _ = namespace.push(lua_state);
-            lua_pcall(lua_state, 2, 0, 0)
-        })
-    };
+    diagnostics: D,
+    opts: &ConfigOpts,
+) -> Result<()>
+where
+    D: IntoIterator<Item = Diagnostic>,
+{
+    let diagnostics = diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.serialize(Serializer::new()))
+        .collect::<std::result::Result<Array, _>>()?;
 
-    match res {
-        LUA_OK => Ok(()),
+    call(
+        cstr!("set"),
+        (namespace, buffer.clone(), diagnostics, Dictionary::from(opts)),
+    )
+}
+
+/// Calls the function `vim.diagnostic.{fun}` with the given arguments.
+fn call<A, R>(fun: *const c_char, args: A) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            lua_getfield(lstate, -1, cstr!("diagnostic"));
+            lua_getfield(lstate, -1, fun);
+
+            let nargs = match args.push(lstate) {
+                Ok(nargs) => nargs,
+                Err(err) => {
+                    lua_settop(lstate, top);
+                    return Err(err.into());
+                },
+            };
+
+            let res = match lua_pcall(lstate, nargs, 1, 0) {
+                LUA_OK => R::pop(lstate).map_err(Into::into),
 
-        _err_code => {
-            // TODO: error handling
-            todo!()
-        },
+                err_code => {
+                    let msg = lua::utils::error_message(lstate, -1);
+
+                    Err(match err_code {
+                        LUA_ERRMEM => lua::Error::MemoryError(msg),
+                        _ => lua::Error::RuntimeError(msg),
+                    }
+                    .into())
+                },
+            };
+
+            // Also pops `vim` and `vim.diagnostic`.
+            lua_settop(lstate, top);
+            res
+        })
     }
 }
+
+fn deserialize<T: DeserializeOwned>(obj: Object) -> Result<T> {
+    Ok(T::deserialize(Deserializer::new(obj))?)
+}
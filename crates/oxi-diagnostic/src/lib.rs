@@ -1,5 +1,16 @@
+//! Rust bindings to Neovim's [`vim.diagnostic`][1] module.
+//!
+//! Diagnostics are published per namespace, which can be created with
+//! [`create_namespace()`](oxi_api::create_namespace).
+//!
+//! [1]: https://neovim.io/doc/user/diagnostic.html
+
 mod diagnostic;
-mod opts;
+mod error;
+pub mod opts;
+mod types;
 
 pub use diagnostic::*;
+pub use error::{Error, Result};
 pub use opts::*;
+pub use types::*;
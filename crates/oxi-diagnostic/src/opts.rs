@@ -1 +1,158 @@
+//! Options passed to the functions of this crate.
 
+use oxi_types::{Dictionary, Integer, Object};
+
+use crate::Severity;
+
+/// Options passed to [`config()`](crate::config) and [`set()`](crate::set).
+///
+/// Every option accepts either a boolean or a table of options, as described
+/// in [`:h vim.diagnostic.config()`][1]. Options that aren't set are left
+/// unchanged.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.config()
+#[derive(Clone, Debug, Default)]
+pub struct ConfigOpts {
+    underline: Object,
+    virtual_text: Object,
+    signs: Object,
+    float: Object,
+    update_in_insert: Object,
+    severity_sort: Object,
+}
+
+impl ConfigOpts {
+    /// Creates a new [`ConfigOptsBuilder`].
+    #[inline]
+    pub fn builder() -> ConfigOptsBuilder {
+        ConfigOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ConfigOptsBuilder(ConfigOpts);
+
+impl ConfigOptsBuilder {
+    /// Whether to underline the text of the diagnostics.
+    #[inline]
+    pub fn underline(&mut self, underline: impl Into<Object>) -> &mut Self {
+        self.0.underline = underline.into();
+        self
+    }
+
+    /// Whether to show the diagnostics as virtual text.
+    #[inline]
+    pub fn virtual_text(
+        &mut self,
+        virtual_text: impl Into<Object>,
+    ) -> &mut Self {
+        self.0.virtual_text = virtual_text.into();
+        self
+    }
+
+    /// Whether to show signs in the sign column.
+    #[inline]
+    pub fn signs(&mut self, signs: impl Into<Object>) -> &mut Self {
+        self.0.signs = signs.into();
+        self
+    }
+
+    /// Options for the floating windows opened by
+    /// [`vim.diagnostic.open_float()`][1].
+    ///
+    /// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.open_float()
+    #[inline]
+    pub fn float(&mut self, float: impl Into<Object>) -> &mut Self {
+        self.0.float = float.into();
+        self
+    }
+
+    /// Whether to update the diagnostics while in Insert mode.
+    #[inline]
+    pub fn update_in_insert(&mut self, update_in_insert: bool) -> &mut Self {
+        self.0.update_in_insert = update_in_insert.into();
+        self
+    }
+
+    /// Whether to sort the diagnostics by severity.
+    #[inline]
+    pub fn severity_sort(&mut self, severity_sort: bool) -> &mut Self {
+        self.0.severity_sort = severity_sort.into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> ConfigOpts {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<&ConfigOpts> for Dictionary {
+    fn from(opts: &ConfigOpts) -> Self {
+        Self::from_iter([
+            ("underline", opts.underline.clone()),
+            ("virtual_text", opts.virtual_text.clone()),
+            ("signs", opts.signs.clone()),
+            ("float", opts.float.clone()),
+            ("update_in_insert", opts.update_in_insert.clone()),
+            ("severity_sort", opts.severity_sort.clone()),
+        ])
+    }
+}
+
+/// Options passed to [`get()`](crate::get).
+#[derive(Clone, Debug, Default)]
+pub struct GetOpts {
+    namespace: Object,
+    lnum: Object,
+    severity: Object,
+}
+
+impl GetOpts {
+    /// Creates a new [`GetOptsBuilder`].
+    #[inline]
+    pub fn builder() -> GetOptsBuilder {
+        GetOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct GetOptsBuilder(GetOpts);
+
+impl GetOptsBuilder {
+    /// Only get the diagnostics in the given namespace.
+    #[inline]
+    pub fn namespace(&mut self, namespace: u32) -> &mut Self {
+        self.0.namespace = namespace.into();
+        self
+    }
+
+    /// Only get the diagnostics on the given line.
+    #[inline]
+    pub fn lnum(&mut self, lnum: usize) -> &mut Self {
+        self.0.lnum = (lnum as Integer).into();
+        self
+    }
+
+    /// Only get the diagnostics with the given severity.
+    #[inline]
+    pub fn severity(&mut self, severity: Severity) -> &mut Self {
+        self.0.severity = (severity as u8).into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> GetOpts {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<&GetOpts> for Dictionary {
+    fn from(opts: &GetOpts) -> Self {
+        Self::from_iter([
+            ("namespace", opts.namespace.clone()),
+            ("lnum", opts.lnum.clone()),
+            ("severity", opts.severity.clone()),
+        ])
+    }
+}
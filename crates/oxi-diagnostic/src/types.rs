@@ -0,0 +1,244 @@
+use std::fmt;
+
+use oxi_api::Buffer;
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// The severity of a [`Diagnostic`].
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Severity {
+    #[default]
+    Error = 1,
+
+    Warn = 2,
+
+    Info = 3,
+
+    Hint = 4,
+}
+
+impl From<Severity> for u8 {
+    #[inline]
+    fn from(severity: Severity) -> Self {
+        severity as u8
+    }
+}
+
+impl TryFrom<u8> for Severity {
+    type Error = String;
+
+    #[inline]
+    fn try_from(severity: u8) -> Result<Self, String> {
+        match severity {
+            1 => Ok(Self::Error),
+            2 => Ok(Self::Warn),
+            3 => Ok(Self::Info),
+            4 => Ok(Self::Hint),
+            other => Err(format!("invalid diagnostic severity {other}")),
+        }
+    }
+}
+
+/// A diagnostic, as described in [`:h diagnostic-structure`][1].
+///
+/// Line and column numbers are zero-indexed, and the end position is
+/// exclusive.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#diagnostic-structure
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// The buffer the diagnostic belongs to. Only set on the diagnostics
+    /// returned by [`get()`](crate::get).
+    #[serde(skip_serializing)]
+    pub bufnr: Option<Buffer>,
+
+    /// The starting line of the diagnostic.
+    pub lnum: usize,
+
+    /// The final line of the diagnostic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_lnum: Option<usize>,
+
+    /// The starting column of the diagnostic.
+    pub col: usize,
+
+    /// The final column of the diagnostic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_col: Option<usize>,
+
+    /// The severity of the diagnostic.
+    #[serde(default)]
+    pub severity: Severity,
+
+    /// The diagnostic text.
+    pub message: String,
+
+    /// The source of the diagnostic, e.g. the name of the linter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// The diagnostic code. Codes set from Lua as numbers are converted to
+    /// strings.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "string_or_int"
+    )]
+    pub code: Option<String>,
+
+    /// The namespace the diagnostic belongs to. Only set on the diagnostics
+    /// returned by [`get()`](crate::get).
+    #[serde(skip_serializing)]
+    pub namespace: Option<u32>,
+}
+
+impl Diagnostic {
+    /// Creates a new [`DiagnosticBuilder`].
+    #[inline(always)]
+    pub fn builder() -> DiagnosticBuilder {
+        DiagnosticBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct DiagnosticBuilder(Diagnostic);
+
+impl DiagnosticBuilder {
+    /// The starting line of the diagnostic.
+    #[inline]
+    pub fn lnum(&mut self, lnum: usize) -> &mut Self {
+        self.0.lnum = lnum;
+        self
+    }
+
+    /// The final line of the diagnostic.
+    #[inline]
+    pub fn end_lnum(&mut self, end_lnum: usize) -> &mut Self {
+        self.0.end_lnum = Some(end_lnum);
+        self
+    }
+
+    /// The starting column of the diagnostic.
+    #[inline]
+    pub fn col(&mut self, col: usize) -> &mut Self {
+        self.0.col = col;
+        self
+    }
+
+    /// The final column of the diagnostic.
+    #[inline]
+    pub fn end_col(&mut self, end_col: usize) -> &mut Self {
+        self.0.end_col = Some(end_col);
+        self
+    }
+
+    /// The severity of the diagnostic. Defaults to [`Severity::Error`].
+    #[inline]
+    pub fn severity(&mut self, severity: Severity) -> &mut Self {
+        self.0.severity = severity;
+        self
+    }
+
+    /// The diagnostic text.
+    #[inline]
+    pub fn message(&mut self, message: impl Into<String>) -> &mut Self {
+        self.0.message = message.into();
+        self
+    }
+
+    /// The source of the diagnostic, e.g. the name of the linter.
+    #[inline]
+    pub fn source(&mut self, source: impl Into<String>) -> &mut Self {
+        self.0.source = Some(source.into());
+        self
+    }
+
+    /// The diagnostic code.
+    #[inline]
+    pub fn code(&mut self, code: impl Into<String>) -> &mut Self {
+        self.0.code = Some(code.into());
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> Diagnostic {
+        std::mem::take(&mut self.0)
+    }
+}
+
+/// Informations about a diagnostic namespace, returned by
+/// [`get_namespace()`](crate::get_namespace).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct NamespaceInfo {
+    /// The name of the namespace.
+    pub name: String,
+
+    /// Whether diagnostics from the namespace are currently disabled.
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+fn string_or_int<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrIntVisitor;
+
+    impl<'de> de::Visitor<'de> for StringOrIntVisitor {
+        type Value = Option<String>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string or an integer")
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E> {
+            Ok(Some(s.to_owned()))
+        }
+
+        fn visit_i64<E>(self, n: i64) -> Result<Self::Value, E> {
+            Ok(Some(n.to_string()))
+        }
+
+        fn visit_u64<E>(self, n: u64) -> Result<Self::Value, E> {
+            Ok(Some(n.to_string()))
+        }
+
+        fn visit_f64<E>(self, n: f64) -> Result<Self::Value, E> {
+            Ok(Some(n.to_string()))
+        }
+    }
+
+    deserializer.deserialize_any(StringOrIntVisitor)
+}
@@ -69,4 +69,22 @@ impl<T, D> Handle<T, D> {
             data as *mut c_void,
         )
     }
+
+    /// Closes the handle, freeing its memory and its data once libuv is done
+    /// with it. The handle must not be used after this.
+    pub(crate) unsafe fn close(&mut self) {
+        ffi::uv_close(self.ptr as *mut uv_handle_t, Some(close_cb::<T, D>));
+    }
+}
+
+extern "C" fn close_cb<T, D>(ptr: *mut uv_handle_t) {
+    unsafe {
+        let data = ffi::uv_handle_get_data(ptr) as *mut D;
+
+        if !data.is_null() {
+            drop(Box::from_raw(data));
+        }
+
+        alloc::dealloc(ptr as *mut u8, Layout::new::<T>());
+    }
 }
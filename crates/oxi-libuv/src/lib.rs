@@ -10,4 +10,4 @@ use handle::Handle;
 pub use r#async::AsyncHandle;
 pub use r#loop::init;
 use r#loop::with_loop;
-pub use timer::TimerHandle;
+pub use timer::{TimerGuard, TimerHandle};
@@ -1,7 +1,7 @@
 use std::error::Error as StdError;
 use std::time::Duration;
 
-use libuv_sys2::{self as ffi, uv_timer_t};
+use libuv_sys2::{self as ffi, uv_handle_t, uv_timer_t};
 
 use crate::{Error, Handle};
 
@@ -12,6 +12,10 @@ pub(crate) type Callback = Box<
 /// Binding to libuv's [Timer handle][1] used to schedule callbacks to be
 /// called in the future.
 ///
+/// A `TimerHandle` keeps running after it's dropped, until it's explicitly
+/// [stopped](TimerHandle::stop). Use a [`TimerGuard`] to stop it when it goes
+/// out of scope instead.
+///
 /// [1]: http://docs.libuv.org/en/v1.x/timer.html
 pub struct TimerHandle {
     handle: Handle<uv_timer_t, Callback>,
@@ -27,6 +31,7 @@ impl TimerHandle {
     }
 
     /// Executes a callback every `repeat` interval starting after `timeout`.
+    /// A zero `repeat` interval only executes the callback once.
     ///
     /// If the timeout is zero the callback will fire on the next event loop
     /// iteration.
@@ -80,6 +85,20 @@ impl TimerHandle {
         })
     }
 
+    /// Wraps the timer in a [`TimerGuard`], which stops it when dropped.
+    #[inline]
+    pub fn into_guard(self) -> TimerGuard {
+        TimerGuard { timer: Some(self) }
+    }
+
+    /// Returns whether the timer is active, i.e. if its callback is still
+    /// going to be called.
+    pub fn is_active(&self) -> bool {
+        unsafe {
+            ffi::uv_is_active(self.handle.as_ptr() as *const uv_handle_t) != 0
+        }
+    }
+
     /// Stops the timer.
     pub fn stop(&mut self) -> Result<(), Error> {
         let retv = unsafe { ffi::uv_timer_stop(self.handle.as_mut_ptr()) };
@@ -92,16 +111,103 @@ impl TimerHandle {
     }
 }
 
+/// A [`TimerHandle`] which is stopped and freed when dropped.
+///
+/// This is useful to debounce events, e.g. to only lint a buffer once the
+/// user has stopped typing: replacing the guard of the pending timer with a
+/// new one cancels the previous callback.
+///
+/// # Examples
+///
+/// ```ignore
+/// use std::time::Duration;
+/// use nvim_oxi::libuv::TimerGuard;
+///
+/// let mut pending = None;
+///
+/// // On every `TextChanged` event.
+/// pending = Some(TimerGuard::once(Duration::from_millis(300), || {
+///     // Lint the buffer..
+///     Ok::<_, nvim_oxi::Error>(())
+/// })?);
+/// ```
+#[must_use = "dropping a `TimerGuard` stops its timer"]
+pub struct TimerGuard {
+    timer: Option<TimerHandle>,
+}
+
+impl TimerGuard {
+    /// Same as [`TimerHandle::start()`], but the timer is stopped when the
+    /// guard is dropped.
+    #[inline]
+    pub fn start<Cb, E>(
+        timeout: Duration,
+        repeat: Duration,
+        callback: Cb,
+    ) -> Result<Self, Error>
+    where
+        Cb: FnMut(&mut TimerHandle) -> Result<(), E> + 'static,
+        E: StdError + 'static,
+    {
+        TimerHandle::start(timeout, repeat, callback)
+            .map(TimerHandle::into_guard)
+    }
+
+    /// Same as [`TimerHandle::once()`], but the timer is stopped when the
+    /// guard is dropped.
+    #[inline]
+    pub fn once<Cb, E>(timeout: Duration, callback: Cb) -> Result<Self, Error>
+    where
+        Cb: FnOnce() -> Result<(), E> + 'static,
+        E: StdError + 'static,
+    {
+        TimerHandle::once(timeout, callback).map(TimerHandle::into_guard)
+    }
+
+    /// Returns whether the timer is active.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        matches!(&self.timer, Some(timer) if timer.is_active())
+    }
+
+    /// Stops the timer.
+    #[inline]
+    pub fn stop(&mut self) -> Result<(), Error> {
+        match &mut self.timer {
+            Some(timer) => timer.stop(),
+            None => Ok(()),
+        }
+    }
+
+    /// Consumes the guard without stopping the timer, which keeps running
+    /// until it's explicitly stopped.
+    #[inline]
+    pub fn detach(mut self) -> TimerHandle {
+        self.timer.take().expect("only taken on drop or detach")
+    }
+}
+
+impl Drop for TimerGuard {
+    fn drop(&mut self) {
+        if let Some(mut timer) = self.timer.take() {
+            let _ = timer.stop();
+            // The callback and the handle's memory are freed once libuv is
+            // done with the handle.
+            unsafe { timer.handle.close() };
+        }
+    }
+}
+
 extern "C" fn timer_cb(ptr: *mut uv_timer_t) {
     let handle: Handle<_, Callback> = unsafe { Handle::from_raw(ptr) };
 
     let callback = unsafe { handle.get_data() };
 
     if !callback.is_null() {
-        let mut handle = TimerHandle { handle };
+        let mut timer = TimerHandle { handle };
         let callback = unsafe { &mut *callback };
 
-        if let Err(_err) = callback(&mut handle) {
+        if let Err(_err) = callback(&mut timer) {
             // TODO: what now?
         }
     }
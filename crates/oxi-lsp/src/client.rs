@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use oxi_api::Buffer;
+use oxi_luajit::macros::cstr;
+use oxi_types::{self as nvim, Object, ObjectKind};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::lsp::{
+    call_client,
+    deserialize,
+    get_client_field,
+    response_handler,
+    serialize,
+};
+use crate::{Error, HandlerContext, ResponseError, Result};
+
+/// A handle to an active LSP client, as returned by
+/// [`get_active_clients()`](crate::get_active_clients).
+///
+/// The client is looked up by its id every time one of its methods is
+/// called, which fails with [`Error::ClientNotFound`] once the client has
+/// exited.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LspClient {
+    id: u32,
+}
+
+impl LspClient {
+    #[inline]
+    pub(crate) fn from_id(id: u32) -> Self {
+        Self { id }
+    }
+
+    /// The buffers the client is attached to.
+    pub fn attached_buffers(&self) -> Result<Vec<Buffer>> {
+        let buffers = get_client_field::<HashMap<Buffer, bool>>(
+            self.id,
+            cstr!("attached_buffers"),
+        )?;
+
+        Ok(buffers
+            .into_iter()
+            .filter_map(|(buffer, attached)| attached.then_some(buffer))
+            .collect())
+    }
+
+    /// Binding to `client.cancel_request()`.
+    ///
+    /// Cancels the request with the given id, returning whether the
+    /// cancellation could be sent.
+    pub fn cancel_request(&self, request_id: u32) -> Result<bool> {
+        call_client(self.id, cstr!("cancel_request"), request_id)
+    }
+
+    /// Returns whether the server supports the capability at the given path
+    /// in its [`ServerCapabilities`][1], using dots to separate the keys of
+    /// nested capabilities, e.g. `"hoverProvider"` or
+    /// `"workspace.workspaceFolders.supported"`.
+    ///
+    /// Capabilities set to `false` or missing are considered unsupported.
+    ///
+    /// [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#serverCapabilities
+    pub fn has_capability(&self, path: &str) -> Result<bool> {
+        let mut capability =
+            get_client_field::<Object>(self.id, cstr!("server_capabilities"))?;
+
+        for key in path.split('.') {
+            capability = match capability.as_dictionary() {
+                Some(dict) => dict.get(key).cloned().unwrap_or_default(),
+                None => return Ok(false),
+            };
+        }
+
+        Ok(match capability.kind() {
+            ObjectKind::Nil => false,
+            ObjectKind::Boolean => unsafe {
+                capability.as_boolean_unchecked()
+            },
+            _ => true,
+        })
+    }
+
+    /// The id of the client.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Binding to `client.is_stopped()`.
+    pub fn is_stopped(&self) -> Result<bool> {
+        call_client(self.id, cstr!("is_stopped"), ())
+    }
+
+    /// The name of the client.
+    pub fn name(&self) -> Result<String> {
+        get_client_field(self.id, cstr!("name"))
+    }
+
+    /// Binding to `client.notify()`.
+    ///
+    /// Sends a notification to the server, returning whether it could be
+    /// sent.
+    pub fn notify<P>(&self, method: &str, params: &P) -> Result<bool>
+    where
+        P: Serialize + ?Sized,
+    {
+        call_client(
+            self.id,
+            cstr!("notify"),
+            (nvim::String::from(method), serialize(params)?),
+        )
+    }
+
+    /// The encoding used by the server to compute column offsets, i.e. one
+    /// of `"utf-8"`, `"utf-16"` or `"utf-32"`.
+    pub fn offset_encoding(&self) -> Result<String> {
+        get_client_field(self.id, cstr!("offset_encoding"))
+    }
+
+    /// Binding to `client.request()`.
+    ///
+    /// Sends a request to the server, calling `handler` with its response.
+    /// Returns the id of the request, which can be passed to
+    /// [`cancel_request()`](Self::cancel_request).
+    pub fn request<P, R, F>(
+        &self,
+        method: &str,
+        params: &P,
+        buffer: &Buffer,
+        handler: F,
+    ) -> Result<u32>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+        F: FnMut(Result<R>, HandlerContext) + 'static,
+    {
+        let handler = response_handler(handler);
+
+        let res = call_client::<_, (bool, Object)>(
+            self.id,
+            cstr!("request"),
+            (
+                nvim::String::from(method),
+                serialize(params)?,
+                handler.clone(),
+                buffer.clone(),
+            ),
+        );
+
+        // The client keeps the handler alive until it's done with it.
+        handler.remove_from_lua_registry();
+
+        let (sent, request_id) = res?;
+
+        if !sent {
+            return Err(Error::RequestNotSent(method.to_owned()));
+        }
+
+        deserialize(request_id)
+    }
+
+    /// Binding to `client.request_sync()`.
+    ///
+    /// Sends a request to the server, blocking until it responds or until
+    /// `timeout` has elapsed.
+    pub fn request_sync<P, R>(
+        &self,
+        method: &str,
+        params: &P,
+        timeout: Duration,
+        buffer: &Buffer,
+    ) -> Result<R>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(default)]
+            err: Option<ResponseError>,
+
+            #[serde(default)]
+            result: Object,
+        }
+
+        let (response, reason) = call_client::<_, (Object, Option<String>)>(
+            self.id,
+            cstr!("request_sync"),
+            (
+                nvim::String::from(method),
+                serialize(params)?,
+                timeout.as_millis() as u32,
+                buffer.clone(),
+            ),
+        )?;
+
+        if response.is_nil() {
+            return Err(Error::RequestFailed {
+                method: method.to_owned(),
+                reason: reason.unwrap_or_default(),
+            });
+        }
+
+        match deserialize::<Response>(response)? {
+            Response { err: Some(err), .. } => Err(err.into()),
+            Response { result, .. } => deserialize(result),
+        }
+    }
+
+    /// The [`ServerCapabilities`][1] of the server, deserialized into `T`.
+    ///
+    /// [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#serverCapabilities
+    pub fn server_capabilities<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        deserialize(get_client_field(self.id, cstr!("server_capabilities"))?)
+    }
+
+    /// Binding to `client.stop()`.
+    ///
+    /// Stops the client, forcing it to shut down if `force` is true.
+    pub fn stop(&self, force: bool) -> Result<()> {
+        call_client(self.id, cstr!("stop"), force)
+    }
+
+    /// Binding to `client.supports_method()`.
+    ///
+    /// Returns whether the server supports the given method.
+    pub fn supports_method(&self, method: &str) -> Result<bool> {
+        call_client(
+            self.id,
+            cstr!("supports_method"),
+            nvim::String::from(method),
+        )
+    }
+}
@@ -0,0 +1,28 @@
+use thiserror::Error as ThisError;
+
+use crate::ResponseError;
+
+/// Result type returned by the functions of this crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error type returned by the functions of this crate.
+#[derive(Clone, Debug, PartialEq, ThisError)]
+pub enum Error {
+    #[error("no active LSP client with id {0}")]
+    ClientNotFound(u32),
+
+    #[error(transparent)]
+    Lua(#[from] oxi_luajit::Error),
+
+    #[error("LSP request {0:?} couldn't be sent, the client has shut down")]
+    RequestNotSent(String),
+
+    #[error("LSP request {method:?} failed: {reason}")]
+    RequestFailed { method: String, reason: String },
+
+    #[error(transparent)]
+    Response(#[from] ResponseError),
+
+    #[error(transparent)]
+    Serde(#[from] oxi_types::serde::Error),
+}
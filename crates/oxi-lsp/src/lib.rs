@@ -0,0 +1,36 @@
+//! Rust bindings to Neovim's [`vim.lsp`][1] module.
+//!
+//! This allows plugins to send requests and notifications through the
+//! language servers already attached to a buffer, instead of spawning their
+//! own. Request parameters and responses are (de)serialized with `serde`, so
+//! they can be typed using e.g. the [`lsp-types`][2] crate.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::Buffer;
+//! use nvim_oxi::lsp::{self, opts::GetClientsOpts};
+//!
+//! let buf = Buffer::current();
+//! let opts = GetClientsOpts::builder().buffer(buf.clone()).build();
+//!
+//! for client in lsp::get_active_clients(&opts)? {
+//!     if client.has_capability("hoverProvider")? {
+//!         nvim_oxi::print!("{} supports hovering", client.name()?);
+//!     }
+//! }
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/lsp.html
+//! [2]: https://docs.rs/lsp-types
+
+mod client;
+mod error;
+mod lsp;
+pub mod opts;
+mod types;
+
+pub use client::*;
+pub use error::{Error, Result};
+pub use lsp::*;
+pub use types::*;
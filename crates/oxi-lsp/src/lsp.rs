@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, CStr};
+
+use oxi_api::Buffer;
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable, Pushable};
+use oxi_types::{
+    self as nvim,
+    serde::{Deserializer, Serializer},
+    Dictionary,
+    Function,
+    Object,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::opts::GetClientsOpts;
+use crate::{Error, HandlerContext, LspClient, ResponseError, Result};
+
+/// Binding to [`vim.lsp.buf_is_attached()`][1].
+///
+/// Returns whether the client with the given id is attached to `buffer`.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.buf_is_attached()
+pub fn buf_is_attached(buffer: &Buffer, client_id: u32) -> Result<bool> {
+    call(&[cstr!("buf_is_attached")], (buffer.clone(), client_id))
+}
+
+/// Binding to [`vim.lsp.buf_notify()`][1].
+///
+/// Sends a notification to all the clients attached to `buffer`, returning
+/// whether any client received it.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.buf_notify()
+pub fn buf_notify<P>(buffer: &Buffer, method: &str, params: &P) -> Result<bool>
+where
+    P: Serialize + ?Sized,
+{
+    call(
+        &[cstr!("buf_notify")],
+        (buffer.clone(), nvim::String::from(method), serialize(params)?),
+    )
+}
+
+/// Binding to [`vim.lsp.buf_request()`][1].
+///
+/// Sends a request to all the clients attached to `buffer`, calling
+/// `handler` with the response of each one of them. Returns the ids of the
+/// sent requests, indexed by the id of the client they were sent to.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.buf_request()
+pub fn buf_request<P, R, F>(
+    buffer: &Buffer,
+    method: &str,
+    params: &P,
+    handler: F,
+) -> Result<HashMap<u32, u32>>
+where
+    P: Serialize + ?Sized,
+    R: DeserializeOwned,
+    F: FnMut(Result<R>, HandlerContext) + 'static,
+{
+    let handler = response_handler(handler);
+
+    let res = call(
+        &[cstr!("buf_request")],
+        (
+            buffer.clone(),
+            nvim::String::from(method),
+            serialize(params)?,
+            handler.clone(),
+        ),
+    );
+
+    // `vim.lsp` keeps the handler alive until it's done with it.
+    handler.remove_from_lua_registry();
+
+    res
+}
+
+/// Binding to [`vim.lsp.get_active_clients()`][1].
+///
+/// Returns the active clients, filtered by the given options. Uses
+/// `vim.lsp.get_clients()` on the versions of Neovim that provide it.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.get_active_clients()
+pub fn get_active_clients(opts: &GetClientsOpts) -> Result<Vec<LspClient>> {
+    let ClientIds(ids) = call(
+        &[cstr!("get_clients"), cstr!("get_active_clients")],
+        Dictionary::from(opts),
+    )?;
+
+    Ok(ids.into_iter().map(LspClient::from_id).collect())
+}
+
+/// Binding to [`vim.lsp.get_client_by_id()`][1].
+///
+/// Returns `None` if there's no active client with the given id.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.get_client_by_id()
+pub fn get_client_by_id(id: u32) -> Result<Option<LspClient>> {
+    let res = unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+            let res = push_client(lstate, id);
+            lua_settop(lstate, top);
+            res
+        })
+    };
+
+    match res {
+        Ok(()) => Ok(Some(LspClient::from_id(id))),
+        Err(Error::ClientNotFound(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// The ids of a list of clients.
+///
+/// Only the ids are popped, since converting the whole client tables to
+/// `Object`s would also copy their (possibly cyclic) internal state.
+struct ClientIds(Vec<u32>);
+
+impl Poppable for ClientIds {
+    unsafe fn pop(
+        lstate: *mut lua_State,
+    ) -> std::result::Result<Self, lua::Error> {
+        if lua_gettop(lstate) == 0 {
+            return Err(lua::Error::PopEmptyStack);
+        }
+
+        let ty = lua_type(lstate, -1);
+        if ty != LUA_TTABLE {
+            return Err(lua::Error::pop_wrong_type::<Self>(LUA_TTABLE, ty));
+        }
+
+        let len = lua_objlen(lstate, -1);
+        let ids = (1..=len)
+            .map(|idx| {
+                lua_rawgeti(lstate, -1, idx as c_int);
+                lua_getfield(lstate, -1, cstr!("id"));
+                let id = u32::pop(lstate);
+                // Pops the client.
+                lua_pop(lstate, 1);
+                id
+            })
+            .collect::<std::result::Result<_, _>>();
+
+        lua_pop(lstate, 1);
+        ids.map(Self)
+    }
+}
+
+/// Calls the first function among `vim.lsp.{names}` that exists with the
+/// given arguments, which allows falling back to the older names of
+/// functions that have been renamed between Neovim versions.
+fn call<A, R>(names: &[*const c_char], args: A) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            lua_getfield(lstate, -1, cstr!("lsp"));
+
+            let found = names.iter().any(|&name| {
+                lua_settop(lstate, top + 2);
+                lua_getfield(lstate, -1, name);
+                lua_type(lstate, -1) == LUA_TFUNCTION
+            });
+
+            if !found {
+                let name = CStr::from_ptr(names[0]);
+                lua_settop(lstate, top);
+                return Err(lua::Error::RuntimeError(format!(
+                    "no function `{}` in `vim.lsp`",
+                    name.to_string_lossy()
+                ))
+                .into());
+            }
+
+            let res = pcall(lstate, args);
+
+            // Also pops `vim` and `vim.lsp`.
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Calls `client.{method}(args)` on the client with the given id.
+pub(crate) fn call_client<A, R>(
+    id: u32,
+    method: *const c_char,
+    args: A,
+) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            let res = push_client(lstate, id).and_then(|()| {
+                lua_getfield(lstate, -1, method);
+                pcall(lstate, args)
+            });
+
+            // Also pops the client.
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Gets the field `field` of the client with the given id.
+pub(crate) fn get_client_field<R: Poppable>(
+    id: u32,
+    field: *const c_char,
+) -> Result<R> {
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            let res = push_client(lstate, id).and_then(|()| {
+                lua_getfield(lstate, -1, field);
+                Ok(R::pop(lstate)?)
+            });
+
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Pushes the table of the client with the given id on the stack, failing
+/// if there's no active client with that id. The caller has to reset the
+/// stack afterwards.
+unsafe fn push_client(lstate: *mut lua_State, id: u32) -> Result<()> {
+    lua_getglobal(lstate, cstr!("vim"));
+    lua_getfield(lstate, -1, cstr!("lsp"));
+    lua_getfield(lstate, -1, cstr!("get_client_by_id"));
+    lua_pushinteger(lstate, id as lua_Integer);
+
+    match lua_pcall(lstate, 1, 1, 0) {
+        LUA_OK if lua_type(lstate, -1) == LUA_TTABLE => Ok(()),
+        LUA_OK => Err(Error::ClientNotFound(id)),
+        err_code => Err(pop_error(lstate, err_code)),
+    }
+}
+
+/// Pushes `args` and calls the function right below them, popping the
+/// return values into `R`. The caller has to reset the stack afterwards.
+unsafe fn pcall<A, R>(lstate: *mut lua_State, args: A) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    let nargs = args.push(lstate)?;
+
+    match lua_pcall(lstate, nargs, R::N, 0) {
+        LUA_OK => Ok(R::pop(lstate)?),
+        err_code => Err(pop_error(lstate, err_code)),
+    }
+}
+
+/// Converts the error message at the top of the stack left by a failed
+/// `lua_pcall` into an [`Error`].
+unsafe fn pop_error(lstate: *mut lua_State, err_code: c_int) -> Error {
+    let msg = lua::utils::error_message(lstate, -1);
+
+    match err_code {
+        LUA_ERRMEM => lua::Error::MemoryError(msg),
+        _ => lua::Error::RuntimeError(msg),
+    }
+    .into()
+}
+
+/// Wraps `handler` in a Lua function with the signature of an
+/// [`lsp-handler`][1].
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#lsp-handler
+pub(crate) fn response_handler<R, F>(
+    mut handler: F,
+) -> Function<(Object, Object, Object), ()>
+where
+    R: DeserializeOwned,
+    F: FnMut(Result<R>, HandlerContext) + 'static,
+{
+    Function::from_fn_mut(
+        move |(err, result, ctx): (Object, Object, Object)| {
+            let ctx = deserialize::<HandlerContext>(ctx)?;
+
+            let response = if err.is_nil() {
+                deserialize(result)
+            } else {
+                deserialize::<ResponseError>(err)
+                    .and_then(|err| Err(err.into()))
+            };
+
+            handler(response, ctx);
+
+            Ok::<_, Error>(())
+        },
+    )
+}
+
+pub(crate) fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Object> {
+    Ok(value.serialize(Serializer::new())?)
+}
+
+pub(crate) fn deserialize<T: DeserializeOwned>(obj: Object) -> Result<T> {
+    Ok(T::deserialize(Deserializer::new(obj))?)
+}
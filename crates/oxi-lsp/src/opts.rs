@@ -0,0 +1,62 @@
+//! Options passed to the functions of this crate.
+
+use oxi_api::Buffer;
+use oxi_types::{Dictionary, Object};
+
+/// Options passed to [`get_active_clients()`](crate::get_active_clients),
+/// used to filter the returned clients.
+#[derive(Clone, Debug, Default)]
+pub struct GetClientsOpts {
+    id: Object,
+    bufnr: Object,
+    name: Object,
+}
+
+impl GetClientsOpts {
+    /// Creates a new [`GetClientsOptsBuilder`].
+    #[inline]
+    pub fn builder() -> GetClientsOptsBuilder {
+        GetClientsOptsBuilder::default()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct GetClientsOptsBuilder(GetClientsOpts);
+
+impl GetClientsOptsBuilder {
+    /// Only return the client with the given id.
+    #[inline]
+    pub fn id(&mut self, id: u32) -> &mut Self {
+        self.0.id = id.into();
+        self
+    }
+
+    /// Only return the clients attached to the given buffer.
+    #[inline]
+    pub fn buffer(&mut self, buffer: Buffer) -> &mut Self {
+        self.0.bufnr = buffer.into();
+        self
+    }
+
+    /// Only return the clients with the given name.
+    #[inline]
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.0.name = name.into();
+        self
+    }
+
+    #[inline]
+    pub fn build(&mut self) -> GetClientsOpts {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<&GetClientsOpts> for Dictionary {
+    fn from(opts: &GetClientsOpts) -> Self {
+        Self::from_iter([
+            ("id", opts.id.clone()),
+            ("bufnr", opts.bufnr.clone()),
+            ("name", opts.name.clone()),
+        ])
+    }
+}
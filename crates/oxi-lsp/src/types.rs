@@ -0,0 +1,52 @@
+use std::fmt;
+
+use oxi_api::Buffer;
+use oxi_types::Object;
+use serde::Deserialize;
+
+/// The error returned by a language server in response to a request, as
+/// described in the [LSP specification][1].
+///
+/// [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#responseError
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ResponseError {
+    /// A number indicating the error type that occurred.
+    pub code: i64,
+
+    /// A short description of the error.
+    pub message: String,
+
+    /// Additional information about the error, if any.
+    #[serde(default)]
+    pub data: Option<Object>,
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+/// The context a response handler is called with, as described in
+/// [`:h lsp-handler`][1].
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#lsp-handler
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct HandlerContext {
+    /// The method of the request.
+    pub method: String,
+
+    /// The id of the client the response comes from.
+    pub client_id: u32,
+
+    /// The buffer the request was sent for.
+    pub bufnr: Buffer,
+
+    /// The parameters the request was sent with.
+    #[serde(default)]
+    pub params: Option<Object>,
+}
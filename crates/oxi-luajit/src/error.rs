@@ -21,6 +21,11 @@ pub enum Error {
     #[error("Lua runtime error: {0}")]
     RuntimeError(String),
 
+    /// An error raised by a Lua function called from Rust, together with
+    /// the stack traceback at the point where it was raised.
+    #[error("Lua error: {message}")]
+    LuaError { message: String, traceback: Option<String> },
+
     #[error("Lua memory error: {0}")]
     MemoryError(String),
 
@@ -24,6 +24,9 @@ pub const fn lua_upvalueindex(i: c_int) -> c_int {
     LUA_GLOBALSINDEX - i
 }
 
+// Option for multiple returns in `lua_pcall` and `lua_call`.
+pub const LUA_MULTRET: c_int = -1;
+
 // Thread status.
 pub const LUA_OK: c_int = 0;
 pub const LUA_ERRRUN: c_int = 2;
@@ -80,6 +83,9 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_gettop
     pub fn lua_gettop(L: *mut lua_State) -> c_int;
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_insert
+    pub fn lua_insert(L: *mut lua_State, index: c_int);
+
     // https://www.lua.org/manual/5.1/manual.html#lua_newuserdata
     pub fn lua_newuserdata(L: *mut lua_State, size: usize) -> *mut c_void;
 
@@ -124,6 +130,9 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_pushvalue
     pub fn lua_pushvalue(L: *mut lua_State, index: c_int);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_rawget
+    pub fn lua_rawget(L: *mut lua_State, index: c_int);
+
     // https://www.lua.org/manual/5.1/manual.html#lua_rawgeti
     pub fn lua_rawgeti(L: *mut lua_State, index: c_int, n: c_int);
 
@@ -133,6 +142,9 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_rawseti
     pub fn lua_rawseti(L: *mut lua_State, index: c_int, n: c_int);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_remove
+    pub fn lua_remove(L: *mut lua_State, index: c_int);
+
     // https://www.lua.org/manual/5.1/manual.html#lua_settop
     pub fn lua_settop(L: *mut lua_State, index: c_int);
 
@@ -166,11 +178,43 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#luaL_error
     pub fn luaL_error(L: *mut lua_State, fmt: *const c_char, ...) -> !;
 
+    // https://www.lua.org/manual/5.1/manual.html#luaL_loadstring
+    pub fn luaL_loadstring(L: *mut lua_State, s: *const c_char) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#luaL_ref
+    #[cfg(not(feature = "leak-check"))]
     pub fn luaL_ref(L: *mut lua_State, t: c_int) -> c_int;
 
     // https://www.lua.org/manual/5.1/manual.html#luaL_unref
+    #[cfg(not(feature = "leak-check"))]
     pub fn luaL_unref(L: *mut lua_State, t: c_int, r#ref: c_int);
+
+    #[cfg(feature = "leak-check")]
+    #[link_name = "luaL_ref"]
+    fn luaL_ref_untracked(L: *mut lua_State, t: c_int) -> c_int;
+
+    #[cfg(feature = "leak-check")]
+    #[link_name = "luaL_unref"]
+    fn luaL_unref_untracked(L: *mut lua_State, t: c_int, r#ref: c_int);
+}
+
+// https://www.lua.org/manual/5.1/manual.html#luaL_ref
+#[cfg(feature = "leak-check")]
+pub unsafe fn luaL_ref(L: *mut lua_State, t: c_int) -> c_int {
+    let r#ref = luaL_ref_untracked(L, t);
+    if t == LUA_REGISTRYINDEX {
+        crate::leak_check::on_ref(r#ref);
+    }
+    r#ref
+}
+
+// https://www.lua.org/manual/5.1/manual.html#luaL_unref
+#[cfg(feature = "leak-check")]
+pub unsafe fn luaL_unref(L: *mut lua_State, t: c_int, r#ref: c_int) {
+    if t == LUA_REGISTRYINDEX {
+        crate::leak_check::on_unref(r#ref);
+    }
+    luaL_unref_untracked(L, t, r#ref)
 }
 
 // https://www.lua.org/manual/5.1/manual.html#lua_getglobal
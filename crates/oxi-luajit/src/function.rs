@@ -1,9 +1,10 @@
 use std::error::Error;
-use std::ffi::{c_int, CStr};
+use std::ffi::c_int;
 use std::mem;
 use std::ptr;
 
 use crate::ffi::{self, lua_State};
+use crate::macros::cstr;
 use crate::{utils, Poppable, Pushable};
 
 /// Stores a function in the Lua registry, returning its ref.
@@ -46,6 +47,12 @@ where
 }
 
 /// Calls a function previously stored in the Lua registry via [store].
+///
+/// Like in Lua, the values returned by the function are adjusted to the
+/// number of values popped by `R`, i.e. the extra ones are discarded and the
+/// missing ones are set to `nil`. Errors raised by the function are returned
+/// as [`Error::LuaError`](crate::Error::LuaError), including their stack
+/// traceback.
 pub fn call<A, R>(lua_ref: c_int, args: A) -> Result<R, crate::Error>
 where
     A: Pushable,
@@ -53,38 +60,76 @@ where
 {
     unsafe {
         crate::with_state(move |lstate| {
-            ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, lua_ref);
-            let nargs = args.push(lstate)?;
-
-            match ffi::lua_pcall(lstate, nargs, -1, 0 /* <- errorfunc */) {
-                ffi::LUA_OK => R::pop(lstate),
-
-                err_code => {
-                    let msg = CStr::from_ptr(ffi::lua_tostring(lstate, -1))
-                        .to_string_lossy()
-                        .to_string();
+            let top = ffi::lua_gettop(lstate);
 
-                    ffi::lua_pop(lstate, 1);
+            // Use `debug.traceback` as the message handler.
+            ffi::lua_getglobal(lstate, cstr!("debug"));
+            ffi::lua_getfield(lstate, -1, cstr!("traceback"));
+            ffi::lua_remove(lstate, -2);
+            let msgh = top + 1;
 
-                    match err_code {
-                        ffi::LUA_ERRRUN => {
-                            Err(crate::Error::RuntimeError(msg))
-                        },
-
-                        ffi::LUA_ERRMEM => Err(crate::Error::MemoryError(msg)),
-
-                        ffi::LUA_ERRERR => {
-                            unreachable!("errorfunc is 0, this never happens!")
-                        },
+            ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, lua_ref);
 
-                        _ => unreachable!(),
-                    }
+            let nargs = match args.push(lstate) {
+                Ok(nargs) => nargs,
+                Err(err) => {
+                    ffi::lua_settop(lstate, top);
+                    return Err(err);
                 },
-            }
+            };
+
+            let res =
+                match ffi::lua_pcall(lstate, nargs, ffi::LUA_MULTRET, msgh) {
+                    ffi::LUA_OK => {
+                        ffi::lua_remove(lstate, msgh);
+
+                        if R::N == 0 {
+                            ffi::lua_settop(lstate, top);
+                            ffi::lua_pushnil(lstate);
+                        } else {
+                            ffi::lua_settop(lstate, top + R::N);
+                        }
+
+                        R::pop(lstate)
+                    },
+
+                    err_code => {
+                        let msg = crate::utils::error_message(lstate, -1);
+
+                        match err_code {
+                            ffi::LUA_ERRRUN => Err(lua_error(msg)),
+                            ffi::LUA_ERRMEM => {
+                                Err(crate::Error::MemoryError(msg))
+                            },
+                            ffi::LUA_ERRERR => {
+                                Err(crate::Error::RuntimeError(msg))
+                            },
+                            _ => unreachable!(),
+                        }
+                    },
+                };
+
+            ffi::lua_settop(lstate, top);
+
+            res
         })
     }
 }
 
+/// Splits the message produced by `debug.traceback` into the original error
+/// message and the traceback.
+fn lua_error(msg: String) -> crate::Error {
+    const SEPARATOR: &str = "\nstack traceback:\n";
+
+    match msg.split_once(SEPARATOR) {
+        Some((message, traceback)) => crate::Error::LuaError {
+            message: message.to_owned(),
+            traceback: Some(traceback.to_owned()),
+        },
+        None => crate::Error::LuaError { message: msg, traceback: None },
+    }
+}
+
 /// Removes the function reference stored in the Lua registry
 pub fn remove(lua_ref: c_int) {
     unsafe {
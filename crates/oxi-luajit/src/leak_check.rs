@@ -0,0 +1,29 @@
+//! Tracks the references to Lua values created in the registry, used by the
+//! `leak-check` feature of `nvim-oxi`.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::ffi::c_int;
+
+thread_local! {
+    static LIVE_REFS: RefCell<HashSet<c_int>> = RefCell::new(HashSet::new());
+}
+
+pub(crate) fn on_ref(lua_ref: c_int) {
+    // `LUA_NOREF` and `LUA_REFNIL` are negative and don't reference anything.
+    if lua_ref > 0 {
+        let _ = LIVE_REFS.try_with(|refs| refs.borrow_mut().insert(lua_ref));
+    }
+}
+
+pub(crate) fn on_unref(lua_ref: c_int) {
+    let _ = LIVE_REFS.try_with(|refs| refs.borrow_mut().remove(&lua_ref));
+}
+
+/// Returns the number of registry references created by `luaL_ref()` that
+/// haven't been released by `luaL_unref()` yet.
+///
+/// References created by Neovim itself aren't counted.
+pub fn live_refs() -> usize {
+    LIVE_REFS.try_with(|refs| refs.borrow().len()).unwrap_or(0)
+}
@@ -2,10 +2,14 @@
 mod error;
 pub mod ffi;
 pub mod function;
+#[cfg(feature = "leak-check")]
+#[doc(hidden)]
+pub mod leak_check;
 pub mod macros;
 mod poppable;
 mod pushable;
 mod state;
+mod table;
 pub mod utils;
 
 pub use error::Error;
@@ -14,3 +18,4 @@ pub use macros::__print;
 pub use poppable::Poppable;
 pub use pushable::Pushable;
 pub use state::{init, with_state};
+pub use table::{LuaTable, Pairs};
@@ -20,10 +20,11 @@ macro_rules! count {
 
 pub(crate) use count;
 
-/// Same as [`std::dbg!`](dbg) but writes to the Neovim message area instead of
-/// stdout.
+/// Same as [`std::dbg!`](dbg) but displays the message in the Neovim
+/// message area via [`nvim_echo()`][1], adding it to the message history.
 ///
 /// [dbg]: https://doc.rust-lang.org/std/macro.dbg.html
+/// [1]: https://neovim.io/doc/user/api.html#nvim_echo()
 #[macro_export]
 macro_rules! dbg {
     () => {
@@ -43,8 +44,11 @@ macro_rules! dbg {
     };
 }
 
-/// Same as [`std::print!`](print) but writes to the Neovim message area
-/// instead of stdout.
+/// Same as [`std::print!`](print) but displays the message in the Neovim
+/// message area via [`nvim_echo()`][1], adding it to the message history.
+///
+/// Since Neovim displays every message on its own line this is equivalent to
+/// [`println!`](crate::println).
 ///
 /// # Examples
 ///
@@ -56,6 +60,7 @@ macro_rules! dbg {
 /// ```
 ///
 /// [print]: https://doc.rust-lang.org/std/macro.print.html
+/// [1]: https://neovim.io/doc/user/api.html#nvim_echo()
 #[macro_export]
 macro_rules! print {
     ($($arg:tt)*) => {{
@@ -63,19 +68,60 @@ macro_rules! print {
     }}
 }
 
-/// Prints a message to the Neovim message area.
+/// Same as [`std::println!`](println) but displays the message in the Neovim
+/// message area via [`nvim_echo()`][1], adding it to the message history.
+///
+/// [println]: https://doc.rust-lang.org/std/macro.println.html
+/// [1]: https://neovim.io/doc/user/api.html#nvim_echo()
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("")
+    };
+    ($($arg:tt)*) => {
+        $crate::print!($($arg)*)
+    };
+}
+
+/// Displays a message in the Neovim message area, adding it to the message
+/// history.
+///
+/// Errors, e.g. when called in a context where messages can't be displayed,
+/// are ignored.
 #[doc(hidden)]
 pub fn __print(text: impl Into<String>) {
     unsafe {
         crate::with_state(move |lstate| {
             let text = text.into();
-            lua_getglobal(lstate, cstr!("print"));
-            lua_pushlstring(
-                lstate,
-                text.as_ptr() as *const c_char,
-                text.len(),
-            );
-            lua_call(lstate, 1, 0);
+            let top = lua_gettop(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            if lua_type(lstate, -1) == LUA_TTABLE {
+                lua_getfield(lstate, -1, cstr!("api"));
+            }
+            if lua_type(lstate, -1) == LUA_TTABLE {
+                lua_getfield(lstate, -1, cstr!("nvim_echo"));
+            }
+
+            if lua_type(lstate, -1) == LUA_TFUNCTION {
+                // `{ { text } }`
+                lua_createtable(lstate, 1, 0);
+                lua_createtable(lstate, 1, 0);
+                lua_pushlstring(
+                    lstate,
+                    text.as_ptr() as *const c_char,
+                    text.len(),
+                );
+                lua_rawseti(lstate, -2, 1);
+                lua_rawseti(lstate, -2, 1);
+
+                lua_pushboolean(lstate, 1);
+                lua_createtable(lstate, 0, 0);
+
+                let _ = lua_pcall(lstate, 3, 0, 0);
+            }
+
+            lua_settop(lstate, top);
         })
     };
 }
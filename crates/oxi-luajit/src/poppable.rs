@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::ffi::c_int;
 use std::hash::Hash;
 
 use crate::ffi::*;
@@ -7,11 +8,19 @@ use crate::Error;
 
 /// Trait implemented for types that can be popped off the Lua stack.
 pub trait Poppable: Sized {
+    /// The number of stack slots popped by [`pop`](Poppable::pop), used to
+    /// adjust the number of values returned by Lua functions called from
+    /// Rust.
+    #[doc(hidden)]
+    const N: c_int = 1;
+
     /// Pops the value at the top of the stack.
     unsafe fn pop(lua_state: *mut lua_State) -> Result<Self, Error>;
 }
 
 impl Poppable for () {
+    const N: c_int = 0;
+
     #[inline(always)]
     unsafe fn pop(state: *mut lua_State) -> Result<Self, crate::Error> {
         if lua_gettop(state) == 0 {
@@ -238,6 +247,8 @@ macro_rules! pop_tuple {
         where
             $($name: Poppable,)*
         {
+            const N: c_int = 0 $(+ $name::N)*;
+
             #[allow(non_snake_case)]
             unsafe fn pop(state: *mut lua_State) -> Result<Self, crate::Error> {
                 crate::utils::grow_stack(state, count!($($name)*));
@@ -125,6 +125,20 @@ impl Pushable for String {
     }
 }
 
+impl Pushable for &str {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        ffi::lua_pushlstring(
+            lstate,
+            self.as_ptr() as *const c_char,
+            self.len(),
+        );
+        Ok(1)
+    }
+}
+
 impl<T> Pushable for Option<T>
 where
     T: Pushable,
@@ -0,0 +1,297 @@
+use std::ffi::c_int;
+use std::marker::PhantomData;
+
+use crate::ffi::{self, lua_State};
+use crate::{Error, Poppable, Pushable};
+
+/// A reference to a Lua table.
+///
+/// The table is stored in the Lua registry and its fields are read and
+/// written one at a time, so a large table, e.g. the options passed to a
+/// plugin's `setup()` function, doesn't have to be converted into a
+/// `Dictionary` to look up a few of its keys. Fields are accessed without
+/// invoking metamethods, like `rawget()` and `rawset()` do.
+///
+/// The table stays alive until every reference to it is dropped.
+#[derive(Debug)]
+pub struct LuaTable {
+    lua_ref: c_int,
+}
+
+impl LuaTable {
+    /// Creates a new, empty table.
+    pub fn new() -> Self {
+        unsafe {
+            crate::with_state(|lstate| {
+                ffi::lua_createtable(lstate, 0, 0);
+                Self { lua_ref: ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX) }
+            })
+        }
+    }
+
+    /// Returns whether the table has a non-`nil` value for `key`.
+    pub fn contains_key<K: Pushable>(&self, key: K) -> Result<bool, Error> {
+        self.with_field(key, |lstate| {
+            Ok(unsafe { ffi::lua_type(lstate, -1) } != ffi::LUA_TNIL)
+        })
+    }
+
+    /// Returns the value of `key`, or `nil` if it's missing. Use an
+    /// [`Option`] as `V` to get `None` for missing keys instead of an error.
+    pub fn get<K, V>(&self, key: K) -> Result<V, Error>
+    where
+        K: Pushable,
+        V: Poppable,
+    {
+        self.with_field(key, |lstate| unsafe { V::pop(lstate) })
+    }
+
+    /// Returns whether the [length](LuaTable::len) of the table is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the length of the table, as returned by the `#` operator.
+    pub fn len(&self) -> usize {
+        unsafe {
+            crate::with_state(|lstate| {
+                self.push_table(lstate);
+                let len = ffi::lua_objlen(lstate, -1);
+                ffi::lua_pop(lstate, 1);
+                len
+            })
+        }
+    }
+
+    /// Returns an iterator over the key-value pairs of the table, in the
+    /// same unspecified order as Lua's `pairs()`.
+    ///
+    /// Like in Lua, the table must not be assigned new keys while it's being
+    /// iterated over, but existing keys can be modified or cleared.
+    #[inline]
+    pub fn pairs<K, V>(&self) -> Pairs<'_, K, V>
+    where
+        K: Poppable,
+        V: Poppable,
+    {
+        Pairs { table: self, key_ref: None, done: false, _pd: PhantomData }
+    }
+
+    /// Sets the value of `key`, or removes it if `value` is `nil`. Fails if
+    /// the key is `nil` or `NaN`.
+    pub fn set<K, V>(&self, key: K, value: V) -> Result<(), Error>
+    where
+        K: Pushable,
+        V: Pushable,
+    {
+        unsafe {
+            crate::with_state(|lstate| {
+                let top = ffi::lua_gettop(lstate);
+                self.push_table(lstate);
+
+                let res = push_one(lstate, key)
+                    .and_then(|()| push_one(lstate, value))
+                    .and_then(|()| {
+                        if is_invalid_key(lstate, -2) {
+                            return Err(Error::push_error(
+                                "table key",
+                                "table keys can't be nil or NaN",
+                            ));
+                        }
+                        ffi::lua_rawset(lstate, -3);
+                        Ok(())
+                    });
+
+                ffi::lua_settop(lstate, top);
+                res
+            })
+        }
+    }
+
+    /// Pushes the value of `key` on the stack and calls `fun`, restoring the
+    /// stack afterwards.
+    fn with_field<K, F, R>(&self, key: K, fun: F) -> Result<R, Error>
+    where
+        K: Pushable,
+        F: FnOnce(*mut lua_State) -> Result<R, Error>,
+    {
+        unsafe {
+            crate::with_state(|lstate| {
+                let top = ffi::lua_gettop(lstate);
+                self.push_table(lstate);
+
+                let res = push_one(lstate, key).and_then(|()| {
+                    ffi::lua_rawget(lstate, -2);
+                    fun(lstate)
+                });
+
+                ffi::lua_settop(lstate, top);
+                res
+            })
+        }
+    }
+
+    #[inline]
+    unsafe fn push_table(&self, lstate: *mut lua_State) {
+        ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+    }
+}
+
+impl Default for LuaTable {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for LuaTable {
+    fn clone(&self) -> Self {
+        unsafe {
+            crate::with_state(|lstate| {
+                self.push_table(lstate);
+                Self { lua_ref: ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX) }
+            })
+        }
+    }
+}
+
+impl Drop for LuaTable {
+    fn drop(&mut self) {
+        unsafe {
+            crate::with_state(|lstate| {
+                ffi::luaL_unref(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref)
+            })
+        }
+    }
+}
+
+impl Poppable for LuaTable {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self, Error> {
+        if ffi::lua_gettop(lstate) == 0 {
+            return Err(Error::PopEmptyStack);
+        }
+
+        match ffi::lua_type(lstate, -1) {
+            ffi::LUA_TTABLE => Ok(Self {
+                lua_ref: ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX),
+            }),
+
+            other => {
+                Err(Error::pop_wrong_type::<Self>(ffi::LUA_TTABLE, other))
+            },
+        }
+    }
+}
+
+impl Pushable for LuaTable {
+    unsafe fn push(self, lstate: *mut lua_State) -> Result<c_int, Error> {
+        self.push_table(lstate);
+        Ok(1)
+    }
+}
+
+/// An iterator over the key-value pairs of a [`LuaTable`], returned by
+/// [`LuaTable::pairs`].
+pub struct Pairs<'a, K, V> {
+    table: &'a LuaTable,
+    /// A registry reference to the last key returned, from which `lua_next`
+    /// resumes the traversal.
+    key_ref: Option<c_int>,
+    done: bool,
+    _pd: PhantomData<(K, V)>,
+}
+
+impl<K, V> Iterator for Pairs<'_, K, V>
+where
+    K: Poppable,
+    V: Poppable,
+{
+    type Item = Result<(K, V), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        unsafe {
+            crate::with_state(|lstate| {
+                let top = ffi::lua_gettop(lstate);
+                self.table.push_table(lstate);
+
+                match self.key_ref.take() {
+                    Some(key_ref) => {
+                        ffi::lua_rawgeti(
+                            lstate,
+                            ffi::LUA_REGISTRYINDEX,
+                            key_ref,
+                        );
+                        ffi::luaL_unref(
+                            lstate,
+                            ffi::LUA_REGISTRYINDEX,
+                            key_ref,
+                        );
+                    },
+                    None => ffi::lua_pushnil(lstate),
+                }
+
+                if ffi::lua_next(lstate, -2) == 0 {
+                    self.done = true;
+                    ffi::lua_settop(lstate, top);
+                    return None;
+                }
+
+                // Pop a copy of the key, since popping it could convert it
+                // in place (e.g. a number into a string), which would break
+                // the traversal.
+                let res = V::pop(lstate).and_then(|value| {
+                    ffi::lua_pushvalue(lstate, -1);
+                    K::pop(lstate).map(|key| (key, value))
+                });
+
+                ffi::lua_settop(lstate, top + 2);
+                self.key_ref =
+                    Some(ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX));
+
+                ffi::lua_settop(lstate, top);
+                Some(res)
+            })
+        }
+    }
+}
+
+impl<K, V> Drop for Pairs<'_, K, V> {
+    fn drop(&mut self) {
+        if let Some(key_ref) = self.key_ref {
+            unsafe {
+                crate::with_state(|lstate| {
+                    ffi::luaL_unref(lstate, ffi::LUA_REGISTRYINDEX, key_ref)
+                })
+            }
+        }
+    }
+}
+
+/// Pushes `value` on the stack, failing if it takes more or less than a
+/// single slot.
+unsafe fn push_one<T: Pushable>(
+    lstate: *mut lua_State,
+    value: T,
+) -> Result<(), Error> {
+    match value.push(lstate)? {
+        1 => Ok(()),
+        n => Err(Error::push_error(
+            std::any::type_name::<T>(),
+            format!("expected a single value, got {n}"),
+        )),
+    }
+}
+
+/// Returns whether the value at `idx` can't be used as a table key.
+unsafe fn is_invalid_key(lstate: *mut lua_State, idx: c_int) -> bool {
+    match ffi::lua_type(lstate, idx) {
+        ffi::LUA_TNIL => true,
+        ffi::LUA_TNUMBER => ffi::lua_tonumber(lstate, idx).is_nan(),
+        _ => false,
+    }
+}
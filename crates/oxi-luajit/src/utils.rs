@@ -32,6 +32,21 @@ pub unsafe fn debug_value(
     }
 }
 
+/// Returns the message of the error at a given stack index, e.g. the one
+/// left by a failed `lua_pcall`.
+///
+/// Errors that are neither strings nor numbers, e.g. tables passed to
+/// `error()`, are described by their type instead.
+pub unsafe fn error_message(lstate: *mut lua_State, n: c_int) -> String {
+    let ptr = ffi::lua_tostring(lstate, n);
+
+    if ptr.is_null() {
+        format!("(error object is a {} value)", debug_type(lstate, n))
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
 /// Assumes that the value at index `index` is a table and returns whether it's
 /// an array table (as opposed to a dictionary table).
 pub unsafe fn is_table_array(lstate: *mut lua_State, index: c_int) -> bool {
@@ -7,6 +7,20 @@ use syn::parse_macro_input;
 //
 /// Marks the plugin entrypoint.
 ///
+/// The attribute can either be placed on a function returning the value
+/// handed to Lua when the plugin is `require`d, or on an inline module. In
+/// the latter case every function of the module marked with
+/// [`#[export]`](macro@oxi_export) is collected into a `Dictionary` keyed by
+/// the function's name, whose arguments are converted from the Lua arguments
+/// the function is called with. Exported functions can return any
+/// `Pushable` value, or a `Result` whose error is raised as a Lua error.
+///
+/// The `deferred = path` argument defers calling `path` until Neovim has
+/// started up (see `nvim_oxi::defer_init_on`), or until a UI has attached if
+/// `on = UIEnter` is also given. The exported functions of a deferred
+/// module call `nvim_oxi::run_deferred_init` before running, so that the
+/// initialization also happens on their first use.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -17,33 +31,395 @@ use syn::parse_macro_input;
 ///     Ok(())
 /// }
 /// ```
+///
+/// ```ignore
+/// use nvim_oxi as nvim;
+///
+/// // Only registers the commands at startup, loading the index they search
+/// // in once Neovim is ready.
+/// #[nvim::module(deferred = load_index)]
+/// fn search() -> nvim::Result<()> {
+///     // Commands call `nvim::run_deferred_init()` before using the index.
+///     Ok(())
+/// }
+///
+/// fn load_index() -> nvim::Result<()> {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```ignore
+/// use nvim_oxi as nvim;
+///
+/// // Sets up the highlight groups once a UI has attached. The exported
+/// // functions run the deferred initialization first if they're called
+/// // before that.
+/// #[nvim::module(deferred = set_highlights, on = UIEnter)]
+/// mod colors {
+///     use nvim_oxi as nvim;
+///
+///     #[nvim::export]
+///     fn pick() -> nvim::Result<()> {
+///         Ok(())
+///     }
+///
+///     fn set_highlights() -> nvim::Result<()> {
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// ```ignore
+/// use nvim_oxi as nvim;
+///
+/// // `require("calc")` returns `{ add = <function>, div = <function> }`.
+/// #[nvim::module]
+/// mod calc {
+///     use nvim_oxi as nvim;
+///
+///     #[nvim::export]
+///     fn add(a: i32, b: i32) -> i32 {
+///         a + b
+///     }
+///
+///     #[nvim::export]
+///     fn div(a: i32, b: i32) -> nvim::Result<i32> {
+///         a.checked_div(b)
+///             .ok_or_else(|| nvim::api::Error::Other("division by zero".into()).into())
+///     }
+/// }
+/// ```
 #[cfg(feature = "module")]
 #[proc_macro_attribute]
-pub fn oxi_module(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item = parse_macro_input!(item as syn::ItemFn);
+pub fn oxi_module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut module_attrs = ModuleAttrs::default();
+    let parser = syn::meta::parser(|meta| module_attrs.parse(meta));
+    parse_macro_input!(attr with parser);
+
+    if let Err(err) = module_attrs.validate() {
+        return err.into_compile_error().into();
+    }
 
+    let item = parse_macro_input!(item as syn::Item);
+
+    match item {
+        syn::Item::Fn(item) => module_fn(item, module_attrs),
+        syn::Item::Mod(item) => match module_mod(item, module_attrs) {
+            Ok(tokens) => tokens,
+            Err(err) => err.into_compile_error(),
+        },
+        other => syn::Error::new_spanned(
+            other,
+            "`#[module]` can only be placed on a function or an inline module",
+        )
+        .into_compile_error(),
+    }
+    .into()
+}
+
+/// Marks a function of a [`#[module]`](macro@oxi_module) module to be
+/// exported to Lua.
+///
+/// The attribute is consumed by the enclosing `#[module]`, so using it
+/// anywhere else is an error.
+#[cfg(feature = "module")]
+#[proc_macro_attribute]
+pub fn oxi_export(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+
+    let err = syn::Error::new(
+        Span::call_site(),
+        "`#[export]` can only be used on functions of a `#[module]` module",
+    )
+    .into_compile_error();
+
+    quote! { #err #item }.into()
+}
+
+/// The attributes accepted by [`oxi_module`].
+#[cfg(feature = "module")]
+#[derive(Default)]
+struct ModuleAttrs {
+    deferred: Option<syn::Path>,
+    on: Option<Ident>,
+}
+
+#[cfg(feature = "module")]
+impl ModuleAttrs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("deferred") {
+            if self.deferred.is_some() {
+                return Err(meta.error("duplicate `deferred` attribute"));
+            }
+            self.deferred = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("on") {
+            if self.on.is_some() {
+                return Err(meta.error("duplicate `on` attribute"));
+            }
+            let on: Ident = meta.value()?.parse()?;
+            if on != "VimEnter" && on != "UIEnter" {
+                return Err(syn::Error::new_spanned(
+                    on,
+                    "expected `VimEnter` or `UIEnter`",
+                ));
+            }
+            self.on = Some(on);
+        } else {
+            return Err(meta
+                .error("unsupported attribute, expected `deferred` or `on`"));
+        }
+        Ok(())
+    }
+
+    /// Checks that the attributes are consistent once they're all parsed.
+    fn validate(&self) -> syn::Result<()> {
+        match (&self.on, &self.deferred) {
+            (Some(on), None) => Err(syn::Error::new_spanned(
+                on,
+                "`on` can only be used together with `deferred`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The statement registering the deferred function, if any.
+    fn defer_init(&self) -> Option<proc_macro2::TokenStream> {
+        self.deferred.as_ref().map(|deferred| {
+            let on = self
+                .on
+                .clone()
+                .unwrap_or_else(|| Ident::new("VimEnter", Span::call_site()));
+            quote! {
+                ::nvim_oxi::defer_init_on(
+                    ::nvim_oxi::DeferredInitEvent::#on,
+                    #deferred,
+                )?;
+            }
+        })
+    }
+}
+
+#[cfg(feature = "module")]
+fn module_fn(
+    item: syn::ItemFn,
+    attrs: ModuleAttrs,
+) -> proc_macro2::TokenStream {
     #[allow(clippy::redundant_clone)]
     let module_name = item.sig.ident.clone();
 
     let lua_module =
         Ident::new(&format!("luaopen_{module_name}"), Span::call_site());
 
-    let module_body = quote! {
+    let body = match attrs.defer_init() {
+        Some(defer_init) => {
+            let output = &item.sig.output;
+            quote! {
+                fn __module() #output {
+                    let module = #module_name()?;
+                    #defer_init
+                    ::std::result::Result::Ok(module)
+                }
+
+                ::nvim_oxi::entrypoint(state, __module)
+            }
+        },
+        None => quote! { ::nvim_oxi::entrypoint(state, #module_name) },
+    };
+
+    quote! {
         #item
 
         #[no_mangle]
         unsafe extern "C" fn #lua_module(
             state: *mut ::nvim_oxi::lua::ffi::lua_State,
         ) -> ::std::ffi::c_int {
-            ::nvim_oxi::entrypoint(state, #module_name)
+            #body
+        }
+    }
+}
+
+#[cfg(feature = "module")]
+fn module_mod(
+    mut item: syn::ItemMod,
+    attrs: ModuleAttrs,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let module_name = &item.ident;
+
+    let lua_module =
+        Ident::new(&format!("luaopen_{module_name}"), Span::call_site());
+
+    let items = match &mut item.content {
+        Some((_, items)) => items,
+        None => {
+            return Err(syn::Error::new_spanned(
+                &item,
+                "`#[module]` can only be placed on inline modules",
+            ))
+        },
+    };
+
+    let mut exports = Vec::new();
+
+    for item in items.iter_mut() {
+        if let syn::Item::Fn(fun) = item {
+            let len = fun.attrs.len();
+            fun.attrs.retain(|attr| !is_export_attr(attr));
+            if fun.attrs.len() != len {
+                exports.push(export_fn(fun, attrs.deferred.is_some())?);
+            }
+        }
+    }
+
+    let len = exports.len();
+    let defer_init = attrs.defer_init();
+
+    items.push(syn::parse_quote! {
+        #[no_mangle]
+        unsafe extern "C" fn #lua_module(
+            state: *mut ::nvim_oxi::lua::ffi::lua_State,
+        ) -> ::std::ffi::c_int {
+            fn __exports() -> ::nvim_oxi::Result<::nvim_oxi::Dictionary> {
+                let exports: [(&str, ::nvim_oxi::Object); #len] = [
+                    #(#exports),*
+                ];
+                #defer_init
+                ::std::result::Result::Ok(
+                    ::nvim_oxi::Dictionary::from_iter(exports),
+                )
+            }
+
+            ::nvim_oxi::entrypoint(state, __exports)
+        }
+    });
+
+    Ok(quote! { #item })
+}
+
+/// Returns whether the attribute is `#[export]`, possibly prefixed by a
+/// path like `#[nvim_oxi::export]`.
+#[cfg(feature = "module")]
+fn is_export_attr(attr: &syn::Attribute) -> bool {
+    matches!(attr.meta, syn::Meta::Path(_))
+        && matches!(
+            attr.path().segments.last(),
+            Some(segment) if segment.ident == "export"
+        )
+}
+
+/// Returns a `(name, Object)` pair wrapping the function in a `Function`
+/// that pops its arguments from the Lua stack, running the deferred
+/// initialization first if `deferred` is set.
+#[cfg(feature = "module")]
+fn export_fn(
+    fun: &syn::ItemFn,
+    deferred: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &fun.sig;
+
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "exported functions can't be generic",
+        ));
+    }
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "exported functions can't be async",
+        ));
+    }
+
+    let mut args = Vec::new();
+    let mut types = Vec::new();
+
+    for (idx, input) in sig.inputs.iter().enumerate() {
+        match input {
+            syn::FnArg::Typed(pat) => {
+                args.push(Ident::new(
+                    &format!("__arg{idx}"),
+                    Span::call_site(),
+                ));
+                types.push(&pat.ty);
+            },
+
+            syn::FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "exported functions can't take `self`",
+                ))
+            },
         }
+    }
+
+    let name = &sig.ident;
+    let lua_name = name.to_string();
+
+    let call = quote! { #name(#(#args),*) };
+
+    let body = if returns_result(&sig.output) {
+        quote! { #call }
+    } else {
+        quote! {
+            ::std::result::Result::Ok::<_, ::std::convert::Infallible>(#call)
+        }
+    };
+
+    let body = if deferred {
+        quote! { ::nvim_oxi::__module::with_deferred_init(move || #body) }
+    } else {
+        body
+    };
+
+    Ok(quote! {
+        (
+            #lua_name,
+            ::nvim_oxi::Object::from(::nvim_oxi::Function::from_fn(
+                |(#(#args,)*): (#(#types,)*)| #body,
+            )),
+        )
+    })
+}
+
+/// Returns whether the return type of a function is a `Result`, in which
+/// case its error is turned into a Lua error.
+#[cfg(feature = "module")]
+fn returns_result(output: &syn::ReturnType) -> bool {
+    let ty = match output {
+        syn::ReturnType::Type(_, ty) => ty,
+        syn::ReturnType::Default => return false,
     };
 
-    module_body.into()
+    matches!(
+        &**ty,
+        syn::Type::Path(path)
+            if matches!(
+                path.path.segments.last(),
+                Some(segment) if segment.ident == "Result"
+            )
+    )
 }
 
 /// Tests a piece of code inside a Neovim session.
 ///
+/// By default Neovim is started with `-u NONE --headless` and with swap
+/// files disabled. This can be customized with the following attributes:
+///
+/// - `arg = "..."`: an extra command line argument passed to Neovim. Can be
+///   repeated;
+/// - `clean`: starts Neovim with `--clean` instead of `-u NONE`, which loads
+///   the builtin plugins and enables filetype detection;
+/// - `cmd = "..."`: an Ex command executed after Neovim has started, but
+///   before the test is loaded. Can be repeated;
+/// - `leak_check`: fails the test if its body leaks any of the values
+///   tracked by the `leak-check` feature of `nvim-oxi`, which must be
+///   enabled;
+/// - `no_swap`: disables swap files. This is already the default, but can be
+///   set to make it explicit in tests relying on it. Use
+///   `cmd = "set swapfile"` to enable them instead;
+/// - `setup = "..."`: a chunk of Lua code executed right before the test
+///   body. The test fails if the code raises an error.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -55,13 +431,22 @@ pub fn oxi_module(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     assert_eq!(Ok(42), api::get_var("foo"));
 ///     assert_eq!(Ok(()), api::del_var("foo"));
 /// }
+///
+/// #[nvim::test(clean, cmd = "set rtp+=./fixtures", setup = "vim.g.foo = 42")]
+/// fn with_fixtures() {
+///     assert_eq!(Ok(42), api::get_var("foo"));
+/// }
 /// ```
 #[cfg(feature = "test")]
 #[proc_macro_attribute]
-pub fn oxi_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn oxi_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut test_attrs = TestAttrs::default();
+    let parser = syn::meta::parser(|meta| test_attrs.parse(meta));
+    parse_macro_input!(attr with parser);
+
     let item = parse_macro_input!(item as syn::ItemFn);
 
-    let syn::ItemFn { sig, block, .. } = item;
+    let syn::ItemFn { attrs, sig, block, .. } = item;
 
     // TODO: here we'd need to append something like the module path of the
     // call site to `test_name` to avoid collisions between equally named tests
@@ -73,8 +458,36 @@ pub fn oxi_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let module_name = Ident::new(&format!("__{test_name}"), Span::call_site());
 
+    let TestAttrs { args, clean, cmds, leak_check, setup } = test_attrs;
+
+    let init = if clean {
+        quote! { .arg("--clean") }
+    } else {
+        quote! { .args(["-u", "NONE"]) }
+    };
+
+    let setup = setup.map(|setup| {
+        quote! { ::nvim_oxi::__test::exec_lua(#setup); }
+    });
+
+    let test_body = if leak_check {
+        quote! {
+            let counts = ::nvim_oxi::debug::live_counts();
+            let result = #test_body;
+            if let Some(leaked) =
+                ::nvim_oxi::debug::live_counts().leaked_since(&counts)
+            {
+                panic!("test leaked {}", leaked);
+            }
+            result
+        }
+    } else {
+        quote! { #test_body }
+    };
+
     quote! {
         #[test]
+        #(#attrs)*
         fn #test_name() {
             let mut library_filename = String::new();
             library_filename.push_str(::std::env::consts::DLL_PREFIX);
@@ -140,8 +553,10 @@ pub fn oxi_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
 
             let out = ::std::process::Command::new("nvim")
-                .args(["-u", "NONE", "--headless"])
+                #init
+                .args(["--headless"])
                 .args(["-c", "set noswapfile"])
+                #(.arg(#args))*
                 .args([
                     "-c",
                     &format!(
@@ -149,6 +564,7 @@ pub fn oxi_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         target_dir.join("oxi-test").display()
                     ),
                 ])
+                #(.args(["-c", #cmds]))*
                 .args([
                     "-c",
                     &format!("lua require('__{}')", stringify!(#test_name)),
@@ -183,8 +599,10 @@ pub fn oxi_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
 
         #[::nvim_oxi::module]
+        #(#attrs)*
         fn #module_name() -> ::nvim_oxi::Result<()> {
             let result = ::std::panic::catch_unwind(|| {
+                #setup
                 #test_body
             });
 
@@ -200,3 +618,42 @@ pub fn oxi_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// The attributes accepted by [`oxi_test`].
+#[cfg(feature = "test")]
+#[derive(Default)]
+struct TestAttrs {
+    args: Vec<syn::LitStr>,
+    clean: bool,
+    cmds: Vec<syn::LitStr>,
+    leak_check: bool,
+    setup: Option<syn::LitStr>,
+}
+
+#[cfg(feature = "test")]
+impl TestAttrs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("arg") {
+            self.args.push(meta.value()?.parse()?);
+        } else if meta.path.is_ident("clean") {
+            self.clean = true;
+        } else if meta.path.is_ident("cmd") {
+            self.cmds.push(meta.value()?.parse()?);
+        } else if meta.path.is_ident("leak_check") {
+            self.leak_check = true;
+        } else if meta.path.is_ident("no_swap") {
+            // Swap files are always disabled before the `cmd`s are run.
+        } else if meta.path.is_ident("setup") {
+            if self.setup.is_some() {
+                return Err(meta.error("duplicate `setup` attribute"));
+            }
+            self.setup = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error(
+                "unsupported attribute, expected one of `arg`, `clean`, \
+                 `cmd`, `leak_check`, `no_swap` or `setup`",
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use thiserror::Error as ThisError;
+
+/// Result type returned by the functions of this crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error type returned by the functions of this crate.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("{0:?} is not a valid plugin name: {1}")]
+    InvalidName(String, &'static str),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("at most one Neovim version can be enabled, got {0:?} and {1:?}")]
+    MultipleNeovimVersions(&'static str, &'static str),
+
+    #[error("refusing to overwrite {0:?}")]
+    WouldOverwrite(PathBuf),
+}
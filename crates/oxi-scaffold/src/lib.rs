@@ -0,0 +1,122 @@
+//! Generates the skeleton of a Neovim plugin built with `nvim-oxi`.
+//!
+//! The generated crate follows the setup described in the [examples][1]:
+//!
+//! - a `cdylib` crate whose entrypoint is marked with
+//!   `#[nvim_oxi::module]`;
+//! - tests using `#[nvim_oxi::test]`, with the `test` feature enabled as a
+//!   dev-dependency;
+//! - a Lua shim in `lua/{module}/init.lua` loading the compiled library
+//!   from `target/release`, so that the plugin can be added to the
+//!   runtimepath without copying or renaming the library;
+//! - a `.cargo/config.toml` with the linker arguments needed on macOS;
+//! - a release profile optimized for size and speed.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use oxi_scaffold::{Feature, PluginTemplate};
+//!
+//! let scaffold = oxi_scaffold::generate(PluginTemplate {
+//!     name: "my-plugin".into(),
+//!     features: vec![Feature::Neovim09, Feature::Lsp],
+//! })?;
+//!
+//! scaffold.write("my-plugin")?;
+//! # Ok::<_, oxi_scaffold::Error>(())
+//! ```
+//!
+//! [1]: https://github.com/noib3/nvim-oxi/tree/main/examples
+
+mod error;
+mod scaffold;
+mod template;
+
+pub use error::{Error, Result};
+pub use scaffold::*;
+pub use template::*;
+
+/// Generates the files of the plugin described by `template`, failing if its
+/// name isn't a valid crate name or if it enables more than one Neovim
+/// version.
+pub fn generate(template: PluginTemplate) -> Result<Scaffold> {
+    template.validate()?;
+    Ok(Scaffold::new(&template))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, features: &[Feature]) -> PluginTemplate {
+        PluginTemplate { name: name.into(), features: features.to_vec() }
+    }
+
+    #[test]
+    fn cargo_manifest() {
+        let scaffold = generate(template(
+            "my-plugin",
+            &[Feature::Lsp, Feature::Neovim09, Feature::Lsp],
+        ))
+        .unwrap();
+
+        let manifest = &scaffold.get("Cargo.toml").unwrap().contents;
+
+        assert!(manifest.contains("name = \"my-plugin\""));
+        assert!(manifest.contains("crate-type = [\"cdylib\"]"));
+        assert!(manifest.contains("features = [\"neovim-0-9\", \"lsp\"]"));
+        assert!(manifest.contains("[profile.release]"));
+    }
+
+    #[test]
+    fn module_name() {
+        let scaffold = generate(template("my-plugin", &[])).unwrap();
+
+        let lib = &scaffold.get("src/lib.rs").unwrap().contents;
+        assert!(lib.contains("fn my_plugin() -> oxi::Result<Dictionary>"));
+        assert!(lib.contains("format!(\"Hello, {name}!\")"));
+
+        let shim = &scaffold.get("lua/my_plugin/init.lua").unwrap().contents;
+        assert!(shim.contains("\"luaopen_my_plugin\""));
+        assert!(shim.contains("libmy_plugin.so"));
+    }
+
+    #[test]
+    fn invalid_names() {
+        for name in ["", "1plugin", "MyPlugin", "my plugin", "my.plugin"] {
+            let res = generate(template(name, &[]));
+            assert!(matches!(res, Err(Error::InvalidName(..))), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn multiple_neovim_versions() {
+        let res =
+            generate(template("foo", &[Feature::Neovim08, Feature::Neovim09]));
+
+        assert!(matches!(
+            res,
+            Err(Error::MultipleNeovimVersions("neovim-0-8", "neovim-0-9"))
+        ));
+    }
+
+    #[test]
+    fn write_doesnt_overwrite() {
+        let root = std::env::temp_dir()
+            .join(format!("oxi-scaffold-{}", std::process::id()));
+
+        let scaffold = generate(template("foo", &[])).unwrap();
+
+        scaffold.write(&root).unwrap();
+
+        for file in scaffold.files() {
+            let contents = std::fs::read_to_string(root.join(&file.path));
+            assert_eq!(file.contents, contents.unwrap());
+        }
+
+        let res = scaffold.write(&root);
+        assert!(matches!(res, Err(Error::WouldOverwrite(_))));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
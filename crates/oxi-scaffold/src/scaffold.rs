@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, PluginTemplate, Result};
+
+/// The version requirement on `nvim-oxi` used by the generated crates.
+const NVIM_OXI_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION_MAJOR"),
+    ".",
+    env!("CARGO_PKG_VERSION_MINOR")
+);
+
+/// A file of a generated plugin.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GeneratedFile {
+    /// The path of the file, relative to the root of the plugin.
+    pub path: PathBuf,
+
+    /// The contents of the file.
+    pub contents: String,
+}
+
+/// The files of a generated plugin, as returned by
+/// [`generate()`](crate::generate).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Scaffold {
+    files: Vec<GeneratedFile>,
+}
+
+impl Scaffold {
+    pub(crate) fn new(template: &PluginTemplate) -> Self {
+        let module = template.module_name();
+
+        let mut features = template.features.clone();
+        features.sort();
+        features.dedup();
+
+        let features = features
+            .iter()
+            .map(|feature| format!("\"{}\"", feature.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let render = |src: &str| {
+            src.replace("{{name}}", &template.name)
+                .replace("{{module}}", &module)
+                .replace("{{version}}", NVIM_OXI_VERSION)
+                .replace("{{features}}", &features)
+        };
+
+        let lua_shim = format!("lua/{module}/init.lua");
+
+        let files = [
+            ("Cargo.toml", include_str!("../templates/Cargo.toml.in")),
+            ("src/lib.rs", include_str!("../templates/lib.rs")),
+            (&*lua_shim, include_str!("../templates/init.lua")),
+            (".cargo/config.toml", include_str!("../templates/config.toml")),
+            (".gitignore", include_str!("../templates/gitignore")),
+        ];
+
+        let files = files
+            .into_iter()
+            .map(|(path, src)| GeneratedFile {
+                path: PathBuf::from(path),
+                contents: render(src),
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    /// Returns the generated files.
+    #[inline]
+    pub fn files(&self) -> &[GeneratedFile] {
+        &self.files
+    }
+
+    /// Returns the generated file at the given path, relative to the root of
+    /// the plugin.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&GeneratedFile> {
+        let path = path.as_ref();
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    /// Writes the files under `root`, creating the directories as needed.
+    ///
+    /// Fails without writing anything if any of the files already exists.
+    pub fn write(&self, root: impl AsRef<Path>) -> Result<()> {
+        let root = root.as_ref();
+
+        if let Some(file) = self
+            .files
+            .iter()
+            .map(|file| root.join(&file.path))
+            .find(|path| path.exists())
+        {
+            return Err(Error::WouldOverwrite(file));
+        }
+
+        for file in &self.files {
+            let path = root.join(&file.path);
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(path, &file.contents)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{Error, Result};
+
+/// Describes the plugin to generate.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct PluginTemplate {
+    /// The name of the plugin, used both as the name of the crate and as the
+    /// name of the Lua module returned by `require`. Can only contain
+    /// lowercase ASCII letters, digits, `_` and `-`, and has to start with a
+    /// letter. Dashes are replaced by underscores in the Lua module name.
+    pub name: String,
+
+    /// The features of `nvim-oxi` the plugin is built with.
+    pub features: Vec<Feature>,
+}
+
+impl PluginTemplate {
+    /// The name of the Lua module and of the function marked with
+    /// `#[nvim_oxi::module]`.
+    pub(crate) fn module_name(&self) -> String {
+        self.name.replace('-', "_")
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let invalid =
+            |reason| Err(Error::InvalidName(self.name.clone(), reason));
+
+        let mut chars = self.name.chars();
+
+        match chars.next() {
+            None => return invalid("it's empty"),
+            Some(c) if !c.is_ascii_lowercase() => {
+                return invalid("it has to start with a lowercase letter")
+            },
+            _ => {},
+        }
+
+        if !chars.all(|c| {
+            c.is_ascii_lowercase()
+                || c.is_ascii_digit()
+                || c == '_'
+                || c == '-'
+        }) {
+            return invalid(
+                "it can only contain lowercase letters, digits, `_` and `-`",
+            );
+        }
+
+        let mut versions =
+            self.features.iter().filter(|f| f.is_neovim_version());
+
+        if let (Some(first), Some(second)) = (versions.next(), versions.next())
+        {
+            return Err(Error::MultipleNeovimVersions(
+                first.as_str(),
+                second.as_str(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// A cargo feature of `nvim-oxi`.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Feature {
+    /// Targets Neovim 0.8.
+    Neovim08,
+
+    /// Targets Neovim 0.9.
+    Neovim09,
+
+    /// Targets Neovim nightly.
+    NeovimNightly,
+
+    /// Enables the `diagnostic` module.
+    Diagnostic,
+
+    /// Enables the `libuv` module.
+    Libuv,
+
+    /// Enables the `lsp` module.
+    Lsp,
+
+    /// Enables the `mlua` module.
+    Mlua,
+
+    /// Enables the `treesitter` module.
+    Treesitter,
+
+    /// Enables the `vim_api` module.
+    VimApi,
+}
+
+impl Feature {
+    /// The name of the feature in `nvim-oxi`'s manifest.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Neovim08 => "neovim-0-8",
+            Self::Neovim09 => "neovim-0-9",
+            Self::NeovimNightly => "neovim-nightly",
+            Self::Diagnostic => "diagnostic",
+            Self::Libuv => "libuv",
+            Self::Lsp => "lsp",
+            Self::Mlua => "mlua",
+            Self::Treesitter => "treesitter",
+            Self::VimApi => "vim-api",
+        }
+    }
+
+    fn is_neovim_version(&self) -> bool {
+        matches!(self, Self::Neovim08 | Self::Neovim09 | Self::NeovimNightly)
+    }
+}
@@ -0,0 +1,22 @@
+-- Loads the library compiled by `cargo build --release`, so that the plugin
+-- can be added to the runtimepath straight from its repository.
+
+local root =
+  vim.fn.fnamemodify(debug.getinfo(1, "S").source:sub(2), ":p:h:h:h")
+
+local lib
+if vim.fn.has("win32") == 1 then
+  lib = root .. "/target/release/{{module}}.dll"
+elseif vim.fn.has("mac") == 1 then
+  lib = root .. "/target/release/lib{{module}}.dylib"
+else
+  lib = root .. "/target/release/lib{{module}}.so"
+end
+
+local open, err = package.loadlib(lib, "luaopen_{{module}}")
+
+if not open then
+  error("couldn't load {{name}}, run `cargo build --release` first\n" .. err)
+end
+
+return open()
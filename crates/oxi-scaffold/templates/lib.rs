@@ -0,0 +1,23 @@
+use nvim_oxi::{self as oxi, Dictionary, Function, Object};
+
+#[oxi::module]
+fn {{module}}() -> oxi::Result<Dictionary> {
+    let greet = Function::from_fn(|name: String| {
+        Ok::<_, oxi::Error>(format!("Hello, {name}!"))
+    });
+
+    Ok(Dictionary::from_iter([("greet", Object::from(greet))]))
+}
+
+// Run `cargo build` before `cargo test`, since the tests are run from within
+// a Neovim instance loading the compiled library.
+#[cfg(test)]
+mod tests {
+    use nvim_oxi::{self as oxi, api};
+
+    #[oxi::test]
+    fn {{module}}_set_get_var() {
+        api::set_var("{{module}}_loaded", true).unwrap();
+        assert_eq!(Ok(true), api::get_var("{{module}}_loaded"));
+    }
+}
@@ -0,0 +1,11 @@
+use thiserror::Error as ThisError;
+
+/// Result type returned by the functions of this crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error type returned by the functions of this crate.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum Error {
+    #[error(transparent)]
+    Lua(#[from] oxi_luajit::Error),
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use oxi_api::Buffer;
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable};
+
+use crate::lua_ref::{self, LuaRef};
+use crate::{Result, Tree};
+
+/// Binding to [`vim.treesitter.get_parser()`][1].
+///
+/// Returns the parser of `buffer`, creating it if it doesn't exist yet. If
+/// `lang` is `None` the language is inferred from the buffer's filetype.
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.get_parser()
+pub fn get_parser(
+    buffer: &Buffer,
+    lang: Option<&str>,
+) -> Result<LanguageTree> {
+    lua_ref::call_function(
+        &[&[cstr!("get_parser")]],
+        (buffer.clone(), lang.map(ToOwned::to_owned)),
+        1,
+    )
+}
+
+/// A parser for a buffer, wrapping a Lua [`LanguageTree`][1].
+///
+/// A language tree parses the regions of the buffer written in its language,
+/// and has a child language tree for every language injected in them.
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#LanguageTree
+#[derive(Clone, Debug)]
+pub struct LanguageTree(LuaRef);
+
+impl Poppable for LanguageTree {
+    unsafe fn pop(
+        lstate: *mut lua_State,
+    ) -> std::result::Result<Self, lua::Error> {
+        LuaRef::pop::<Self>(lstate, LUA_TTABLE).map(Self)
+    }
+}
+
+impl LanguageTree {
+    /// Returns the child language trees, indexed by language.
+    pub fn children(&self) -> Result<HashMap<String, LanguageTree>> {
+        lua_ref::call_method(&self.0, cstr!("children"), (), 1)
+    }
+
+    /// Returns `true` if the trees are up to date with the contents of the
+    /// buffer.
+    pub fn is_valid(&self) -> Result<bool> {
+        lua_ref::call_method(&self.0, cstr!("is_valid"), (), 1)
+    }
+
+    /// Returns the language of the tree.
+    pub fn lang(&self) -> Result<String> {
+        lua_ref::call_method(&self.0, cstr!("lang"), (), 1)
+    }
+
+    /// Returns the language tree, either this one or one of its descendants,
+    /// that contains the given range.
+    pub fn language_for_range(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Result<LanguageTree> {
+        let range = vec![start.0, start.1, end.0, end.1];
+        lua_ref::call_method(&self.0, cstr!("language_for_range"), range, 1)
+    }
+
+    /// Parses the buffer if needed, returning the syntax trees of the regions
+    /// written in this language.
+    pub fn parse(&self) -> Result<Vec<Tree>> {
+        lua_ref::call_method(&self.0, cstr!("parse"), (), 1)
+    }
+
+    /// Returns the syntax trees from the last parse, without parsing the
+    /// buffer again.
+    pub fn trees(&self) -> Result<Vec<Tree>> {
+        lua_ref::call_method(&self.0, cstr!("trees"), (), 1)
+    }
+}
@@ -0,0 +1,38 @@
+//! Rust bindings to Neovim's [`vim.treesitter`][1] module.
+//!
+//! The parsers, trees, nodes and queries returned by the functions of this
+//! crate are handles to the underlying Lua values, so they're only valid on
+//! the thread running Neovim.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::api::Buffer;
+//! use nvim_oxi::treesitter::{self, Query};
+//!
+//! let buf = Buffer::current();
+//! let parser = treesitter::get_parser(&buf, Some("lua"))?;
+//! let root = parser.parse()?[0].root()?;
+//!
+//! let query = Query::parse("lua", "(function_call name: (_) @name)")?;
+//!
+//! for capture in query.iter_captures(&root, &buf, ..)? {
+//!     let capture = capture?;
+//!     nvim_oxi::print!("{}", capture.node.text(&buf)?);
+//! }
+//! ```
+//!
+//! [1]: https://neovim.io/doc/user/treesitter.html
+
+mod error;
+mod language_tree;
+mod lua_ref;
+mod node;
+mod query;
+mod tree;
+
+pub use error::{Error, Result};
+pub use language_tree::*;
+pub use node::*;
+pub use query::*;
+pub use tree::*;
@@ -0,0 +1,210 @@
+use std::ffi::{c_char, c_int, CStr};
+
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable, Pushable};
+
+use crate::Result;
+
+/// A Lua value stored in the registry, which is released when the reference
+/// is dropped.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub(crate) struct LuaRef(c_int);
+
+impl LuaRef {
+    /// Pops the value at the top of the stack and stores it in the registry,
+    /// failing if it's not of the `expected` type.
+    pub(crate) unsafe fn pop<T>(
+        lstate: *mut lua_State,
+        expected: c_int,
+    ) -> std::result::Result<Self, lua::Error> {
+        if lua_gettop(lstate) == 0 {
+            return Err(lua::Error::PopEmptyStack);
+        }
+
+        match lua_type(lstate, -1) {
+            ty if ty == expected => {
+                Ok(Self(luaL_ref(lstate, LUA_REGISTRYINDEX)))
+            },
+            other => Err(lua::Error::pop_wrong_type::<T>(expected, other)),
+        }
+    }
+}
+
+impl Clone for LuaRef {
+    fn clone(&self) -> Self {
+        unsafe {
+            lua::with_state(|lstate| {
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                Self(luaL_ref(lstate, LUA_REGISTRYINDEX))
+            })
+        }
+    }
+}
+
+impl Drop for LuaRef {
+    fn drop(&mut self) {
+        unsafe {
+            lua::with_state(|lstate| {
+                luaL_unref(lstate, LUA_REGISTRYINDEX, self.0)
+            })
+        }
+    }
+}
+
+impl Pushable for &LuaRef {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> std::result::Result<c_int, lua::Error> {
+        lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+        Ok(1)
+    }
+}
+
+/// Calls the first function found at one of the given paths under
+/// `vim.treesitter`, which allows falling back to the older names of
+/// functions that have been moved between Neovim versions.
+///
+/// `nresults` is the number of values returned by the function, which are
+/// all popped into `R`.
+pub(crate) fn call_function<A, R>(
+    paths: &[&[*const c_char]],
+    args: A,
+    nresults: c_int,
+) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            let found = paths.iter().any(|path| {
+                lua_settop(lstate, top);
+                lua_getglobal(lstate, cstr!("vim"));
+                lua_getfield(lstate, -1, cstr!("treesitter"));
+                for &field in path.iter() {
+                    if lua_type(lstate, -1) != LUA_TTABLE {
+                        return false;
+                    }
+                    lua_getfield(lstate, -1, field);
+                }
+                lua_type(lstate, -1) == LUA_TFUNCTION
+            });
+
+            if !found {
+                let name = CStr::from_ptr(*paths[0].last().unwrap());
+                lua_settop(lstate, top);
+                return Err(lua::Error::RuntimeError(format!(
+                    "no function `{}` in `vim.treesitter`",
+                    name.to_string_lossy()
+                ))
+                .into());
+            }
+
+            let res = pcall(lstate, 0, args, nresults);
+
+            // Also pops the tables the function was looked up in.
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Calls `this:method(args)`.
+///
+/// `nresults` is the number of values returned by the method, which are all
+/// popped into `R`.
+pub(crate) fn call_method<A, R>(
+    this: &LuaRef,
+    method: *const c_char,
+    args: A,
+    nresults: c_int,
+) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, this.0);
+            lua_getfield(lstate, -1, method);
+            lua_pushvalue(lstate, -2);
+
+            let res = pcall(lstate, 1, args, nresults);
+
+            // Also pops `this`.
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Calls the referenced Lua function.
+///
+/// `nresults` is the number of values returned by the function, which are
+/// all popped into `R`.
+pub(crate) fn call<A, R>(fun: &LuaRef, args: A, nresults: c_int) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, fun.0);
+            let res = pcall(lstate, 0, args, nresults);
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Gets the field `field` of the Lua value.
+pub(crate) fn get_field<R: Poppable>(
+    this: &LuaRef,
+    field: *const c_char,
+) -> Result<R> {
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, this.0);
+            lua_getfield(lstate, -1, field);
+            let res = R::pop(lstate).map_err(Into::into);
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Pushes `args` and calls the function right below them and the
+/// `pushed` arguments that are already on the stack, popping `nresults`
+/// return values into `R`. The caller has to reset the stack afterwards.
+unsafe fn pcall<A, R>(
+    lstate: *mut lua_State,
+    pushed: c_int,
+    args: A,
+    nresults: c_int,
+) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    let nargs = pushed + args.push(lstate)?;
+
+    match lua_pcall(lstate, nargs, nresults, 0) {
+        LUA_OK => Ok(R::pop(lstate)?),
+
+        err_code => {
+            let msg = lua::utils::error_message(lstate, -1);
+
+            Err(match err_code {
+                LUA_ERRMEM => lua::Error::MemoryError(msg),
+                _ => lua::Error::RuntimeError(msg),
+            }
+            .into())
+        },
+    }
+}
@@ -0,0 +1,197 @@
+use std::ffi::c_int;
+
+use oxi_api::Buffer;
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable};
+
+use crate::lua_ref::{self, LuaRef};
+use crate::Result;
+
+/// A node in a syntax [`Tree`](crate::Tree), wrapping a Lua [`TSNode`][1].
+///
+/// Rows and columns are zero-indexed, and columns are byte offsets.
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#TSNode
+#[derive(Clone)]
+pub struct Node(pub(crate) LuaRef);
+
+impl core::fmt::Debug for Node {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut debug = f.debug_struct("Node");
+        if let Ok(kind) = self.kind() {
+            debug.field("kind", &kind);
+        }
+        if let Ok(range) = self.range() {
+            debug.field("range", &range);
+        }
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            lua_ref::call_method::<_, bool>(
+                &self.0,
+                cstr!("equal"),
+                &other.0,
+                1
+            ),
+            Ok(true)
+        )
+    }
+}
+
+impl Poppable for Node {
+    unsafe fn pop(
+        lstate: *mut lua_State,
+    ) -> std::result::Result<Self, lua::Error> {
+        LuaRef::pop::<Self>(lstate, LUA_TUSERDATA).map(Self)
+    }
+}
+
+impl Node {
+    /// Returns the `index`-th child of the node, or `None` if the node has
+    /// fewer children.
+    pub fn child(&self, index: usize) -> Result<Option<Node>> {
+        self.call(cstr!("child"), index, 1)
+    }
+
+    /// Returns the number of children of the node.
+    pub fn child_count(&self) -> Result<usize> {
+        self.call(cstr!("child_count"), (), 1)
+    }
+
+    /// Returns all the children of the node, named or not.
+    pub fn children(&self) -> Result<Vec<Node>> {
+        (0..self.child_count()?)
+            .filter_map(|index| self.child(index).transpose())
+            .collect()
+    }
+
+    /// Returns the end position of the node as a `(row, col)` tuple.
+    pub fn end(&self) -> Result<(usize, usize)> {
+        let (_, _, end_row, end_col) = self.range()?;
+        Ok((end_row, end_col))
+    }
+
+    /// Returns `true` if the node is a syntax error or contains one.
+    pub fn has_error(&self) -> Result<bool> {
+        self.call(cstr!("has_error"), (), 1)
+    }
+
+    /// Returns a string that uniquely identifies the node within its tree.
+    pub fn id(&self) -> Result<String> {
+        self.call(cstr!("id"), (), 1)
+    }
+
+    /// Returns `true` if the node was inserted by the parser to recover from
+    /// a syntax error.
+    pub fn is_missing(&self) -> Result<bool> {
+        self.call(cstr!("missing"), (), 1)
+    }
+
+    /// Returns `true` if the node is named, i.e. if it corresponds to a
+    /// named rule in the grammar rather than to an anonymous token.
+    pub fn is_named(&self) -> Result<bool> {
+        self.call(cstr!("named"), (), 1)
+    }
+
+    /// Returns the type of the node, e.g. `"function_call"`.
+    pub fn kind(&self) -> Result<String> {
+        self.call(cstr!("type"), (), 1)
+    }
+
+    /// Returns the smallest named node spanning the given range, which has
+    /// to be contained in this node.
+    pub fn named_descendant_for_range(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Result<Option<Node>> {
+        self.call(
+            cstr!("named_descendant_for_range"),
+            (start.0, start.1, end.0, end.1),
+            1,
+        )
+    }
+
+    /// Returns the `index`-th named child of the node, or `None` if the node
+    /// has fewer named children.
+    pub fn named_child(&self, index: usize) -> Result<Option<Node>> {
+        self.call(cstr!("named_child"), index, 1)
+    }
+
+    /// Returns the number of named children of the node.
+    pub fn named_child_count(&self) -> Result<usize> {
+        self.call(cstr!("named_child_count"), (), 1)
+    }
+
+    /// Returns the named children of the node.
+    pub fn named_children(&self) -> Result<Vec<Node>> {
+        (0..self.named_child_count()?)
+            .filter_map(|index| self.named_child(index).transpose())
+            .collect()
+    }
+
+    /// Returns the next sibling of the node, if any.
+    pub fn next_sibling(&self) -> Result<Option<Node>> {
+        self.call(cstr!("next_sibling"), (), 1)
+    }
+
+    /// Returns the parent of the node, or `None` if it's the root.
+    pub fn parent(&self) -> Result<Option<Node>> {
+        self.call(cstr!("parent"), (), 1)
+    }
+
+    /// Returns the previous sibling of the node, if any.
+    pub fn prev_sibling(&self) -> Result<Option<Node>> {
+        self.call(cstr!("prev_sibling"), (), 1)
+    }
+
+    /// Returns the range spanned by the node as a `(start_row, start_col,
+    /// end_row, end_col)` tuple, where the end is exclusive.
+    pub fn range(&self) -> Result<(usize, usize, usize, usize)> {
+        self.call(cstr!("range"), (), 4)
+    }
+
+    /// Returns the S-expression representing the node and its descendants.
+    pub fn sexpr(&self) -> Result<String> {
+        self.call(cstr!("sexpr"), (), 1)
+    }
+
+    /// Returns the start position of the node as a `(row, col)` tuple.
+    pub fn start(&self) -> Result<(usize, usize)> {
+        let (start_row, start_col, ..) = self.range()?;
+        Ok((start_row, start_col))
+    }
+
+    /// Binding to [`vim.treesitter.get_node_text()`][1].
+    ///
+    /// Returns the text spanned by the node in `buffer`, which should be the
+    /// buffer the node was parsed from.
+    ///
+    /// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.get_node_text()
+    pub fn text(&self, buffer: &Buffer) -> Result<String> {
+        lua_ref::call_function(
+            &[
+                &[cstr!("get_node_text")],
+                &[cstr!("query"), cstr!("get_node_text")],
+            ],
+            (&self.0, buffer.clone()),
+            1,
+        )
+    }
+
+    fn call<A, R>(
+        &self,
+        method: *const std::ffi::c_char,
+        args: A,
+        nresults: c_int,
+    ) -> Result<R>
+    where
+        A: lua::Pushable,
+        R: Poppable,
+    {
+        lua_ref::call_method(&self.0, method, args, nresults)
+    }
+}
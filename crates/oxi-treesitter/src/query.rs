@@ -0,0 +1,133 @@
+use std::ops::{Bound, RangeBounds};
+
+use oxi_api::Buffer;
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable};
+
+use crate::lua_ref::{self, LuaRef};
+use crate::{Node, Result};
+
+/// A parsed tree-sitter query, wrapping a Lua [`Query`][1].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#Query
+#[derive(Clone, Debug)]
+pub struct Query(LuaRef);
+
+impl Poppable for Query {
+    unsafe fn pop(
+        lstate: *mut lua_State,
+    ) -> std::result::Result<Self, lua::Error> {
+        LuaRef::pop::<Self>(lstate, LUA_TTABLE).map(Self)
+    }
+}
+
+impl Query {
+    /// Binding to [`vim.treesitter.query.parse()`][1].
+    ///
+    /// Parses the `source` of a query for the language `lang`.
+    ///
+    /// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.query.parse()
+    pub fn parse(lang: &str, source: &str) -> Result<Self> {
+        lua_ref::call_function(
+            &[
+                &[cstr!("query"), cstr!("parse")],
+                &[cstr!("query"), cstr!("parse_query")],
+            ],
+            (lang.to_owned(), source.to_owned()),
+            1,
+        )
+    }
+
+    /// Returns the names of the captures defined in the query, without the
+    /// leading `@`, indexed by their id minus one.
+    pub fn capture_names(&self) -> Result<Vec<String>> {
+        lua_ref::get_field(&self.0, cstr!("captures"))
+    }
+
+    /// Binding to [`Query:iter_captures()`][1].
+    ///
+    /// Returns an iterator over the captures of the query in `node`, in the
+    /// order they appear in the buffer, limited to the given range of
+    /// (zero-indexed) rows.
+    ///
+    /// [1]: https://neovim.io/doc/user/treesitter.html#Query%3Aiter_captures()
+    pub fn iter_captures<R>(
+        &self,
+        node: &Node,
+        buffer: &Buffer,
+        rows: R,
+    ) -> Result<QueryCaptures>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match rows.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => Some(start + 1),
+            Bound::Unbounded => None,
+        };
+
+        let stop = match rows.end_bound() {
+            Bound::Included(&end) => Some(end + 1),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => None,
+        };
+
+        let iter = lua_ref::call_method::<_, LuaFunction>(
+            &self.0,
+            cstr!("iter_captures"),
+            (&node.0, buffer.clone(), start, stop),
+            1,
+        )?;
+
+        Ok(QueryCaptures { iter: iter.0, names: self.capture_names()? })
+    }
+}
+
+/// A node captured by a [`Query`].
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct QueryCapture {
+    /// The name of the capture, without the leading `@`.
+    pub name: String,
+
+    /// The captured node.
+    pub node: Node,
+}
+
+/// An iterator over the captures of a [`Query`], created by
+/// [`Query::iter_captures`].
+pub struct QueryCaptures {
+    iter: LuaRef,
+    names: Vec<String>,
+}
+
+impl Iterator for QueryCaptures {
+    type Item = Result<QueryCapture>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, node) = match lua_ref::call::<_, (Option<usize>, Option<Node>)>(
+            &self.iter,
+            (),
+            2,
+        ) {
+            Ok((Some(id), Some(node))) => (id, node),
+            Ok(_) => return None,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let name =
+            self.names.get(id.wrapping_sub(1)).cloned().unwrap_or_default();
+
+        Some(Ok(QueryCapture { name, node }))
+    }
+}
+
+/// A Lua function, e.g. the iterator returned by `Query:iter_captures()`.
+struct LuaFunction(LuaRef);
+
+impl Poppable for LuaFunction {
+    unsafe fn pop(
+        lstate: *mut lua_State,
+    ) -> std::result::Result<Self, lua::Error> {
+        LuaRef::pop::<Self>(lstate, LUA_TFUNCTION).map(Self)
+    }
+}
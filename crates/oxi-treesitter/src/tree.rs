@@ -0,0 +1,26 @@
+use oxi_luajit::{self as lua, ffi::*, macros::cstr, Poppable};
+
+use crate::lua_ref::{self, LuaRef};
+use crate::{Node, Result};
+
+/// A syntax tree produced by a [`LanguageTree`](crate::LanguageTree),
+/// wrapping a Lua [`TSTree`][1].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#TSTree
+#[derive(Clone, Debug)]
+pub struct Tree(LuaRef);
+
+impl Poppable for Tree {
+    unsafe fn pop(
+        lstate: *mut lua_State,
+    ) -> std::result::Result<Self, lua::Error> {
+        LuaRef::pop::<Self>(lstate, LUA_TUSERDATA).map(Self)
+    }
+}
+
+impl Tree {
+    /// Returns the root node of the tree.
+    pub fn root(&self) -> Result<Node> {
+        lua_ref::call_method(&self.0, cstr!("root"), (), 1)
+    }
+}
@@ -0,0 +1,70 @@
+//! Compares building the arrays and dictionaries of 1000 extmarks with an
+//! `ObjectArena` against building them as owned `Array`s and `Dictionary`s.
+//!
+//! Run with `cargo bench -p oxi-types --bench arena`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use oxi_types::{Array, Dictionary, ObjectArena};
+
+const EXTMARKS: usize = 1000;
+
+/// The `virt_text` option of every extmark, whose strings are allocated
+/// with both approaches.
+fn virt_text(c: &mut Criterion) {
+    let mut group = c.benchmark_group("virt_text");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            for n in 0..EXTMARKS {
+                let chunk = Array::from_iter([n.to_string(), "Comment".into()]);
+                let virt_text = Array::from_iter([chunk]);
+                black_box(Dictionary::from_iter([("virt_text", virt_text)]));
+            }
+        })
+    });
+
+    let mut arena = ObjectArena::new();
+
+    group.bench_function("arena", |b| {
+        b.iter(|| {
+            for n in 0..EXTMARKS {
+                let chunk = arena.array([n.to_string(), "Comment".into()]);
+                let virt_text = arena.array([chunk]);
+                black_box(arena.dictionary([("virt_text", virt_text)]));
+            }
+            arena.reset();
+        })
+    });
+
+    group.finish();
+}
+
+/// Arrays of integers, e.g. the positions of the extmarks, where the arena
+/// doesn't allocate at all.
+fn positions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("positions");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            for n in 0..EXTMARKS as i64 {
+                black_box(Array::from_iter([n, 0, n, 80]));
+            }
+        })
+    });
+
+    let mut arena = ObjectArena::new();
+
+    group.bench_function("arena", |b| {
+        b.iter(|| {
+            for n in 0..EXTMARKS as i64 {
+                black_box(arena.array([n, 0, n, 80]));
+            }
+            arena.reset();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, virt_text, positions);
+criterion_main!(benches);
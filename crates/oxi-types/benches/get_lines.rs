@@ -0,0 +1,82 @@
+//! Compares the ways of converting the 100k lines returned by
+//! `Buffer::get_lines()` into Rust values.
+//!
+//! `nvim_buf_get_lines()` hands back an `Array` of strings which is then
+//! converted on the Rust side. Calling it requires a running Neovim, so this
+//! only measures the conversion of an equivalent `Array`, which is the part
+//! the `ObjectRef` view is meant to speed up. The time Neovim spends
+//! building the array isn't included.
+//!
+//! Run with `cargo bench -p oxi-types --bench get_lines`.
+
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    BatchSize,
+    Criterion,
+};
+use oxi_types::{
+    self as nvim,
+    conversion::{FromObject, FromObjectRef},
+    Array,
+    Object,
+};
+
+const LINES: usize = 100_000;
+
+fn lines() -> Array {
+    (0..LINES)
+        .map(|n| {
+            format!("    let line_{n} = compute(&input, {n}); // comment")
+        })
+        .collect()
+}
+
+fn get_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_lines_100k");
+
+    // What `Buffer::get_lines()` does, moving every line out of the array.
+    group.bench_function("nvim_strings", |b| {
+        b.iter_batched(
+            lines,
+            |lines| {
+                lines
+                    .into_iter()
+                    .map(nvim::String::from_object)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap()
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    // Converting the whole array into owned Rust strings, which copies every
+    // line.
+    group.bench_function("rust_strings", |b| {
+        b.iter_batched(
+            || Object::from(lines()),
+            |lines| Vec::<String>::from_object(lines).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    // Reading every line through a view, without allocating.
+    let lines = lines();
+
+    group.bench_function("view", |b| {
+        b.iter(|| {
+            let total_len = lines
+                .iter()
+                .map(|line| <&str>::from_object_ref(line.view()).map(str::len))
+                .sum::<Result<usize, _>>()
+                .unwrap();
+            black_box(total_len)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, get_lines);
+criterion_main!(benches);
@@ -0,0 +1,397 @@
+//! An arena to build the [`Array`]s and [`Dictionary`]s passed to Neovim's
+//! API without allocating a new vector for each of them.
+
+use core::cell::RefCell;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::dictionary::KeyValuePair;
+use crate::kvec::KVec;
+use crate::{Array, Dictionary, NonOwning, Object};
+
+/// The capacity of the first chunk allocated by a [`Pool`].
+const MIN_CHUNK_CAPACITY: usize = 64;
+
+/// An arena to build [`Array`]s and [`Dictionary`]s in hot paths, e.g. when
+/// setting thousands of extmarks on every redraw.
+///
+/// The items of every array and dictionary built by the arena are stored in
+/// a few big chunks of memory which are reused after calling
+/// [`reset`](ObjectArena::reset), so building them doesn't allocate once
+/// the arena has grown big enough, although the strings stored in them still
+/// do. How much this saves depends on the allocator, see the `arena`
+/// benchmark of this crate.
+///
+/// The arrays and dictionaries are handed out as [`NonOwning`] values, which
+/// are valid until the arena is reset. They can be passed to the API
+/// functions taking them by value, or turned into options with e.g.
+/// `SetExtmarkOpts::from_dict()`.
+///
+/// Nil values are kept, like when extending an [`Array`] but unlike when
+/// collecting an iterator into an [`Array`] or a [`Dictionary`].
+///
+/// # Examples
+///
+/// ```
+/// use oxi_types::ObjectArena;
+///
+/// let mut arena = ObjectArena::new();
+///
+/// for _ in 0..3 {
+///     let chunk = arena.array(["foo", "Comment"]);
+///     let virt_text = arena.array([chunk]);
+///     let opts = arena.dictionary([("virt_text", virt_text)]);
+///     assert_eq!(1, opts.len());
+///
+///     // Pass `SetExtmarkOpts::from_dict(opts)?` to
+///     // `Buffer::set_extmark()`..
+///
+///     arena.reset();
+/// }
+/// ```
+#[derive(Default)]
+pub struct ObjectArena {
+    objects: Slots<Object>,
+    pairs: Slots<KeyValuePair>,
+}
+
+/// A value that can be stored in the arrays and dictionaries built by an
+/// [`ObjectArena`], i.e. anything that can be converted into an [`Object`],
+/// or an array or dictionary built by the arena itself.
+pub trait ArenaValue<'a> {
+    /// Returns the object to store in the arena, and whether the arena owns
+    /// it and should drop it when reset.
+    #[doc(hidden)]
+    fn into_arena_object(self) -> (Object, bool);
+}
+
+impl<'a, T: Into<Object>> ArenaValue<'a> for T {
+    #[inline]
+    fn into_arena_object(self) -> (Object, bool) {
+        (self.into(), true)
+    }
+}
+
+impl<'a> ArenaValue<'a> for NonOwning<'a, Array> {
+    #[inline]
+    fn into_arena_object(self) -> (Object, bool) {
+        (Array(KVec { ..self.0 }).into(), false)
+    }
+}
+
+impl<'a> ArenaValue<'a> for NonOwning<'a, Dictionary> {
+    #[inline]
+    fn into_arena_object(self) -> (Object, bool) {
+        (Dictionary(KVec { ..self.0 }).into(), false)
+    }
+}
+
+impl ObjectArena {
+    /// Creates a new, empty arena. It doesn't allocate until the first array
+    /// or dictionary is built.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an array with the given items in the arena.
+    pub fn array<'a, I>(&'a self, items: I) -> NonOwning<'a, Array>
+    where
+        I: IntoIterator,
+        I::Item: ArenaValue<'a>,
+    {
+        let items = items.into_iter().map(ArenaValue::into_arena_object);
+        let (items, size) = self.objects.store(items);
+        NonOwning::new(Array(KVec { size, capacity: size, items }))
+    }
+
+    /// Builds a dictionary with the given key-value pairs in the arena.
+    pub fn dictionary<'a, I, K, V>(
+        &'a self,
+        pairs: I,
+    ) -> NonOwning<'a, Dictionary>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<crate::String>,
+        V: ArenaValue<'a>,
+    {
+        let pairs = pairs.into_iter().map(|(key, value)| {
+            let (value, owned) = value.into_arena_object();
+            (KeyValuePair { key: key.into(), value }, owned)
+        });
+        let (items, size) = self.pairs.store(pairs);
+        NonOwning::new(Dictionary(KVec { size, capacity: size, items }))
+    }
+
+    /// Drops all the values stored in the arena, making its memory available
+    /// to the next arrays and dictionaries.
+    pub fn reset(&mut self) {
+        // The values not owned by the arena are replaced with nil before
+        // dropping every slot.
+        self.objects.reset(|obj| unsafe { ptr::write(obj, Object::nil()) });
+
+        self.pairs.reset(|pair| unsafe {
+            ptr::write(&mut (*pair).value, Object::nil())
+        });
+    }
+}
+
+impl Drop for ObjectArena {
+    #[inline]
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
+
+/// The slots of an arena holding values of type `T`.
+struct Slots<T> {
+    pool: RefCell<Pool<T>>,
+
+    /// The values being collected by [`store`](Slots::store) when the
+    /// number of values isn't known in advance. It's shared by nested
+    /// calls, which only push and pop values at its end.
+    scratch: RefCell<Vec<(T, bool)>>,
+
+    /// The ranges of slots in use, as a pointer to their first slot and
+    /// their number.
+    used: RefCell<Vec<(*mut T, usize)>>,
+
+    /// The slots holding values not owned by the arena, which mustn't be
+    /// dropped.
+    borrowed: RefCell<Vec<*mut T>>,
+}
+
+impl<T> Default for Slots<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pool: RefCell::new(Pool::default()),
+            scratch: RefCell::new(Vec::new()),
+            used: RefCell::new(Vec::new()),
+            borrowed: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<T> Slots<T> {
+    /// Moves the values into contiguous slots, returning a pointer to the
+    /// first one and their number.
+    fn store<I>(&self, values: I) -> (*mut T, usize)
+    where
+        I: Iterator<Item = (T, bool)>,
+    {
+        match values.size_hint() {
+            (0, Some(0)) => (ptr::null_mut(), 0),
+            (lower, Some(upper)) if lower == upper => {
+                self.store_exact(values, lower)
+            },
+            _ => self.store_scratch(values),
+        }
+    }
+
+    /// Moves at most `n` values directly into the slots reserved for them.
+    /// Nested calls made while advancing the iterator reserve their slots
+    /// after these ones, so they're still contiguous.
+    fn store_exact<I>(&self, values: I, n: usize) -> (*mut T, usize)
+    where
+        I: Iterator<Item = (T, bool)>,
+    {
+        let slots = self.pool.borrow_mut().alloc(n);
+        let mut len = 0;
+
+        for (value, owned) in values.take(n) {
+            unsafe {
+                let slot = slots.add(len);
+                ptr::write(slot, value);
+                if !owned {
+                    self.borrowed.borrow_mut().push(slot);
+                }
+            }
+            len += 1;
+        }
+
+        self.used.borrow_mut().push((slots, len));
+
+        (if len == 0 { ptr::null_mut() } else { slots }, len)
+    }
+
+    /// Collects the values in the scratch buffer before moving them into
+    /// contiguous slots.
+    fn store_scratch<I>(&self, values: I) -> (*mut T, usize)
+    where
+        I: Iterator<Item = (T, bool)>,
+    {
+        let start = self.scratch.borrow().len();
+
+        // The iterator can build other arrays or dictionaries, so the
+        // scratch buffer can't be borrowed while advancing it.
+        for value in values {
+            self.scratch.borrow_mut().push(value);
+        }
+
+        let mut scratch = self.scratch.borrow_mut();
+        let len = scratch.len() - start;
+
+        if len == 0 {
+            return (ptr::null_mut(), 0);
+        }
+
+        let slots = self.pool.borrow_mut().alloc(len);
+        let mut borrowed = self.borrowed.borrow_mut();
+
+        for (idx, (value, owned)) in scratch.drain(start..).enumerate() {
+            unsafe {
+                let slot = slots.add(idx);
+                ptr::write(slot, value);
+                if !owned {
+                    borrowed.push(slot);
+                }
+            }
+        }
+
+        self.used.borrow_mut().push((slots, len));
+
+        (slots, len)
+    }
+
+    /// Calls `forget` on every slot holding a value not owned by the arena,
+    /// which should overwrite it without dropping it, then drops every slot
+    /// in use and makes them available again.
+    fn reset(&mut self, mut forget: impl FnMut(*mut T)) {
+        for slot in self.borrowed.get_mut().drain(..) {
+            forget(slot);
+        }
+        for (slots, len) in self.used.get_mut().drain(..) {
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(slots, len))
+            };
+        }
+        self.pool.get_mut().reset();
+    }
+}
+
+/// A bump allocator handing out contiguous slots from chunks of memory that
+/// never move.
+struct Pool<T> {
+    chunks: Vec<Box<[MaybeUninit<T>]>>,
+
+    /// The number of slots handed out from the last chunk.
+    len: usize,
+}
+
+impl<T> Default for Pool<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { chunks: Vec::new(), len: 0 }
+    }
+}
+
+impl<T> Pool<T> {
+    /// Returns a pointer to `n` contiguous uninitialized slots, allocating a
+    /// new chunk if the last one doesn't have enough room.
+    fn alloc(&mut self, n: usize) -> *mut T {
+        let has_room = matches!(
+            self.chunks.last(),
+            Some(chunk) if chunk.len() - self.len >= n
+        );
+
+        if !has_room {
+            let capacity = self
+                .chunks
+                .last()
+                .map_or(MIN_CHUNK_CAPACITY, |chunk| chunk.len() * 2)
+                .max(n);
+            self.chunks.push(chunk(capacity));
+            self.len = 0;
+        }
+
+        let chunk = self.chunks.last_mut().expect("there's at least one chunk");
+        let slots = chunk[self.len..].as_mut_ptr() as *mut T;
+        self.len += n;
+        slots
+    }
+
+    /// Makes every slot available again. If more than one chunk was
+    /// allocated they're merged into a single one big enough for all of
+    /// them, so that it's enough next time.
+    fn reset(&mut self) {
+        if self.chunks.len() > 1 {
+            let capacity = self.chunks.iter().map(|chunk| chunk.len()).sum();
+            self.chunks.clear();
+            self.chunks.push(chunk(capacity));
+        }
+        self.len = 0;
+    }
+}
+
+fn chunk<T>(capacity: usize) -> Box<[MaybeUninit<T>]> {
+    (0..capacity).map(|_| MaybeUninit::uninit()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ObjectKind;
+
+    #[test]
+    fn nested() {
+        let arena = ObjectArena::new();
+
+        let chunks = arena.array((0..3).map(|n| arena.array([n, n * 2])));
+        let dict = arena.dictionary([("foo", Object::nil())]);
+
+        assert_eq!(3, chunks.len());
+        assert_eq!(Object::from(Array::from((2, 4))), chunks[2]);
+        assert_eq!(ObjectKind::Nil, dict["foo"].kind());
+
+        let array = arena.array([chunks]);
+        let dict =
+            arena.dictionary([("foo", array), ("bar", arena.array([1]))]);
+        assert_eq!(Object::from(Array::from((2, 4))), dict["foo"][0][2]);
+    }
+
+    #[test]
+    fn reset_reuses_memory() {
+        let mut arena = ObjectArena::new();
+
+        for n in 0..100 {
+            let _ = arena.array([n.to_string()]);
+        }
+        assert_eq!(2, arena.objects.pool.borrow().chunks.len());
+
+        arena.reset();
+        assert_eq!(1, arena.objects.pool.borrow().chunks.len());
+
+        let first = arena.array([0]).0.items;
+        for n in 1..100 {
+            let _ = arena.array([n]);
+        }
+        assert_eq!(1, arena.objects.pool.borrow().chunks.len());
+
+        arena.reset();
+        assert_eq!(first, arena.array([0]).0.items);
+    }
+
+    #[cfg(feature = "leak-check")]
+    #[test]
+    fn reset_drops_owned_values() {
+        use crate::leak_check::live_arrays;
+
+        let mut arena = ObjectArena::new();
+        let arrays = live_arrays();
+
+        let inner = arena.array([Array::from_iter([1, 2])]);
+        let _ = arena.dictionary([("foo", inner), ("bar", arena.array([3]))]);
+        assert_eq!(arrays + 1, live_arrays());
+
+        arena.reset();
+        assert_eq!(arrays, live_arrays());
+    }
+
+    #[test]
+    fn empty() {
+        let arena = ObjectArena::new();
+        assert!(arena.array(Vec::<Object>::new()).is_empty());
+        assert!(arena.dictionary(Vec::<(&str, Object)>::new()).is_empty());
+    }
+}
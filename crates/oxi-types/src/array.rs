@@ -1,5 +1,6 @@
 use oxi_luajit as lua;
 
+use crate::conversion::{self, FromObject};
 use crate::kvec::{self, KVec};
 use crate::NonOwning;
 use crate::Object;
@@ -17,12 +18,58 @@ impl core::fmt::Debug for Array {
 }
 
 impl Array {
+    /// Moves all the elements of `other` to the back of the array, leaving
+    /// `other` empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        self.0.append(&mut other.0);
+    }
+
     /// Returns the number of elements in the array.
     #[inline]
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    /// Decodes the array into a value of type `T`, typically a tuple whose
+    /// elements are decoded positionally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use oxi_types::Array;
+    ///
+    /// let array = Array::from((1, 0, "foo"));
+    /// let (row, col, name) = array.decode::<(usize, usize, String)>().unwrap();
+    ///
+    /// assert_eq!((1, 0, "foo".to_owned()), (row, col, name));
+    /// ```
+    #[inline]
+    pub fn decode<T: FromObject>(self) -> Result<T, conversion::Error> {
+        T::from_object(self.into())
+    }
+
+    /// Clones and appends all the elements of `other` to the back of the
+    /// array.
+    #[inline]
+    pub fn extend_from_slice(&mut self, other: &[Object]) {
+        self.0.extend(other.iter().cloned());
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it's out
+    /// of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Object> {
+        self.0.as_slice().get(index)
+    }
+
+    /// Returns a mutable reference to the element at `index`, or `None` if
+    /// it's out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Object> {
+        self.0.as_mut_slice().get_mut(index)
+    }
+
     /// Returns `true` if the array contains no elements.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -35,6 +82,13 @@ impl Array {
         self.0.as_slice().iter()
     }
 
+    /// Returns an iterator over mutable references to the `Object`s of the
+    /// array.
+    #[inline]
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, Object> {
+        self.0.as_mut_slice().iter_mut()
+    }
+
     /// Creates a new, empty `Array`.
     #[inline]
     pub fn new() -> Self {
@@ -47,6 +101,28 @@ impl Array {
         #[allow(clippy::unnecessary_struct_initialization)]
         NonOwning::new(Self(KVec { ..self.0 }))
     }
+
+    /// Appends an element to the back of the array.
+    #[inline]
+    pub fn push<V: Into<Object>>(&mut self, value: V) {
+        self.0.push(value.into());
+    }
+}
+
+impl core::ops::Index<usize> for Array {
+    type Output = Object;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0.as_slice()[index]
+    }
+}
+
+impl core::ops::IndexMut<usize> for Array {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0.as_mut_slice()[index]
+    }
 }
 
 impl<T: Into<Object>> FromIterator<T> for Array {
@@ -61,6 +137,16 @@ impl<T: Into<Object>> FromIterator<T> for Array {
     }
 }
 
+/// Unlike [`FromIterator`], nil objects are kept, like in
+/// [`extend_from_slice`](Array::extend_from_slice) and
+/// [`append`](Array::append).
+impl<T: Into<Object>> Extend<T> for Array {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(Into::into));
+    }
+}
+
 impl IntoIterator for Array {
     type Item = Object;
     type IntoIter = ArrayIterator;
@@ -186,6 +272,61 @@ from_tuple!(A B C D E F G H I J K L M N);
 from_tuple!(A B C D E F G H I J K L M N O);
 from_tuple!(A B C D E F G H I J K L M N O P);
 
+#[cfg(feature = "serde")]
+mod serde {
+    use std::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+
+    use super::Array;
+    use crate::Object;
+
+    impl Serialize for Array {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Array {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct ArrayVisitor;
+
+            impl<'de> Visitor<'de> for ArrayVisitor {
+                type Value = Array;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a sequence")
+                }
+
+                fn visit_seq<A>(
+                    self,
+                    mut seq: A,
+                ) -> Result<Self::Value, A::Error>
+                where
+                    A: de::SeqAccess<'de>,
+                {
+                    let mut array = Array::new();
+
+                    while let Some(obj) = seq.next_element::<Object>()? {
+                        array.push(obj);
+                    }
+
+                    Ok(array)
+                }
+            }
+
+            deserializer.deserialize_seq(ArrayVisitor)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +338,31 @@ mod tests {
         assert_eq!(Layout::new::<Array>(), Layout::new::<KVec<Object>>());
     }
 
+    #[test]
+    fn extend_append() {
+        let mut array = Array::from_iter(["Foo"]);
+        array.extend([Object::from("Bar"), Object::nil()]);
+        array.extend_from_slice(&[Object::from(1), Object::nil()]);
+        assert_eq!(5, array.len());
+
+        let mut other = Array::new();
+        other.push(2);
+        other.push(Object::nil());
+        array.append(&mut other);
+        assert!(other.is_empty());
+
+        let expected = [
+            Object::from("Foo"),
+            Object::from("Bar"),
+            Object::nil(),
+            Object::from(1),
+            Object::nil(),
+            Object::from(2),
+            Object::nil(),
+        ];
+        assert_eq!(&expected[..], array.iter().cloned().collect::<Vec<_>>());
+    }
+
     #[test]
     fn iter_basic() {
         let array = Array::from_iter(["Foo", "Bar", "Baz"]);
@@ -216,6 +382,49 @@ mod tests {
         assert_eq!(Some(Object::from("Foo")), iter.next());
     }
 
+    #[test]
+    fn decode_tuple() {
+        let array = Array::from((1, "foo", true));
+        assert_eq!(
+            Ok((1usize, "foo".to_owned(), true)),
+            array.decode::<(usize, String, bool)>()
+        );
+    }
+
+    #[test]
+    fn decode_tuple_optional_tail() {
+        let array = Array::from((4, 2));
+        assert_eq!(
+            Ok((4u32, 2u32, None::<String>)),
+            array.decode::<(u32, u32, Option<String>)>()
+        );
+    }
+
+    #[test]
+    fn decode_tuple_errors() {
+        assert_eq!(
+            Err(conversion::Error::FromWrongLength { expected: 1, actual: 2 }),
+            Array::from((1, 2)).decode::<(u8,)>()
+        );
+
+        assert!(Array::from((1,)).decode::<(u8, u8)>().is_err());
+        assert!(Array::from(("foo",)).decode::<(u8,)>().is_err());
+    }
+
+    #[test]
+    fn mutate_in_place() {
+        let mut array = Array::from((1, "foo"));
+        array[0] = Object::from(2);
+        array.push(true);
+
+        for obj in array.iter_mut().skip(1) {
+            *obj = Object::from("bar");
+        }
+
+        assert_eq!(Array::from((2, "bar", "bar")), array);
+        assert_eq!(None, array.get(3));
+    }
+
     #[test]
     fn empty_array() {
         let empty = Array::default();
@@ -1,8 +1,20 @@
 //! Traits for converting between Neovim [`Object`]s and Rust types.
-
+//!
+//! # Integer conversions
+//!
+//! Neovim represents every integer as an [`Integer`], i.e. an `i64`. The
+//! conversions between an `Integer` and the other Rust integer types are
+//! checked: converting a value that doesn't fit in the target type, like a
+//! negative number into a `u32` or a `u64` bigger than `i64::MAX` into an
+//! `Object`, fails with [`Error::IntOutOfRange`] instead of wrapping or
+//! panicking.
+//!
+//! Wrap the target type in [`Saturating`] to clamp the value to the range of
+//! the target type instead.
+
+use std::borrow::Cow;
 use std::collections::HashMap;
-
-use thiserror::Error as ThisError;
+use std::fmt;
 
 use crate::{
     Array,
@@ -13,22 +25,167 @@ use crate::{
     Integer,
     Object,
     ObjectKind,
+    ObjectRef,
 };
 
-#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Error {
-    #[error("Was expecting a \"{expected}\" but received a \"{actual}\"")]
     FromWrongType { expected: &'static str, actual: &'static str },
 
-    #[error(transparent)]
-    FromInt(#[from] std::num::TryFromIntError),
+    FromWrongLength { expected: usize, actual: usize },
+
+    FromInt(std::num::TryFromIntError),
+
+    IntOutOfRange { value: i128, target: &'static str },
+
+    FromUtf8(std::string::FromUtf8Error),
 
-    #[error(transparent)]
-    FromUtf8(#[from] std::string::FromUtf8Error),
+    FromUtf8Str(std::str::Utf8Error),
 
     #[cfg(feature = "serde")]
-    #[error(transparent)]
-    Serde(#[from] crate::serde::Error),
+    Serde(crate::serde::Error),
+
+    /// Converting an element of an array or a value of a dictionary failed.
+    /// The `path` leads from the converted object to the value that couldn't
+    /// be converted, and `source` is the error returned for that value.
+    #[non_exhaustive]
+    AtPath { path: Vec<PathSegment>, source: Box<Error> },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FromWrongType { expected, actual } => write!(
+                f,
+                "Was expecting a \"{expected}\" but received a \"{actual}\""
+            ),
+            Self::FromWrongLength { expected, actual } => write!(
+                f,
+                "Was expecting an array of at most {expected} elements but \
+                 received one of {actual}"
+            ),
+            Self::FromInt(err) => err.fmt(f),
+            Self::IntOutOfRange { value, target } => write!(
+                f,
+                "Integer {value} is out of the range of a \"{target}\""
+            ),
+            Self::FromUtf8(err) => err.fmt(f),
+            Self::FromUtf8Str(err) => err.fmt(f),
+            #[cfg(feature = "serde")]
+            Self::Serde(err) => err.fmt(f),
+            Self::AtPath { path, source } => {
+                write!(f, "{source} (at `{}`)", DisplayPath(path))
+            },
+        }
+    }
+}
+
+// Implemented by hand rather than derived so that the source of an
+// `AtPath` is the boxed error itself instead of the `Box`, which couldn't be
+// downcast to an `Error`.
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FromInt(err) => Some(err),
+            Self::FromUtf8(err) => Some(err),
+            Self::FromUtf8Str(err) => Some(err),
+            #[cfg(feature = "serde")]
+            Self::Serde(err) => Some(err),
+            Self::AtPath { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::num::TryFromIntError> for Error {
+    fn from(err: std::num::TryFromIntError) -> Self {
+        Self::FromInt(err)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(err: std::string::FromUtf8Error) -> Self {
+        Self::FromUtf8(err)
+    }
+}
+
+impl From<std::str::Utf8Error> for Error {
+    fn from(err: std::str::Utf8Error) -> Self {
+        Self::FromUtf8Str(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<crate::serde::Error> for Error {
+    fn from(err: crate::serde::Error) -> Self {
+        Self::Serde(err)
+    }
+}
+
+/// A step in the path to the value that caused an [`Error::AtPath`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PathSegment {
+    /// The zero-indexed position of an element in an array.
+    Index(usize),
+
+    /// The key of a value in a dictionary.
+    Key(String),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Index(idx) => write!(f, "[{idx}]"),
+            Self::Key(key) => write!(f, ".{key}"),
+        }
+    }
+}
+
+struct DisplayPath<'a>(&'a [PathSegment]);
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|segment| segment.fmt(f))
+    }
+}
+
+impl Error {
+    fn out_of_range<T>(value: i128) -> Self {
+        Self::IntOutOfRange { value, target: std::any::type_name::<T>() }
+    }
+
+    /// Marks the error as having occurred while converting the element at
+    /// position `idx` of an array.
+    pub fn at_index(self, idx: usize) -> Self {
+        self.prepend(PathSegment::Index(idx))
+    }
+
+    /// Marks the error as having occurred while converting the value at
+    /// `key` of a dictionary.
+    pub fn at_key<K: Into<String>>(self, key: K) -> Self {
+        self.prepend(PathSegment::Key(key.into()))
+    }
+
+    /// Returns the path to the value that caused the error, which is empty
+    /// if the error wasn't caused by a nested value.
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            Self::AtPath { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    fn prepend(self, segment: PathSegment) -> Self {
+        match self {
+            Self::AtPath { mut path, source } => {
+                path.insert(0, segment);
+                Self::AtPath { path, source }
+            },
+            other => {
+                Self::AtPath { path: vec![segment], source: Box::new(other) }
+            },
+        }
+    }
 }
 
 /// Trait implemented for types can be obtained from an [`Object`].
@@ -36,6 +193,12 @@ pub trait FromObject: Sized {
     fn from_object(object: Object) -> Result<Self, Error>;
 }
 
+/// Trait implemented for types that can be obtained from an [`ObjectRef`],
+/// i.e. by borrowing the contents of an [`Object`] instead of consuming it.
+pub trait FromObjectRef<'a>: Sized {
+    fn from_object_ref(object: ObjectRef<'a>) -> Result<Self, Error>;
+}
+
 /// Trait implemented for types can be converted into an [`Object`].
 pub trait ToObject {
     fn to_object(self) -> Result<Object, Error>;
@@ -174,7 +337,9 @@ macro_rules! try_from_int {
     ($integer:ty) => {
         impl FromObject for $integer {
             fn from_object(obj: Object) -> Result<Self, Error> {
-                Integer::from_object(obj).and_then(|n| Ok(n.try_into()?))
+                let n = Integer::from_object(obj)?;
+                n.try_into()
+                    .map_err(|_| Error::out_of_range::<$integer>(n.into()))
             }
         }
     };
@@ -220,11 +385,81 @@ where
     fn from_object(obj: Object) -> Result<Self, Error> {
         Array::from_object(obj)?
             .into_iter()
-            .map(FromObject::from_object)
+            .enumerate()
+            .map(|(idx, obj)| T::from_object(obj).map_err(|e| e.at_index(idx)))
+            .collect()
+    }
+}
+
+impl<V> FromObject for HashMap<String, V>
+where
+    V: FromObject,
+{
+    fn from_object(obj: Object) -> Result<Self, Error> {
+        Dictionary::from_object(obj)?
+            .into_iter()
+            .map(|(key, obj)| {
+                let key = key.to_string_lossy().into_owned();
+                match V::from_object(obj) {
+                    Ok(value) => Ok((key, value)),
+                    Err(err) => Err(err.at_key(key)),
+                }
+            })
             .collect()
     }
 }
 
+/// Implements `FromObject` for tuples `(A, B, C, ..)` where all the elements
+/// in the tuple are `FromObject`.
+///
+/// The tuple is decoded positionally from an array. Arrays shorter than the
+/// tuple are padded with `nil`s, so that trailing elements can be optional.
+macro_rules! from_object_tuple {
+    ($($ty:ident)*) => {
+        impl<$($ty: FromObject),*> FromObject for ($($ty,)*) {
+            fn from_object(obj: Object) -> Result<Self, Error> {
+                let array = Array::from_object(obj)?;
+
+                let expected = [$(stringify!($ty)),*].len();
+
+                if array.len() > expected {
+                    return Err(Error::FromWrongLength {
+                        expected,
+                        actual: array.len(),
+                    });
+                }
+
+                let mut iter = array
+                    .into_iter()
+                    .chain(std::iter::repeat_with(Object::default))
+                    .enumerate();
+
+                Ok(($({
+                    let (idx, obj) = iter.next().expect("iterator is infinite");
+                    $ty::from_object(obj).map_err(|e| e.at_index(idx))?
+                },)*))
+            }
+        }
+    };
+}
+
+from_object_tuple!(A);
+from_object_tuple!(A B);
+from_object_tuple!(A B C);
+from_object_tuple!(A B C D);
+from_object_tuple!(A B C D E);
+from_object_tuple!(A B C D E F);
+from_object_tuple!(A B C D E F G);
+from_object_tuple!(A B C D E F G H);
+from_object_tuple!(A B C D E F G H I);
+from_object_tuple!(A B C D E F G H I J);
+from_object_tuple!(A B C D E F G H I J K);
+from_object_tuple!(A B C D E F G H I J K L);
+from_object_tuple!(A B C D E F G H I J K L M);
+from_object_tuple!(A B C D E F G H I J K L M N);
+from_object_tuple!(A B C D E F G H I J K L M N O);
+from_object_tuple!(A B C D E F G H I J K L M N O P);
+
 impl<T> ToObject for T
 where
     T: Into<Object>,
@@ -239,7 +474,11 @@ macro_rules! bigint_to_obj {
     ($type:ty) => {
         impl ToObject for $type {
             fn to_object(self) -> Result<Object, Error> {
-                Ok(i64::try_from(self)?.into())
+                Integer::try_from(self).map(Into::into).map_err(|_| {
+                    // Only `u128`s can be too big to fit in an `i128`.
+                    let value = i128::try_from(self).unwrap_or(i128::MAX);
+                    Error::out_of_range::<Integer>(value)
+                })
             }
         }
     };
@@ -276,3 +515,266 @@ where
             .map(Into::into)
     }
 }
+
+/// A wrapper around an integer which is clamped to the range of the target
+/// type when converted, instead of failing on overflow.
+///
+/// # Examples
+///
+/// ```
+/// use oxi_types::conversion::{FromObject, Saturating, ToObject};
+/// use oxi_types::Object;
+///
+/// let n = Saturating::<u8>::from_object(Object::from(-1)).unwrap();
+/// assert_eq!(Saturating(0), n);
+///
+/// let obj = Saturating(u64::MAX).to_object().unwrap();
+/// assert_eq!(Object::from(i64::MAX), obj);
+/// ```
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+pub struct Saturating<T>(pub T);
+
+/// Implements `FromObject` and `ToObject` for `Saturating<$integer>`.
+macro_rules! saturating_int {
+    ($integer:ty) => {
+        impl FromObject for Saturating<$integer> {
+            fn from_object(obj: Object) -> Result<Self, Error> {
+                let n = i128::from(Integer::from_object(obj)?);
+                let min = <$integer>::MIN as i128;
+                let max = <$integer>::MAX as i128;
+                Ok(Self(n.clamp(min, max) as $integer))
+            }
+        }
+
+        impl ToObject for Saturating<$integer> {
+            fn to_object(self) -> Result<Object, Error> {
+                let n = (self.0 as i128)
+                    .clamp(Integer::MIN.into(), Integer::MAX.into());
+                Ok((n as Integer).into())
+            }
+        }
+    };
+}
+
+saturating_int!(i8);
+saturating_int!(u8);
+saturating_int!(i16);
+saturating_int!(u16);
+saturating_int!(i32);
+saturating_int!(u32);
+saturating_int!(i64);
+saturating_int!(u64);
+saturating_int!(isize);
+saturating_int!(usize);
+
+impl<'a> FromObjectRef<'a> for ObjectRef<'a> {
+    fn from_object_ref(obj: ObjectRef<'a>) -> Result<Self, Error> {
+        Ok(obj)
+    }
+}
+
+/// Implements `FromObjectRef` for a type obtained from a single variant of
+/// `ObjectRef`.
+macro_rules! from_object_ref {
+    ($ty:ty, $expected:literal, $($pat:pat => $value:expr),+) => {
+        impl<'a> FromObjectRef<'a> for $ty {
+            fn from_object_ref(obj: ObjectRef<'a>) -> Result<Self, Error> {
+                match obj {
+                    $($pat => Ok($value),)+
+
+                    other => Err(Error::FromWrongType {
+                        expected: $expected,
+                        actual: other.kind().as_static(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+from_object_ref!((), "nil", ObjectRef::Nil => ());
+from_object_ref!(Boolean, "bool", ObjectRef::Boolean(b) => b);
+from_object_ref!(
+    Integer,
+    "integer",
+    ObjectRef::Integer(n)
+    | ObjectRef::Buffer(n)
+    | ObjectRef::Window(n)
+    | ObjectRef::TabPage(n) => n
+);
+from_object_ref!(Float, "float", ObjectRef::Float(n) => n);
+from_object_ref!(&'a crate::String, "string", ObjectRef::String(s) => s);
+from_object_ref!(&'a [u8], "string", ObjectRef::String(s) => s.as_bytes());
+from_object_ref!(
+    Cow<'a, str>,
+    "string",
+    ObjectRef::String(s) => s.to_string_lossy()
+);
+from_object_ref!(&'a Array, "array", ObjectRef::Array(a) => a);
+from_object_ref!(&'a Dictionary, "dictionary", ObjectRef::Dictionary(d) => d);
+
+impl<'a> FromObjectRef<'a> for &'a str {
+    fn from_object_ref(obj: ObjectRef<'a>) -> Result<Self, Error> {
+        <&crate::String>::from_object_ref(obj)?.to_str().map_err(Into::into)
+    }
+}
+
+impl<'a, T> FromObjectRef<'a> for Option<T>
+where
+    T: FromObjectRef<'a>,
+{
+    fn from_object_ref(obj: ObjectRef<'a>) -> Result<Self, Error> {
+        match obj {
+            ObjectRef::Nil => Ok(None),
+            other => T::from_object_ref(other).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_int_out_of_range() {
+        let err = u32::from_object(Object::from(-1)).unwrap_err();
+        assert_eq!(Error::IntOutOfRange { value: -1, target: "u32" }, err);
+
+        let err = i8::from_object(Object::from(128)).unwrap_err();
+        assert_eq!(Error::IntOutOfRange { value: 128, target: "i8" }, err);
+
+        assert_eq!(Ok(42u32), u32::from_object(Object::from(42)));
+    }
+
+    #[test]
+    fn checked_bigint_to_object() {
+        let err = u64::MAX.to_object().unwrap_err();
+        assert_eq!(
+            Error::IntOutOfRange { value: u64::MAX.into(), target: "i64" },
+            err
+        );
+
+        assert_eq!(Ok(Object::from(42)), 42usize.to_object());
+    }
+
+    #[test]
+    fn saturating_from_object() {
+        let from = |n: i64| Object::from(n);
+
+        assert_eq!(Ok(Saturating(0u32)), FromObject::from_object(from(-1)));
+        assert_eq!(
+            Ok(Saturating(u8::MAX)),
+            FromObject::from_object(from(300))
+        );
+        assert_eq!(
+            Ok(Saturating(i8::MIN)),
+            FromObject::from_object(from(-300))
+        );
+        assert_eq!(Ok(Saturating(7usize)), FromObject::from_object(from(7)));
+        assert_eq!(
+            Ok(Saturating(i64::MAX as u64)),
+            FromObject::from_object(from(i64::MAX))
+        );
+    }
+
+    #[test]
+    fn saturating_to_object() {
+        assert_eq!(
+            Ok(Object::from(i64::MAX)),
+            Saturating(u64::MAX).to_object()
+        );
+        assert_eq!(Ok(Object::from(-1)), Saturating(-1isize).to_object());
+    }
+
+    #[test]
+    fn error_source_chain() {
+        use std::error::Error as _;
+
+        let utf8_err = String::from_utf8(vec![0xff]).unwrap_err();
+        let err = Error::from(utf8_err.clone()).at_index(1);
+
+        let source = err.source().unwrap();
+        assert_eq!(
+            Some(&Error::FromUtf8(utf8_err.clone())),
+            source.downcast_ref::<Error>()
+        );
+
+        let source = source.source().unwrap();
+        assert_eq!(
+            Some(&utf8_err),
+            source.downcast_ref::<std::string::FromUtf8Error>()
+        );
+    }
+
+    #[test]
+    fn from_object_ref() {
+        let obj = Object::from(Array::from_iter(["foo", "bar"]));
+
+        let array = <&Array>::from_object_ref(obj.view()).unwrap();
+        let strs = array
+            .iter()
+            .map(|obj| <&str>::from_object_ref(obj.view()))
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_eq!(Ok(vec!["foo", "bar"]), strs);
+
+        assert_eq!(
+            Err(Error::FromWrongType { expected: "string", actual: "array" }),
+            <&str>::from_object_ref(obj.view())
+        );
+
+        assert_eq!(Ok(None), Option::<&str>::from_object_ref(ObjectRef::Nil));
+        assert_eq!(Ok(7), Integer::from_object_ref(Object::from(7).view()));
+    }
+
+    #[test]
+    fn from_object_ref_invalid_utf8() {
+        let obj = Object::from(crate::String::from_bytes(b"a\xFF"));
+
+        assert!(matches!(
+            <&str>::from_object_ref(obj.view()),
+            Err(Error::FromUtf8Str(_))
+        ));
+
+        let lossy = Cow::<str>::from_object_ref(obj.view()).unwrap();
+        assert_eq!("a\u{FFFD}", lossy);
+    }
+
+    #[test]
+    fn error_path() {
+        let obj = Object::from(Array::from_iter([
+            Object::from(Array::from_iter([1, 2])),
+            Object::from(Array::from_iter([
+                Object::from(3),
+                Object::from("4"),
+            ])),
+        ]));
+
+        let err = Vec::<Vec<u8>>::from_object(obj).unwrap_err();
+
+        assert_eq!(
+            &[PathSegment::Index(1), PathSegment::Index(1)],
+            err.path()
+        );
+        assert_eq!(
+            "Was expecting a \"integer\" but received a \"string\" (at \
+             `[1][1]`)",
+            err.to_string()
+        );
+
+        let obj = Object::from(Dictionary::from_iter([(
+            "foo",
+            Object::from(Array::from_iter([Object::from(1), Object::nil()])),
+        )]));
+
+        let err = HashMap::<String, (u8, bool)>::from_object(obj).unwrap_err();
+
+        assert_eq!(
+            &[PathSegment::Key("foo".into()), PathSegment::Index(1)],
+            err.path()
+        );
+        assert!(u8::from_object(Object::nil()).unwrap_err().path().is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use oxi_luajit as lua;
 
 use crate::kvec::{self, KVec};
@@ -16,8 +18,8 @@ pub struct Dictionary(pub(super) KVec<KeyValuePair>);
 #[derive(Clone, PartialEq)]
 #[repr(C)]
 pub(super) struct KeyValuePair {
-    key: crate::String,
-    value: Object,
+    pub(super) key: crate::String,
+    pub(super) value: Object,
 }
 
 impl core::fmt::Debug for Dictionary {
@@ -47,6 +49,52 @@ impl Dictionary {
             .find_map(|(key, value)| (query == key).then_some(value))
     }
 
+    /// Gets the entry corresponding to the key for in-place manipulation.
+    #[inline]
+    pub fn entry<K>(&mut self, key: K) -> Entry<'_>
+    where
+        K: Into<crate::String>,
+    {
+        let key = key.into();
+        match self.0.as_slice().iter().position(|pair| pair.key == key) {
+            Some(idx) => Entry::Occupied(OccupiedEntry { dict: self, idx }),
+            None => Entry::Vacant(VacantEntry { dict: self, key }),
+        }
+    }
+
+    /// Returns a view of the dictionary with an index of its keys, which can
+    /// be used to look up values in constant time.
+    ///
+    /// Building the index takes linear time, so this is only worth it when
+    /// doing many lookups in the same dictionary.
+    #[inline]
+    pub fn indexed(&self) -> IndexedDict<'_> {
+        let mut index = HashMap::with_capacity(self.len());
+        // Iterating in reverse means the first occurrence of a key wins,
+        // like in `get`.
+        for (idx, pair) in self.0.as_slice().iter().enumerate().rev() {
+            index.insert(pair.key.as_bytes(), idx);
+        }
+        IndexedDict { dict: self, index }
+    }
+
+    /// Inserts a key-value pair into the dictionary, returning the previous
+    /// value of the key if it was present.
+    #[inline]
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Option<Object>
+    where
+        K: Into<crate::String>,
+        V: Into<Object>,
+    {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value.into())),
+            Entry::Vacant(entry) => {
+                entry.insert(value.into());
+                None
+            },
+        }
+    }
+
     /// Returns `true` if the dictionary contains no elements.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -129,6 +177,232 @@ where
     }
 }
 
+/// Like [`FromIterator`], pairs with nil values are skipped. Unlike it, the
+/// value of a key that's already in the dictionary is replaced.
+impl<K, V> Extend<(K, V)> for Dictionary
+where
+    K: Into<crate::String>,
+    V: Into<Object>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.0.reserve(iter.size_hint().0);
+
+        // Indexing the keys avoids a linear lookup for every pair. Like in
+        // `get`, the first occurrence of a duplicated key wins.
+        let mut index = HashMap::with_capacity(self.len());
+        for (idx, pair) in self.0.as_slice().iter().enumerate().rev() {
+            index.insert(pair.key.clone(), idx);
+        }
+
+        for (key, value) in iter {
+            let value = value.into();
+
+            if value.is_nil() {
+                continue;
+            }
+
+            let key = key.into();
+
+            match index.get(&key) {
+                Some(&idx) => self.0.as_mut_slice()[idx].value = value,
+                None => {
+                    index.insert(key.clone(), self.len());
+                    self.0.push(KeyValuePair { key, value });
+                },
+            }
+        }
+    }
+}
+
+/// A view into a single entry of a [`Dictionary`], which may either be vacant
+/// or occupied.
+///
+/// This is constructed from the [`entry`](Dictionary::entry) method.
+pub enum Entry<'a> {
+    Occupied(OccupiedEntry<'a>),
+    Vacant(VacantEntry<'a>),
+}
+
+/// A view into an occupied entry of a [`Dictionary`].
+pub struct OccupiedEntry<'a> {
+    dict: &'a mut Dictionary,
+    idx: usize,
+}
+
+/// A view into a vacant entry of a [`Dictionary`].
+pub struct VacantEntry<'a> {
+    dict: &'a mut Dictionary,
+    key: crate::String,
+}
+
+impl<'a> Entry<'a> {
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the dictionary.
+    #[inline]
+    pub fn and_modify<F>(self, fun: F) -> Self
+    where
+        F: FnOnce(&mut Object),
+    {
+        match self {
+            Self::Occupied(mut entry) => {
+                fun(entry.get_mut());
+                Self::Occupied(entry)
+            },
+            Self::Vacant(entry) => Self::Vacant(entry),
+        }
+    }
+
+    /// Returns a reference to the entry's key.
+    #[inline]
+    pub fn key(&self) -> &crate::String {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `Object::nil()` if empty,
+    /// and returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_default(self) -> &'a mut Object {
+        self.or_insert_with(Object::nil)
+    }
+
+    /// Ensures a value is in the entry by inserting `default` if empty, and
+    /// returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert<V: Into<Object>>(self, default: V) -> &'a mut Object {
+        self.or_insert_with(|| default.into())
+    }
+
+    /// Ensures a value is in the entry by inserting the result of `default`
+    /// if empty, and returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut Object
+    where
+        F: FnOnce() -> Object,
+    {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(default()),
+        }
+    }
+}
+
+impl<'a> OccupiedEntry<'a> {
+    #[inline]
+    fn pair(&self) -> &KeyValuePair {
+        &self.dict.0.as_slice()[self.idx]
+    }
+
+    #[inline]
+    fn pair_mut(&mut self) -> &mut KeyValuePair {
+        &mut self.dict.0.as_mut_slice()[self.idx]
+    }
+
+    /// Returns a reference to the value in the entry.
+    #[inline]
+    pub fn get(&self) -> &Object {
+        &self.pair().value
+    }
+
+    /// Returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Object {
+        &mut self.pair_mut().value
+    }
+
+    /// Sets the value of the entry, returning the entry's old value.
+    #[inline]
+    pub fn insert(&mut self, value: Object) -> Object {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to
+    /// the lifetime of the dictionary.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut Object {
+        &mut self.dict.0.as_mut_slice()[self.idx].value
+    }
+
+    /// Returns a reference to the entry's key.
+    #[inline]
+    pub fn key(&self) -> &crate::String {
+        &self.pair().key
+    }
+}
+
+impl<'a> VacantEntry<'a> {
+    /// Inserts the value into the dictionary with the entry's key, returning
+    /// a mutable reference to it.
+    #[inline]
+    pub fn insert(self, value: Object) -> &'a mut Object {
+        let kvec = &mut self.dict.0;
+        kvec.push(KeyValuePair { key: self.key, value });
+        let idx = kvec.len() - 1;
+        &mut kvec.as_mut_slice()[idx].value
+    }
+
+    /// Takes ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> crate::String {
+        self.key
+    }
+
+    /// Returns a reference to the key that would be used when inserting a
+    /// value through the entry.
+    #[inline]
+    pub fn key(&self) -> &crate::String {
+        &self.key
+    }
+}
+
+/// A view of a [`Dictionary`] with an index of its keys, allowing lookups in
+/// constant time.
+///
+/// This is constructed from the [`indexed`](Dictionary::indexed) method.
+#[derive(Clone)]
+pub struct IndexedDict<'a> {
+    dict: &'a Dictionary,
+    index: HashMap<&'a [u8], usize>,
+}
+
+impl<'a> IndexedDict<'a> {
+    /// Returns `true` if the dictionary contains a value for the key.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + AsRef<[u8]>,
+    {
+        self.index.contains_key(key.as_ref())
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a Object>
+    where
+        Q: ?Sized + AsRef<[u8]>,
+    {
+        let idx = *self.index.get(key.as_ref())?;
+        Some(&self.dict.0.as_slice()[idx].value)
+    }
+
+    /// Returns the underlying dictionary.
+    #[inline]
+    pub fn into_inner(self) -> &'a Dictionary {
+        self.dict
+    }
+}
+
+impl core::fmt::Debug for IndexedDict<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.dict.fmt(f)
+    }
+}
+
 impl IntoIterator for Dictionary {
     type Item = (crate::String, Object);
     type IntoIter = DictIterator;
@@ -297,6 +571,66 @@ impl lua::Pushable for Dictionary {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serde {
+    use std::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+
+    use super::Dictionary;
+    use crate::Object;
+
+    impl Serialize for Dictionary {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_map(self.iter())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Dictionary {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct DictionaryVisitor;
+
+            impl<'de> Visitor<'de> for DictionaryVisitor {
+                type Value = Dictionary;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a map")
+                }
+
+                fn visit_map<A>(
+                    self,
+                    mut map: A,
+                ) -> Result<Self::Value, A::Error>
+                where
+                    A: de::MapAccess<'de>,
+                {
+                    let mut vec =
+                        Vec::<(crate::String, Object)>::with_capacity(
+                            map.size_hint().unwrap_or_default(),
+                        );
+
+                    while let Some(pair) =
+                        map.next_entry::<crate::String, Object>()?
+                    {
+                        vec.push(pair);
+                    }
+
+                    Ok(vec.into_iter().collect())
+                }
+            }
+
+            deserializer.deserialize_map(DictionaryVisitor)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,6 +670,75 @@ mod tests {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn extend_last_wins() {
+        let mut dict = Dictionary::from_iter([("foo", 1), ("bar", 2)]);
+
+        dict.extend([
+            ("bar", Object::from(3)),
+            ("baz", Object::from(4)),
+            ("baz", Object::from(5)),
+            ("qux", Object::nil()),
+        ]);
+
+        assert_eq!(
+            Dictionary::from_iter([("foo", 1), ("bar", 3), ("baz", 5)]),
+            dict
+        );
+    }
+
+    #[test]
+    fn entry_insert_and_modify() {
+        let mut dict = Dictionary::from_iter([("foo", 1), ("bar", 2)]);
+
+        *dict.entry("foo").or_insert(42) = Object::from(3);
+        dict.entry("baz").or_insert(42);
+        dict.entry("bar").and_modify(|obj| *obj = Object::from(4));
+        dict.entry("qux").and_modify(|obj| *obj = Object::from(5));
+
+        assert_eq!(Some(&Object::from(3)), dict.get("foo"));
+        assert_eq!(Some(&Object::from(4)), dict.get("bar"));
+        assert_eq!(Some(&Object::from(42)), dict.get("baz"));
+        assert_eq!(None, dict.get("qux"));
+        assert_eq!(3, dict.len());
+    }
+
+    #[test]
+    fn entry_occupied_vacant() {
+        let mut dict = Dictionary::from_iter([("foo", 1)]);
+
+        match dict.entry("foo") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!("foo", entry.key());
+                assert_eq!(Object::from(1), entry.insert(Object::from(2)));
+            },
+            Entry::Vacant(_) => panic!("`foo` is in the dictionary"),
+        }
+
+        match dict.entry("bar") {
+            Entry::Vacant(entry) => assert_eq!("bar", entry.into_key()),
+            Entry::Occupied(_) => panic!("`bar` isn't in the dictionary"),
+        }
+
+        assert_eq!(None, dict.insert("bar", 3));
+        assert_eq!(Some(Object::from(2)), dict.insert("foo", 4));
+        assert_eq!(Some(&Object::from(4)), dict.get("foo"));
+    }
+
+    #[test]
+    fn indexed_lookup() {
+        let dict = Dictionary::from_iter(
+            (0..100).map(|i| (NvimString::from(&*format!("key{i}")), i)),
+        );
+
+        let indexed = dict.indexed();
+        assert_eq!(Some(&Object::from(42)), indexed.get("key42"));
+        assert_eq!(Some(&Object::from(99)), indexed.get("key99"));
+        assert!(indexed.contains_key("key0"));
+        assert!(!indexed.contains_key("key100"));
+        assert_eq!(None, indexed.get("key100"));
+    }
+
     #[test]
     fn drop_iter_halfway() {
         let dict = Dictionary::from_iter([
@@ -27,6 +27,17 @@ enum ErrorType {
     Validation,
 }
 
+/// The kind of an [`Error`] returned by Neovim.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ErrorKind {
+    /// An error raised while executing the function, e.g. a Vimscript or Lua
+    /// exception.
+    Exception,
+
+    /// The function was called with invalid arguments.
+    Validation,
+}
+
 impl Error {
     pub const fn new() -> Self {
         Self { r#type: ErrorType::None, msg: std::ptr::null_mut() }
@@ -76,4 +87,13 @@ impl Error {
     pub fn is_err(&self) -> bool {
         !matches!(self.r#type, ErrorType::None)
     }
+
+    /// Returns the kind of the error, or `None` if no error occurred.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self.r#type {
+            ErrorType::None => None,
+            ErrorType::Exception => Some(ErrorKind::Exception),
+            ErrorType::Validation => Some(ErrorKind::Validation),
+        }
+    }
 }
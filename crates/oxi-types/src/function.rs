@@ -130,6 +130,15 @@ impl<A, R> Function<A, R> {
         })
     }
 
+    /// Calls the function with the given arguments.
+    ///
+    /// Multiple arguments and return values are passed as tuples, e.g. a
+    /// `Function<(i32, i32), (i32, String)>` takes two integers and returns
+    /// an integer and a string. Like in Lua, the return values are adjusted
+    /// to the arity of `R`, so extra values are discarded and missing ones
+    /// are `nil`. If the function raises an error it's returned as a
+    /// [`lua::Error::LuaError`] containing the message and the stack
+    /// traceback.
     pub fn call(&self, args: A) -> Result<R, lua::Error>
     where
         A: Pushable,
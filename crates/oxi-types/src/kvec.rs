@@ -81,6 +81,28 @@ impl<T> KVec<T> {
         Self { items: core::ptr::null_mut(), size: 0, capacity: 0 }
     }
 
+    /// Moves all the elements of `other` to the back of the vector, leaving
+    /// `other` empty.
+    #[inline]
+    pub(crate) fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        self.reserve(other.size);
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                other.items,
+                self.items.add(self.size),
+                other.size,
+            );
+        }
+
+        self.size += other.size;
+        other.size = 0;
+    }
+
     /// Appends an element to the back of a collection.
     ///
     /// # Panics
@@ -88,24 +110,8 @@ impl<T> KVec<T> {
     /// Panics if the new capacity exceeds `isize::MAX`.
     #[inline]
     pub(crate) fn push(&mut self, item: T) {
-        if self.capacity == 0 {
-            self.capacity = 4;
-            self.items = unsafe {
-                libc::malloc(self.capacity * mem::size_of::<T>()) as *mut T
-            };
-        } else if self.size == self.capacity {
-            self.capacity *= 2;
-
-            assert!(
-                self.capacity * mem::size_of::<T>() <= isize::MAX as usize
-            );
-
-            self.items = unsafe {
-                libc::realloc(
-                    self.items as *mut libc::c_void,
-                    self.capacity * mem::size_of::<T>(),
-                ) as *mut T
-            };
+        if self.size == self.capacity {
+            self.reserve(1);
         }
 
         unsafe {
@@ -115,12 +121,52 @@ impl<T> KVec<T> {
         self.size += 1;
     }
 
+    /// Reserves capacity for at least `additional` more elements, growing
+    /// the vector geometrically to amortize the cost of many pushes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX`.
+    #[inline]
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        let needed =
+            self.size.checked_add(additional).expect("capacity overflow");
+
+        if needed <= self.capacity {
+            return;
+        }
+
+        let capacity = needed.max(self.capacity * 2).max(4);
+
+        assert!(capacity * mem::size_of::<T>() <= isize::MAX as usize);
+
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::on_free(self.items);
+
+        // `realloc` behaves like `malloc` if the vector hasn't been allocated
+        // yet.
+        self.items = unsafe {
+            libc::realloc(
+                self.items as *mut libc::c_void,
+                capacity * mem::size_of::<T>(),
+            ) as *mut T
+        };
+
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::on_alloc(self.items);
+
+        self.capacity = capacity;
+    }
+
     /// Creates a new, empty `KVec<T>` with the specified capacity.
     #[inline]
     pub(crate) fn with_capacity(capacity: usize) -> Self {
         let items =
             unsafe { libc::malloc(capacity * mem::size_of::<T>()) as *mut T };
 
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::on_alloc(items);
+
         Self { items, size: 0, capacity }
     }
 }
@@ -132,6 +178,9 @@ impl<T: Clone> Clone for KVec<T> {
             libc::malloc(self.capacity * mem::size_of::<T>()) as *mut T
         };
 
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::on_alloc(items);
+
         for idx in 0..self.size {
             unsafe {
                 let item = &*self.items.add(idx);
@@ -179,6 +228,17 @@ impl<T> FromIterator<T> for KVec<T> {
     }
 }
 
+impl<T> Extend<T> for KVec<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
 impl<T> IntoIterator for KVec<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -203,6 +263,9 @@ impl<T> Drop for KVec<T> {
                 }
             }
 
+            #[cfg(feature = "leak-check")]
+            crate::leak_check::on_free(self.items);
+
             unsafe { libc::free(self.items as *mut libc::c_void) };
         }
     }
@@ -224,6 +287,10 @@ impl<T: Clone> Clone for IntoIter<T> {
     fn clone(&self) -> Self {
         let len = unsafe { self.end.offset_from(self.start) as usize };
         let ptr = unsafe { libc::malloc(len * mem::size_of::<T>()) as *mut T };
+
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::on_alloc(ptr);
+
         for idx in 0..len {
             unsafe {
                 let item = &*self.start.add(idx);
@@ -286,6 +353,9 @@ impl<T> Drop for IntoIter<T> {
             unsafe { ptr::drop_in_place(current) };
         }
 
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::on_free(self.ptr);
+
         unsafe { libc::free(self.ptr as *mut libc::c_void) };
     }
 }
@@ -340,6 +410,32 @@ mod tests {
         assert_eq!(kvec.capacity(), 4);
     }
 
+    #[test]
+    fn kvec_reserve() {
+        let mut kvec = KVec::<i32>::new();
+        kvec.reserve(10);
+        assert_eq!(kvec.capacity(), 10);
+
+        kvec.extend(0..10);
+        assert_eq!(kvec.capacity(), 10);
+
+        kvec.push(10);
+        assert_eq!(kvec.capacity(), 20);
+    }
+
+    #[test]
+    fn kvec_append() {
+        let mut kvec: KVec<String> =
+            ["foo"].into_iter().map(Into::into).collect();
+        let mut other: KVec<String> =
+            ["bar", "baz"].into_iter().map(Into::into).collect();
+
+        kvec.append(&mut other);
+
+        assert_eq!(kvec.as_slice(), &["foo", "bar", "baz"]);
+        assert!(other.is_empty());
+    }
+
     #[test]
     fn kvec_drop() {
         let mut kvec = KVec::new();
@@ -0,0 +1,99 @@
+//! Tracks the buffers allocated by [`Array`](crate::Array)s and
+//! [`Dictionary`](crate::Dictionary)s, used by
+//! the `leak-check` feature of `nvim-oxi`.
+
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::dictionary::KeyValuePair;
+use crate::Object;
+
+thread_local! {
+    /// Maps the address of every live buffer to the name of the type of its
+    /// items.
+    static LIVE_BUFFERS: RefCell<HashMap<usize, &'static str>> =
+        RefCell::new(HashMap::new());
+}
+
+pub(crate) fn on_alloc<T>(ptr: *mut T) {
+    if !ptr.is_null() {
+        let _ = LIVE_BUFFERS.try_with(|buffers| {
+            buffers.borrow_mut().insert(ptr as usize, type_name::<T>())
+        });
+    }
+}
+
+pub(crate) fn on_free<T>(ptr: *mut T) {
+    let _ = LIVE_BUFFERS
+        .try_with(|buffers| buffers.borrow_mut().remove(&(ptr as usize)));
+}
+
+fn count<T>() -> usize {
+    LIVE_BUFFERS
+        .try_with(|buffers| {
+            buffers
+                .borrow()
+                .values()
+                .filter(|&&ty| ty == type_name::<T>())
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Returns the number of [`Array`](crate::Array)s allocated by `nvim-oxi` that haven't
+/// been freed yet.
+///
+/// Arrays allocated by Neovim aren't counted.
+pub fn live_arrays() -> usize {
+    count::<Object>()
+}
+
+/// Returns the number of [`Dictionary`](crate::Dictionary)s allocated by `nvim-oxi` that
+/// haven't been freed yet.
+///
+/// Dictionaries allocated by Neovim aren't counted.
+pub fn live_dictionaries() -> usize {
+    count::<KeyValuePair>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Array, Dictionary};
+
+    #[test]
+    fn arrays_and_dictionaries() {
+        let (arrays, dicts) = (live_arrays(), live_dictionaries());
+
+        let array = Array::from_iter([1, 2, 3]);
+        let dict = Dictionary::from_iter([("foo", array.clone())]);
+        assert_eq!(arrays + 2, live_arrays());
+        assert_eq!(dicts + 1, live_dictionaries());
+
+        let mut iter = array.into_iter();
+        iter.next();
+        assert_eq!(arrays + 2, live_arrays());
+
+        drop(iter);
+        drop(dict);
+        assert_eq!(arrays, live_arrays());
+        assert_eq!(dicts, live_dictionaries());
+    }
+
+    #[test]
+    fn reallocations() {
+        let arrays = live_arrays();
+
+        let mut array = Array::new();
+        assert_eq!(arrays, live_arrays());
+
+        for n in 0..100 {
+            array.push(n);
+        }
+        assert_eq!(arrays + 1, live_arrays());
+
+        drop(array);
+        assert_eq!(arrays, live_arrays());
+    }
+}
@@ -1,25 +1,48 @@
 //! Rust bindings to the C types used by Neovim's API.
 
+mod arena;
 mod array;
 pub mod conversion;
 mod dictionary;
 mod error;
 mod function;
 mod kvec;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "leak-check")]
+#[doc(hidden)]
+pub mod leak_check;
 mod non_owning;
 mod object;
+mod object_ref;
 #[cfg(feature = "serde")]
 pub mod serde;
+mod small_string;
 mod string;
 
+pub use arena::{ArenaValue, ObjectArena};
 pub use array::Array;
 pub use dictionary::Dictionary;
-pub use error::Error;
+pub use error::{Error, ErrorKind};
 pub use function::Function;
 pub use non_owning::NonOwning;
-pub use object::{Object, ObjectKind};
+pub use object::{Object, ObjectIndex, ObjectKind};
+pub use object_ref::ObjectRef;
+pub use small_string::SmallString;
 pub use string::String;
 
+pub mod dict {
+    //! Types used to manipulate and look up the entries of
+    //! [`Dictionary`](crate::Dictionary)s.
+
+    pub use super::dictionary::{
+        Entry,
+        IndexedDict,
+        OccupiedEntry,
+        VacantEntry,
+    };
+}
+
 pub mod iter {
     //! Iterators over [`Array`](crate::Array)s and
     //! [`Dictionary`](crate::Dictionary)s.
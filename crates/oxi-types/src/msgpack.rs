@@ -0,0 +1,258 @@
+//! Encoding and decoding of [`Object`]s as [MessagePack][1], using the same
+//! format Neovim uses for its [RPC API][2].
+//!
+//! Buffer, window and tabpage handles are encoded as the MessagePack
+//! extension types Neovim advertises in `nvim_get_api_info()`, i.e. `0`, `1`
+//! and `2` respectively, whose data is the handle encoded as an integer.
+//! Lua references can't be sent over the wire, so they're encoded as `nil`.
+//!
+//! # Examples
+//!
+//! ```
+//! use oxi_types::{msgpack, Array, Object};
+//!
+//! let obj = Object::from(Array::from((1, "foo", true)));
+//! let bytes = msgpack::encode(&obj);
+//! assert_eq!(Ok(obj), msgpack::decode(&bytes));
+//! ```
+//!
+//! [1]: https://msgpack.org
+//! [2]: https://neovim.io/doc/user/api.html#api-rpc
+
+use std::io::{self, Read, Write};
+
+use rmpv::Value;
+use thiserror::Error as ThisError;
+
+use crate::{Array, Dictionary, Integer, Object, ObjectKind};
+
+const BUFFER_EXT: i8 = 0;
+const WINDOW_EXT: i8 = 1;
+const TABPAGE_EXT: i8 = 2;
+
+/// The error returned when decoding an [`Object`] from MessagePack.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum Error {
+    /// The bytes aren't valid MessagePack.
+    #[error("invalid msgpack: {0}")]
+    Invalid(String),
+
+    /// An unsigned integer is too big to fit in an [`Integer`].
+    #[error("integer {0} is out of the range of an i64")]
+    IntOutOfRange(u64),
+
+    /// A map has a key that's not a string.
+    #[error("dictionary keys must be strings, found {0}")]
+    NonStringKey(String),
+
+    /// An extension type that Neovim doesn't use.
+    #[error("unknown msgpack extension type {0}")]
+    UnknownExtType(i8),
+
+    /// The data of a handle extension type isn't an integer.
+    #[error("invalid handle in msgpack extension of type {0}")]
+    InvalidHandle(i8),
+
+    /// There are bytes left after the first value.
+    #[error("{0} trailing bytes after the msgpack value")]
+    TrailingBytes(usize),
+}
+
+/// Encodes an [`Object`] as MessagePack.
+pub fn encode(obj: &Object) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write(&mut bytes, obj).expect("writing to a Vec can't fail");
+    bytes
+}
+
+/// Writes an [`Object`] encoded as MessagePack to the given writer.
+pub fn write<W: Write>(wr: &mut W, obj: &Object) -> io::Result<()> {
+    rmpv::encode::write_value(wr, &Value::from(obj)).map_err(Into::into)
+}
+
+/// Decodes an [`Object`] from MessagePack, failing if `bytes` contains
+/// anything after the first value.
+pub fn decode(mut bytes: &[u8]) -> Result<Object, Error> {
+    let obj = read(&mut bytes)?;
+
+    if !bytes.is_empty() {
+        return Err(Error::TrailingBytes(bytes.len()));
+    }
+
+    Ok(obj)
+}
+
+/// Reads an [`Object`] encoded as MessagePack from the given reader.
+pub fn read<R: Read>(rd: &mut R) -> Result<Object, Error> {
+    rmpv::decode::read_value(rd)
+        .map_err(|err| Error::Invalid(err.to_string()))?
+        .try_into()
+}
+
+impl From<&Object> for Value {
+    fn from(obj: &Object) -> Self {
+        unsafe {
+            match obj.kind() {
+                ObjectKind::Nil | ObjectKind::LuaRef => Value::Nil,
+                ObjectKind::Boolean => obj.as_boolean_unchecked().into(),
+                ObjectKind::Integer => obj.as_integer_unchecked().into(),
+                ObjectKind::Float => obj.as_float_unchecked().into(),
+                ObjectKind::String => {
+                    string(obj.as_string_unchecked().as_bytes())
+                },
+                ObjectKind::Array => Value::Array(
+                    obj.as_array_unchecked().iter().map(Value::from).collect(),
+                ),
+                ObjectKind::Dictionary => Value::Map(
+                    obj.as_dict_unchecked()
+                        .iter()
+                        .map(|(key, value)| {
+                            (string(key.as_bytes()), value.into())
+                        })
+                        .collect(),
+                ),
+                ObjectKind::Buffer => handle(BUFFER_EXT, obj),
+                ObjectKind::Window => handle(WINDOW_EXT, obj),
+                ObjectKind::TabPage => handle(TABPAGE_EXT, obj),
+            }
+        }
+    }
+}
+
+impl From<Object> for Value {
+    #[inline]
+    fn from(obj: Object) -> Self {
+        Self::from(&obj)
+    }
+}
+
+impl TryFrom<Value> for Object {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let obj = match value {
+            Value::Nil => Object::nil(),
+            Value::Boolean(b) => b.into(),
+            Value::Integer(n) => to_integer(n)?.into(),
+            Value::F32(f) => f64::from(f).into(),
+            Value::F64(f) => f.into(),
+            Value::String(s) => crate::String::from_bytes(s.as_bytes()).into(),
+            Value::Binary(b) => crate::String::from_bytes(&b).into(),
+            Value::Array(values) => {
+                let mut array = Array::new();
+                for value in values {
+                    array.push(Object::try_from(value)?);
+                }
+                array.into()
+            },
+            Value::Map(entries) => {
+                let mut dict = Dictionary::new();
+                for (key, value) in entries {
+                    let key = match key {
+                        Value::String(s) => {
+                            crate::String::from_bytes(s.as_bytes())
+                        },
+                        Value::Binary(b) => crate::String::from_bytes(&b),
+                        other => {
+                            return Err(Error::NonStringKey(other.to_string()))
+                        },
+                    };
+                    dict.insert(key, Object::try_from(value)?);
+                }
+                dict.into()
+            },
+            Value::Ext(ty, data) => {
+                let kind = match ty {
+                    BUFFER_EXT => ObjectKind::Buffer,
+                    WINDOW_EXT => ObjectKind::Window,
+                    TABPAGE_EXT => ObjectKind::TabPage,
+                    other => return Err(Error::UnknownExtType(other)),
+                };
+                let handle = match rmpv::decode::read_value(&mut &*data) {
+                    Ok(Value::Integer(n)) => to_integer(n)?,
+                    _ => return Err(Error::InvalidHandle(ty)),
+                };
+                Object::from_handle(kind, handle)
+            },
+        };
+
+        Ok(obj)
+    }
+}
+
+/// Encodes a string as a msgpack `str`, or as a `bin` if it's not valid UTF-8.
+/// Neovim decodes both into a `String`.
+fn string(bytes: &[u8]) -> Value {
+    match String::from_utf8(bytes.to_vec()) {
+        Ok(str) => Value::String(str.into()),
+        Err(err) => Value::Binary(err.into_bytes()),
+    }
+}
+
+/// Encodes a buffer, window or tabpage handle as the given extension type.
+unsafe fn handle(ty: i8, obj: &Object) -> Value {
+    let mut data = Vec::new();
+    let handle = Value::from(obj.as_integer_unchecked());
+    rmpv::encode::write_value(&mut data, &handle)
+        .expect("writing to a Vec can't fail");
+    Value::Ext(ty, data)
+}
+
+fn to_integer(n: rmpv::Integer) -> Result<Integer, Error> {
+    match (n.as_i64(), n.as_u64()) {
+        (Some(n), _) => Ok(n),
+        (None, Some(n)) => Err(Error::IntOutOfRange(n)),
+        (None, None) => unreachable!("msgpack integers fit in an i64 or u64"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let dict = Dictionary::from_iter([
+            ("foo", Object::from(1.5)),
+            ("bar", Object::from(Array::from((1, "baz", false)))),
+        ]);
+
+        let obj = Object::from(Array::from((dict, -42, "")));
+
+        assert_eq!(Ok(obj.clone()), decode(&encode(&obj)));
+    }
+
+    #[test]
+    fn handles_are_ext_types() {
+        let buf = Object::from_handle(ObjectKind::Buffer, 1);
+        let win = Object::from_handle(ObjectKind::Window, 1000);
+
+        // Same bytes Neovim sends for buffer 1, i.e. a fixext1 of type 0.
+        assert_eq!(vec![0xd4, 0x00, 0x01], encode(&buf));
+
+        assert_eq!(Ok(buf), decode(&[0xd4, 0x00, 0x01]));
+        assert_eq!(ObjectKind::Window, decode(&encode(&win)).unwrap().kind());
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(Err(Error::UnknownExtType(5)), decode(&[0xd4, 0x05, 0x01]));
+        assert_eq!(Err(Error::TrailingBytes(1)), decode(&[0xc0, 0xc0]));
+        assert_eq!(
+            Err(Error::IntOutOfRange(u64::MAX)),
+            decode(&encode_value(Value::from(u64::MAX)))
+        );
+        assert!(matches!(
+            decode(&encode_value(Value::Map(vec![(1.into(), 2.into())]))),
+            Err(Error::NonStringKey(_))
+        ));
+        assert!(matches!(decode(&[0x92, 0x01]), Err(Error::Invalid(_))));
+    }
+
+    fn encode_value(value: Value) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &value).unwrap();
+        bytes
+    }
+}
@@ -17,6 +17,15 @@ impl<'a, T> NonOwning<'a, T> {
     }
 }
 
+impl<'a, T> core::ops::Deref for NonOwning<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
 impl<'a, T> core::fmt::Debug for NonOwning<'a, T>
 where
     T: core::fmt::Debug,
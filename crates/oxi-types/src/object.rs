@@ -116,7 +116,7 @@ impl core::fmt::Debug for Object {
 impl Object {
     /// Returns a new nil object.
     #[inline]
-    pub fn nil() -> Self {
+    pub const fn nil() -> Self {
         Self { ty: ObjectKind::Nil, data: ObjectData { integer: 0 } }
     }
 
@@ -135,6 +135,17 @@ impl Object {
         Self { ty: ObjectKind::LuaRef, data: ObjectData { luaref } }
     }
 
+    /// Returns a new buffer, window or tabpage object.
+    #[cfg(feature = "msgpack")]
+    #[inline]
+    pub(crate) fn from_handle(kind: ObjectKind, handle: Integer) -> Self {
+        debug_assert!(matches!(
+            kind,
+            ObjectKind::Buffer | ObjectKind::Window | ObjectKind::TabPage
+        ));
+        Self { ty: kind, data: ObjectData { integer: handle } }
+    }
+
     #[inline]
     pub fn kind(&self) -> ObjectKind {
         self.ty
@@ -148,6 +159,103 @@ impl Object {
         unsafe { NonOwning::new(std::ptr::read(self)) }
     }
 
+    /// Returns a reference to the contained [`Array`], or `None` if the
+    /// object is not an array.
+    #[inline]
+    pub fn as_array(&self) -> Option<&Array> {
+        match self.ty {
+            ObjectKind::Array => Some(unsafe { &self.data.array }),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the contained [`Array`], or `None` if
+    /// the object is not an array.
+    #[inline]
+    pub fn as_array_mut(&mut self) -> Option<&mut Array> {
+        match self.ty {
+            ObjectKind::Array => Some(unsafe { &mut self.data.array }),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the contained [`Dictionary`], or `None` if the
+    /// object is not a dictionary.
+    #[inline]
+    pub fn as_dictionary(&self) -> Option<&Dictionary> {
+        match self.ty {
+            ObjectKind::Dictionary => Some(unsafe { &self.data.dictionary }),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the contained [`Dictionary`], or
+    /// `None` if the object is not a dictionary.
+    #[inline]
+    pub fn as_dictionary_mut(&mut self) -> Option<&mut Dictionary> {
+        match self.ty {
+            ObjectKind::Dictionary => {
+                Some(unsafe { &mut self.data.dictionary })
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the contained [`String`](crate::String), or
+    /// `None` if the object is not a string.
+    #[inline]
+    pub fn as_string(&self) -> Option<&crate::String> {
+        match self.ty {
+            ObjectKind::String => Some(unsafe { &self.data.string }),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the contained
+    /// [`String`](crate::String), or `None` if the object is not a string.
+    #[inline]
+    pub fn as_string_mut(&mut self) -> Option<&mut crate::String> {
+        match self.ty {
+            ObjectKind::String => Some(unsafe { &mut self.data.string }),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the element at `index` if the object is an
+    /// array, or to the value of the key `index` if it's a dictionary.
+    ///
+    /// Returns `None` if the object is neither, or if the element or key is
+    /// missing.
+    #[inline]
+    pub fn get<I: ObjectIndex>(&self, index: I) -> Option<&Object> {
+        index.index_into(self)
+    }
+
+    /// Like [`get`](Self::get), but returns a mutable reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use oxi_types::{Array, Dictionary, Object};
+    ///
+    /// let mut config = Object::from(Dictionary::from_iter([(
+    ///     "servers",
+    ///     Array::from_iter(["rust-analyzer", "lua_ls"]),
+    /// )]));
+    ///
+    /// *config.get_mut("servers").and_then(|s| s.get_mut(1)).unwrap() =
+    ///     "clangd".into();
+    ///
+    /// assert_eq!(Some(&Object::from("clangd")), config["servers"].get(1));
+    /// ```
+    #[inline]
+    pub fn get_mut<I: ObjectIndex>(
+        &mut self,
+        index: I,
+    ) -> Option<&mut Object> {
+        index.index_into_mut(self)
+    }
+
     /// # Safety
     ///
     /// TODO
@@ -180,6 +288,40 @@ impl Object {
         self.data.luaref
     }
 
+    /// # Safety
+    ///
+    /// TODO
+    ///
+    /// Returns a reference to the contained [`String`](crate::String) value
+    /// without checking that the object actually contains a
+    /// [`String`](crate::String).
+    #[inline(always)]
+    pub unsafe fn as_string_unchecked(&self) -> &crate::String {
+        &self.data.string
+    }
+
+    /// # Safety
+    ///
+    /// TODO
+    ///
+    /// Returns a reference to the contained [`Array`] value without checking
+    /// that the object actually contains an [`Array`].
+    #[inline(always)]
+    pub unsafe fn as_array_unchecked(&self) -> &Array {
+        &self.data.array
+    }
+
+    /// # Safety
+    ///
+    /// TODO
+    ///
+    /// Returns a reference to the contained [`Dictionary`] value without
+    /// checking that the object actually contains a [`Dictionary`].
+    #[inline(always)]
+    pub unsafe fn as_dict_unchecked(&self) -> &Dictionary {
+        &self.data.dictionary
+    }
+
     /// # Safety
     ///
     /// TODO
@@ -220,6 +362,86 @@ impl Object {
     }
 }
 
+/// A type that can be used to index into an [`Object`], either a position in
+/// an [`Array`] or a key in a [`Dictionary`].
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait ObjectIndex: sealed::Sealed {
+    #[doc(hidden)]
+    fn index_into(self, obj: &Object) -> Option<&Object>;
+
+    #[doc(hidden)]
+    fn index_into_mut(self, obj: &mut Object) -> Option<&mut Object>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for usize {}
+    impl Sealed for &str {}
+    impl Sealed for &crate::String {}
+}
+
+impl ObjectIndex for usize {
+    #[inline]
+    fn index_into(self, obj: &Object) -> Option<&Object> {
+        obj.as_array()?.get(self)
+    }
+
+    #[inline]
+    fn index_into_mut(self, obj: &mut Object) -> Option<&mut Object> {
+        obj.as_array_mut()?.get_mut(self)
+    }
+}
+
+impl ObjectIndex for &str {
+    #[inline]
+    fn index_into(self, obj: &Object) -> Option<&Object> {
+        obj.as_dictionary()?.get(self)
+    }
+
+    #[inline]
+    fn index_into_mut(self, obj: &mut Object) -> Option<&mut Object> {
+        obj.as_dictionary_mut()?.get_mut(self)
+    }
+}
+
+impl ObjectIndex for &crate::String {
+    #[inline]
+    fn index_into(self, obj: &Object) -> Option<&Object> {
+        obj.as_dictionary()?.get(self)
+    }
+
+    #[inline]
+    fn index_into_mut(self, obj: &mut Object) -> Option<&mut Object> {
+        obj.as_dictionary_mut()?.get_mut(self)
+    }
+}
+
+impl<I: ObjectIndex> core::ops::Index<I> for Object {
+    type Output = Object;
+
+    /// # Panics
+    ///
+    /// Panics if the object is not an array or a dictionary, or if the
+    /// element or key is missing.
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        self.get(index).expect("invalid object index")
+    }
+}
+
+impl<I: ObjectIndex> core::ops::IndexMut<I> for Object {
+    /// # Panics
+    ///
+    /// Panics if the object is not an array or a dictionary, or if the
+    /// element or key is missing.
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        self.get_mut(index).expect("invalid object index")
+    }
+}
+
 macro_rules! clone_copy {
     ($self:expr, $field:ident) => {{
         Self {
@@ -535,10 +757,49 @@ mod serde {
     use std::fmt;
 
     use serde::de::{self, Deserialize};
+    use serde::ser::{Serialize, Serializer};
 
-    use super::Object;
+    use super::{Object, ObjectKind};
     use crate::{Array, Dictionary, Integer, LuaRef};
 
+    impl Serialize for Object {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            unsafe {
+                match self.kind() {
+                    ObjectKind::Nil => serializer.serialize_unit(),
+                    ObjectKind::Boolean => {
+                        serializer.serialize_bool(self.as_boolean_unchecked())
+                    },
+                    ObjectKind::Integer
+                    | ObjectKind::Buffer
+                    | ObjectKind::Window
+                    | ObjectKind::TabPage => {
+                        serializer.serialize_i64(self.as_integer_unchecked())
+                    },
+                    ObjectKind::Float => {
+                        serializer.serialize_f64(self.as_float_unchecked())
+                    },
+                    ObjectKind::String => {
+                        self.as_string_unchecked().serialize(serializer)
+                    },
+                    ObjectKind::Array => {
+                        self.as_array_unchecked().serialize(serializer)
+                    },
+                    ObjectKind::Dictionary => {
+                        self.as_dict_unchecked().serialize(serializer)
+                    },
+                    // Lua references are serialized as `f32`s, like
+                    // `Function`s.
+                    ObjectKind::LuaRef => serializer
+                        .serialize_f32(self.as_luaref_unchecked() as f32),
+                }
+            }
+        }
+    }
+
     impl<'de> Deserialize<'de> for Object {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -661,4 +922,26 @@ mod tests {
         assert!(str_again.is_ok());
         assert_eq!(str, str_again.unwrap());
     }
+
+    #[test]
+    fn mutate_nested_objects() {
+        let mut obj = Object::from(Dictionary::from_iter([
+            ("list", Object::from(Array::from((1, 2)))),
+            ("name", Object::from("foo")),
+        ]));
+
+        obj["list"][1] = Object::from(3);
+        obj["list"].as_array_mut().unwrap().push(4);
+        *obj.get_mut("name").unwrap().as_string_mut().unwrap() = "bar".into();
+        obj.as_dictionary_mut().unwrap().insert("new", true);
+
+        assert_eq!(Some(&Object::from(3)), obj["list"].get(1));
+        assert_eq!(Some(&Object::from(4)), obj["list"].get(2));
+        assert_eq!(Some(&crate::String::from("bar")), obj["name"].as_string());
+        assert_eq!(Some(&Object::from(true)), obj.get("new"));
+
+        assert_eq!(None, obj.get(0));
+        assert_eq!(None, obj["list"].get("name"));
+        assert!(obj["name"].as_array().is_none());
+    }
 }
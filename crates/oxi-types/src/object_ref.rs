@@ -0,0 +1,108 @@
+use crate::{Array, Dictionary, Float, Integer, LuaRef, Object, ObjectKind};
+
+/// A borrowed view of an [`Object`], returned by [`Object::view`].
+///
+/// Unlike the [`FromObject`](crate::conversion::FromObject) conversions,
+/// which consume the object, the values obtained from a view borrow the
+/// data of the object. Reading the strings of a large [`Array`] returned by
+/// Neovim through a view doesn't allocate, and the whole array is freed at
+/// once when it's dropped.
+///
+/// # Examples
+///
+/// ```
+/// use oxi_types::{conversion::FromObjectRef, Array};
+///
+/// let lines = Array::from_iter(["foo", "bar"]);
+///
+/// let total_len = lines
+///     .iter()
+///     .map(|line| <&str>::from_object_ref(line.view()).map(str::len))
+///     .sum::<Result<usize, _>>()?;
+///
+/// assert_eq!(6, total_len);
+/// # Ok::<_, oxi_types::conversion::Error>(())
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ObjectRef<'a> {
+    Nil,
+    Boolean(bool),
+    Integer(Integer),
+    Float(Float),
+    String(&'a crate::String),
+    Array(&'a Array),
+    Dictionary(&'a Dictionary),
+    LuaRef(LuaRef),
+    Buffer(Integer),
+    Window(Integer),
+    TabPage(Integer),
+}
+
+impl ObjectRef<'_> {
+    /// Returns the kind of the viewed object.
+    #[inline]
+    pub fn kind(&self) -> ObjectKind {
+        match self {
+            Self::Nil => ObjectKind::Nil,
+            Self::Boolean(_) => ObjectKind::Boolean,
+            Self::Integer(_) => ObjectKind::Integer,
+            Self::Float(_) => ObjectKind::Float,
+            Self::String(_) => ObjectKind::String,
+            Self::Array(_) => ObjectKind::Array,
+            Self::Dictionary(_) => ObjectKind::Dictionary,
+            Self::LuaRef(_) => ObjectKind::LuaRef,
+            Self::Buffer(_) => ObjectKind::Buffer,
+            Self::Window(_) => ObjectKind::Window,
+            Self::TabPage(_) => ObjectKind::TabPage,
+        }
+    }
+}
+
+impl Object {
+    /// Returns a borrowed view of the object.
+    #[inline]
+    pub fn view(&self) -> ObjectRef<'_> {
+        unsafe {
+            match self.kind() {
+                ObjectKind::Nil => ObjectRef::Nil,
+                ObjectKind::Boolean => {
+                    ObjectRef::Boolean(self.as_boolean_unchecked())
+                },
+                ObjectKind::Integer => {
+                    ObjectRef::Integer(self.as_integer_unchecked())
+                },
+                ObjectKind::Float => {
+                    ObjectRef::Float(self.as_float_unchecked())
+                },
+                ObjectKind::String => {
+                    ObjectRef::String(self.as_string_unchecked())
+                },
+                ObjectKind::Array => {
+                    ObjectRef::Array(self.as_array_unchecked())
+                },
+                ObjectKind::Dictionary => {
+                    ObjectRef::Dictionary(self.as_dict_unchecked())
+                },
+                ObjectKind::LuaRef => {
+                    ObjectRef::LuaRef(self.as_luaref_unchecked())
+                },
+                ObjectKind::Buffer => {
+                    ObjectRef::Buffer(self.as_integer_unchecked())
+                },
+                ObjectKind::Window => {
+                    ObjectRef::Window(self.as_integer_unchecked())
+                },
+                ObjectKind::TabPage => {
+                    ObjectRef::TabPage(self.as_integer_unchecked())
+                },
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a Object> for ObjectRef<'a> {
+    #[inline]
+    fn from(obj: &'a Object) -> Self {
+        obj.view()
+    }
+}
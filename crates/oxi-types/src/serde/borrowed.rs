@@ -0,0 +1,500 @@
+use std::borrow::Cow;
+
+use serde::de::{self, value::BorrowedStrDeserializer};
+
+use super::Result;
+use crate::{Object, ObjectKind};
+
+/// A struct used for deserializing Rust values which borrow from a Neovim
+/// `Object`.
+///
+/// Unlike [`Deserializer`](super::Deserializer), which takes ownership of the
+/// object, this only holds a reference to it, so strings can be deserialized
+/// into `&str`s and `Cow<'_, str>`s pointing into the buffers of the
+/// underlying [`String`](crate::String)s instead of being copied.
+///
+/// Note that a string can only be borrowed if it's valid UTF-8, so `&str`
+/// fields will fail to deserialize if it's not. `Cow<'_, str>` fields marked
+/// with `#[serde(borrow)]` fall back to a lossy copy.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use oxi_types::serde::BorrowedDeserializer;
+/// use oxi_types::{Dictionary, Object};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Item<'a> {
+///     label: &'a str,
+///     #[serde(borrow)]
+///     detail: Cow<'a, str>,
+/// }
+///
+/// let obj = Object::from(Dictionary::from_iter([
+///     ("label", "foo"),
+///     ("detail", "bar"),
+/// ]));
+///
+/// let item = Item::deserialize(BorrowedDeserializer::new(&obj)).unwrap();
+/// assert_eq!(item.label, "foo");
+/// assert!(matches!(item.detail, Cow::Borrowed("bar")));
+/// ```
+#[derive(Copy, Clone)]
+pub struct BorrowedDeserializer<'de> {
+    obj: &'de Object,
+}
+
+impl<'de> BorrowedDeserializer<'de> {
+    pub fn new(obj: &'de Object) -> Self {
+        Self { obj }
+    }
+}
+
+/// Borrows the contents of a string if they're valid UTF-8, falling back to
+/// a lossy copy if they're not.
+#[inline]
+fn borrow_str(string: &crate::String) -> Cow<'_, str> {
+    match string.to_str() {
+        Ok(str) => Cow::Borrowed(str),
+        Err(_) => string.to_string_lossy(),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for BorrowedDeserializer<'de> {
+    type Error = super::Error;
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct identifier ignored_any
+    }
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        use ObjectKind::*;
+        match self.obj.kind() {
+            Nil => visitor.visit_unit(),
+
+            Boolean => {
+                visitor.visit_bool(unsafe { self.obj.as_boolean_unchecked() })
+            },
+
+            Integer | Buffer | Window | TabPage => {
+                visitor.visit_i64(unsafe { self.obj.as_integer_unchecked() })
+            },
+
+            Float => unsafe {
+                visitor.visit_f64(self.obj.as_float_unchecked())
+            },
+
+            String => {
+                match borrow_str(unsafe { self.obj.as_string_unchecked() }) {
+                    Cow::Borrowed(str) => visitor.visit_borrowed_str(str),
+                    Cow::Owned(string) => visitor.visit_string(string),
+                }
+            },
+
+            Array => self.deserialize_seq(visitor),
+
+            Dictionary => self.deserialize_map(visitor),
+
+            // See the comment in `Deserializer::deserialize_any`.
+            LuaRef => unsafe {
+                visitor.visit_f32(self.obj.as_luaref_unchecked() as f32)
+            },
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.obj.kind() {
+            ObjectKind::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _name: &str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let (s, obj) = match self.obj.kind() {
+            ObjectKind::Dictionary => {
+                let dict = unsafe { self.obj.as_dict_unchecked() };
+
+                let (variant, value) = match dict.len() {
+                    1 => dict.iter().next().expect("checked length"),
+                    _ => {
+                        return Err(de::Error::invalid_value(
+                            de::Unexpected::Map,
+                            &"dictionary with a single key-value pair",
+                        ))
+                    },
+                };
+
+                (variant, Some(value))
+            },
+
+            ObjectKind::String => {
+                (unsafe { self.obj.as_string_unchecked() }, None)
+            },
+
+            _ => return Err(de::Error::custom("bad enum value")),
+        };
+
+        visitor.visit_enum(EnumDeserializer { variant: borrow_str(s), obj })
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.obj.kind() {
+            ObjectKind::Array => {
+                let iter = unsafe { self.obj.as_array_unchecked() }.iter();
+                visitor.visit_seq(SeqDeserializer { iter })
+            },
+
+            ty => Err(de::Error::invalid_type(
+                de::Unexpected::Other(&format!("{ty:?}")),
+                &"array",
+            )),
+        }
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.obj.kind() {
+            ObjectKind::Dictionary => {
+                let iter = unsafe { self.obj.as_dict_unchecked() }.iter();
+                visitor.visit_map(MapDeserializer { iter, obj: None })
+            },
+
+            ty => Err(de::Error::invalid_type(
+                de::Unexpected::Other(&format!("{ty:?}")),
+                &"dictionary",
+            )),
+        }
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+}
+
+struct SeqDeserializer<'de> {
+    iter: core::slice::Iter<'de, Object>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = super::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(obj) => {
+                seed.deserialize(BorrowedDeserializer { obj }).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer<'de> {
+    iter: crate::dictionary::DictIter<'de>,
+    obj: Option<&'de Object>,
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer<'de> {
+    type Error = super::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if let Some((name, obj)) = self.iter.next() {
+            self.obj = Some(obj);
+            return seed
+                .deserialize(StrDeserializer(borrow_str(name)))
+                .map(Some);
+        }
+
+        Ok(None)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.obj.take() {
+            Some(obj) => seed.deserialize(BorrowedDeserializer { obj }),
+            _ => Err(de::Error::custom("object is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// A deserializer for dictionary keys and enum variants.
+struct StrDeserializer<'de>(Cow<'de, str>);
+
+impl<'de> de::Deserializer<'de> for StrDeserializer<'de> {
+    type Error = super::Error;
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+    }
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(str) => {
+                BorrowedStrDeserializer::new(str).deserialize_any(visitor)
+            },
+            Cow::Owned(string) => visitor.visit_string(string),
+        }
+    }
+}
+
+struct EnumDeserializer<'de> {
+    variant: Cow<'de, str>,
+    obj: Option<&'de Object>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = super::Error;
+    type Variant = VariantDeserializer<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let deserializer = VariantDeserializer { obj: self.obj };
+        seed.deserialize(StrDeserializer(self.variant))
+            .map(|v| (v, deserializer))
+    }
+}
+
+struct VariantDeserializer<'de> {
+    obj: Option<&'de Object>,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = super::Error;
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.obj {
+            Some(obj) => seed.deserialize(BorrowedDeserializer { obj }),
+
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.obj {
+            Some(obj) => de::Deserializer::deserialize_map(
+                BorrowedDeserializer { obj },
+                visitor,
+            ),
+
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.obj {
+            Some(obj) => de::Deserializer::deserialize_seq(
+                BorrowedDeserializer { obj },
+                visitor,
+            ),
+
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn unit_variant(self) -> Result<()> {
+        match self.obj {
+            None => Ok(()),
+
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::NewtypeVariant,
+                &"unit variant",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{Array, Dictionary};
+
+    fn d<'de, T: Deserialize<'de>>(obj: &'de Object) -> Result<T> {
+        T::deserialize(BorrowedDeserializer::new(obj))
+    }
+
+    #[test]
+    fn deserialize_borrowed_str() {
+        let obj = Object::from("foo");
+        let str: &str = d(&obj).unwrap();
+        assert_eq!("foo", str);
+
+        let string = unsafe { obj.as_string_unchecked() };
+        assert_eq!(string.as_bytes().as_ptr(), str.as_ptr());
+    }
+
+    #[test]
+    fn deserialize_cow_str() {
+        #[derive(Deserialize)]
+        struct Foo<'a>(#[serde(borrow)] Cow<'a, str>);
+
+        let obj = Object::from("foo");
+        let Foo(cow) = d(&obj).unwrap();
+        assert!(matches!(cow, Cow::Borrowed("foo")));
+    }
+
+    #[test]
+    fn deserialize_invalid_utf8() {
+        #[derive(Deserialize)]
+        struct Foo<'a>(#[serde(borrow)] Cow<'a, str>);
+
+        let obj = Object::from(crate::String::from_bytes(b"foo\xFF"));
+        assert!(d::<&str>(&obj).is_err());
+
+        let Foo(cow) = d(&obj).unwrap();
+        assert!(matches!(cow, Cow::Owned(_)));
+        assert_eq!("foo\u{FFFD}", cow);
+    }
+
+    #[test]
+    fn deserialize_borrowed_map() {
+        let obj = Object::from(Dictionary::from_iter([
+            ("foo", Object::from("bar")),
+            ("baz", Object::from(Array::from_iter(["a", "b"]))),
+        ]));
+
+        let map: HashMap<&str, Object> = d(&obj).unwrap();
+        assert_eq!(Some(&Object::from("bar")), map.get("foo"));
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Foo<'a> {
+            foo: &'a str,
+            baz: Vec<&'a str>,
+        }
+
+        assert_eq!(Ok(Foo { foo: "bar", baz: vec!["a", "b"] }), d(&obj));
+    }
+
+    #[test]
+    fn deserialize_borrowed_enum() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Foo<'a> {
+            Bar,
+            Baz(&'a str),
+        }
+
+        let obj = Object::from("Bar");
+        assert_eq!(Ok(Foo::Bar), d(&obj));
+
+        let obj = Object::from(Dictionary::from_iter([("Baz", "qux")]));
+        assert_eq!(Ok(Foo::Baz("qux")), d(&obj));
+    }
+}
@@ -362,7 +362,6 @@ mod tests {
 
     fn d(value: impl Into<Object>) -> Result<Object> {
         Object::deserialize(Deserializer::new(value.into()))
-            .map_err(Into::into)
     }
 
     #[test]
@@ -403,4 +402,27 @@ mod tests {
         ]);
         assert_eq!(Ok(Object::from(map.clone())), d(map));
     }
+
+    #[test]
+    fn deserialize_array_and_dict() {
+        #[derive(Deserialize)]
+        struct Config {
+            list: Array,
+            opts: Dictionary,
+            name: crate::String,
+        }
+
+        let list = Array::from((1, "foo", false));
+        let opts = Dictionary::from_iter([("foo", 42)]);
+        let obj = Object::from(Dictionary::from_iter([
+            ("list", Object::from(list.clone())),
+            ("opts", Object::from(opts.clone())),
+            ("name", Object::from("bar")),
+        ]));
+
+        let config = Config::deserialize(Deserializer::new(obj)).unwrap();
+        assert_eq!(list, config.list);
+        assert_eq!(opts, config.opts);
+        assert_eq!("bar", config.name);
+    }
 }
@@ -3,10 +3,12 @@
 //!
 //! [Serde]: https://serde.rs/
 
+mod borrowed;
 mod de;
 mod error;
 mod ser;
 
+pub use borrowed::BorrowedDeserializer;
 pub use de::Deserializer;
 pub use error::{Error, Result};
-pub use ser::Serializer;
+pub use ser::{Case, Serializer};
@@ -2,18 +2,112 @@ use serde::ser::{self, Error};
 
 use super::Result;
 use crate::conversion::FromObject;
-use crate::Object;
+use crate::dictionary::KeyValuePair;
+use crate::{Array, Dictionary, Object, ObjectKind};
 
 /// A struct for serializing Rust values into Neovim `Object`s.
+///
+/// The default serializer maps Rust values to `Object`s as directly as
+/// possible. Its behaviour can be tweaked via the [`variant_case`],
+/// [`skip_none`] and [`int_keys_as_array`] methods to better match the
+/// conventions of the Neovim API.
+///
+/// [`variant_case`]: Serializer::variant_case
+/// [`skip_none`]: Serializer::skip_none
+/// [`int_keys_as_array`]: Serializer::int_keys_as_array
 #[non_exhaustive]
-#[derive(Debug)]
-pub struct Serializer;
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Serializer {
+    variant_case: Case,
+    skip_none: bool,
+    int_keys_as_array: bool,
+}
+
+/// The case unit enum variants are converted to when serialized.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Case {
+    /// Keeps the name of the variant as it is, e.g. `BufEnter`.
+    #[default]
+    AsIs,
+
+    /// `bufenter`.
+    Lowercase,
+
+    /// `BUFENTER`.
+    Uppercase,
+
+    /// `buf_enter`.
+    SnakeCase,
+
+    /// `buf-enter`.
+    KebabCase,
+}
+
+impl Case {
+    fn convert(self, variant: &str) -> std::borrow::Cow<'_, str> {
+        match self {
+            Self::AsIs => variant.into(),
+            Self::Lowercase => variant.to_lowercase().into(),
+            Self::Uppercase => variant.to_uppercase().into(),
+            Self::SnakeCase => Self::separate(variant, '_').into(),
+            Self::KebabCase => Self::separate(variant, '-').into(),
+        }
+    }
+
+    /// Lowercases the variant, separating its words with `sep`. A run of
+    /// uppercase letters is treated as a single word, so `HTTPServer`
+    /// becomes `http_server` and not `h_t_t_p_server`.
+    fn separate(variant: &str, sep: char) -> String {
+        let chars = variant.chars().collect::<Vec<_>>();
+        let mut out = String::with_capacity(variant.len() + 4);
+        for (idx, &ch) in chars.iter().enumerate() {
+            if ch.is_uppercase() && idx != 0 {
+                let prev = chars[idx - 1];
+                let next = chars.get(idx + 1);
+                let starts_word = !prev.is_uppercase()
+                    || matches!(next, Some(next) if next.is_lowercase());
+                if starts_word {
+                    out.push(sep);
+                }
+            }
+            out.extend(ch.to_lowercase());
+        }
+        out
+    }
+}
 
 impl Serializer {
-    #[allow(dead_code)]
-    #[allow(clippy::new_without_default)]
+    #[inline]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Sets the [`Case`] unit enum variants are converted to. The default is
+    /// [`Case::AsIs`].
+    #[inline]
+    pub fn variant_case(mut self, case: Case) -> Self {
+        self.variant_case = case;
+        self
+    }
+
+    /// Whether fields of structs and maps whose value is nil (e.g.
+    /// `Option::None`) should be omitted from the resulting `Dictionary`
+    /// instead of being serialized as `Object::nil()`. The default is
+    /// `false`.
+    #[inline]
+    pub fn skip_none(mut self, skip: bool) -> Self {
+        self.skip_none = skip;
+        self
+    }
+
+    /// Whether maps with integer keys should be serialized into `Array`s
+    /// instead of failing. Keys are treated as 1-based Lua indices, with any
+    /// gap being filled with `Object::nil()`. The default is `false`.
+    #[inline]
+    pub fn int_keys_as_array(mut self, as_array: bool) -> Self {
+        self.int_keys_as_array = as_array;
+        self
     }
 }
 
@@ -108,7 +202,7 @@ impl ser::Serializer for Serializer {
         _variant_index: u32,
         variant: &'static str,
     ) -> Result<Self::Ok> {
-        self.serialize_str(variant)
+        self.serialize_str(&self.variant_case.convert(variant))
     }
 
     #[inline]
@@ -140,7 +234,7 @@ impl ser::Serializer for Serializer {
     #[inline]
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
         let len = len.unwrap_or_default();
-        Ok(SerializeSeq { items: Vec::with_capacity(len) })
+        Ok(SerializeSeq { ser: self, items: Vec::with_capacity(len) })
     }
 
     #[inline]
@@ -171,7 +265,11 @@ impl ser::Serializer for Serializer {
     #[inline]
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
         let len = len.unwrap_or_default();
-        Ok(SerializeMap { key: None, pairs: Vec::with_capacity(len) })
+        Ok(SerializeMap {
+            ser: self,
+            key: None,
+            pairs: Vec::with_capacity(len),
+        })
     }
 
     #[inline]
@@ -196,6 +294,7 @@ impl ser::Serializer for Serializer {
 }
 
 pub struct SerializeSeq {
+    ser: Serializer,
     items: Vec<Object>,
 }
 
@@ -207,7 +306,7 @@ impl ser::SerializeSeq for SerializeSeq {
     where
         T: ser::Serialize + ?Sized,
     {
-        self.items.push(value.serialize(Serializer)?);
+        self.items.push(value.serialize(self.ser)?);
         Ok(())
     }
 
@@ -241,8 +340,14 @@ serialize_seq!(SerializeTupleStruct, serialize_field);
 serialize_seq!(SerializeTupleVariant, serialize_field);
 
 pub struct SerializeMap {
-    key: Option<crate::String>,
-    pairs: Vec<(crate::String, Object)>,
+    ser: Serializer,
+    key: Option<MapKey>,
+    pairs: Vec<(MapKey, Object)>,
+}
+
+enum MapKey {
+    String(crate::String),
+    Integer(crate::Integer),
 }
 
 impl ser::SerializeMap for SerializeMap {
@@ -253,10 +358,19 @@ impl ser::SerializeMap for SerializeMap {
     where
         T: ser::Serialize + ?Sized,
     {
-        let a = key.serialize(Serializer)?;
-        // TODO: don't unwrap
-        self.key =
-            Some(crate::String::from_object(a).map_err(super::Error::custom)?);
+        let key = key.serialize(self.ser)?;
+
+        let key = match key.kind() {
+            ObjectKind::Integer if self.ser.int_keys_as_array => {
+                MapKey::Integer(unsafe { key.as_integer_unchecked() })
+            },
+            _ => MapKey::String(
+                crate::String::from_object(key)
+                    .map_err(super::Error::custom)?,
+            ),
+        };
+
+        self.key = Some(key);
         Ok(())
     }
 
@@ -265,13 +379,66 @@ impl ser::SerializeMap for SerializeMap {
         T: ser::Serialize + ?Sized,
     {
         let key = self.key.take().expect("value serialized before key");
-        let obj = value.serialize(Serializer)?;
-        self.pairs.push((key, obj));
+        let obj = value.serialize(self.ser)?;
+        if !(self.ser.skip_none && obj.is_nil()) {
+            self.pairs.push((key, obj));
+        }
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok> {
-        Ok(Object::from_iter(self.pairs))
+        let mut indices = Vec::new();
+        let mut pairs = Vec::with_capacity(self.pairs.len());
+
+        for (key, obj) in self.pairs {
+            match key {
+                MapKey::String(key) => pairs.push((key, obj)),
+                MapKey::Integer(idx) => indices.push((idx, obj)),
+            }
+        }
+
+        // The results are built from their `KVec`s directly since collecting
+        // into a `Dictionary` or an `Array` would drop the nil values, which
+        // are kept if `skip_none` is false or used to fill gaps in the
+        // indices.
+        if indices.is_empty() {
+            let pairs = pairs
+                .into_iter()
+                .map(|(key, value)| KeyValuePair { key, value })
+                .collect();
+            return Ok(Dictionary(pairs).into());
+        }
+
+        if !pairs.is_empty() {
+            return Err(super::Error::custom(
+                "can't serialize a map with both string and integer keys",
+            ));
+        }
+
+        indices.sort_by_key(|(idx, _)| *idx);
+
+        let mut items = Vec::with_capacity(indices.len());
+
+        for (idx, obj) in indices {
+            let pos = usize::try_from(idx)
+                .ok()
+                .and_then(|i| i.checked_sub(1))
+                .ok_or_else(|| {
+                    super::Error::custom(format!(
+                        "array indices must be positive, got {idx}"
+                    ))
+                })?;
+            // Like in a Lua table, the last value wins if an index is
+            // repeated.
+            if pos < items.len() {
+                items[pos] = obj;
+            } else {
+                items.resize_with(pos, Object::nil);
+                items.push(obj);
+            }
+        }
+
+        Ok(Array(items.into_iter().collect()).into())
     }
 }
 
@@ -302,3 +469,131 @@ macro_rules! serialize_map {
 
 serialize_map!(SerializeStruct);
 serialize_map!(SerializeStructVariant);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    enum Event {
+        BufEnter,
+        UIEnter,
+        HTTPServer,
+        LspAttach2,
+    }
+
+    #[derive(Serialize)]
+    struct Opts {
+        foo: Option<u32>,
+        bar: Option<u32>,
+    }
+
+    #[test]
+    fn serialize_variant_case() {
+        let s = |case| {
+            Event::BufEnter.serialize(Serializer::new().variant_case(case))
+        };
+        assert_eq!(Ok(Object::from("BufEnter")), s(Case::AsIs));
+        assert_eq!(Ok(Object::from("bufenter")), s(Case::Lowercase));
+        assert_eq!(Ok(Object::from("BUFENTER")), s(Case::Uppercase));
+        assert_eq!(Ok(Object::from("buf_enter")), s(Case::SnakeCase));
+        assert_eq!(Ok(Object::from("buf-enter")), s(Case::KebabCase));
+    }
+
+    #[test]
+    fn serialize_variant_case_acronyms() {
+        let s = |event: Event, case| {
+            event.serialize(Serializer::new().variant_case(case))
+        };
+        assert_eq!(
+            Ok(Object::from("ui_enter")),
+            s(Event::UIEnter, Case::SnakeCase)
+        );
+        assert_eq!(
+            Ok(Object::from("http-server")),
+            s(Event::HTTPServer, Case::KebabCase)
+        );
+        assert_eq!(
+            Ok(Object::from("lsp_attach2")),
+            s(Event::LspAttach2, Case::SnakeCase)
+        );
+    }
+
+    #[test]
+    fn serialize_skip_none() {
+        let opts = Opts { foo: Some(42), bar: None };
+
+        let obj = opts.serialize(Serializer::new()).unwrap();
+        let dict = Dictionary::from_object(obj).unwrap();
+        assert_eq!(2, dict.len());
+        assert_eq!(Some(&Object::from(42)), dict.get("foo"));
+        assert_eq!(Some(&Object::nil()), dict.get("bar"));
+
+        let obj = opts.serialize(Serializer::new().skip_none(true)).unwrap();
+        let dict = Dictionary::from_object(obj).unwrap();
+        assert_eq!(1, dict.len());
+        assert_eq!(Some(&Object::from(42)), dict.get("foo"));
+        assert_eq!(None, dict.get("bar"));
+    }
+
+    #[test]
+    fn serialize_int_keys_as_array() {
+        let map = BTreeMap::from([(1, "foo"), (3, "baz")]);
+
+        assert!(map.serialize(Serializer::new()).is_err());
+
+        let obj = map.serialize(Serializer::new().int_keys_as_array(true));
+        let array = Array::from_object(obj.unwrap()).unwrap();
+        assert_eq!(3, array.len());
+        assert_eq!(
+            vec![Object::from("foo"), Object::nil(), Object::from("baz")],
+            array.into_iter().collect::<Vec<_>>()
+        );
+
+        let map = BTreeMap::from([(0, "foo")]);
+        let obj = map.serialize(Serializer::new().int_keys_as_array(true));
+        assert!(obj.is_err());
+    }
+
+    /// A map which can contain the same key more than once, unlike the ones
+    /// in `std`.
+    struct Pairs(Vec<(i64, &'static str)>);
+
+    impl Serialize for Pairs {
+        fn serialize<S: ser::Serializer>(
+            &self,
+            serializer: S,
+        ) -> std::result::Result<S::Ok, S::Error> {
+            use ser::SerializeMap;
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for (key, value) in &self.0 {
+                map.serialize_entry(key, value)?;
+            }
+            map.end()
+        }
+    }
+
+    #[test]
+    fn serialize_int_keys_as_array_repeated() {
+        let pairs =
+            Pairs(vec![(2, "bar"), (1, "foo"), (2, "baz"), (3, "qux")]);
+        let obj = pairs.serialize(Serializer::new().int_keys_as_array(true));
+        let expected = Array::from_iter(["foo", "baz", "qux"]);
+        assert_eq!(Ok(Object::from(expected)), obj);
+    }
+
+    #[test]
+    fn serialize_array_and_dict() {
+        let dict = Dictionary::from_iter([
+            ("foo", Object::from(Array::from((1, "bar", Object::nil())))),
+            ("bar", Object::from(crate::String::from_bytes(b"baz\xFF"))),
+        ]);
+
+        let obj = dict.serialize(Serializer::new());
+        assert_eq!(Ok(Object::from(dict)), obj);
+    }
+}
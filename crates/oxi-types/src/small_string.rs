@@ -0,0 +1,241 @@
+//! This module contains [`SmallString`], a string storing short contents
+//! inline.
+
+use core::ffi;
+
+use crate::{NonOwning, String};
+
+/// A string storing up to [`INLINE_CAPACITY`](Self::INLINE_CAPACITY) bytes
+/// inline, which can be passed to Neovim as a [`String`] without allocating.
+///
+/// [`String`] has the layout Neovim expects, so it always stores its
+/// contents on the heap. Most of the strings passed to the API are short
+/// names like the ones of variables, marks or modes, which a `SmallString`
+/// keeps on the stack and lends to Neovim via [`non_owning`]. Longer
+/// strings are stored in a Rust-owned buffer, which is freed on drop.
+///
+/// Like [`String`], it's not guaranteed to contain valid UTF-8, and it
+/// dereferences to its bytes.
+///
+/// [`non_owning`]: SmallString::non_owning
+#[derive(Clone)]
+pub struct SmallString(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    /// The bytes are followed by a null byte, like the ones of a [`String`].
+    Inline { len: u8, buf: [u8; SmallString::INLINE_CAPACITY + 1] },
+
+    /// Also ends with a null byte, which isn't part of the string.
+    Heap(Vec<u8>),
+}
+
+impl SmallString {
+    /// The maximum length of a string stored inline.
+    pub const INLINE_CAPACITY: usize = 22;
+
+    /// Creates a new, empty `SmallString`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(Repr::Inline { len: 0, buf: [0; Self::INLINE_CAPACITY + 1] })
+    }
+
+    /// Creates a `SmallString` by copying `bytes`, which only allocates if
+    /// they're longer than [`INLINE_CAPACITY`](Self::INLINE_CAPACITY).
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        if bytes.len() <= Self::INLINE_CAPACITY {
+            let mut buf = [0; Self::INLINE_CAPACITY + 1];
+            buf[..bytes.len()].copy_from_slice(bytes);
+            Self(Repr::Inline { len: bytes.len() as u8, buf })
+        } else {
+            let mut buf = Vec::with_capacity(bytes.len() + 1);
+            buf.extend_from_slice(bytes);
+            buf.push(0);
+            Self(Repr::Heap(buf))
+        }
+    }
+
+    /// Returns the bytes of the string, *not* including the final null
+    /// byte.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let bytes = self.as_bytes_with_nul();
+        &bytes[..bytes.len() - 1]
+    }
+
+    #[inline]
+    fn as_bytes_with_nul(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Inline { len, buf } => &buf[..*len as usize + 1],
+            Repr::Heap(buf) => buf,
+        }
+    }
+
+    /// Converts the string into a [`String`], which always allocates.
+    #[inline]
+    pub fn into_string(self) -> String {
+        String::from_bytes(self.as_bytes())
+    }
+
+    /// Returns `true` if the string has a length of zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the string is stored inline, i.e. if creating it
+    /// didn't allocate.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Returns the length of the string, *not* including the final null
+    /// byte.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns a non-owning [`String`] borrowing the contents of this
+    /// string, to be passed to Neovim.
+    #[inline]
+    pub fn non_owning(&self) -> NonOwning<'_, String> {
+        let bytes = self.as_bytes();
+        NonOwning::new(String {
+            data: bytes.as_ptr() as *mut ffi::c_char,
+            size: bytes.len(),
+        })
+    }
+}
+
+impl Default for SmallString {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for SmallString {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&std::string::String::from_utf8_lossy(self.as_bytes()))
+    }
+}
+
+impl core::ops::Deref for SmallString {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for SmallString {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl PartialEq for SmallString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for SmallString {}
+
+impl PartialEq<str> for SmallString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for SmallString {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl From<&[u8]> for SmallString {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<char> for SmallString {
+    #[inline]
+    fn from(ch: char) -> Self {
+        Self::from(ch.encode_utf8(&mut [0; 4]) as &str)
+    }
+}
+
+impl From<&str> for SmallString {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl From<SmallString> for String {
+    #[inline]
+    fn from(s: SmallString) -> Self {
+        s.into_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline() {
+        let s = SmallString::from("nvim_oxi");
+        assert!(s.is_inline());
+        assert_eq!(s, "nvim_oxi");
+        assert_eq!(8, s.len());
+
+        let max = "x".repeat(SmallString::INLINE_CAPACITY);
+        assert!(SmallString::from(max.as_str()).is_inline());
+
+        assert_eq!(SmallString::from('€'), "€");
+
+        assert!(SmallString::new().is_empty());
+        assert!(SmallString::new().is_inline());
+    }
+
+    #[test]
+    fn heap() {
+        let long = "x".repeat(SmallString::INLINE_CAPACITY + 1);
+        let s = SmallString::from(long.as_str());
+        assert!(!s.is_inline());
+        assert_eq!(s, long.as_str());
+    }
+
+    #[test]
+    fn non_owning_is_null_terminated() {
+        let long = "y".repeat(SmallString::INLINE_CAPACITY * 2);
+
+        for s in [SmallString::from(&b"a\xffb"[..]), SmallString::from(&*long)]
+        {
+            let nvim_str = s.non_owning();
+            assert_eq!(s.as_ptr() as *const ffi::c_char, nvim_str.as_ptr());
+            assert_eq!(s.as_bytes(), nvim_str.as_bytes());
+            assert_eq!(0, unsafe { *nvim_str.as_ptr().add(nvim_str.len()) });
+        }
+    }
+
+    #[test]
+    fn into_string() {
+        let s = SmallString::from("mode");
+        assert_eq!(String::from("mode"), String::from(s.clone()));
+        assert_eq!(String::from("mode"), s.into_string());
+    }
+}
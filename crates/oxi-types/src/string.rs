@@ -10,7 +10,13 @@ use crate::NonOwning;
 /// Binding to the string type used by Neovim.
 ///
 /// Unlike Rust's `String`, this type is not guaranteed to contain valid UTF-8
-/// byte sequences, it can contain null bytes, and it is null-terminated.
+/// byte sequences, it can contain null bytes, and it is null-terminated. It
+/// dereferences to its bytes, not including the final null byte.
+///
+/// Its layout is the one Neovim expects, which is why it always stores its
+/// contents on the heap, even for short strings. Use [`String::borrowed`] to
+/// pass a string literal to Neovim without allocating, or a
+/// [`SmallString`](crate::SmallString) for other short strings.
 //
 // https://github.com/neovim/neovim/blob/v0.9.0/src/nvim/api/private/defs.h#L79-L82
 #[derive(Eq, Ord, PartialOrd)]
@@ -35,6 +41,8 @@ impl core::fmt::Debug for String {
 }
 
 impl String {
+    /// Returns the bytes of the `String`, *not* including the final null
+    /// byte.
     #[inline]
     pub fn as_bytes(&self) -> &[u8] {
         if self.data.is_null() {
@@ -45,6 +53,20 @@ impl String {
         }
     }
 
+    /// Makes a non-owning `String` borrowing the bytes of `s`, without
+    /// allocating.
+    ///
+    /// Only null-terminated strings can be borrowed, since Neovim expects its
+    /// strings to be.
+    #[inline]
+    pub fn borrowed(s: &ffi::CStr) -> NonOwning<'_, Self> {
+        let bytes = s.to_bytes();
+        NonOwning::new(Self {
+            data: bytes.as_ptr() as *mut ffi::c_char,
+            size: bytes.len(),
+        })
+    }
+
     /// Returns a pointer to the `String`'s buffer.
     #[inline]
     pub fn as_ptr(&self) -> *const ffi::c_char {
@@ -97,6 +119,13 @@ impl String {
         NonOwning::new(Self { ..*self })
     }
 
+    /// Returns a `&str` borrowing the `String`'s buffer if it holds a valid
+    /// UTF-8 byte sequence, without copying it.
+    #[inline]
+    pub fn to_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(self.as_bytes())
+    }
+
     /// Converts the `String` into Rust's `std::string::String`. If it already
     /// holds a valid UTF-8 byte sequence no allocation is made. If it doesn't
     /// the `String` is copied and all invalid sequences are replaced with `�`.
@@ -123,6 +152,43 @@ impl Drop for String {
     }
 }
 
+impl core::ops::Deref for String {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for String {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl From<&[u8]> for String {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for String {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl From<std::string::String> for String {
+    #[inline]
+    fn from(s: std::string::String) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
 impl From<&str> for String {
     #[inline]
     fn from(s: &str) -> Self {
@@ -261,6 +327,19 @@ mod serde {
     use std::fmt;
 
     use serde::de::{self, Deserialize, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+
+    impl Serialize for super::String {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match self.to_str() {
+                Ok(s) => serializer.serialize_str(s),
+                Err(_) => serializer.serialize_bytes(self.as_bytes()),
+            }
+        }
+    }
 
     impl<'de> Deserialize<'de> for super::String {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -306,6 +385,30 @@ mod tests {
         assert_eq!(s.as_bytes(), &[b'h', b'e', b'l', b'l', b'o'][..]);
     }
 
+    #[test]
+    fn from_deref_bytes() {
+        let s = String::from(&b"foo\xffbar"[..]);
+        assert_eq!(7, s.len());
+        assert_eq!(Some(&0xff), s.get(3));
+        assert!(s.starts_with(b"foo"));
+        assert_eq!(s, String::from(b"foo\xffbar".to_vec()));
+        assert_eq!(s.as_ref(), &*s);
+    }
+
+    // C string literals require a more recent Rust version than the MSRV.
+    #[allow(clippy::manual_c_str_literals)]
+    #[test]
+    fn borrowed() {
+        let cstr = ffi::CStr::from_bytes_with_nul(b"nvo\0").unwrap();
+        let s = String::borrowed(cstr);
+        assert_eq!(cstr.as_ptr(), s.as_ptr());
+        assert_eq!(*s, "nvo");
+        assert_eq!(0, unsafe { *s.as_ptr().add(s.len()) });
+
+        let empty = ffi::CStr::from_bytes_with_nul(b"\0").unwrap();
+        assert!(String::borrowed(empty).is_empty());
+    }
+
     #[test]
     fn partial_eq() {
         let lhs = String::from("foo bar baz");
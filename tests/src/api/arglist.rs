@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use nvim_oxi::{
+    self as oxi,
+    api::{self, types::ArglistError, Error},
+};
+
+#[oxi::test]
+fn argadd_argdelete() {
+    assert_eq!(Ok(Vec::new()), api::arglist());
+
+    api::argadd(["foo.txt", "b%r.txt", "*.rs"]).unwrap();
+
+    let expected = ["foo.txt", "b%r.txt", "*.rs"].map(PathBuf::from).to_vec();
+    assert_eq!(Ok(expected), api::arglist());
+
+    api::argdelete(["*.rs", "foo.txt"]).unwrap();
+    assert_eq!(Ok(vec![PathBuf::from("b%r.txt")]), api::arglist());
+
+    assert!(api::argdelete(["foo.txt"]).is_err());
+}
+
+#[oxi::test]
+fn argument_navigation() {
+    assert_eq!(Err(Error::Arglist(ArglistError::Empty)), api::argnext());
+
+    api::argadd(["a.txt", "b.txt"]).unwrap();
+
+    api::argument(1).unwrap();
+    assert_eq!(Ok(1), api::argidx());
+    let name = api::Buffer::current().get_name().unwrap();
+    assert!(name.ends_with("b.txt"), "{name:?}");
+
+    assert_eq!(Err(Error::Arglist(ArglistError::AfterLast)), api::argnext());
+
+    api::argprev().unwrap();
+    assert_eq!(Ok(0), api::argidx());
+
+    assert_eq!(Err(Error::Arglist(ArglistError::BeforeFirst)), api::argprev());
+
+    assert_eq!(
+        Err(Error::Arglist(ArglistError::OutOfRange { index: 2, len: 2 })),
+        api::argument(2)
+    );
+
+    api::argnext().unwrap();
+    assert_eq!(Ok(1), api::argidx());
+}
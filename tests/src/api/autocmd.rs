@@ -1,6 +1,6 @@
 use all_asserts::*;
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, opts::*, Buffer};
+use nvim_oxi::api::{self, opts::*, types::AutocmdEvent, Buffer};
 
 #[oxi::test]
 fn clear_autocmds_current_buf() {
@@ -14,16 +14,13 @@ fn clear_autocmds_current_buf() {
 #[oxi::test]
 fn clear_autocmds_events() {
     let opts = ClearAutocmdsOpts::builder()
-        .events(["BufFilePre", "BufFilePost"])
+        .events([AutocmdEvent::BufFilePre, AutocmdEvent::BufFilePost])
         .build();
 
     assert_eq!(Ok(()), api::clear_autocmds(&opts));
 
     let opts = ClearAutocmdsOpts::builder()
-        .events(vec![
-            String::from("CompleteDone").as_ref(),
-            String::from("CursorHold").as_ref(),
-        ])
+        .events(vec![AutocmdEvent::CompleteDone, AutocmdEvent::CursorHold])
         .build();
 
     assert_eq!(Ok(()), api::clear_autocmds(&opts));
@@ -53,6 +50,38 @@ fn create_augroup() {
     assert_eq!(Ok(id), got);
 }
 
+#[oxi::test]
+fn augroup_add_clear_del() {
+    let group = api::create_augroup("Foo", &Default::default())
+        .expect("create_augroup failed");
+
+    let num_autocmds = |group: api::AuGroup| {
+        let opts = GetAutocmdsOpts::builder().group(group).build();
+        api::get_autocmds(&opts).map(|autocmds| autocmds.count())
+    };
+
+    let opts = CreateAutocmdOpts::builder()
+        .group("Bar")
+        .command("echo 'hi there'")
+        .build();
+
+    let id = group.add_autocmd([AutocmdEvent::BufEnter], &opts);
+    assert!(id.is_ok(), "{id:?}");
+    assert_eq!(Ok(1), num_autocmds(group));
+
+    // Re-creating the group clears its autocommands.
+    let group = api::create_augroup("Foo", &Default::default())
+        .expect("create_augroup failed");
+    assert_eq!(Ok(0), num_autocmds(group));
+
+    let _ = group.add_autocmd([AutocmdEvent::BufLeave], &opts).unwrap();
+    assert_eq!(Ok(()), group.clear());
+    assert_eq!(Ok(0), num_autocmds(group));
+
+    assert_eq!(Ok(()), group.del());
+    assert!(num_autocmds(group).is_err());
+}
+
 #[oxi::test]
 fn create_autocmd() {
     let opts = CreateAutocmdOpts::builder()
@@ -61,7 +90,7 @@ fn create_autocmd() {
         .callback(|_args| Ok::<_, oxi::Error>(false))
         .build();
 
-    let id = api::create_autocmd(["VimEnter"], &opts);
+    let id = api::create_autocmd([AutocmdEvent::VimEnter], &opts);
     assert!(id.is_ok(), "{id:?}");
 }
 
@@ -73,7 +102,7 @@ fn create_autocmd_buffer_n_patterns() {
         .patterns(["*.py", "*.ts"])
         .build();
 
-    let id = api::create_autocmd(["VimEnter"], &opts);
+    let id = api::create_autocmd([AutocmdEvent::VimEnter], &opts);
     assert!(id.is_err(), "{id:?}");
 }
 
@@ -96,16 +125,16 @@ fn exec_autocmds() {
         .once(true)
         .build();
 
-    let id = api::create_autocmd(["BufAdd"], &opts);
+    let id = api::create_autocmd([AutocmdEvent::BufAdd], &opts);
     assert!(id.is_ok(), "{id:?}");
 
     let opts = ExecAutocmdsOpts::builder().buffer(Buffer::current()).build();
 
-    let res = api::exec_autocmds(["BufAdd"], &opts);
+    let res = api::exec_autocmds([AutocmdEvent::BufAdd], &opts);
     assert_eq!(Ok(()), res);
     assert_eq!(1, *i.try_borrow().unwrap());
 
-    let res = api::exec_autocmds(["BufAdd"], &opts);
+    let res = api::exec_autocmds([AutocmdEvent::BufAdd], &opts);
     assert_eq!(Ok(()), res);
     // `i` should still be equal to 1 since `once` was set to `true`.
     assert_eq!(1, *i.try_borrow().unwrap());
@@ -118,11 +147,64 @@ fn get_autocmds() {
     assert_lt!(0, autocmds.collect::<Vec<_>>().len());
 }
 
+#[oxi::test]
+fn get_autocmds_filtered() {
+    let group = api::create_augroup("Foo", &Default::default())
+        .expect("create_augroup failed");
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(group)
+        .patterns(["*.rs"])
+        .desc("Formats Rust files")
+        .once(true)
+        .callback(|_args| Ok::<_, oxi::Error>(false))
+        .build();
+
+    let id = api::create_autocmd([AutocmdEvent::BufWritePre], &opts).unwrap();
+
+    let opts = CreateAutocmdOpts::builder()
+        .group(group)
+        .patterns(["*.rs"])
+        .command("echo 'saved'")
+        .build();
+
+    api::create_autocmd([AutocmdEvent::BufWritePost], &opts).unwrap();
+
+    let opts = GetAutocmdsOpts::builder()
+        .group(group)
+        .events([AutocmdEvent::BufWritePre])
+        .patterns(["*.rs"])
+        .build();
+
+    let infos = api::get_autocmds(&opts).unwrap().collect::<Vec<_>>();
+    assert_eq!(1, infos.len());
+
+    let infos = &infos[0];
+    assert_eq!(Some(id), infos.id);
+    assert_eq!(Some(group.id()), infos.group);
+    assert_eq!(Some(AutocmdEvent::BufWritePre), infos.autocmd_event());
+    assert_eq!("*.rs", infos.pattern);
+    assert_eq!(Some("Formats Rust files"), infos.desc.as_deref());
+    assert!(infos.once);
+    assert!(infos.has_callback);
+
+    let opts = GetAutocmdsOpts::builder()
+        .group(group)
+        .events([AutocmdEvent::BufWritePost])
+        .build();
+
+    let infos = api::get_autocmds(&opts).unwrap().next().unwrap();
+    assert!(!infos.has_callback);
+    assert_eq!("echo 'saved'", infos.command);
+
+    assert_eq!(Ok(()), group.del());
+}
+
 #[oxi::test]
 fn set_del_augroup_by_id() {
-    let id = api::create_augroup("Foo", &Default::default())
+    let group = api::create_augroup("Foo", &Default::default())
         .expect("create_augroup failed");
-    assert_eq!(Ok(()), api::del_augroup_by_id(id));
+    assert_eq!(Ok(()), api::del_augroup_by_id(group.id()));
 }
 
 #[oxi::test]
@@ -138,13 +220,39 @@ fn set_exec_del_autocmd() {
         .callback(|_args| Ok::<_, oxi::Error>(false))
         .build();
 
-    let id = api::create_autocmd(["BufAdd, BufDelete"], &opts)
-        .expect("create_autocmd failed");
+    let id = api::create_autocmd(
+        [AutocmdEvent::BufAdd, AutocmdEvent::BufDelete],
+        &opts,
+    )
+    .expect("create_autocmd failed");
 
     assert_eq!(
         Ok(()),
-        api::exec_autocmds(["BufAdd"], &ExecAutocmdsOpts::default())
+        api::exec_autocmds(
+            [AutocmdEvent::BufAdd],
+            &ExecAutocmdsOpts::default()
+        )
     );
 
     assert_eq!(Ok(()), api::del_autocmd(id));
 }
+
+#[oxi::test]
+fn autocmd_event_names() {
+    let opts = CreateAutocmdOpts::builder()
+        .callback(|_args| Ok::<_, oxi::Error>(false))
+        .build();
+
+    let id = api::create_autocmd([AutocmdEvent::LspAttach], &opts)
+        .expect("create_autocmd failed");
+
+    let got = api::get_autocmds(
+        &GetAutocmdsOpts::builder().events([AutocmdEvent::LspAttach]).build(),
+    )
+    .expect("couldn't get autocmds")
+    .map(|infos| infos.event)
+    .collect::<Vec<_>>();
+
+    assert_eq!(vec![AutocmdEvent::LspAttach.to_string()], got);
+    assert_eq!(Ok(()), api::del_autocmd(id));
+}
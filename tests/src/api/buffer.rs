@@ -1,6 +1,7 @@
 use all_asserts::*;
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, opts::*, types::*, Buffer};
+use nvim_oxi::api::options::{self, Scope};
+use nvim_oxi::api::{self, opts::*, types::*, Buffer, Window};
 
 #[oxi::test]
 fn attach() {
@@ -21,6 +22,131 @@ fn attach() {
     assert!(bytes_written.is_ok(), "{bytes_written:?}");
 }
 
+#[oxi::test]
+fn attach_on_bytes() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let opts = BufAttachOpts::builder()
+        .on_bytes({
+            let events = Rc::clone(&events);
+            move |event: BytesChangeEvent| {
+                events.borrow_mut().push(event);
+                Ok(false)
+            }
+        })
+        .build();
+
+    buf.attach(false, &opts).unwrap();
+    buf.set_text(1..=1, 1, 1, ["aa"]).unwrap();
+
+    let events = events.borrow();
+    assert_eq!(1, events.len());
+
+    let event = &events[0];
+    assert_eq!(buf, event.buffer);
+    assert_eq!(
+        (1, 1, 5),
+        (event.start_row, event.start_col, event.byte_offset)
+    );
+    assert_eq!(
+        (0, 0, 0),
+        (event.old_end_row, event.old_end_col, event.old_end_len)
+    );
+    assert_eq!(
+        (0, 2, 2),
+        (event.new_end_row, event.new_end_col, event.new_end_len)
+    );
+}
+
+#[oxi::test]
+fn attach_on_bytes_multiline_delete() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let opts = BufAttachOpts::builder()
+        .on_bytes({
+            let events = Rc::clone(&events);
+            move |event: BytesChangeEvent| {
+                events.borrow_mut().push(event);
+                Ok(false)
+            }
+        })
+        .build();
+
+    buf.attach(false, &opts).unwrap();
+    buf.set_lines(0..2, true, Vec::<&str>::new()).unwrap();
+
+    let events = events.borrow();
+    assert_eq!(1, events.len());
+
+    // Deleting "foo\nbar\n" replaces two whole rows with nothing.
+    let event = &events[0];
+    assert_eq!(buf.get_changedtick().unwrap(), event.changedtick);
+    assert_eq!(
+        (0, 0, 0),
+        (event.start_row, event.start_col, event.byte_offset)
+    );
+    assert_eq!(
+        (2, 0, 8),
+        (event.old_end_row, event.old_end_col, event.old_end_len)
+    );
+    assert_eq!(
+        (0, 0, 0),
+        (event.new_end_row, event.new_end_col, event.new_end_len)
+    );
+}
+
+#[oxi::test]
+fn attach_handle_detach() {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    let mut buf = Buffer::current();
+
+    let changes = Rc::new(Cell::new(0));
+    let detached = Rc::new(RefCell::new(None));
+
+    let opts = BufAttachOpts::builder()
+        .on_lines({
+            let changes = Rc::clone(&changes);
+            move |_args| {
+                changes.set(changes.get() + 1);
+                Ok(false)
+            }
+        })
+        .on_detach({
+            let detached = Rc::clone(&detached);
+            move |(_, buf): OnDetachArgs| {
+                *detached.borrow_mut() = Some(buf);
+                Ok(false)
+            }
+        })
+        .build();
+
+    let handle = buf.attach_scoped(false, &opts).unwrap();
+
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    assert_eq!(1, changes.get());
+
+    handle.detach();
+
+    buf.set_lines(.., true, ["bar"]).unwrap();
+    buf.set_lines(.., true, ["baz"]).unwrap();
+    assert_eq!(1, changes.get());
+    assert_eq!(Some(buf), *detached.borrow());
+}
+
 #[oxi::test]
 fn buf_call() {
     let buf = Buffer::current();
@@ -53,6 +179,40 @@ fn buf_create_del_user_command() {
     assert_eq!(Ok(()), buf.del_user_command("Bar"));
 }
 
+#[oxi::test]
+fn byte_to_pos_to_byte() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["foo", "héllo", ""]).unwrap();
+
+    assert_eq!(Ok((0, 0)), buf.byte_to_pos(0));
+    assert_eq!(Ok((0, 3)), buf.byte_to_pos(3));
+    assert_eq!(Ok((1, 3)), buf.byte_to_pos(7));
+    assert_eq!(Ok((2, 0)), buf.byte_to_pos(11));
+    assert_eq!(Ok((3, 0)), buf.byte_to_pos(12));
+    assert!(buf.byte_to_pos(13).is_err());
+
+    assert_eq!(Ok(7), buf.pos_to_byte(1, 3));
+    assert_eq!(Ok(10), buf.pos_to_byte(1, 6));
+    assert!(buf.pos_to_byte(1, 7).is_err());
+    assert!(buf.pos_to_byte(4, 0).is_err());
+}
+
+#[oxi::test]
+fn pos_to_byte_noeol() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    options::set("eol", false, Scope::Buffer(&buf)).unwrap();
+    options::set("fixeol", false, Scope::Buffer(&buf)).unwrap();
+
+    // The last line isn't followed by a line break.
+    assert_eq!(Ok(7), buf.get_offset(2));
+
+    assert_eq!(Ok(7), buf.pos_to_byte(1, 3));
+    assert!(buf.pos_to_byte(1, 4).is_err());
+    assert_eq!(Ok((1, 2)), buf.byte_to_pos(6));
+}
+
 #[oxi::test]
 fn get_changedtick() {
     let buf = Buffer::current();
@@ -132,11 +292,83 @@ fn set_get_del_lines() {
     assert_eq!(Ok(1), buf.line_count());
 }
 
+#[oxi::test]
+fn get_keymap_all_modes() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    let opts = SetKeymapOpts::default();
+
+    buf.set_keymap(Mode::Normal, "a", "b", &opts).unwrap();
+    buf.set_keymap(Mode::Insert, "c", "d", &opts).unwrap();
+
+    let keymaps = buf.get_keymap(Mode::All).unwrap().collect::<Vec<_>>();
+    assert_eq!(2, keymaps.len());
+    assert!(keymaps.iter().all(|keymap| keymap.buffer == Some(buf.clone())));
+    assert!(keymaps.iter().any(|keymap| keymap.lhs == "a"));
+    assert!(keymaps.iter().any(|keymap| keymap.lhs == "c"));
+}
+
+#[oxi::test]
+fn lines_chunked() {
+    let mut buf = Buffer::current();
+
+    let lines = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+    buf.set_lines(.., true, lines.iter().map(String::as_str)).unwrap();
+
+    let all = buf
+        .lines_chunked(.., 3)
+        .map(|line| line.unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(lines, all);
+
+    let some = buf
+        .lines_chunked(2..7, 4)
+        .map(|line| line.unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(&lines[2..7], &some[..]);
+
+    assert_eq!(0, buf.lines_chunked(20.., 4).count());
+}
+
+#[oxi::test]
+fn set_lines_chunked() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["first", "last"]).unwrap();
+
+    let lines = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+    let opts = SetLinesChunkedOpts::builder().chunk_size(3).build();
+
+    let written = buf
+        .set_lines_chunked(1..1, lines.iter().map(String::as_str), &opts)
+        .unwrap();
+    assert_eq!(10, written);
+    assert_eq!(12, buf.line_count().unwrap());
+
+    let all = buf
+        .get_lines(.., true)
+        .unwrap()
+        .map(|line| line.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!("first", all[0]);
+    assert_eq!(&lines[..], &all[1..11]);
+    assert_eq!("last", all[11]);
+
+    let reader = std::io::Cursor::new("foo\r\nbar\nbaz\n");
+    let written = buf.set_lines_from_reader(.., reader, &opts).unwrap();
+    assert_eq!(3, written);
+    assert_eq!(
+        vec!["foo", "bar", "baz"],
+        buf.get_lines(.., true)
+            .unwrap()
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+    );
+}
+
 #[oxi::test]
 fn buf_set_get_del_mark() {
     let mut buf = Buffer::current();
 
-    let res = buf.set_mark('a', 1, 0);
+    let res = buf.set_mark('a', 1, 0, &Default::default());
     assert_eq!(Ok(()), res);
 
     assert_eq!((1, 0), buf.get_mark('a').unwrap());
@@ -185,6 +417,29 @@ fn buf_set_get_del_var() {
     assert_eq!(Ok(()), buf.del_var("foo"));
 }
 
+#[oxi::test]
+fn fileformat() {
+    let buf = Buffer::current();
+    assert_eq!(Ok(FileFormat::Unix), buf.fileformat());
+
+    options::set("fileformat", "dos", Scope::Buffer(&buf)).unwrap();
+    assert_eq!(Ok(FileFormat::Dos), buf.fileformat());
+}
+
+#[oxi::test]
+fn buf_vars() {
+    let mut buf = Buffer::current();
+    buf.set_var("foo", 42).unwrap();
+
+    let names = buf
+        .vars()
+        .unwrap()
+        .map(|(name, _)| name.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    assert!(names.iter().any(|name| name == "foo"), "{names:?}");
+}
+
 #[oxi::test]
 fn set_get_name() {
     let mut buf = Buffer::current();
@@ -202,6 +457,7 @@ fn set_get_name() {
 }
 
 #[oxi::test]
+#[allow(deprecated)]
 fn buf_set_get_option() {
     let mut buf = Buffer::current();
 
@@ -211,3 +467,176 @@ fn buf_set_get_option() {
     buf.set_option("modified", false).unwrap();
     assert!(!buf.get_option::<bool>("modified").unwrap());
 }
+
+#[oxi::test]
+fn buf_display_info() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    let lines = (0..100).map(|i| i.to_string()).collect::<Vec<_>>();
+    buf.set_lines(.., true, lines.iter().map(String::as_str)).unwrap();
+
+    assert_eq!(Ok(false), buf.is_visible());
+    assert_eq!(Ok(Vec::new()), buf.display_info());
+
+    let mut win = Window::current();
+    win.set_buf(&buf).unwrap();
+    win.set_cursor(50, 0).unwrap();
+    api::command("normal! zt").unwrap();
+
+    assert_eq!(Ok(true), buf.is_visible());
+
+    let infos = buf.display_info().unwrap();
+    assert_eq!(1, infos.len());
+    assert_eq!(win, infos[0].window);
+    assert_eq!(49, infos[0].visible_lines.start);
+    assert!(infos[0].visible_lines.end > 49);
+}
+
+#[oxi::test]
+fn save_all_modified() {
+    let path = std::env::temp_dir().join("oxi-save-all.txt");
+
+    let mut named = api::create_buf(true, false).unwrap();
+    named.set_name(&path).unwrap();
+    named.set_lines(.., true, ["foo"]).unwrap();
+
+    let mut unnamed = api::create_buf(true, false).unwrap();
+    unnamed.set_lines(.., true, ["bar"]).unwrap();
+
+    let scratch = api::create_buf(true, true).unwrap();
+
+    assert_eq!(Ok(true), named.is_modified());
+    assert_eq!(Ok(true), named.has_unsaved_changes());
+    assert_eq!(Ok(false), scratch.has_unsaved_changes());
+
+    let modified = api::modified_buffers().collect::<Vec<_>>();
+    assert!(modified.contains(&named));
+    assert!(modified.contains(&unnamed));
+    assert!(!modified.contains(&scratch));
+
+    let failures = api::save_all(&Default::default()).unwrap();
+    assert_eq!(
+        vec![SaveFailure {
+            buffer: unnamed.clone(),
+            reason: SaveFailureReason::NoName
+        }],
+        failures
+    );
+    assert_eq!(Ok(false), named.is_modified());
+    assert_eq!("foo\n", std::fs::read_to_string(&path).unwrap());
+
+    let opts = SaveAllOpts::builder().only_named(true).build();
+    assert_eq!(Ok(vec![]), api::save_all(&opts));
+
+    std::fs::remove_file(path).unwrap();
+    options::set("modified", false, Scope::Buffer(&unnamed)).unwrap();
+}
+
+#[oxi::test]
+fn undo_break() {
+    let mut buf = Buffer::current();
+
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    buf.undo_break().unwrap();
+    buf.set_lines(.., true, ["bar"]).unwrap();
+
+    api::command("undo").unwrap();
+
+    let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec!["foo"], lines);
+}
+
+#[oxi::test]
+fn with_undolevels() {
+    let mut buf = Buffer::current();
+    let levels =
+        options::get::<i64>("undolevels", Scope::Buffer(&buf)).unwrap();
+
+    let res = buf.with_undolevels(-1, |buf| {
+        assert_eq!(
+            Ok(-1),
+            options::get::<i64>("undolevels", Scope::Buffer(buf))
+        );
+        buf.set_lines(.., true, ["foo"])
+    });
+    assert_eq!(Ok(()), res);
+
+    api::command("silent! undo").unwrap();
+
+    let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec!["foo"], lines);
+
+    // The previous value is restored even if the function fails.
+    let res = buf.with_undolevels(-1, |_| {
+        Err::<(), _>(api::Error::Other("foo".into()))
+    });
+    assert!(res.is_err());
+
+    assert_eq!(
+        Ok(levels),
+        options::get::<i64>("undolevels", Scope::Buffer(&buf))
+    );
+}
+
+#[oxi::test]
+fn invalid_buffer_error_kind() {
+    let err = Buffer::from(9999).line_count().unwrap_err();
+    assert_eq!(Some(oxi::ErrorKind::Validation), err.nvim_error_kind());
+}
+
+#[oxi::test(cmd = "set swapfile")]
+fn swap_info_and_recover() {
+    let path = std::env::temp_dir().join("oxi-buffer-swap-info.txt");
+    std::fs::write(&path, "foo\n").unwrap();
+
+    let opts = OpenFileOpts::default();
+    let (mut buf, _) = api::open_file(&path, OpenMode::Edit, &opts).unwrap();
+    buf.set_lines(.., true, ["bar"]).unwrap();
+    api::command("preserve").unwrap();
+
+    let swap_file = buf.swap_file().unwrap().unwrap();
+    let info = buf.swap_info().unwrap().unwrap();
+    assert_eq!(std::process::id(), info.pid);
+    assert!(info.dirty);
+
+    // Keep a copy of the swap file around after the buffer is wiped out, as
+    // if Neovim had crashed while editing the file.
+    let swap = std::fs::read(&swap_file).unwrap();
+    buf.delete(&BufDeleteOpts::builder().force(true).build()).unwrap();
+    std::fs::write(&swap_file, swap).unwrap();
+
+    let opts = OpenFileOpts::builder()
+        .on_swap_exists(SwapExistsAction::ReadOnly)
+        .build();
+    let (buf, _) = api::open_file(&path, OpenMode::Edit, &opts).unwrap();
+    assert_eq!(
+        vec!["foo"],
+        buf.get_lines(.., true).unwrap().collect::<Vec<_>>()
+    );
+
+    buf.recover(Some(&swap_file)).unwrap();
+    assert_eq!(
+        vec!["bar"],
+        buf.get_lines(.., true).unwrap().collect::<Vec<_>>()
+    );
+
+    assert!(api::fn_::swapinfo(&path).is_err());
+
+    buf.delete(&BufDeleteOpts::builder().force(true).build()).unwrap();
+    let _ = std::fs::remove_file(swap_file);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[oxi::test]
+fn invalid_buffer_error_source() {
+    use std::error::Error as _;
+
+    let err = oxi::Error::from(Buffer::from(9999).line_count().unwrap_err());
+
+    let api_err =
+        err.source().and_then(|err| err.downcast_ref::<api::Error>());
+    assert!(matches!(api_err, Some(api::Error::Nvim(_))), "{api_err:?}");
+
+    let nvim_err = api_err.unwrap().source().unwrap();
+    assert_eq!(err.to_string(), nvim_err.to_string());
+    assert!(nvim_err.source().is_none());
+}
@@ -0,0 +1,63 @@
+use nvim_oxi::{
+    self as oxi,
+    api::{
+        self,
+        completion::{self, CompletionItem, CompletionSource},
+        options::{self, Scope},
+        Buffer,
+        Window,
+    },
+    Dictionary,
+    Object,
+};
+
+struct Words(Vec<&'static str>);
+
+impl CompletionSource for Words {
+    fn complete(
+        &mut self,
+        base: &str,
+    ) -> Result<Vec<CompletionItem>, api::Error> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|word| word.starts_with(base))
+            .map(|word| CompletionItem::new(*word).menu("[words]"))
+            .collect())
+    }
+}
+
+#[oxi::test]
+fn completion_set_completefunc() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["let fo"]).unwrap();
+    Window::current().set_cursor(1, 6).unwrap();
+
+    let res =
+        completion::set_completefunc(&buf, Words(vec!["foo", "bar", "fob"]));
+    assert_eq!(Ok(()), res);
+
+    let completefunc =
+        options::get::<String>("completefunc", Scope::Buffer(&buf));
+    assert!(completefunc.unwrap().starts_with("v:lua.nvim_oxi_exprs."));
+
+    let bufnr = api::call_function::<_, i64>("bufnr", ("%",)).unwrap();
+    let call = |findstart: u8, base: &str| {
+        format!("nvim_oxi_exprs.completefunc_{bufnr}({findstart}, '{base}')")
+    };
+
+    // The completion starts at the keyword before the cursor.
+    let start = api::call_function::<_, i64>("luaeval", (call(1, ""),));
+    assert_eq!(Ok(4), start);
+
+    let items =
+        api::call_function::<_, Vec<Dictionary>>("luaeval", (call(0, "fo"),))
+            .unwrap();
+
+    let words = items.iter().map(|item| item.get("word")).collect::<Vec<_>>();
+    assert_eq!(
+        vec![Some(&Object::from("foo")), Some(&Object::from("fob"))],
+        words
+    );
+    assert_eq!(Some(&Object::from("[words]")), items[0].get("menu"));
+}
@@ -0,0 +1,84 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{
+    self,
+    exprs,
+    options::{self, Scope},
+    Buffer,
+    Window,
+};
+
+#[oxi::test]
+fn exprs_set_foldexpr() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "", "baz"]).unwrap();
+
+    let win = Window::current();
+    options::set("foldmethod", "expr", Scope::Window(&win)).unwrap();
+
+    let res =
+        exprs::set_foldexpr(&win, |lnum| Ok(if lnum < 3 { 1 } else { 0 }));
+    assert_eq!(Ok(()), res);
+
+    let foldlevel = |lnum| api::call_function::<_, i64>("foldlevel", (lnum,));
+    assert_eq!(Ok(1), foldlevel(1));
+    assert_eq!(Ok(1), foldlevel(2));
+    assert_eq!(Ok(0), foldlevel(4));
+}
+
+#[oxi::test]
+fn exprs_set_omnifunc() {
+    let buf = Buffer::current();
+    let bufnr = api::call_function::<_, i64>("bufnr", ("%",)).unwrap();
+
+    let res = exprs::set_omnifunc(&buf, |(findstart, base)| {
+        Ok(if findstart { 0.to_string() } else { format!("{base}!") })
+    });
+    assert_eq!(Ok(()), res);
+
+    let omnifunc = options::get::<String>("omnifunc", Scope::Buffer(&buf));
+    assert_eq!(
+        Ok(format!("v:lua.nvim_oxi_exprs.omnifunc_{}", bufnr)),
+        omnifunc
+    );
+
+    let complete = api::call_function::<_, String>(
+        "luaeval",
+        (format!("nvim_oxi_exprs.omnifunc_{}(0, 'foo')", bufnr),),
+    );
+    assert_eq!(Ok("foo!".to_owned()), complete);
+}
+
+#[oxi::test]
+fn exprs_replace_releases_previous() {
+    let buf = Buffer::current();
+    let bufnr = api::call_function::<_, i64>("bufnr", ("%",)).unwrap();
+
+    for suffix in ["?", "!"] {
+        let res = exprs::set_completefunc(&buf, move |(_, base)| {
+            Ok(format!("{base}{suffix}"))
+        });
+        assert_eq!(Ok(()), res);
+    }
+
+    let complete = api::call_function::<_, String>(
+        "luaeval",
+        (format!("nvim_oxi_exprs.completefunc_{}(0, 'foo')", bufnr),),
+    );
+    assert_eq!(Ok("foo!".to_owned()), complete);
+
+    // The table is the only owner of the closure.
+    let in_registry = api::call_function::<_, bool>(
+        "luaeval",
+        (format!(
+            "(function()
+                local fun = nvim_oxi_exprs.completefunc_{}
+                for _, value in pairs(debug.getregistry()) do
+                    if value == fun then return true end
+                end
+                return false
+            end)()",
+            bufnr
+        ),),
+    );
+    assert_eq!(Ok(false), in_registry);
+}
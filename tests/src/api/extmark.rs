@@ -67,6 +67,49 @@ fn get_extmarks() {
     assert_eq!(Some(ExtmarkVirtTextPosition::Overlay), infos.virt_text_pos);
 }
 
+#[cfg(not(feature = "neovim-0-8"))]
+#[oxi::test]
+fn get_extmarks_filtered() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+
+    let ns_id = api::create_namespace("Foo");
+
+    let opts = SetExtmarkOpts::builder()
+        .sign_text("F")
+        .sign_hl_group("Foo")
+        .priority(42)
+        .build();
+    let sign_id = buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+
+    let opts = SetExtmarkOpts::builder().virt_text([("bar", "Bar")]).build();
+    buf.set_extmark(ns_id, 1, 0, &opts).unwrap();
+    buf.set_extmark(ns_id, 2, 0, &opts).unwrap();
+
+    let start = ExtmarkPosition::ByTuple((0, 0));
+    let end = ExtmarkPosition::ByTuple((2, 0));
+
+    let opts = GetExtmarksOpts::builder().limit(2).build();
+    let extmarks = buf.get_extmarks(ns_id, start, end, &opts).unwrap();
+    assert_eq!(2, extmarks.count());
+
+    let opts =
+        GetExtmarksOpts::builder().details(true).ty(ExtmarkType::Sign).build();
+    let extmarks = buf
+        .get_extmarks(ns_id, start, end, &opts)
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(1, extmarks.len());
+
+    let (id, _, _, infos) = extmarks.into_iter().next().unwrap();
+    let infos = infos.unwrap();
+    assert_eq!(sign_id, id);
+    assert_eq!(Some(ns_id), infos.ns_id);
+    assert_eq!(Some(42), infos.priority);
+    assert_eq!(Some("F ".to_owned()), infos.sign_text);
+    assert_eq!(Some("Foo".to_owned()), infos.sign_hl_group);
+}
+
 #[oxi::test]
 fn get_namespaces() {
     let id = api::create_namespace("Foo");
@@ -118,6 +161,114 @@ fn set_decoration_provider() {
     assert!(bytes_written.is_ok(), "{bytes_written:?}");
 }
 
+#[oxi::test]
+fn set_decoration_provider_args() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+
+    let starts = Rc::new(RefCell::new(Vec::new()));
+    let bufs = Rc::new(RefCell::new(Vec::new()));
+    let wins = Rc::new(RefCell::new(Vec::new()));
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let ends = Rc::new(RefCell::new(Vec::new()));
+
+    let opts = DecorationProviderOpts::builder()
+        .on_start({
+            let starts = Rc::clone(&starts);
+            move |args: OnStartArgs| {
+                starts.borrow_mut().push(args.tick);
+                Ok(true)
+            }
+        })
+        .on_buf({
+            let bufs = Rc::clone(&bufs);
+            move |args: OnBufArgs| {
+                bufs.borrow_mut().push(args.buffer);
+                Ok(())
+            }
+        })
+        .on_win({
+            let wins = Rc::clone(&wins);
+            move |args: OnWinArgs| {
+                wins.borrow_mut().push(args);
+                Ok(true)
+            }
+        })
+        .on_line({
+            let lines = Rc::clone(&lines);
+            move |args: OnLineArgs| {
+                lines.borrow_mut().push(args);
+                Ok(())
+            }
+        })
+        .on_end({
+            let ends = Rc::clone(&ends);
+            move |args: OnEndArgs| {
+                ends.borrow_mut().push(args.tick);
+                Ok(())
+            }
+        })
+        .build();
+
+    let id = api::create_namespace("Foo");
+    api::set_decoration_provider(id, &opts).unwrap();
+    api::command("redraw!").unwrap();
+
+    // Every redraw cycle starts and ends with the same tick.
+    let starts = starts.borrow();
+    assert!(!starts.is_empty());
+    assert_eq!(*starts, *ends.borrow());
+
+    assert!(bufs.borrow().contains(&buf));
+
+    let win = api::Window::current();
+    let wins = wins.borrow();
+    let on_win = wins.iter().find(|args| args.window == win).unwrap();
+    assert_eq!(buf, on_win.buffer);
+    assert_eq!(0, on_win.toprow);
+    assert!(on_win.botrow >= 2, "{on_win:?}");
+
+    let rows = lines
+        .borrow()
+        .iter()
+        .filter(|args| args.window == win)
+        .inspect(|args| assert_eq!(buf, args.buffer))
+        .map(|args| args.row)
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(vec![0, 1, 2], rows.into_iter().collect::<Vec<_>>());
+}
+
+#[oxi::test]
+fn set_decoration_provider_function_guarded() {
+    use std::cell::Cell;
+    use std::convert::Infallible;
+    use std::rc::Rc;
+
+    let seen = Rc::new(Cell::new(None));
+
+    // Passing a `Function` instead of a closure goes through the default
+    // `ToFunction::to_decoration_callback`.
+    let on_win = oxi::Function::from_fn({
+        let seen = Rc::clone(&seen);
+        move |_: OnWinArgs| {
+            seen.set(api::current_decoration_callback());
+            Ok::<_, Infallible>(true)
+        }
+    });
+
+    let opts = DecorationProviderOpts::builder().on_win(on_win).build();
+
+    let id = api::create_namespace("Foo");
+    api::set_decoration_provider(id, &opts).unwrap();
+    api::command("redraw!").unwrap();
+
+    assert_eq!(Some(api::DecorationCallback::Win), seen.get());
+    assert_eq!(None, api::current_decoration_callback());
+}
+
 #[oxi::test]
 fn set_get_del_extmark() {
     let mut buf = Buffer::current();
@@ -167,3 +318,150 @@ fn set_get_del_extmark() {
     let res = buf.del_extmark(ns_id, extmark_id);
     assert_eq!(Ok(()), res);
 }
+
+#[oxi::test]
+fn set_extmark_conceal_hl_eol() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo bar"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let opts = SetExtmarkOpts::builder()
+        .conceal(Some('x'))
+        .end_col(3)
+        .hl_group("Conceal")
+        .hl_eol(true)
+        .build();
+
+    let concealed = buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+
+    let opts = SetExtmarkOpts::builder().conceal(None).end_col(7).build();
+    let hidden = buf.set_extmark(ns_id, 0, 4, &opts).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+
+    let (_, _, infos) =
+        buf.get_extmark_by_id(ns_id, concealed, &opts).unwrap();
+    let infos = infos.unwrap();
+    assert_eq!(Some("x".to_owned()), infos.conceal);
+    assert_eq!(Some("Conceal".to_owned()), infos.hl_group);
+    assert_eq!(Some(true), infos.hl_eol);
+    assert_eq!(None, infos.url);
+
+    let (_, _, infos) = buf.get_extmark_by_id(ns_id, hidden, &opts).unwrap();
+    assert_eq!(Some(String::new()), infos.unwrap().conceal);
+}
+
+#[cfg(feature = "neovim-nightly")]
+#[oxi::test]
+fn set_extmark_url() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["see the docs"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let opts = SetExtmarkOpts::builder()
+        .end_col(12)
+        .url("https://neovim.io/doc")
+        .build();
+    let id = buf.set_extmark(ns_id, 0, 8, &opts).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+    let (_, _, infos) = buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+    assert_eq!(Some("https://neovim.io/doc".to_owned()), infos.unwrap().url);
+}
+
+#[oxi::test]
+fn set_extmark_hl() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let opts = SetExtmarkOpts::hl("Search", (1, 2));
+    let id = buf.set_extmark(ns_id, 0, 1, &opts).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+    let (row, col, infos) = buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+    let infos = infos.unwrap();
+    assert_eq!((0, 1), (row, col));
+    assert_eq!(Some("Search".to_owned()), infos.hl_group);
+    assert_eq!(Some(1), infos.end_row);
+    assert_eq!(Some(2), infos.end_col);
+
+    buf.set_extmark(ns_id, 1, 0, &SetExtmarkOpts::DEFAULT).unwrap();
+}
+
+#[oxi::test]
+fn namespace_guard() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let ns = api::Namespace::anonymous();
+    assert_ne!(ns, api::Namespace::anonymous());
+
+    let count = |buf: &Buffer| {
+        buf.get_extmarks(
+            ns.id(),
+            ExtmarkPosition::ByTuple((0, 0)),
+            ExtmarkPosition::ByTuple((1, 0)),
+            &Default::default(),
+        )
+        .unwrap()
+        .count()
+    };
+
+    let guard = ns.scoped(buf.clone());
+    guard.add_highlight("IncSearch", 0, ..).unwrap();
+    guard.set_extmark(1, 0, &Default::default()).unwrap();
+    assert_eq!(2, count(&buf));
+
+    drop(guard);
+    assert_eq!(0, count(&buf));
+}
+
+#[oxi::test]
+fn namespace_set_for_window() {
+    let ns = api::Namespace::new("Foo");
+    let opts = SetHighlightOpts::builder().bold(true).build();
+    assert_eq!(Ok(()), ns.set_hl("Normal", &opts));
+    assert_eq!(Ok(()), ns.set_for_window(&api::Window::current()));
+}
+
+#[oxi::test]
+fn set_extmark_from_arena() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let mut arena = oxi::ObjectArena::new();
+    let mut ids = Vec::new();
+
+    for line in 0..3 {
+        let chunk = arena.array([line.to_string(), "Comment".to_owned()]);
+        let dict = arena.dictionary([("virt_text", arena.array([chunk]))]);
+
+        let opts = SetExtmarkOpts::from_dict(dict).unwrap();
+        ids.push(buf.set_extmark(ns_id, line, 0, &opts).unwrap());
+
+        arena.reset();
+    }
+
+    let dict = arena.dictionary([
+        ("hl_group", oxi::Object::from("Bar")),
+        ("end_col", oxi::Object::from(3)),
+    ]);
+    let opts = SetExtmarkOpts::from_dict(dict).unwrap();
+    ids.push(buf.set_extmark(ns_id, 0, 0, &opts).unwrap());
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+
+    let (_, _, infos) = buf.get_extmark_by_id(ns_id, ids[2], &opts).unwrap();
+    assert_eq!(
+        Some(vec![("2".into(), "Comment".into())]),
+        infos.unwrap().virt_text
+    );
+
+    let (_, _, infos) = buf.get_extmark_by_id(ns_id, ids[3], &opts).unwrap();
+    assert_eq!(Some(String::from("Bar")), infos.unwrap().hl_group);
+
+    let dict = arena.dictionary([("foo", 1)]);
+    assert!(SetExtmarkOpts::from_dict(dict).is_err());
+}
@@ -0,0 +1,43 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, fn_};
+
+#[oxi::test]
+fn expand() {
+    let buf = api::create_buf(true, false).unwrap();
+    api::set_current_buf(&buf).unwrap();
+    api::command("file foo.rs").unwrap();
+
+    assert_eq!(Ok("foo.rs".into()), fn_::expand("%"));
+    assert_eq!(Ok("rs".into()), fn_::expand("%:e"));
+}
+
+#[oxi::test]
+fn fnamemodify() {
+    let res = fn_::fnamemodify("/foo/bar/baz.txt", ":t:r");
+    assert_eq!(Ok("baz".into()), res);
+}
+
+#[oxi::test]
+fn getline() {
+    let mut buf = api::get_current_buf();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+
+    assert_eq!(Ok("foo".into()), fn_::getline(1));
+    assert_eq!(Ok("baz".into()), fn_::getline("$"));
+    assert_eq!(
+        Ok(vec!["bar".to_owned(), "baz".to_owned()]),
+        fn_::getlines(2, "$")
+    );
+}
+
+#[oxi::test]
+fn has() {
+    assert_eq!(Ok(true), fn_::has("nvim"));
+    assert_eq!(Ok(false), fn_::has("this_is_not_a_feature"));
+}
+
+#[oxi::test]
+fn systemlist() {
+    let res = fn_::systemlist(vec!["echo", "foo"]);
+    assert_eq!(Ok(vec!["foo".to_owned()]), res);
+}
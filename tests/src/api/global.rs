@@ -1,6 +1,13 @@
 use all_asserts::*;
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, opts::*, types::*, Buffer, Window};
+use nvim_oxi::api::{
+    self,
+    options::{self, Scope},
+    opts::*,
+    types::*,
+    Buffer,
+    Window,
+};
 
 #[oxi::test]
 fn chan_send_fail() {
@@ -38,6 +45,18 @@ fn eval_statusline() {
     assert_eq!(Ok("foo".into()), res.map(|infos| infos.str));
 }
 
+#[oxi::test]
+fn feedkeys_typed() {
+    let keys = api::Keys::new().char('i').str("a<CR>\\").cr().char('b').esc();
+    api::feedkeys_typed(keys, Mode::Normal);
+    api::command("call feedkeys('', 'x')").unwrap();
+
+    let buf = Buffer::current();
+    let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec!["a<CR>\\", "b"], lines);
+    assert_eq!(Mode::Normal, api::get_mode().unwrap().mode);
+}
+
 #[oxi::test]
 fn get_chan_info() {
     let res = api::get_chan_info(0);
@@ -59,7 +78,38 @@ fn get_context() {
     assert!(res.is_ok());
 }
 
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+#[oxi::test]
+fn get_hl() {
+    let opts =
+        SetHighlightOpts::builder().bold(true).foreground("#ff0000").build();
+    api::set_hl(0, "OxiGetHl", &opts).unwrap();
+
+    let opts = SetHighlightOpts::builder().link("OxiGetHl").build();
+    api::set_hl(0, "OxiGetHlLink", &opts).unwrap();
+
+    let opts = GetHighlightOpts::builder().name("OxiGetHl").build();
+    let infos = api::get_hl(0, &opts).unwrap().into_single().unwrap();
+    assert_eq!(Some(true), infos.bold);
+    assert_eq!(Some(0xff0000), infos.foreground);
+
+    let opts = GetHighlightOpts::builder().name("OxiGetHlLink").build();
+    let infos = api::get_hl(0, &opts).unwrap().into_single().unwrap();
+    assert_eq!(Some("OxiGetHl"), infos.link.as_deref());
+
+    let opts =
+        GetHighlightOpts::builder().name("OxiGetHlLink").link(false).build();
+    let infos = api::get_hl(0, &opts).unwrap().into_single().unwrap();
+    assert_eq!(None, infos.link);
+    assert_eq!(Some(true), infos.bold);
+
+    let all = api::get_hl(0, &Default::default()).unwrap().into_map().unwrap();
+    assert!(all.contains_key("OxiGetHl"));
+    assert!(all.contains_key("OxiGetHlLink"));
+}
+
 #[oxi::test]
+#[allow(deprecated)]
 fn get_highlights() {
     let (name, _) = api::get_color_map().next().unwrap();
     let id = api::get_hl_id_by_name(&*name).unwrap();
@@ -81,7 +131,7 @@ fn get_options() {
 
 #[oxi::test]
 fn get_option_info() {
-    api::set_option("number", true).unwrap();
+    options::set("number", true, Scope::Global).unwrap();
     assert!(api::get_option_info("number").is_ok());
 }
 
@@ -91,6 +141,7 @@ fn get_runtime_file() {
 }
 
 #[oxi::test]
+#[allow(deprecated)]
 fn hl_underline() {
     let opts = SetHighlightOpts::builder().underline(true).build();
     api::set_hl(0, "MatchParen", &opts).unwrap();
@@ -99,6 +150,18 @@ fn hl_underline() {
     assert_eq!(Some(true), infos.underline);
 }
 
+#[oxi::test]
+fn id() {
+    let arr = oxi::Array::from((1, "foo", oxi::Dictionary::new(), 1.5));
+    assert_eq!(oxi::Object::from(arr.clone()), api::id(&arr.clone().into()));
+    assert_eq!(arr, api::id_array(&arr));
+
+    let dict = oxi::Dictionary::from_iter([("foo", arr)]);
+    assert_eq!(dict, api::id_dictionary(&dict));
+
+    assert_eq!(0.1, api::id_float(0.1));
+}
+
 #[oxi::test]
 fn list_bufs() {
     let _ = api::create_buf(true, false);
@@ -129,6 +192,54 @@ fn list_wins() {
     );
 }
 
+#[oxi::test]
+fn notify_replace() {
+    api::command(
+        "lua vim.notify = function(_, _, opts) _G.opts = opts; return 42 end",
+    )
+    .unwrap();
+
+    let opts = NotifyOpts::builder().title("Foo").build();
+    let notification = api::notify("bar", LogLevel::Info, &opts).unwrap();
+    assert_eq!(oxi::Object::from(42), notification);
+
+    let opts = NotifyOpts::builder()
+        .replace(notification)
+        .timeout(std::time::Duration::from_secs(1))
+        .build();
+    let res = api::notify("baz", LogLevel::Info, &opts);
+    assert!(res.is_ok(), "{res:?}");
+
+    let replace =
+        api::call_function::<_, i64>("luaeval", ("_G.opts.replace",));
+    assert_eq!(Ok(42), replace);
+
+    let timeout =
+        api::call_function::<_, i64>("luaeval", ("_G.opts.timeout",));
+    assert_eq!(Ok(1000), timeout);
+}
+
+#[cfg(feature = "neovim-nightly")]
+#[oxi::test]
+fn redraw() {
+    let opts = RedrawOpts::builder()
+        .win(&Window::current())
+        .statusline(true)
+        .flush(true)
+        .build();
+
+    let res = api::redraw(&opts);
+
+    // `nvim__redraw()` is only available on recent nightlies.
+    let has_redraw = api::call_function::<_, bool>(
+        "luaeval",
+        ("vim.api.nvim__redraw ~= nil",),
+    )
+    .unwrap();
+
+    assert_eq!(has_redraw, res.is_ok(), "{res:?}");
+}
+
 #[oxi::test]
 fn set_get_del_current_line() {
     let res = api::set_current_line("foo");
@@ -159,11 +270,26 @@ fn set_get_del_keymap() {
     assert_eq!(Ok(()), res);
 }
 
+#[oxi::test]
+fn set_del_keymap_mode_all() {
+    let opts = SetKeymapOpts::default();
+
+    assert!(api::set_keymap(Mode::All, "a", "b", &opts).is_err());
+    assert!(!api::get_keymap(Mode::All).any(|keymap| keymap.lhs == "a"));
+
+    api::set_keymap(Mode::NormalVisualOperator, "a", "b", &opts).unwrap();
+    assert!(api::del_keymap(Mode::All, "a").is_err());
+
+    let keymap =
+        api::get_keymap(Mode::All).find(|keymap| keymap.lhs == "a").unwrap();
+    assert_eq!(Mode::NormalVisualOperator, keymap.mode);
+}
+
 #[oxi::test]
 fn set_get_del_mark() {
     let mut buf = Buffer::current();
 
-    let res = buf.set_mark('A', 1, 0);
+    let res = buf.set_mark('A', 1, 0, &Default::default());
     assert_eq!(Ok(()), res);
 
     assert_eq!(
@@ -175,6 +301,51 @@ fn set_get_del_mark() {
     assert_eq!(Ok(()), res);
 }
 
+#[oxi::test]
+fn batch() {
+    let buf = Buffer::current();
+
+    let results = api::batch(|batch| {
+        batch.set_var("foo", 42).set_lines(&buf, .., true, ["foo", "bar"]);
+        batch.call("nvim_get_var", ("foo",));
+    })
+    .unwrap();
+
+    assert_eq!(3, results.len());
+    assert_eq!(Ok(oxi::Object::from(42)), results[2]);
+    assert_eq!(Ok(2), buf.line_count());
+
+    let results = api::batch(|batch| {
+        batch.command("let g:bar = 1").command("foo").command("let g:baz = 1");
+    })
+    .unwrap();
+
+    assert_eq!(2, results.len());
+    assert!(results[1].is_err());
+    assert!(api::get_var::<u8>("baz").is_err());
+}
+
+#[oxi::test]
+fn set_file_mark() {
+    let buf = api::create_buf(true, false).unwrap();
+    buf.clone().set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let res = api::set_mark('B', &buf, 2, 1, &Default::default());
+    assert_eq!(Ok(()), res);
+
+    let mark = Mark::get('B', &Buffer::current()).unwrap().unwrap();
+    assert_eq!(MarkKind::File, mark.kind);
+    assert_eq!((&buf, 2, 1), (&mark.buffer, mark.row, mark.col));
+
+    api::del_mark('B').unwrap();
+    assert_eq!(Ok(None), Mark::get('B', &buf));
+
+    assert_eq!(Ok(()), mark.set());
+    assert_eq!(Ok(Some(mark)), Mark::get('B', &buf));
+
+    assert!(api::set_mark('b', &buf, 1, 0, &Default::default()).is_err());
+}
+
 #[oxi::test]
 fn set_get_del_var() {
     api::set_var("foo", 42).unwrap();
@@ -183,6 +354,7 @@ fn set_get_del_var() {
 }
 
 #[oxi::test]
+#[allow(deprecated)]
 fn set_get_option() {
     api::set_option("modified", true).unwrap();
     assert!(api::get_option::<bool>("modified").unwrap());
@@ -217,3 +389,120 @@ fn user_command_with_count() {
 
     assert!(res.is_ok(), "{res:?}");
 }
+
+#[oxi::test]
+fn user_command_typed_range() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let got = Rc::new(RefCell::new(None));
+
+    let opts = CreateCommandOpts::builder()
+        .addr(CommandAddr::Buffers)
+        .range(CommandRange::CurrentLine)
+        .build();
+
+    let cloned = Rc::clone(&got);
+    api::create_user_command(
+        "Foo",
+        move |args: CommandArgs| {
+            assert_eq!(Ok(CommandAddr::Buffers), args.addr());
+            *cloned.borrow_mut() = Some(args.typed_range()?);
+            Ok(())
+        },
+        &opts,
+    )
+    .unwrap();
+
+    let _ = api::create_buf(true, false).unwrap();
+    api::command("1,2Foo").unwrap();
+
+    assert_eq!(Some(Some(CommandArgsRange::Buffers(1..=2))), got.take());
+
+    let opts = CreateCommandOpts::builder().count(3).build();
+
+    let cloned = Rc::clone(&got);
+    api::create_user_command(
+        "Bar",
+        move |args: CommandArgs| {
+            *cloned.borrow_mut() = Some(args.typed_range()?);
+            Ok(())
+        },
+        &opts,
+    )
+    .unwrap();
+
+    api::command("Bar").unwrap();
+    assert_eq!(Some(Some(CommandArgsRange::Count(3))), got.take());
+
+    api::command("Bar 5").unwrap();
+    assert_eq!(Some(Some(CommandArgsRange::Count(5))), got.take());
+
+    api::del_user_command("Foo").unwrap();
+    api::del_user_command("Bar").unwrap();
+}
+
+#[oxi::test]
+fn user_command_complete_fn() {
+    let opts = CreateCommandOpts::builder()
+        .nargs(CommandNArgs::Any)
+        .complete_fn(|arg_lead, cmd_line, cursor_pos| {
+            assert_eq!("Foo b", cmd_line);
+            assert_eq!(5, cursor_pos);
+
+            ["bar", "baz", "foo"]
+                .into_iter()
+                .filter(|cand| cand.starts_with(arg_lead))
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .build();
+
+    api::create_user_command("Foo", ":", &opts).unwrap();
+
+    let completions = api::call_function::<_, Vec<String>>(
+        "getcompletion",
+        ("Foo b", "cmdline"),
+    );
+
+    assert_eq!(Ok(vec!["bar".to_owned(), "baz".to_owned()]), completions);
+
+    api::del_user_command("Foo").unwrap();
+}
+
+#[oxi::test]
+fn yank_into() {
+    use oxi::conversion::FromObject;
+
+    let res = api::yank_into(
+        'a',
+        ["foo", "barbaz"],
+        RegisterType::BlockwiseVisual(Some(4)),
+        false,
+    );
+    assert_eq!(Ok(()), res);
+
+    let regtype = api::call_function::<_, String>("getregtype", ('a',));
+    assert_eq!(Ok("\u{16}4".to_owned()), regtype);
+
+    let yanked = std::rc::Rc::new(std::cell::RefCell::new(None));
+
+    let opts = CreateAutocmdOpts::builder()
+        .callback({
+            let yanked = yanked.clone();
+            move |args: AutocmdCallbackArgs| {
+                let data = oxi::Dictionary::from_object(args.data)?;
+                *yanked.borrow_mut() = data.get("regname").cloned();
+                Ok::<_, api::Error>(true)
+            }
+        })
+        .build();
+    api::create_autocmd([AutocmdEvent::TextYankPost], &opts).unwrap();
+
+    let res = api::yank_into('b', ["foo"], RegisterType::Linewise, true);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Some(oxi::Object::from("b")), *yanked.borrow());
+
+    let reg = api::call_function::<_, String>("getreg", ('b',));
+    assert_eq!(Ok("foo\n".to_owned()), reg);
+}
@@ -0,0 +1,49 @@
+use nvim_oxi::{
+    self as oxi,
+    api::{history, types::HistoryKind},
+};
+
+#[oxi::test]
+fn history_append_get_remove() {
+    let kind = HistoryKind::Cmd;
+
+    history::clear(kind).unwrap();
+    assert_eq!(Ok(Vec::new()), history::entries(kind));
+    assert_eq!(Ok(None), history::get(kind, -1));
+
+    assert_eq!(Ok(true), history::append(kind, "echo 'foo'"));
+    assert_eq!(Ok(true), history::append(kind, "echo 'bar'"));
+    assert_eq!(Ok(false), history::append(kind, ""));
+
+    assert_eq!(Ok(Some("echo 'bar'".into())), history::get(kind, -1));
+    assert_eq!(Ok(Some("echo 'foo'".into())), history::get(kind, -2));
+    assert_eq!(
+        Ok(vec!["echo 'foo'".to_owned(), "echo 'bar'".to_owned()]),
+        history::entries(kind)
+    );
+
+    assert_eq!(Ok(true), history::remove(kind, -1));
+    assert_eq!(Ok(vec!["echo 'foo'".to_owned()]), history::entries(kind));
+
+    // The search history is separate from the command one.
+    assert_eq!(Ok(true), history::append(HistoryKind::Search, "foo"));
+    assert_eq!(Ok(vec!["echo 'foo'".to_owned()]), history::entries(kind));
+}
+
+#[oxi::test]
+fn history_remove_matching_clear() {
+    let kind = HistoryKind::Search;
+
+    history::clear(kind).unwrap();
+
+    for entry in ["foo", "bar", "foobar"] {
+        history::append(kind, entry).unwrap();
+    }
+
+    assert_eq!(Ok(true), history::remove_matching(kind, "^foo"));
+    assert_eq!(Ok(vec!["bar".to_owned()]), history::entries(kind));
+    assert_eq!(Ok(false), history::remove_matching(kind, "^foo"));
+
+    history::clear(kind).unwrap();
+    assert_eq!(Ok(None), history::last_index(kind));
+}
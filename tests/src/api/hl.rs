@@ -0,0 +1,53 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, hl, opts::*, Buffer};
+
+#[oxi::test]
+fn intern_caches_id() {
+    let id = hl::intern("DiagnosticWarn").unwrap();
+    assert_eq!(Ok(id.as_u32()), api::get_hl_id_by_name("DiagnosticWarn"));
+    assert_eq!(Ok(id), hl::intern("DiagnosticWarn"));
+
+    api::command("doautocmd ColorScheme").unwrap();
+    assert_eq!(Ok(id), hl::intern("DiagnosticWarn"));
+}
+
+#[oxi::test]
+fn intern_cleared_on_colorscheme() {
+    hl::intern("DiagnosticWarn").unwrap();
+    assert!(hl::is_interned("DiagnosticWarn"));
+
+    // Another plugin registering the augroup nvim-oxi used to share.
+    let opts = CreateAugroupOpts::builder().clear(true).build();
+    api::create_augroup("nvim-oxi-hl-intern", &opts).unwrap();
+
+    api::command("colorscheme default").unwrap();
+    assert!(!hl::is_interned("DiagnosticWarn"));
+
+    let opts = GetAutocmdsOpts::builder()
+        .events([api::types::AutocmdEvent::ColorScheme])
+        .build();
+    let groups = api::get_autocmds(&opts)
+        .unwrap()
+        .filter_map(|infos| infos.group_name)
+        .collect::<Vec<_>>();
+    assert_eq!(1, groups.len(), "{groups:?}");
+    assert!(groups[0].starts_with("nvim-oxi-hl-intern-"));
+}
+
+#[oxi::test]
+fn intern_invalid_name() {
+    assert!(hl::intern("Not Valid").is_err());
+}
+
+#[oxi::test]
+fn set_extmark_with_interned_hl() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo"]).unwrap();
+
+    let ns_id = api::create_namespace("Foo");
+    let warn = hl::intern("DiagnosticWarn").unwrap();
+
+    let opts = SetExtmarkOpts::builder().end_col(3).hl_group(warn).build();
+    let res = buf.set_extmark(ns_id, 0, 0, &opts);
+    assert!(res.is_ok(), "{res:?}");
+}
@@ -0,0 +1,123 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use nvim_oxi as oxi;
+use nvim_oxi::api::{
+    self,
+    keymap::{self, KeymapOpts},
+    types::Mode,
+    Buffer,
+};
+
+#[oxi::test]
+fn keymap_set_del_multiple_modes() {
+    let opts = KeymapOpts::builder().desc("foo").build();
+    let res = keymap::set([Mode::Normal, Mode::Visual], "<F2>", "bar", &opts);
+    assert_eq!(Ok(()), res);
+
+    for mode in [Mode::Normal, Mode::Visual] {
+        let keymaps = api::get_keymap(mode)
+            .filter(|keymap| keymap.lhs == "<F2>")
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, keymaps.len());
+        assert_eq!(Some("bar"), keymaps[0].rhs.as_deref());
+        assert!(keymaps[0].noremap);
+    }
+
+    let res = keymap::del([Mode::Normal, Mode::Visual], "<F2>", None);
+    assert_eq!(Ok(()), res);
+
+    assert!(!api::get_keymap(Mode::Normal).any(|keymap| keymap.lhs == "<F2>"));
+}
+
+#[oxi::test]
+fn keymap_set_buffer_local_callback() {
+    let buf = Buffer::current();
+    let count = Rc::new(Cell::new(0));
+
+    let opts = KeymapOpts::builder().buffer(buf.clone()).build();
+
+    let counter = Rc::clone(&count);
+    let res = keymap::set(
+        [Mode::Normal, Mode::Insert],
+        "<F3>",
+        move |()| {
+            counter.set(counter.get() + 1);
+            Ok(())
+        },
+        &opts,
+    );
+    assert_eq!(Ok(()), res);
+
+    let keymaps = buf.get_keymap(Mode::Normal).unwrap().collect::<Vec<_>>();
+    assert_eq!(1, keymaps.len());
+    assert!(keymaps[0].callback.is_some());
+
+    api::command(r#"execute "normal \<F3>""#).unwrap();
+    assert_eq!(1, count.get());
+
+    let res = keymap::del([Mode::Normal, Mode::Insert], "<F3>", Some(&buf));
+    assert_eq!(Ok(()), res);
+}
+
+#[oxi::test]
+fn keymap_scoped_to_filetype() {
+    let buf = Buffer::current();
+    let num_keymaps =
+        |buf: &Buffer| buf.get_keymap(Mode::Normal).unwrap().count();
+
+    api::command("setlocal filetype=rust").unwrap();
+
+    let keymaps = buf.scoped_keymaps(None).unwrap();
+    let res = keymaps.set([Mode::Normal], "<F4>", "bar", &Default::default());
+    assert_eq!(Ok(()), res);
+    assert_eq!(1, num_keymaps(&buf));
+
+    // Setting the same filetype again doesn't end the scope.
+    api::command("setlocal filetype=rust").unwrap();
+    assert!(!keymaps.has_ended());
+    assert_eq!(1, num_keymaps(&buf));
+
+    api::command("setlocal filetype=lua").unwrap();
+    assert!(keymaps.has_ended());
+    assert_eq!(0, num_keymaps(&buf));
+
+    let res = keymaps.set([Mode::Normal], "<F4>", "bar", &Default::default());
+    assert!(res.is_err());
+}
+
+#[oxi::test]
+fn keymap_tree() {
+    let opts = KeymapOpts::builder().desc("Find files").build();
+    keymap::set([Mode::Normal], "<Space>ff", ":Files<CR>", &opts).unwrap();
+
+    let opts = KeymapOpts::builder().desc("Find buffers").build();
+    keymap::set([Mode::Normal], "<Space>fb", ":Buffers<CR>", &opts).unwrap();
+
+    let opts = KeymapOpts::builder()
+        .buffer(Buffer::current())
+        .desc("Find local files")
+        .build();
+    keymap::set([Mode::Normal], "<Space>ff", ":Local<CR>", &opts).unwrap();
+
+    let tree = keymap::tree(Mode::Normal, "<space>").unwrap();
+    assert_eq!("<Space>", tree.lhs);
+
+    let find = tree.get("f").unwrap();
+    assert!(find.is_prefix());
+    assert_eq!(None, find.desc());
+    assert_eq!(
+        vec!["b", "f"],
+        find.children.iter().map(|n| &*n.key).collect::<Vec<_>>()
+    );
+
+    let files = find.get("f").unwrap();
+    assert!(files.is_leaf());
+    assert_eq!("<Space>ff", files.lhs);
+    assert_eq!(Some("Find local files"), files.desc());
+
+    let tree = keymap::tree(Mode::Normal, "<Space>fb").unwrap();
+    assert!(tree.is_leaf());
+    assert_eq!(Some("Find buffers"), tree.desc());
+}
@@ -1,8 +1,23 @@
+mod arglist;
 mod autocmd;
 mod buffer;
+mod completion;
+mod exprs;
 mod extmark;
+mod fn_;
 mod global;
+mod history;
+mod hl;
+mod keymap;
+mod open_file;
+mod options;
+mod semantic;
+mod state;
 mod tabpage;
+mod terminal;
+mod text;
+mod viewport;
 mod vimscript;
 mod win_config;
 mod window;
+mod writer;
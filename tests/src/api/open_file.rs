@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::PathBuf;
+
+use nvim_oxi::{
+    self as oxi,
+    api::{
+        self,
+        options::{self, Scope},
+        opts::{BufDeleteOpts, OpenFileOpts},
+        types::{OpenFileError, OpenMode, SwapExistsAction},
+        Buffer,
+        Error,
+        Window,
+    },
+};
+
+#[oxi::test]
+fn open_file_edit_and_reuse_window() {
+    let path = std::env::temp_dir().join("oxi-open-file-edit.txt");
+    fs::write(&path, "foo\nbar\n").unwrap();
+
+    let opts = OpenFileOpts::builder().cursor(2, 1).build();
+    let (buf, win) = api::open_file(&path, OpenMode::Edit, &opts).unwrap();
+    assert_eq!(Buffer::current(), buf);
+    assert_eq!(Some(Window::current()), win);
+    assert_eq!(Ok((2, 1)), Window::current().get_cursor());
+
+    api::command("vsplit | enew").unwrap();
+    let opts = OpenFileOpts::builder().reuse_window(true).build();
+    let (_, reused) = api::open_file(&path, OpenMode::Edit, &opts).unwrap();
+    assert_eq!(win, reused);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[oxi::test]
+fn open_file_badd() {
+    let path = std::env::temp_dir().join("oxi-open-file-badd.txt");
+    fs::write(&path, "foo\n").unwrap();
+
+    let current = Buffer::current();
+    let opts = OpenFileOpts::default();
+    let (buf, win) = api::open_file(&path, OpenMode::BAdd, &opts).unwrap();
+    assert_eq!(None, win);
+    assert_eq!(current, Buffer::current());
+    assert!(buf.is_loaded());
+    let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec!["foo"], lines);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[oxi::test(cmd = "set swapfile")]
+fn open_file_swap_exists() {
+    let path = std::env::temp_dir().join("oxi-open-file-swap.txt");
+    fs::write(&path, "foo\n").unwrap();
+
+    let opts = OpenFileOpts::default();
+    let (buf, _) = api::open_file(&path, OpenMode::Edit, &opts).unwrap();
+
+    // Keep a copy of the swap file around after the buffer is wiped out, as
+    // if Neovim had crashed while editing the file.
+    let swap_file = api::call_function::<_, String>("swapname", (&buf,))
+        .map(PathBuf::from)
+        .unwrap();
+    let swap = fs::read(&swap_file).unwrap();
+    let opts = BufDeleteOpts::builder().force(true).build();
+    buf.delete(&opts).unwrap();
+    fs::write(&swap_file, swap).unwrap();
+
+    let opts = OpenFileOpts::default();
+    assert_eq!(
+        Err(Error::OpenFile(OpenFileError::SwapExists {
+            swap_file: swap_file.clone()
+        })),
+        api::open_file(&path, OpenMode::Edit, &opts)
+    );
+
+    let opts = OpenFileOpts::builder()
+        .on_swap_exists(SwapExistsAction::ReadOnly)
+        .build();
+    let (buf, _) = api::open_file(&path, OpenMode::Edit, &opts).unwrap();
+    assert_eq!(Ok(true), options::get("readonly", Scope::Buffer(&buf)));
+
+    fs::remove_file(swap_file).unwrap();
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,64 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{
+    options::{self, Scope},
+    types::{Flags, ShortmessFlag},
+    Buffer,
+    Window,
+};
+
+#[oxi::test]
+fn set_get_global() {
+    options::set("shiftwidth", 7u8, Scope::Global).unwrap();
+    assert_eq!(Ok(7), options::get::<u8>("shiftwidth", Scope::Global));
+}
+
+#[oxi::test]
+fn set_get_buffer_local() {
+    let buf = Buffer::current();
+    let scope = Scope::Buffer(&buf);
+
+    options::set("modified", true, scope).unwrap();
+    assert_eq!(Ok(true), options::get::<bool>("modified", scope));
+
+    options::set("modified", false, scope).unwrap();
+    assert_eq!(Ok(false), options::get::<bool>("modified", scope));
+}
+
+#[oxi::test]
+fn set_get_window_local() {
+    let win = Window::current();
+    let scope = Scope::Window(&win);
+
+    options::set("spell", true, scope).unwrap();
+    assert_eq!(Ok(true), options::get::<bool>("spell", scope));
+
+    options::set("spell", false, scope).unwrap();
+    assert_eq!(Ok(false), options::get::<bool>("spell", scope));
+}
+
+#[oxi::test]
+fn get_other_buffer() {
+    let other = oxi::api::create_buf(true, false).unwrap();
+    options::set("filetype", "rust", Scope::Buffer(&other)).unwrap();
+
+    let current = Buffer::current();
+    assert_ne!(current, other);
+    assert_eq!(
+        Ok(String::from("rust")),
+        options::get::<String>("filetype", Scope::Buffer(&other))
+    );
+}
+
+#[oxi::test]
+fn update_flags() {
+    options::set("shortmess", "filnxtToOF", Scope::Global).unwrap();
+
+    let res = options::update_flags("shortmess", Scope::Global, |flags| {
+        flags.insert(ShortmessFlag::NoIntro);
+        flags.remove(ShortmessFlag::TruncateFile);
+    });
+    assert_eq!(Ok(()), res);
+
+    let flags = options::get::<Flags>("shortmess", Scope::Global).unwrap();
+    assert_eq!("filnxToOFI", flags.as_str());
+}
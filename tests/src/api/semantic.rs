@@ -0,0 +1,69 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::opts::GetExtmarksOpts;
+use nvim_oxi::api::semantic::{Legend, SemanticHighlighter, TokensEdit};
+use nvim_oxi::api::types::{ExtmarkPosition, OffsetEncoding};
+use nvim_oxi::api::Buffer;
+
+/// Returns the `(line, start_col, end_col, hl_group)` of the highlights.
+fn highlights(
+    highlighter: &SemanticHighlighter,
+) -> Vec<(usize, usize, usize, String)> {
+    let opts = GetExtmarksOpts::builder().details(true).build();
+
+    let mut highlights = highlighter
+        .buffer()
+        .get_extmarks(
+            highlighter.namespace().id(),
+            ExtmarkPosition::ByTuple((0, 0)),
+            ExtmarkPosition::ByTuple((usize::MAX, usize::MAX)),
+            &opts,
+        )
+        .unwrap()
+        .map(|(_, line, col, infos)| {
+            let infos = infos.unwrap();
+            (line, col, infos.end_col.unwrap(), infos.hl_group.unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    highlights.sort();
+    highlights
+}
+
+#[oxi::test]
+fn semantic_highlighter() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["fn foo()", "let bär = 1;"]).unwrap();
+
+    let legend = Legend::new(["function", "variable"], ["readonly"]);
+
+    let mut highlighter =
+        SemanticHighlighter::new(buf, legend, OffsetEncoding::Utf16);
+
+    highlighter.set_tokens(vec![0, 3, 3, 0, 0, 1, 4, 3, 1, 1]).unwrap();
+
+    assert_eq!(
+        vec![
+            (0, 3, 6, "@lsp.type.function".into()),
+            (1, 4, 8, "@lsp.mod.readonly".into()),
+            (1, 4, 8, "@lsp.type.variable".into()),
+            (1, 4, 8, "@lsp.typemod.variable.readonly".into()),
+        ],
+        highlights(&highlighter)
+    );
+
+    // Remove the modifier of the second token.
+    highlighter
+        .apply_edits([TokensEdit { start: 9, delete_count: 1, data: vec![0] }])
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            (0, 3, 6, "@lsp.type.function".into()),
+            (1, 4, 8, "@lsp.type.variable".into()),
+        ],
+        highlights(&highlighter)
+    );
+
+    highlighter.clear().unwrap();
+    assert!(highlights(&highlighter).is_empty());
+}
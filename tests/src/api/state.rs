@@ -0,0 +1,75 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, Buffer, State, StateScope};
+
+#[oxi::test]
+fn state_get_set_update() {
+    let state = State::new(StateScope::Global, "oxi_state", 1u32).unwrap();
+    assert_eq!(Ok(1), state.get());
+    assert_eq!(Ok(1), api::get_var::<u32>("oxi_state"));
+
+    state.set(2).unwrap();
+    assert_eq!(Ok(2), api::get_var::<u32>("oxi_state"));
+
+    state.update(|n| *n *= 10).unwrap();
+    assert_eq!(Ok(20), state.get());
+}
+
+#[oxi::test]
+fn state_keeps_existing_value() {
+    let mut buf = Buffer::current();
+    buf.set_var("oxi_state", vec!["foo"]).unwrap();
+
+    let state = State::<Vec<String>>::new(
+        StateScope::Buffer(buf.clone()),
+        "oxi_state",
+        Vec::new(),
+    )
+    .unwrap();
+
+    assert_eq!(Ok(vec![String::from("foo")]), state.get());
+}
+
+#[oxi::test]
+fn state_notifies_subscribers() {
+    let state = State::new(StateScope::Global, "oxi_state", 0u32).unwrap();
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let also_seen = Rc::clone(&seen);
+    state.subscribe(move |&n| also_seen.borrow_mut().push(n));
+
+    state.set(1).unwrap();
+
+    // Changes made from Lua are picked up after firing the autocommand.
+    api::command(
+        "lua vim.g.oxi_state = 2; vim.api.nvim_exec_autocmds('User', { \
+         pattern = 'g:oxi_state' })",
+    )
+    .unwrap();
+
+    assert_eq!(vec![1, 2], *seen.borrow());
+}
+
+#[oxi::test]
+fn state_subscribe_from_subscriber() {
+    let state = State::new(StateScope::Global, "oxi_state", 0u32).unwrap();
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    state.subscribe({
+        let state = state.clone();
+        let seen = Rc::clone(&seen);
+        move |&n| {
+            let seen = Rc::clone(&seen);
+            state.subscribe(move |&m| seen.borrow_mut().push((n, m)));
+        }
+    });
+
+    state.set(1).unwrap();
+    assert!(seen.borrow().is_empty());
+
+    state.set(2).unwrap();
+    assert_eq!(vec![(1, 2)], *seen.borrow());
+}
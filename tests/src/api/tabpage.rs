@@ -1,4 +1,17 @@
-use nvim_oxi::{self as oxi, api::TabPage};
+use nvim_oxi::{
+    self as oxi,
+    api::{self, TabPage},
+};
+
+#[oxi::test]
+fn tabpage_call() {
+    let tab = TabPage::current();
+    api::command("tabnew").unwrap();
+
+    let res = tab.call(|_| TabPage::current().get_number());
+    assert_eq!(Ok(1), res);
+    assert_eq!(Ok(2), TabPage::current().get_number());
+}
 
 #[oxi::test]
 fn get_list_wins() {
@@ -33,3 +46,17 @@ fn tabpage_set_get_del_var() {
     assert_eq!(Ok(42), tab.get_var("foo"));
     assert_eq!(Ok(()), tab.del_var("foo"));
 }
+
+#[oxi::test]
+fn tabpage_vars() {
+    let mut tab = TabPage::current();
+    tab.set_var("foo", 42).unwrap();
+
+    let names = tab
+        .vars()
+        .unwrap()
+        .map(|(name, _)| name.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    assert!(names.iter().any(|name| name == "foo"), "{names:?}");
+}
@@ -0,0 +1,30 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, opts::*, Buffer, Terminal};
+
+#[oxi::test]
+fn terminal_open() {
+    let buf = api::create_buf(true, true).unwrap();
+    let term = Terminal::open(buf.clone(), &Default::default()).unwrap();
+    assert_eq!(&buf, term.buffer());
+    assert!(term.channel() > 0);
+
+    let from_buf = Terminal::from_buffer(buf).unwrap();
+    assert_eq!(term, from_buf);
+
+    assert_eq!(Ok(()), term.send("foo\r\n"));
+}
+
+#[oxi::test]
+fn terminal_from_non_terminal_buffer() {
+    assert!(Terminal::from_buffer(Buffer::current()).is_err());
+}
+
+#[oxi::test]
+fn terminal_on_output() {
+    let buf = api::create_buf(true, true).unwrap();
+    let opts = OpenTermOpts::builder().build();
+    let term = Terminal::open(buf, &opts).unwrap();
+
+    let res = term.on_output(|_output| Ok(true));
+    assert_eq!(Ok(()), res);
+}
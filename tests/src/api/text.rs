@@ -0,0 +1,33 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{
+    options::{self, Scope},
+    text,
+    Buffer,
+};
+
+#[oxi::test]
+fn is_keyword_char() {
+    let buf = Buffer::current();
+
+    assert_eq!(Ok(true), text::is_keyword_char('a', &buf));
+    assert_eq!(Ok(true), text::is_keyword_char('_', &buf));
+    assert_eq!(Ok(false), text::is_keyword_char('-', &buf));
+
+    options::set("iskeyword", "@,48-57,_,-", Scope::Buffer(&buf)).unwrap();
+    assert_eq!(Ok(true), text::is_keyword_char('-', &buf));
+}
+
+#[oxi::test]
+fn word_at() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["let foo-bar = 1;"]).unwrap();
+
+    assert_eq!(Ok(Some(("foo".into(), 4..7))), text::word_at(&buf, 0, 5));
+    assert_eq!(Ok(Some(("bar".into(), 8..11))), text::word_at(&buf, 0, 8));
+    assert_eq!(Ok(None), text::word_at(&buf, 0, 7));
+    assert_eq!(Ok(None), text::word_at(&buf, 0, 42));
+
+    options::set("iskeyword", "@,48-57,_,-", Scope::Buffer(&buf)).unwrap();
+
+    assert_eq!(Ok(Some(("foo-bar".into(), 4..11))), text::word_at(&buf, 0, 7));
+}
@@ -0,0 +1,61 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{
+    self,
+    opts::*,
+    types::*,
+    viewport::{self, Decoration},
+    Buffer,
+    Window,
+};
+
+#[oxi::test]
+fn viewport_render_visible_lines() {
+    let mut buf = Buffer::current();
+    let lines = (0..1000).map(|n| n.to_string()).collect::<Vec<_>>();
+    buf.set_lines(.., true, lines.iter().map(String::as_str)).unwrap();
+
+    let ns_id = api::create_namespace("viewport");
+    let opts = SetExtmarkOpts::builder().hl_group("Search").end_col(1).build();
+
+    let viewport = viewport::render(ns_id, &buf, move |visible_lines| {
+        visible_lines
+            .map(|line| Decoration::new(line, 0, opts.clone()))
+            .collect()
+    })
+    .unwrap();
+
+    let count_extmarks = |buf: &Buffer| {
+        buf.get_extmarks(
+            ns_id,
+            ExtmarkPosition::ByTuple((0, 0)),
+            ExtmarkPosition::ByTuple((999, 0)),
+            &Default::default(),
+        )
+        .unwrap()
+        .count()
+    };
+
+    let rendered = count_extmarks(&buf);
+    assert!(rendered > 0);
+    assert!(rendered < 1000);
+
+    let mut win = Window::current();
+    win.set_cursor(1000, 0).unwrap();
+    viewport.refresh().unwrap();
+
+    let extmarks = buf
+        .get_extmarks(
+            ns_id,
+            ExtmarkPosition::ByTuple((0, 0)),
+            ExtmarkPosition::ByTuple((999, 0)),
+            &Default::default(),
+        )
+        .unwrap()
+        .collect::<Vec<_>>();
+
+    assert!(extmarks.iter().all(|&(_, row, ..)| row > 0));
+    assert!(extmarks.iter().any(|&(_, row, ..)| row == 999));
+
+    viewport.stop().unwrap();
+    assert_eq!(0, count_extmarks(&buf));
+}
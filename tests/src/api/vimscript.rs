@@ -45,6 +45,7 @@ fn eval() {
 }
 
 #[oxi::test]
+#[allow(deprecated)]
 fn exec() {
     let no_op = api::exec(":", true);
     assert_eq!(Ok(None), no_op);
@@ -53,6 +54,37 @@ fn exec() {
     assert_eq!(Ok(Some("2".into())), add);
 }
 
+#[cfg(not(feature = "neovim-0-8"))]
+#[oxi::test]
+fn exec2() {
+    let opts = ExecOpts::builder().output(true).build();
+
+    let no_op = api::exec2(":", &opts).unwrap();
+    assert_eq!(Some(String::new()), no_op.output);
+
+    let add = api::exec2(":echo 1 + 1", &opts).unwrap();
+    assert_eq!(Some("2".into()), add.output);
+
+    let opts = ExecOpts::builder().output(false).build();
+    let add = api::exec2(":echo 1 + 1", &opts).unwrap();
+    assert_eq!(None, add.output);
+}
+
+#[cfg(not(feature = "neovim-0-8"))]
+#[oxi::test]
+fn exec_capture() {
+    assert_eq!(Ok(String::new()), api::exec_capture(":"));
+    assert_eq!(Ok("2".into()), api::exec_capture("echo 1 + 1"));
+    assert_eq!(
+        Ok("foo\nbar".into()),
+        api::exec_capture("echo 'foo' | echo 'bar'")
+    );
+
+    let err = api::exec_capture("echo 'foo' | echoerr 'bar'").unwrap_err();
+    assert!(err.to_string().contains("bar"), "{err}");
+    assert!(api::exec_capture("NotACommand").is_err());
+}
+
 #[cfg(not(feature = "neovim-0-8"))]
 #[oxi::test]
 fn parse_cmd_basic() {
@@ -77,7 +109,9 @@ fn parse_cmd_basic() {
     assert_eq!(false, mods.browse);
     assert_eq!(false, mods.confirm);
     assert_eq!(false, mods.emsg_silent);
+    assert_eq!(None, mods.filter);
     assert_eq!(false, mods.hide);
+    assert_eq!(false, mods.horizontal);
     assert_eq!(false, mods.keepalt);
     assert_eq!(false, mods.keepjumps);
     assert_eq!(false, mods.keepmarks);
@@ -88,6 +122,7 @@ fn parse_cmd_basic() {
     assert_eq!(false, mods.silent);
     assert_eq!(None, mods.split);
     assert_eq!(-1, mods.tab);
+    assert_eq!(false, mods.unsilent);
     assert_eq!(false, mods.vertical);
 
     assert_eq!(Some(CommandNArgs::Any), infos.nargs);
@@ -95,6 +130,33 @@ fn parse_cmd_basic() {
     assert_eq!(None, infos.range);
 }
 
+#[cfg(not(feature = "neovim-0-8"))]
+#[oxi::test]
+fn parse_cmd_mods_roundtrip() {
+    let opts = ParseCmdOpts::builder().build();
+
+    let infos =
+        api::parse_cmd("unsilent filter! /foo/ horizontal ls", &opts).unwrap();
+
+    let mods = infos.mods.clone().unwrap();
+    assert!(mods.unsilent);
+    assert!(mods.horizontal);
+    assert_eq!(
+        Some(FilterModifier { pattern: "foo".into(), force: true }),
+        mods.filter
+    );
+
+    let expected = CommandModifiers::builder()
+        .unsilent(true)
+        .filter("foo", true)
+        .horizontal(true)
+        .build();
+    assert_eq!(expected, mods);
+
+    let opts = CmdOpts::builder().output(true).build();
+    assert!(api::cmd(&infos, &opts).is_ok());
+}
+
 #[oxi::test]
 fn parse_expression_basic() {
     let res = api::parse_expression("lua print('a')", "", true);
@@ -224,3 +286,42 @@ fn parse_expression_basic() {
 
     assert_eq!(14, len);
 }
+
+#[oxi::test]
+fn cmd_builder() {
+    let res = api::CmdBuilder::new()
+        .cmd("split")
+        .mods(|mods| mods.silent(true).split(SplitModifier::BotRight))
+        .exec();
+    assert_eq!(Ok(()), res);
+    assert_eq!(2, api::list_wins().len());
+
+    let infos = api::CmdBuilder::new()
+        .cmd("echo")
+        .bang(false)
+        .mods(|mods| mods.silent(true))
+        .mods(|mods| mods.verbose(1))
+        .build();
+    let mods = infos.mods.unwrap();
+    assert!(mods.silent);
+    assert_eq!(1, mods.verbose);
+    assert_eq!(-1, mods.tab);
+}
+
+#[oxi::test]
+fn cmd_builder_mods_dont_open_tabpage() {
+    let res = api::CmdBuilder::new()
+        .cmd("new")
+        .mods(|mods| mods.silent(true))
+        .exec();
+    assert_eq!(Ok(()), res);
+    assert_eq!(1, api::list_tabpages().len());
+    assert_eq!(2, api::list_wins().len());
+}
+
+#[cfg(not(feature = "neovim-0-8"))]
+#[oxi::test]
+fn cmd_builder_output() {
+    let output = api::CmdBuilder::new().cmd("echo").args(["1 + 1"]).output();
+    assert_eq!(Ok("2".to_owned()), output);
+}
@@ -1,5 +1,13 @@
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, types::*, Buffer, Window};
+use nvim_oxi::api::{
+    self,
+    options::{self, Scope},
+    types::*,
+    Buffer,
+    FloatLayer,
+    FloatStack,
+    Window,
+};
 
 #[oxi::test]
 fn open_win_empty_config() {
@@ -39,6 +47,20 @@ fn open_win_basic_config() {
     assert_eq!(config.col, got.col);
 }
 
+#[oxi::test]
+fn open_win_float_at_cursor() {
+    let buf = api::create_buf(true, true).unwrap();
+    let config = WindowConfig::float_at_cursor(20, 3);
+    let win = api::open_win(&buf, false, &config).unwrap();
+
+    let got = win.get_config().unwrap();
+    assert_eq!(Some(WindowRelativeTo::Cursor), got.relative);
+    assert_eq!(Some(20), got.width);
+    assert_eq!(Some(3), got.height);
+
+    win.close(true).unwrap();
+}
+
 #[oxi::test]
 fn open_win_full_config() {
     let buf = api::create_buf(true, true).unwrap();
@@ -109,3 +131,86 @@ fn set_config() {
 
     assert_eq!(Ok(()), win.set_config(&config));
 }
+
+#[oxi::test]
+fn float_stack() {
+    let buf = api::create_buf(false, true).unwrap();
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Editor)
+        .height(10)
+        .width(5)
+        .row(1)
+        .col(1)
+        .zindex(1000)
+        .build();
+
+    let mut stack = FloatStack::new();
+    stack.blend(FloatLayer::Background, 30).unwrap();
+
+    let tooltip =
+        stack.open(&buf, false, FloatLayer::Tooltip, &config).unwrap();
+    let first = stack.open(&buf, false, FloatLayer::Content, &config).unwrap();
+    let second =
+        stack.open(&buf, false, FloatLayer::Content, &config).unwrap();
+    let backdrop =
+        stack.open(&buf, false, FloatLayer::Background, &config).unwrap();
+
+    let zindex = |win: &Window| win.get_config().unwrap().zindex.unwrap();
+
+    assert!(zindex(&backdrop) < zindex(&first));
+    assert!(zindex(&first) < zindex(&second));
+    assert!(zindex(&second) < zindex(&tooltip));
+    assert_eq!(
+        Ok(30),
+        options::get::<u8>("winblend", Scope::Window(&backdrop))
+    );
+
+    stack.raise(&first).unwrap();
+    assert!(zindex(&second) < zindex(&first));
+    assert!(zindex(&first) < zindex(&tooltip));
+
+    assert_eq!(Some(FloatLayer::Content), stack.layer_of(&first));
+
+    stack.close_all(true).unwrap();
+    assert!(!tooltip.is_valid());
+    assert_eq!(0, stack.windows(FloatLayer::Content).count());
+}
+
+#[cfg(any(feature = "neovim-0-9", feature = "neovim-nightly"))]
+#[oxi::test]
+fn open_win_title_footer() {
+    let buf = api::create_buf(true, true).unwrap();
+
+    let mut builder = WindowConfig::builder();
+    builder
+        .relative(WindowRelativeTo::Editor)
+        .height(10)
+        .width(20)
+        .row(1)
+        .col(1)
+        .border(WindowBorder::Single)
+        .title([("foo", "Title")].into_iter().collect::<WindowTitle>())
+        .title_pos(WindowTitlePosition::Center);
+
+    #[cfg(feature = "neovim-nightly")]
+    builder
+        .footer([("bar", "Comment")].into_iter().collect::<WindowTitle>())
+        .footer_pos(WindowTitlePosition::Right);
+
+    let config = builder.build();
+
+    let win = api::open_win(&buf, false, &config).unwrap();
+
+    let got = win.get_config();
+    assert!(got.is_ok(), "{got:?}");
+
+    let got = got.unwrap();
+    assert_eq!(config.title, got.title);
+    assert_eq!(config.title_pos, got.title_pos);
+
+    #[cfg(feature = "neovim-nightly")]
+    {
+        assert_eq!(config.footer, got.footer);
+        assert_eq!(config.footer_pos, got.footer_pos);
+    }
+}
@@ -1,11 +1,33 @@
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, types::*, Buffer, TabPage, Window};
+use nvim_oxi::api::{
+    self,
+    options::{self, Scope},
+    opts,
+    types::*,
+    Buffer,
+    PreviewPlacement,
+    TabPage,
+    Window,
+};
+use nvim_oxi::{Array, Object};
 
 #[oxi::test]
 fn win_call() {
     let win = Window::current();
     let res = win.call(|_| Ok(()));
     assert_eq!(Ok(()), res);
+
+    api::command("split").unwrap();
+    let other = Window::current();
+    let expected = win.clone();
+
+    // The result doesn't have to be convertible to an `Object`.
+    let res = win.call(move |_| Ok(Some(Window::current() == expected)));
+    assert_eq!(Ok(Some(true)), res);
+    assert_eq!(other, Window::current());
+
+    let res = win.call(|_| Err::<(), _>(api::Error::Other("foo".into())));
+    assert_eq!(Err(api::Error::Other("foo".into())), res);
 }
 
 #[oxi::test]
@@ -32,6 +54,48 @@ fn win_get_number() {
     assert_eq!(Ok(1), Window::current().get_number());
 }
 
+#[oxi::test]
+fn get_set_conceal() {
+    let mut win = Window::current();
+    assert_eq!(
+        Ok((ConcealLevel::Shown, ConcealCursor::default())),
+        win.get_conceal()
+    );
+
+    let cursor =
+        ConcealCursor { normal: true, insert: true, ..Default::default() };
+    assert_eq!(Ok(()), win.set_conceal(ConcealLevel::Hidden, cursor));
+
+    assert_eq!(Ok(3), options::get::<u8>("conceallevel", Scope::Window(&win)));
+    assert_eq!(
+        Ok("ni".to_owned()),
+        options::get::<String>("concealcursor", Scope::Window(&win))
+    );
+    assert_eq!(Ok((ConcealLevel::Hidden, cursor)), win.get_conceal());
+}
+
+#[oxi::test]
+fn get_set_fillchars_listchars() {
+    let mut win = Window::current();
+
+    let mut fillchars = win.get_fillchars().unwrap();
+    fillchars.eob = Some(' ');
+    fillchars.vert = Some('|');
+    assert_eq!(Ok(()), win.set_fillchars(&fillchars));
+    assert_eq!(Ok(fillchars), win.get_fillchars());
+
+    let mut listchars = win.get_listchars().unwrap();
+    listchars.tab = Some(">-".into());
+    listchars.trail = Some('~');
+    assert_eq!(Ok(()), win.set_listchars(&listchars));
+    assert_eq!(Ok(listchars), win.get_listchars());
+    assert_eq!(
+        Ok(true),
+        options::get::<String>("listchars", Scope::Window(&win))
+            .map(|value| value.contains("tab:>-"))
+    );
+}
+
 #[oxi::test]
 fn get_position() {
     assert_eq!(Ok((0, 0)), Window::current().get_position());
@@ -84,6 +148,26 @@ fn get_tabpage() {
     assert_eq!(Ok(TabPage::current()), Window::current().get_tabpage())
 }
 
+#[oxi::test]
+fn get_set_current_line() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "ee bar"]).unwrap();
+
+    let mut win = Window::current();
+    win.set_cursor(2, 3).unwrap();
+
+    let res = win.set_current_line("éé bar", KeepCursor::Column);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok("éé bar".into()), win.current_line());
+    assert_eq!(Ok((2, 5)), win.get_cursor());
+
+    let res = win.set_current_line("ab", KeepCursor::Clamp);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok("ab".into()), win.current_line());
+    // In normal mode the cursor can't go past the last character.
+    assert_eq!(Ok((2, 1)), win.get_cursor());
+}
+
 #[oxi::test]
 fn set_get_cursor() {
     let mut buf = Buffer::current();
@@ -103,6 +187,7 @@ fn set_get_cursor() {
 }
 
 #[oxi::test]
+#[allow(deprecated)]
 fn win_set_get_option() {
     let mut win = Window::current();
 
@@ -120,3 +205,218 @@ fn win_set_get_del_var() {
     assert_eq!(Ok(42), win.get_var("foo"));
     assert_eq!(Ok(()), win.del_var("foo"));
 }
+
+#[oxi::test]
+fn win_vars() {
+    let mut win = Window::current();
+    win.set_var("foo", 42).unwrap();
+
+    let names = win
+        .vars()
+        .unwrap()
+        .map(|(name, _)| name.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    assert!(names.iter().any(|name| name == "foo"), "{names:?}");
+}
+
+#[oxi::test]
+fn open_preview_split() {
+    let current = Window::current();
+
+    let preview = Window::open_preview(
+        ["foo", "bar"],
+        PreviewPlacement::Split(Some(5)),
+        false,
+    )
+    .unwrap();
+
+    let win = preview.window().clone();
+    let buf = preview.buffer().clone();
+
+    assert_eq!(current, Window::current());
+    assert_eq!(Ok(5), win.get_height());
+    assert_eq!(Ok(buf.clone()), win.get_buf());
+    assert_eq!(
+        Ok(false),
+        options::get::<bool>("modifiable", Scope::Buffer(&buf))
+    );
+
+    drop(preview);
+
+    assert!(!win.is_valid());
+    assert!(!buf.is_valid());
+}
+
+#[oxi::test]
+fn open_preview_split_error_wipes_buffer() {
+    // Split until there's no room left for another window.
+    for _ in 0..100 {
+        if api::command("split").is_err() {
+            break;
+        }
+    }
+
+    let bufs = api::list_bufs().count();
+
+    let res =
+        Window::open_preview(["foo"], PreviewPlacement::Split(None), true);
+
+    assert!(res.is_err());
+    assert_eq!(bufs, api::list_bufs().count());
+}
+
+#[oxi::test]
+fn open_preview_float() {
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Editor)
+        .height(2)
+        .width(10)
+        .row(0)
+        .col(0)
+        .build();
+
+    let preview = Window::open_preview(["foo"], config.into(), true).unwrap();
+
+    assert_eq!(preview.window(), &Window::current());
+
+    let (win, buf) = preview.leak();
+    assert!(win.is_valid());
+    assert!(buf.is_valid());
+}
+
+#[oxi::test]
+fn resize() {
+    api::command("split").unwrap();
+    let mut win = Window::current();
+
+    win.set_height(10).unwrap();
+    assert_eq!(Ok(()), win.resize(Resize::Rows(2)));
+    assert_eq!(Ok(12), win.get_height());
+
+    assert_eq!(Ok(()), win.resize(Resize::Rows(-100)));
+    assert_eq!(Ok(1), win.get_height());
+
+    let mut buf = win.get_buf().unwrap();
+    buf.set_lines(.., true, ["a", "b", "c", "d", "e"]).unwrap();
+
+    assert_eq!(Ok(()), win.resize(Resize::ToFit { max: None }));
+    assert_eq!(Ok(5), win.get_height());
+
+    assert_eq!(Ok(()), win.resize(Resize::ToFit { max: Some(3) }));
+    assert_eq!(Ok(3), win.get_height());
+    assert_eq!(
+        Ok(false),
+        options::get::<bool>("winfixheight", Scope::Window(&win))
+    );
+
+    // Closed folds take a single line.
+    api::command("2,4fold").unwrap();
+    assert_eq!(Ok(()), win.resize(Resize::ToFit { max: None }));
+    assert_eq!(Ok(3), win.get_height());
+    api::command("normal! zE").unwrap();
+
+    // Wrapped lines take as many lines as they wrap to.
+    let long = "x".repeat(win.get_width().unwrap() as usize * 2);
+    buf.set_lines(.., true, [long.as_str(), "a"]).unwrap();
+    options::set("wrap", true, Scope::Window(&win)).unwrap();
+    assert_eq!(Ok(()), win.resize(Resize::ToFit { max: None }));
+    assert_eq!(Ok(3), win.get_height());
+
+    win.close(true).unwrap();
+}
+
+#[oxi::test]
+fn split() {
+    let win = Window::current();
+    let buf = api::create_buf(false, true).unwrap();
+
+    let opts = opts::SplitOpts::builder()
+        .direction(SplitModifier::BelowRight)
+        .size(5)
+        .buffer(buf.clone())
+        .enter(false)
+        .build();
+
+    let new = api::split(&opts).unwrap();
+
+    assert_eq!(win, Window::current());
+    assert_eq!(Ok(buf), new.get_buf());
+    assert_eq!(Ok(5), new.get_height());
+    assert!(new.get_position().unwrap().0 > win.get_position().unwrap().0);
+
+    let opts = opts::SplitOpts::builder().vertical(true).size(20).build();
+    let new = api::split(&opts).unwrap();
+
+    assert_eq!(new, Window::current());
+    assert_eq!(Ok(20), new.get_width());
+
+    let new = api::split(&opts::SplitOpts::default()).unwrap();
+    assert_eq!(new, Window::current());
+    assert_eq!(4, api::list_wins().len());
+    assert_eq!(1, api::list_tabpages().len());
+}
+
+#[oxi::test]
+fn text_lines_height() {
+    let win = Window::current();
+    let mut buf = win.get_buf().unwrap();
+
+    let width = win.get_width().unwrap() as usize;
+    let long = "a".repeat(2 * width + 1);
+    buf.set_lines(.., true, ["foo", &*long, "bar", "baz"]).unwrap();
+
+    assert_eq!(Ok(1), win.text_lines_height(0..1));
+    assert_eq!(Ok(3), win.text_lines_height(1..=1));
+    assert_eq!(Ok(6), win.text_lines_height(..));
+    assert_eq!(Ok(0), win.text_lines_height(2..2));
+
+    api::command("3,4fold").unwrap();
+    assert_eq!(Ok(5), win.text_lines_height(..));
+
+    #[cfg(feature = "neovim-nightly")]
+    {
+        let opts =
+            opts::WinTextHeightOpts::builder().start_row(1).end_row(1).build();
+        let height = win.text_height(&opts).unwrap();
+        assert_eq!(3, height.all);
+        assert_eq!(0, height.fill);
+    }
+}
+
+#[oxi::test]
+fn call_keepjumps() {
+    let mut win = Window::current();
+    let mut buf = win.get_buf().unwrap();
+    buf.set_lines(.., true, ["a", "b", "foo", "c", "foo"]).unwrap();
+    win.set_cursor(1, 0).unwrap();
+
+    let jumps = || {
+        let (list, _) = api::call_function::<_, (Vec<Object>, usize)>(
+            "getjumplist",
+            Array::new(),
+        )
+        .unwrap();
+        list.len()
+    };
+
+    let before = jumps();
+
+    // `search()` with the `s` flag sets the previous context mark, adding
+    // the cursor position to the jumplist.
+    let line = win
+        .call_keepjumps(|()| {
+            api::call_function::<_, usize>("search", ("foo", "s"))
+        })
+        .unwrap();
+
+    assert_eq!(3, line);
+    assert_eq!(before, jumps());
+    assert_eq!(Ok((1, 0)), win.get_cursor());
+
+    win.call(|()| api::call_function::<_, usize>("search", ("foo", "s")))
+        .unwrap();
+
+    assert_eq!(before + 1, jumps());
+    assert_eq!(Ok((3, 0)), win.get_cursor());
+}
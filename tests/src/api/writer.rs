@@ -0,0 +1,58 @@
+use std::io::Write;
+
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, BufferWriter, MessageWriter};
+
+fn lines(writer: &BufferWriter) -> Vec<String> {
+    writer
+        .buffer()
+        .get_lines(.., true)
+        .unwrap()
+        .map(|line| line.to_string_lossy().into())
+        .collect()
+}
+
+#[oxi::test]
+fn buffer_writer_lines() {
+    let buf = api::create_buf(true, true).unwrap();
+    let mut writer = BufferWriter::new(buf);
+
+    write!(writer, "foo\nba").unwrap();
+    assert_eq!(vec!["foo"], lines(&writer));
+
+    write!(writer, "r\nbaz").unwrap();
+    assert_eq!(vec!["foo", "bar"], lines(&writer));
+
+    writer.flush().unwrap();
+    assert_eq!(vec!["foo", "bar", "baz"], lines(&writer));
+
+    writeln!(writer, "!").unwrap();
+    assert_eq!(vec!["foo", "bar", "baz!"], lines(&writer));
+}
+
+#[oxi::test]
+fn buffer_writer_appends() {
+    let mut buf = api::create_buf(true, true).unwrap();
+    buf.set_lines(.., true, ["foo"]).unwrap();
+
+    let mut writer = BufferWriter::new(buf.clone());
+    write!(writer, "bar").unwrap();
+    drop(writer);
+
+    let lines = buf
+        .get_lines(.., true)
+        .unwrap()
+        .map(|line| line.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["foo", "bar"], lines);
+}
+
+#[oxi::test]
+fn message_writer() {
+    writeln!(MessageWriter::out(), "foo").unwrap();
+
+    let messages =
+        api::call_function::<_, String>("execute", ("messages",)).unwrap();
+    assert!(messages.ends_with("foo"), "{messages:?}");
+}
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use nvim_oxi::{self as oxi, conversion};
+
+#[oxi::test]
+fn verify_roundtrips() {
+    assert_eq!(Ok(()), conversion::verify(&42));
+    assert_eq!(Ok(()), conversion::verify(&-1.5));
+    assert_eq!(Ok(()), conversion::verify(&"foo".to_owned()));
+    assert_eq!(Ok(()), conversion::verify(&Some(true)));
+    assert_eq!(Ok(()), conversion::verify(&vec![1, 2, 3]));
+
+    let map = HashMap::from_iter([("foo".to_owned(), vec![0.5])]);
+    assert_eq!(Ok(()), conversion::verify(&map));
+}
+
+#[oxi::test]
+fn verify_lossy_conversion() {
+    // `Some(())` is converted into nil, which is converted back into `None`.
+    assert!(conversion::verify(&Some(())).is_err());
+}
@@ -0,0 +1,18 @@
+use nvim_oxi::{self as oxi, debug, lua::LuaTable, Array, Dictionary};
+
+#[oxi::test]
+fn live_counts() {
+    let before = debug::live_counts();
+
+    let array = Array::from_iter([1, 2, 3]);
+    let dict = Dictionary::from_iter([("foo", array.clone())]);
+    let table = LuaTable::new();
+
+    let leaked = debug::live_counts().leaked_since(&before).unwrap();
+    assert_eq!(2, leaked.arrays);
+    assert_eq!(1, leaked.dictionaries);
+    assert_eq!(1, leaked.lua_refs);
+
+    drop((array, dict, table));
+    assert_eq!(None, debug::live_counts().leaked_since(&before));
+}
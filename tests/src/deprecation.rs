@@ -0,0 +1,32 @@
+#![allow(deprecated)]
+
+use nvim_oxi::{self as oxi, api};
+
+const WARNING: &str =
+    "nvim-oxi: `nvim_get_hl_by_name` is deprecated, use `nvim_get_hl` instead";
+
+#[cfg(not(feature = "strict-deprecations"))]
+#[oxi::test]
+fn deprecation_warns_once() {
+    assert!(api::get_hl_by_name("Normal", true).is_ok());
+    assert!(api::get_hl_by_name("Normal", false).is_ok());
+
+    let messages =
+        api::call_function::<_, String>("execute", ("messages",)).unwrap();
+
+    assert_eq!(1, messages.matches(WARNING).count(), "{messages}");
+}
+
+#[cfg(feature = "strict-deprecations")]
+#[oxi::test]
+fn deprecation_strict_errors() {
+    for _ in 0..2 {
+        let res = api::get_hl_by_name("Normal", true);
+        assert_eq!(Err(api::Error::Other(WARNING.into())), res.map(|_| ()));
+    }
+
+    let messages =
+        api::call_function::<_, String>("execute", ("messages",)).unwrap();
+
+    assert!(!messages.contains(WARNING), "{messages}");
+}
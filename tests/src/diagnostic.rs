@@ -0,0 +1,73 @@
+use nvim_oxi::{
+    self as oxi,
+    api,
+    api::Buffer,
+    diagnostic::{opts::*, *},
+};
+
+#[oxi::test]
+fn diagnostic_set_get_reset() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let ns = api::create_namespace("oxi-diagnostic-test");
+
+    let warn = Diagnostic::builder()
+        .lnum(0)
+        .col(0)
+        .end_col(3)
+        .severity(Severity::Warn)
+        .message("foo is deprecated")
+        .source("oxi")
+        .build();
+
+    let err = Diagnostic::builder().lnum(1).message("bar is wrong").build();
+
+    set(ns, &buf, [warn, err], &Default::default()).unwrap();
+
+    let all =
+        get(Some(&buf), &GetOpts::builder().namespace(ns).build()).unwrap();
+    assert_eq!(2, all.len());
+
+    let warns = get(
+        Some(&buf),
+        &GetOpts::builder().namespace(ns).severity(Severity::Warn).build(),
+    )
+    .unwrap();
+    assert_eq!(1, warns.len());
+    assert_eq!("foo is deprecated", warns[0].message);
+    assert_eq!(Some("oxi"), warns[0].source.as_deref());
+    assert_eq!(Some(3), warns[0].end_col);
+    assert_eq!(Some(ns), warns[0].namespace);
+    assert_eq!(Some(&buf), warns[0].bufnr.as_ref());
+
+    let info = get_namespace(ns).unwrap();
+    assert_eq!("oxi-diagnostic-test", info.name);
+    assert!(get_namespaces().unwrap().contains_key(&ns));
+
+    reset(Some(ns), Some(&buf)).unwrap();
+    assert_eq!(Ok(Vec::new()), get(Some(&buf), &Default::default()));
+}
+
+#[oxi::test]
+fn diagnostic_config() {
+    let opts = ConfigOpts::builder()
+        .underline(false)
+        .virtual_text(true)
+        .severity_sort(true)
+        .build();
+
+    assert_eq!(Ok(()), config(&opts, None));
+}
+
+#[oxi::test]
+fn diagnostic_enable_disable() {
+    let buf = Buffer::current();
+    assert_eq!(Ok(()), disable(Some(&buf), None));
+    assert_eq!(Ok(()), enable(Some(&buf), None));
+}
+
+#[oxi::test]
+fn diagnostic_invalid_namespace() {
+    assert!(get_namespace(u32::MAX).is_err());
+}
@@ -0,0 +1,29 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi::{self as oxi, api, events};
+
+#[oxi::test]
+fn events_emit_on() {
+    let got = Rc::new(RefCell::new(Vec::new()));
+
+    let cloned = Rc::clone(&got);
+    let id = events::on("OxiTestEvent", move |payload: (String, u32)| {
+        cloned.borrow_mut().push(payload);
+        Ok(())
+    })
+    .unwrap();
+
+    events::emit("OxiTestEvent", &("foo", 1)).unwrap();
+    events::emit("OxiOtherEvent", &("bar", 2)).unwrap();
+    events::emit("OxiTestEvent", &("baz", 3)).unwrap();
+
+    assert_eq!(
+        vec![("foo".to_owned(), 1), ("baz".to_owned(), 3)],
+        *got.borrow()
+    );
+
+    api::del_autocmd(id).unwrap();
+    events::emit("OxiTestEvent", &("qux", 4)).unwrap();
+    assert_eq!(2, got.borrow().len());
+}
@@ -1 +1,22 @@
 mod api;
+mod conversion;
+mod debug;
+#[cfg(all(
+    feature = "deprecation-warnings",
+    any(feature = "neovim-0-9", feature = "neovim-nightly")
+))]
+mod deprecation;
+mod diagnostic;
+mod events;
+#[cfg(feature = "libuv")]
+mod libuv;
+mod lsp;
+mod persist;
+mod picker;
+mod process;
+mod rpc;
+mod test_macro;
+mod toplevel;
+mod treesitter;
+mod ui;
+mod vim_api;
@@ -0,0 +1,106 @@
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::io::Write;
+use std::rc::Rc;
+use std::time::Duration;
+
+use nvim_oxi::{
+    self as oxi,
+    api::{self, BufferWriter, ThreadSafeWriter},
+    libuv::{TimerGuard, TimerHandle},
+};
+
+/// Runs Neovim's event loop for `ms` milliseconds.
+fn run_loop(ms: u32) {
+    let _ = api::call_function::<_, i64>("wait", (ms, "0"));
+}
+
+#[oxi::test]
+fn timer_handle_runs_after_drop() {
+    let fired = Rc::new(Cell::new(false));
+
+    let _ = TimerHandle::once(Duration::from_millis(0), {
+        let fired = Rc::clone(&fired);
+        move || {
+            fired.set(true);
+            Ok::<_, Infallible>(())
+        }
+    })
+    .unwrap();
+
+    run_loop(50);
+    assert!(fired.get());
+}
+
+#[oxi::test]
+fn timer_guard_stops_on_drop() {
+    let fired = Rc::new(Cell::new(false));
+
+    let guard = TimerGuard::once(Duration::from_millis(0), {
+        let fired = Rc::clone(&fired);
+        move || {
+            fired.set(true);
+            Ok::<_, Infallible>(())
+        }
+    })
+    .unwrap();
+
+    assert!(guard.is_active());
+    drop(guard);
+
+    run_loop(50);
+    assert!(!fired.get());
+}
+
+#[oxi::test]
+fn timer_guard_detach() {
+    let fired = Rc::new(Cell::new(false));
+
+    let handle = TimerHandle::once(Duration::from_millis(0), {
+        let fired = Rc::clone(&fired);
+        move || {
+            fired.set(true);
+            Ok::<_, Infallible>(())
+        }
+    })
+    .unwrap()
+    .into_guard()
+    .detach();
+
+    assert!(handle.is_active());
+
+    run_loop(50);
+    assert!(fired.get());
+    assert!(!handle.is_active());
+}
+
+#[oxi::test]
+fn thread_safe_writer() {
+    let buf = api::create_buf(true, true).unwrap();
+    let writer = ThreadSafeWriter::new(BufferWriter::new(buf.clone())).unwrap();
+
+    let threads = (0..2)
+        .map(|i| {
+            let mut writer = writer.clone();
+            std::thread::spawn(move || writeln!(writer, "thread {i}"))
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().unwrap().unwrap();
+    }
+
+    // Nothing is written until the event loop runs.
+    assert_eq!(1, buf.line_count().unwrap());
+
+    run_loop(50);
+
+    let mut lines = buf
+        .get_lines(.., true)
+        .unwrap()
+        .map(|line| line.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    lines.sort();
+    assert_eq!(vec!["thread 0", "thread 1"], lines);
+}
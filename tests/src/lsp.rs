@@ -0,0 +1,72 @@
+use nvim_oxi::{
+    self as oxi,
+    api::Buffer,
+    lsp::{self, opts::*},
+};
+
+#[oxi::test]
+fn lsp_no_clients() {
+    let clients = lsp::get_active_clients(&GetClientsOpts::default());
+    assert_eq!(Ok(vec![]), clients);
+    assert_eq!(Ok(None), lsp::get_client_by_id(1));
+
+    let buf = Buffer::current();
+    assert_eq!(Ok(false), lsp::buf_notify(&buf, "foo", &()));
+
+    let err = oxi::Error::from(lsp::Error::ClientNotFound(1));
+    assert_eq!(oxi::Error::Lsp(lsp::Error::ClientNotFound(1)), err);
+}
+
+// Starts a client connected to a fake in-process language server which
+// supports hovering.
+#[cfg(not(feature = "neovim-0-8"))]
+#[oxi::test(setup = r#"
+    vim.g.client_id = vim.lsp.start_client({
+        name = "fake",
+        cmd = function(dispatchers)
+            local closing = false
+            return {
+                request = function(method, params, callback)
+                    if method == "initialize" then
+                        callback(nil, {
+                            capabilities = { hoverProvider = true },
+                        })
+                    elseif method == "textDocument/hover" then
+                        callback(nil, { contents = "hello" })
+                    else
+                        callback(nil, vim.NIL)
+                    end
+                    return true, 1
+                end,
+                notify = function() return true end,
+                is_closing = function() return closing end,
+                terminate = function() closing = true end,
+            }
+        end,
+    })
+"#)]
+fn lsp_client() {
+    use std::collections::HashMap;
+
+    let id = oxi::api::get_var::<u32>("client_id").unwrap();
+
+    let opts = GetClientsOpts::builder().name("fake").build();
+    let clients = lsp::get_active_clients(&opts).unwrap();
+    assert_eq!(1, clients.len());
+
+    let client = clients[0];
+    assert_eq!(id, client.id());
+    assert_eq!(Ok("fake".to_owned()), client.name());
+    assert_eq!(Ok(true), client.has_capability("hoverProvider"));
+    assert_eq!(Ok(false), client.has_capability("renameProvider"));
+
+    let hover = client.request_sync::<_, HashMap<String, String>>(
+        "textDocument/hover",
+        &(),
+        std::time::Duration::from_secs(1),
+        &Buffer::current(),
+    );
+
+    let expected = HashMap::from([("contents".into(), "hello".into())]);
+    assert_eq!(Ok(expected), hover);
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use nvim_oxi::{
+    self as oxi, api,
+    persist::{Error, Format, Store},
+};
+
+fn store(name: &str, format: Format) -> Store {
+    let dir =
+        api::fn_::stdpath("cache").unwrap().join("nvim-oxi-tests").join(name);
+    let store = Store::with_dir(dir).format(format);
+    store.clear().unwrap();
+    store
+}
+
+#[oxi::test]
+fn state_store_json() {
+    let store = store("json", Format::Json);
+
+    assert_eq!(Ok(None), store.get::<u32>("runs"));
+
+    store.set("runs", &3u32).unwrap();
+    assert_eq!(Ok(Some(3)), store.get::<u32>("runs"));
+
+    let map = HashMap::from([("foo".to_owned(), vec![1, 2, 3])]);
+    store.set("map", &map).unwrap();
+    assert_eq!(Ok(Some(map)), store.get("map"));
+
+    assert_eq!(Ok(vec!["map".to_owned(), "runs".to_owned()]), store.keys());
+
+    assert_eq!(Ok(true), store.remove("runs"));
+    assert_eq!(Ok(false), store.remove("runs"));
+    assert_eq!(Ok(None), store.get::<u32>("runs"));
+}
+
+#[oxi::test]
+fn state_store_msgpack() {
+    let store = store("msgpack", Format::Msgpack);
+
+    store.set("name", "foo").unwrap();
+    assert_eq!(Ok(Some("foo".to_owned())), store.get("name"));
+    assert!(store.path("name").unwrap().ends_with("name.msgpack"));
+
+    // Values stored in a different format aren't visible.
+    let json = Store::with_dir(store.dir());
+    assert_eq!(Ok(None), json.get::<String>("name"));
+    assert_eq!(Ok(Vec::new()), json.keys());
+}
+
+#[oxi::test]
+fn state_store_decode_error() {
+    let store = store("decode", Format::Json);
+
+    std::fs::create_dir_all(store.dir()).unwrap();
+    std::fs::write(store.path("bad").unwrap(), "not json").unwrap();
+
+    match store.get::<u32>("bad") {
+        Err(oxi::Error::Persist(Error::Json { path, .. })) => {
+            assert_eq!(store.path("bad").unwrap(), path)
+        },
+        other => panic!("expected a JSON error, got {other:?}"),
+    }
+}
+
+#[oxi::test]
+fn state_store_invalid_keys() {
+    let store = store("invalid", Format::Json);
+
+    assert_eq!(
+        Err(oxi::Error::Persist(Error::InvalidName {
+            what: "key",
+            name: String::new()
+        })),
+        store.set("", &0)
+    );
+    assert!(store.set(".hidden", &0).is_err());
+    assert!(store.set("foo/bar", &0).is_err());
+    assert!(Store::new("foo/bar").is_err());
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi::{
+    self as oxi,
+    api::{self, opts::ExecAutocmdsOpts, types::AutocmdEvent, Buffer},
+    picker::{Matcher, Picker, SubsequenceMatcher},
+};
+
+#[oxi::test]
+fn picker_subsequence_matcher() {
+    let matcher = SubsequenceMatcher;
+
+    assert_eq!(Some(0), matcher.score("", "foo"));
+    assert!(matcher.score("fb", "foo_bar").is_some());
+    assert_eq!(None, matcher.score("fb", "bar_foo"));
+
+    // Smart case.
+    assert!(matcher.score("foo", "FooBar").is_some());
+    assert_eq!(None, matcher.score("Foo", "foobar"));
+
+    // Consecutive matches and word starts score higher.
+    let main = matcher.score("main", "src/main.rs").unwrap();
+    let scattered = matcher.score("main", "src/my_animation.rs").unwrap();
+    assert!(main > scattered);
+
+    let camel = matcher.score("fb", "FooBar").unwrap();
+    let inner = matcher.score("fb", "Foobar").unwrap();
+    assert!(camel > inner);
+}
+
+#[oxi::test]
+fn picker_select() {
+    let windows = api::list_wins().count();
+    let selected = Rc::new(RefCell::new(None));
+
+    let cloned = Rc::clone(&selected);
+    let handle = Picker::new(["foo", "bar", "baz"])
+        .prompt("> ")
+        .on_select(move |item| {
+            *cloned.borrow_mut() = Some(item);
+            Ok(())
+        })
+        .open()
+        .unwrap();
+
+    assert!(handle.is_open());
+    assert_eq!(windows + 2, api::list_wins().count());
+    assert_eq!(Some("foo".to_owned()), handle.selected());
+
+    // Type a query in the prompt.
+    let mut prompt = Buffer::current();
+    prompt.set_lines(.., true, ["> bz"]).unwrap();
+    let opts = ExecAutocmdsOpts::builder().buffer(prompt).build();
+    api::exec_autocmds([AutocmdEvent::TextChanged], &opts).unwrap();
+
+    assert_eq!("bz", handle.query());
+    assert_eq!(Some("baz".to_owned()), handle.selected());
+
+    handle.extend(["buzz"]).unwrap();
+    assert_eq!(Some("baz".to_owned()), handle.selected());
+
+    api::command(r#"execute "normal \<C-n>\<CR>""#).unwrap();
+
+    assert!(!handle.is_open());
+    assert_eq!(Some("buzz"), *selected.borrow());
+    assert_eq!(windows, api::list_wins().count());
+}
+
+#[oxi::test]
+fn picker_custom_matcher() {
+    let handle = Picker::new(["foo", "foobar", "bar"])
+        .matcher(|query: &str, item: &str| {
+            item.contains(query).then_some(item.len() as i64)
+        })
+        .open()
+        .unwrap();
+
+    // Longer items are listed first.
+    assert_eq!(Some("foobar".to_owned()), handle.selected());
+
+    handle.close().unwrap();
+    assert!(!handle.is_open());
+}
@@ -0,0 +1,62 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi::{self as oxi, process::Command};
+
+#[oxi::test]
+fn process_output() {
+    let output = Command::new("echo").args(["foo", "bar"]).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!("foo bar\n", output.stdout);
+    assert_eq!("", output.stderr);
+}
+
+#[oxi::test]
+fn process_stdin_and_env() {
+    let output = Command::new("cat").stdin("foo\nbar").output().unwrap();
+    assert_eq!("foo\nbar", output.stdout);
+
+    let output = Command::new("sh")
+        .args(["-c", "echo $FOO >&2; exit 3"])
+        .env("FOO", "bar")
+        .output()
+        .unwrap();
+
+    assert_eq!(3, output.status.code());
+    assert_eq!("bar\n", output.stderr);
+}
+
+#[oxi::test]
+fn process_callbacks() {
+    let stdout = Rc::new(RefCell::new(String::new()));
+    let status = Rc::new(RefCell::new(None));
+
+    let out = Rc::clone(&stdout);
+    let st = Rc::clone(&status);
+
+    let child = Command::new("echo")
+        .arg("foo")
+        .on_stdout(move |chunk| {
+            out.borrow_mut().push_str(&chunk);
+            Ok(())
+        })
+        .on_exit(move |status| {
+            *st.borrow_mut() = Some(status);
+            Ok(())
+        })
+        .spawn()
+        .unwrap();
+
+    let exit_status = child.wait().unwrap();
+
+    assert!(exit_status.success());
+    assert_eq!(Some(exit_status), child.try_wait());
+    assert_eq!(Some(exit_status), *status.borrow());
+    assert_eq!("foo\n", &*stdout.borrow());
+}
+
+#[oxi::test]
+fn process_not_executable() {
+    assert!(Command::new("nvim-oxi-not-a-command").spawn().is_err());
+}
@@ -0,0 +1,43 @@
+use nvim_oxi::{
+    self as oxi,
+    rpc::{self, RpcChannel},
+    Array,
+    Object,
+};
+
+#[oxi::test]
+fn rpc_request_notify() {
+    let chan =
+        RpcChannel::spawn(["nvim", "--embed", "--headless", "-u", "NONE"])
+            .unwrap();
+
+    assert_eq!(Ok(2), chan.request::<_, i64>("nvim_eval", ("1 + 1",)));
+
+    chan.notify("nvim_set_var", ("foo", 42)).unwrap();
+    assert_eq!(Ok(42), chan.request::<_, i64>("nvim_get_var", ("foo",)));
+
+    chan.close().unwrap();
+}
+
+#[oxi::test]
+fn rpc_on_request() {
+    let chan = RpcChannel::from_id(42);
+
+    chan.on_request("add", |(a, b): (i64, i64)| Ok(a + b));
+
+    let dispatch = rpc::dispatcher();
+
+    let res = dispatch.call((
+        chan.id(),
+        "add".to_owned(),
+        Object::from(Array::from((1, 2))),
+    ));
+    assert_eq!(Ok(Object::from(3)), res);
+
+    // Every call returns the same function.
+    assert_eq!(dispatch, rpc::dispatcher());
+
+    let res =
+        dispatch.call((chan.id(), "sub".to_owned(), Object::from(Array::new())));
+    assert!(res.is_err());
+}
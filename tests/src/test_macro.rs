@@ -0,0 +1,168 @@
+use nvim_oxi::{
+    self as oxi,
+    api::{self, options},
+    Array,
+};
+
+#[oxi::test]
+fn test_macro_default() {
+    let loadplugins = options::get("loadplugins", options::Scope::Global);
+    assert_eq!(Ok(false), loadplugins);
+}
+
+#[oxi::test(clean)]
+fn test_macro_clean() {
+    let loadplugins = options::get("loadplugins", options::Scope::Global);
+    assert_eq!(Ok(true), loadplugins);
+}
+
+#[oxi::test(arg = "--cmd", arg = "let g:foo = 1", cmd = "let g:bar = 2")]
+fn test_macro_args_and_cmds() {
+    assert_eq!(Ok(1), api::get_var::<u8>("foo"));
+    assert_eq!(Ok(2), api::get_var::<u8>("bar"));
+}
+
+#[oxi::test(cmd = "let g:foo = 41", setup = "vim.g.bar = vim.g.foo + 1")]
+fn test_macro_setup() {
+    assert_eq!(Ok(42), api::get_var::<u8>("bar"));
+}
+
+#[oxi::test(no_swap)]
+fn test_macro_no_swap() {
+    let swapfile = options::get("swapfile", options::Scope::Global);
+    assert_eq!(Ok(false), swapfile);
+}
+
+#[oxi::test(leak_check)]
+fn test_macro_leak_check() {
+    let lines = Array::from_iter(["foo", "bar"]);
+    api::set_var("lines", lines).unwrap();
+    assert_eq!(Ok(2), api::get_var::<Array>("lines").map(|lines| lines.len()));
+}
+
+#[oxi::module]
+mod exported {
+    use nvim_oxi as oxi;
+
+    #[oxi::export]
+    fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[oxi::export]
+    fn div(a: i32, b: i32) -> oxi::Result<i32> {
+        a.checked_div(b).ok_or_else(|| {
+            oxi::api::Error::Other("division by zero".into()).into()
+        })
+    }
+
+    #[oxi::export]
+    fn greet() -> String {
+        "Hello from Rust".to_owned()
+    }
+}
+
+#[oxi::test(setup = "
+    local path = vim.api.nvim_get_runtime_file('lua/__module_exports.*', \
+                     false)[1]
+    _G.exported = package.loadlib(path, 'luaopen_exported')()
+")]
+fn module_exports() {
+    let eval =
+        |expr: &str| api::call_function::<_, oxi::Object>("luaeval", (expr,));
+
+    assert_eq!(Ok(oxi::Object::from(3)), eval("exported.add(1, 2)"));
+    assert_eq!(Ok(oxi::Object::from(2)), eval("exported.div(6, 3)"));
+    assert!(eval("exported.div(1, 0)").is_err());
+    assert_eq!(
+        Ok(oxi::Object::from("Hello from Rust")),
+        eval("exported.greet()")
+    );
+}
+
+#[oxi::module(deferred = deferred_init)]
+fn deferred() -> oxi::Result<()> {
+    api::set_var("deferred_loaded", true)?;
+    Ok(())
+}
+
+fn deferred_init() -> oxi::Result<()> {
+    api::set_var("deferred_ran", true)?;
+    Ok(())
+}
+
+#[oxi::test(setup = "
+    local path = \
+                     vim.api.nvim_get_runtime_file('lua/\
+                     __module_deferred_init.*', false)[1]
+    package.loadlib(path, 'luaopen_deferred')()
+")]
+fn module_deferred_init() {
+    assert_eq!(Ok(true), api::get_var::<bool>("deferred_loaded"));
+    assert!(api::get_var::<bool>("deferred_ran").is_err());
+
+    assert_eq!(Ok(()), oxi::run_deferred_init());
+    assert_eq!(Ok(true), api::get_var::<bool>("deferred_ran"));
+
+    // The deferred init only runs once.
+    api::del_var("deferred_ran").unwrap();
+    assert_eq!(Ok(()), oxi::run_deferred_init());
+    assert!(api::get_var::<bool>("deferred_ran").is_err());
+}
+
+#[oxi::module(deferred = ui_deferred_init, on = UIEnter)]
+fn ui_deferred() -> oxi::Result<()> {
+    Ok(())
+}
+
+fn ui_deferred_init() -> oxi::Result<()> {
+    api::set_var("ui_deferred_ran", true)?;
+    Ok(())
+}
+
+#[oxi::test(setup = "
+    local path = \
+                     vim.api.nvim_get_runtime_file('lua/\
+                     __module_deferred_init_on_ui_enter.*', false)[1]
+    package.loadlib(path, 'luaopen_ui_deferred')()
+")]
+fn module_deferred_init_on_ui_enter() {
+    use api::types::AutocmdEvent;
+
+    // No UI is attached to the headless instance.
+    assert!(api::get_var::<bool>("ui_deferred_ran").is_err());
+
+    api::exec_autocmds([AutocmdEvent::UIEnter], &Default::default()).unwrap();
+    assert_eq!(Ok(true), api::get_var::<bool>("ui_deferred_ran"));
+}
+
+#[oxi::module(deferred = init, on = UIEnter)]
+mod deferred_exports {
+    use nvim_oxi::{self as oxi, api};
+
+    #[oxi::export]
+    fn has_run() -> bool {
+        api::get_var::<bool>("deferred_exports_ran").unwrap_or(false)
+    }
+
+    fn init() -> oxi::Result<()> {
+        api::set_var("deferred_exports_ran", true)?;
+        Ok(())
+    }
+}
+
+#[oxi::test(setup = "
+    local path = \
+                     vim.api.nvim_get_runtime_file('lua/\
+                     __module_deferred_init_on_first_use.*', false)[1]
+    _G.deferred_exports = package.loadlib(path, 'luaopen_deferred_exports')()
+")]
+fn module_deferred_init_on_first_use() {
+    assert!(api::get_var::<bool>("deferred_exports_ran").is_err());
+
+    let has_run = api::call_function::<_, bool>(
+        "luaeval",
+        ("deferred_exports.has_run()",),
+    );
+    assert_eq!(Ok(true), has_run);
+}
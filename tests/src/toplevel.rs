@@ -0,0 +1,170 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use nvim_oxi::{self as oxi, api, api::types::Mode, WaitOutcome};
+
+#[oxi::test]
+fn on_key() {
+    let keys = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = oxi::on_key({
+        let keys = Rc::clone(&keys);
+        move |key| {
+            keys.borrow_mut().push(key);
+            Ok(())
+        }
+    })
+    .unwrap();
+
+    let input = api::replace_termcodes("i<BS><Esc>", true, false, true);
+    api::feedkeys(&input.to_string_lossy(), Mode::Normal, false);
+    api::command("call feedkeys('', 'x')").unwrap();
+
+    assert_eq!(vec!["i", "<BS>", "<Esc>"], *keys.borrow());
+
+    drop(handle);
+    keys.borrow_mut().clear();
+
+    api::command("call feedkeys('i', 'x')").unwrap();
+    assert!(keys.borrow().is_empty());
+}
+
+#[oxi::test]
+fn wait_for() {
+    let done = Rc::new(Cell::new(false));
+
+    oxi::schedule({
+        let done = Rc::clone(&done);
+        move |()| {
+            done.set(true);
+            Ok(())
+        }
+    });
+
+    let res =
+        oxi::wait_for(Duration::from_secs(1), Duration::from_millis(5), {
+            let done = Rc::clone(&done);
+            move || done.get()
+        });
+
+    assert_eq!(Ok(WaitOutcome::Ok), res);
+    assert!(done.get());
+
+    let res = oxi::wait_for(
+        Duration::from_millis(20),
+        Duration::from_millis(5),
+        || false,
+    );
+
+    assert_eq!(Ok(WaitOutcome::TimedOut), res);
+}
+
+/// Returns the Lua function `fun` by setting it as the callback of a keymap.
+fn lua_function<A, R>(fun: &str) -> oxi::Function<A, R> {
+    use oxi::conversion::FromObject;
+
+    api::command(&format!("lua vim.keymap.set('n', '<F9>', {fun})")).unwrap();
+
+    let callback = api::get_keymap(Mode::Normal)
+        .find(|keymap| keymap.lhs == "<F9>")
+        .and_then(|keymap| keymap.callback)
+        .unwrap();
+
+    oxi::Function::from_object(callback.into()).unwrap()
+}
+
+#[oxi::test]
+fn function_call_multiple_returns() {
+    let fun = lua_function::<(i32, i32), (i32, String, Option<bool>)>(
+        "function(a, b) return a + b, 'foo' end",
+    );
+    assert_eq!(Ok((3, "foo".into(), None)), fun.call((1, 2)));
+
+    let fun = lua_function::<(), i32>("function() return 1, 2, 3 end");
+    assert_eq!(Ok(1), fun.call(()));
+
+    let fun = lua_function::<(), ()>("function() return 'ignored' end");
+    assert_eq!(Ok(()), fun.call(()));
+}
+
+#[oxi::test]
+fn function_call_error() {
+    let fun = lua_function::<(), ()>("function() error('oops') end");
+
+    match fun.call(()) {
+        Err(oxi::lua::Error::LuaError { message, traceback }) => {
+            assert!(message.ends_with("oops"), "{message}");
+            assert!(traceback.is_some());
+        },
+        other => panic!("expected a Lua error, got {other:?}"),
+    }
+}
+
+#[oxi::test]
+fn function_call_error_not_a_string() {
+    let fun = lua_function::<(), ()>("function() error({ code = 1 }) end");
+
+    match fun.call(()) {
+        Err(oxi::lua::Error::LuaError { message, .. }) => {
+            assert!(message.contains("table"), "{message}");
+        },
+        other => panic!("expected a Lua error, got {other:?}"),
+    }
+}
+
+#[oxi::test]
+fn lua_table() {
+    use oxi::lua::LuaTable;
+
+    let fun = lua_function::<(), LuaTable>(
+        "function() return { 'first', foo = 1, bar = { 'x' } } end",
+    );
+
+    let table = fun.call(()).unwrap();
+
+    assert_eq!(Ok(1), table.get::<_, i32>("foo"));
+    assert_eq!(Ok("first".to_owned()), table.get::<_, String>(1));
+    assert_eq!(Ok(None), table.get::<_, Option<i32>>("baz"));
+    assert!(table.get::<_, i32>("bar").is_err());
+    assert_eq!(1, table.len());
+
+    let bar = table.get::<_, LuaTable>("bar").unwrap();
+    assert_eq!(Ok("x".to_owned()), bar.get::<_, String>(1));
+
+    table.set("baz", 2).unwrap();
+    table.set("foo", ()).unwrap();
+    assert_eq!(Ok(false), table.contains_key("foo"));
+    assert!(table.set((), 1).is_err());
+
+    let mut keys = table
+        .pairs::<String, oxi::Object>()
+        .map(|pair| pair.unwrap().0)
+        .collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(vec!["1", "bar", "baz"], keys);
+
+    // `'first'` and `bar` can't be popped as integers, but the traversal
+    // goes on after them.
+    let pairs = table.pairs::<String, i32>().collect::<Vec<_>>();
+    assert_eq!(3, pairs.len());
+    assert_eq!(2, pairs.iter().filter(|pair| pair.is_err()).count());
+}
+
+#[oxi::test]
+fn print_macros() {
+    oxi::print!("foo {}", 1);
+    oxi::println!("bar {}", 2);
+    let n = oxi::dbg!(3);
+    assert_eq!(3, n);
+
+    let messages =
+        api::call_function::<_, String>("execute", ("messages",)).unwrap();
+
+    let mut lines = messages.lines().rev().take(3).collect::<Vec<_>>();
+    lines.reverse();
+
+    assert_eq!("foo 1", lines[0]);
+    assert_eq!("bar 2", lines[1]);
+    assert!(lines[2].ends_with("3 = 3"), "{messages:?}");
+}
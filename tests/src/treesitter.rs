@@ -0,0 +1,56 @@
+use nvim_oxi::{
+    self as oxi,
+    api::Buffer,
+    treesitter::{self, Query},
+};
+
+#[oxi::test]
+fn treesitter_parse_and_walk() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["local foo = 1", "print(foo)"]).unwrap();
+
+    let parser = treesitter::get_parser(&buf, Some("lua")).unwrap();
+    assert_eq!(Ok("lua".to_owned()), parser.lang());
+
+    let trees = parser.parse().unwrap();
+    assert_eq!(1, trees.len());
+
+    let root = trees[0].root().unwrap();
+    assert_eq!(Ok("chunk".to_owned()), root.kind());
+    assert_eq!(Ok(None), root.parent());
+    assert_eq!(Ok(false), root.has_error());
+
+    let children = root.named_children().unwrap();
+    assert_eq!(2, children.len());
+    assert_eq!(Ok((1, 0)), children[1].start());
+    assert_eq!(Ok("print(foo)".to_owned()), children[1].text(&buf));
+    assert_eq!(Ok(Some(root.clone())), children[0].parent());
+}
+
+#[oxi::test]
+fn treesitter_query_captures() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["print(1)", "foo()", "bar()"]).unwrap();
+
+    let parser = treesitter::get_parser(&buf, Some("lua")).unwrap();
+    let root = parser.parse().unwrap()[0].root().unwrap();
+
+    let query =
+        Query::parse("lua", "(function_call name: (identifier) @name)")
+            .unwrap();
+    assert_eq!(Ok(vec!["name".to_owned()]), query.capture_names());
+
+    let names = query
+        .iter_captures(&root, &buf, 1..)
+        .unwrap()
+        .map(|capture| {
+            let capture = capture.unwrap();
+            assert_eq!("name", capture.name);
+            capture.node.text(&buf).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["foo", "bar"], names);
+
+    assert!(Query::parse("lua", "(not_a_node)").is_err());
+}
@@ -0,0 +1,88 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi::api::{self, opts::ExecAutocmdsOpts, types::AutocmdEvent};
+use nvim_oxi::rpc::RpcChannel;
+use nvim_oxi::ui::{self, Focus, RemoteUi, UiOptions};
+use nvim_oxi::{self as oxi};
+
+#[oxi::test]
+fn on_focus_change() {
+    let changes = Rc::new(RefCell::new(Vec::new()));
+
+    let cloned = Rc::clone(&changes);
+    ui::on_focus_change(move |focus| {
+        cloned.borrow_mut().push(focus);
+        Ok(())
+    })
+    .unwrap();
+
+    let opts = ExecAutocmdsOpts::default();
+    api::exec_autocmds([AutocmdEvent::FocusLost], &opts).unwrap();
+    api::exec_autocmds([AutocmdEvent::FocusGained], &opts).unwrap();
+
+    assert_eq!(vec![Focus::Lost, Focus::Gained], *changes.borrow());
+}
+
+#[oxi::test]
+fn remote_ui_set_focus() {
+    let chan =
+        RpcChannel::spawn(["nvim", "--embed", "--headless", "-u", "NONE"])
+            .unwrap();
+
+    chan.notify("nvim_command", ("autocmd FocusLost * let g:lost = 1",))
+        .unwrap();
+
+    let ui = RemoteUi::attach(chan, 80, 24, &UiOptions::default()).unwrap();
+    ui.set_focus(Focus::Lost).unwrap();
+
+    let lost = ui.channel().request::<_, i64>("nvim_get_var", ("lost",));
+    assert_eq!(Ok(1), lost);
+
+    ui.detach().unwrap().close().unwrap();
+}
+
+#[oxi::test]
+fn ui_event_from_redraw() {
+    use nvim_oxi::ui::{GridCell, UiEvent};
+    use nvim_oxi::{Array, Object};
+
+    let params = Array::from_iter([
+        Object::from(Array::from(("grid_resize", Array::from((1, 80, 24))))),
+        Object::from(Array::from((
+            "grid_line",
+            Array::from((
+                1,
+                0,
+                2,
+                Array::from((Array::from(("a", 3, 2)), Array::from(("b",)))),
+            )),
+        ))),
+        Object::from(Array::from(("flush", Array::new(), Array::new()))),
+        Object::from(Array::from(("win_viewport", Array::from((2, 3))))),
+    ]);
+
+    let events = UiEvent::from_redraw(params).unwrap();
+
+    assert_eq!(
+        vec![
+            UiEvent::GridResize { grid: 1, width: 80, height: 24 },
+            UiEvent::GridLine {
+                grid: 1,
+                row: 0,
+                col_start: 2,
+                cells: vec![
+                    GridCell { text: "a".into(), hl_id: Some(3), repeat: 2 },
+                    GridCell { text: "b".into(), hl_id: None, repeat: 1 },
+                ],
+            },
+            UiEvent::Flush,
+            UiEvent::Flush,
+            UiEvent::Other {
+                name: "win_viewport".into(),
+                args: Array::from((2, 3)),
+            },
+        ],
+        events
+    );
+}
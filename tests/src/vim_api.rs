@@ -0,0 +1,40 @@
+use nvim_oxi::{self as oxi, api, vim_api::*};
+
+#[oxi::test]
+fn vim_api_buf_lines() {
+    let buf = nvim_create_buf(true, false).unwrap();
+
+    let res = nvim_buf_set_lines(buf.clone(), 0, -1, false, ["a", "b", "c"]);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok(3), nvim_buf_line_count(buf.clone()));
+
+    let lines = nvim_buf_get_lines(buf.clone(), -3, -2, true).unwrap();
+    assert_eq!(vec![oxi::String::from("a"), "b".into()], lines);
+
+    let res = nvim_buf_set_lines(buf.clone(), -2, -1, true, ["d"]);
+    assert_eq!(Ok(()), res);
+
+    let lines = nvim_buf_get_lines(buf.clone(), 0, -1, true).unwrap();
+    assert_eq!(vec![oxi::String::from("a"), "b".into(), "d".into()], lines);
+
+    assert!(nvim_buf_get_lines(buf, 0, 10, true).is_err());
+}
+
+#[oxi::test]
+fn vim_api_current_handles() {
+    nvim_buf_set_lines(0, 0, -1, true, ["foo bar"]).unwrap();
+    nvim_win_set_cursor(0, (1, 4)).unwrap();
+
+    assert_eq!(Ok((1, 4)), nvim_win_get_cursor(0));
+    assert_eq!(Ok(api::get_current_buf()), nvim_win_get_buf(0));
+    assert_eq!(Ok("foo bar".to_owned()), nvim_get_current_line());
+    assert!(nvim_buf_is_valid(0));
+}
+
+#[oxi::test]
+fn vim_api_vars() {
+    assert_eq!(Ok(()), nvim_set_var("foo", 42));
+    assert_eq!(Ok(42), nvim_get_var::<u8>("foo"));
+    assert_eq!(Ok(()), nvim_buf_set_var(0, "bar", true));
+    assert_eq!(Ok(true), nvim_buf_get_var::<_, bool>(0, "bar"));
+}